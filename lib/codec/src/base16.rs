@@ -0,0 +1,267 @@
+use core::fmt;
+use core::marker::PhantomData;
+use crate::step::{In, Out, Over};
+use crate::then::{Then, Cont, Done, Fail};
+use crate::input::{Input, AsInput};
+use crate::output::{Output, IntoOutput};
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+
+pub trait DecodeBase16: Sized {
+    fn decode_base16_input<I>(input: &mut I) -> Result<Self, Base16Error> where I: Input<Token=char>;
+
+    fn decode_base16(string: &str) -> Result<Self, Base16Error> {
+        Self::decode_base16_input(&mut string.as_input())
+    }
+}
+
+pub trait EncodeBase16 {
+    fn encode_base16_output<O>(&self, output: O, alphabet: Base16Alphabet)
+        -> Result<O::Out, O::Err> where O: Output<Token=char>;
+
+    fn encode_base16<I, O>(&self, output: I) -> O::Out
+        where I: IntoOutput<IntoOut=O>, O: Output<Token=char>, O::Err: fmt::Debug {
+        let output = output.into_output();
+        self.encode_base16_output(output, Base16).unwrap()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Base16Alphabet {
+    Base16,
+    Base16Lower,
+}
+pub use self::Base16Alphabet::{Base16, Base16Lower};
+
+impl Base16Alphabet {
+    pub fn as_str(self) -> &'static [u8; 16] {
+        match self {
+            Base16      => b"0123456789ABCDEF",
+            Base16Lower => b"0123456789abcdef",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Base16Error {
+    Unexpected,
+}
+
+pub struct Base16Decoder<I: Input<Token=char>, O: Output<Token=u8>> {
+    pub output: O,
+    p: u8,
+    state: u32,
+    input: PhantomData<I>,
+}
+
+pub struct Base16Encoder<I: Input<Token=u8>, O: Output<Token=char>> {
+    alphabet: &'static [u8; 16],
+    pub input: I,
+    x: u8,
+    state: u32,
+    output: PhantomData<O>,
+}
+
+impl<I, O> Base16Decoder<I, O> where I: Input<Token=char>, O: Output<Token=u8> {
+    pub fn new(output: O) -> Self {
+        Self {
+            output: output,
+            p: 0,
+            state: 1,
+            input: PhantomData,
+        }
+    }
+
+    pub fn consume(mut self, input: &mut I) -> Result<O::Out, Base16Error> where O::Err: fmt::Debug {
+        loop {
+            match self.decode(input) {
+                Done(output) => return Ok(output),
+                Fail(error) => return Err(error),
+                Cont(next) => {
+                    if input.is_out() {
+                        input.over();
+                        self = next;
+                    } else {
+                        return Err(Base16Error::Unexpected);
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl<I, O> Decoder for Base16Decoder<I, O>
+    where I: Input<Token=char>,
+          O: Output<Token=u8>,
+          O::Err: fmt::Debug {
+
+    type Input = I;
+    type Output = O::Out;
+    type Error = Base16Error;
+
+    fn decode(mut self, input: &mut I) -> Then<Self, O::Out, Base16Error> {
+        loop {
+            match self.state {
+                1 => {
+                    match input.head() {
+                        In(c) if is_base16_char(c) => {
+                            input.step();
+                            self.p = decode_base16_char(c);
+                            self.state = 2;
+                        },
+                        In(_) | Over => return Done(self.output.take_out().unwrap()),
+                        Out => return Cont(self),
+                    };
+                },
+                2 => {
+                    match input.head() {
+                        In(c) if is_base16_char(c) => {
+                            input.step();
+                            self.output.push(self.p << 4 | decode_base16_char(c));
+                            self.p = 0;
+                            self.state = 1;
+                        },
+                        In(_) | Over => return Fail(Base16Error::Unexpected),
+                        Out => return Cont(self),
+                    };
+                },
+                _ => unreachable!(),
+            };
+        }
+    }
+}
+
+impl<I, O> Base16Encoder<I, O> where I: Input<Token=u8>, O: Output<Token=char> {
+    pub fn new(input: I, alphabet: Base16Alphabet) -> Self {
+        Self {
+            alphabet: alphabet.as_str(),
+            input: input,
+            x: 0,
+            state: 1,
+            output: PhantomData,
+        }
+    }
+
+    pub fn produce(mut self, mut output: O) -> Result<O::Out, O::Err> {
+        loop {
+            match self.encode(&mut output) {
+                Done(_) => return output.take_out(),
+                Fail(_) => unreachable!(),
+                Cont(next) => {
+                    self = next;
+                    self.input.over();
+                }
+            }
+        }
+    }
+
+    fn encode_base16_digit(&self, x: u8) -> char {
+        debug_assert!(x < 16);
+        unsafe { (*self.alphabet.get_unchecked(x as usize)) as char }
+    }
+}
+
+impl<I, O> Encoder for Base16Encoder<I, O> where I: Input<Token=u8>, O: Output<Token=char> {
+    type Input = I;
+    type Output = O;
+    type Error = ();
+
+    fn encode(mut self, output: &mut O) -> Then<Self, I, ()> {
+        while !output.is_full() {
+            match self.state {
+                1 => {
+                    match self.input.head() {
+                        In(x) => {
+                            self.input.step();
+                            self.x = x;
+                            self.state = 2;
+                            output.push(self.encode_base16_digit(x >> 4));
+                        },
+                        Over => return Done(self.input),
+                        Out => break,
+                    };
+                },
+                2 => {
+                    output.push(self.encode_base16_digit(self.x & 0x0F));
+                    self.x = 0;
+                    self.state = 1;
+                },
+                _ => unreachable!(),
+            };
+        }
+        return Cont(self);
+    }
+}
+
+#[inline]
+fn is_base16_char(c: char) -> bool {
+    c >= '0' && c <= '9' ||
+    c >= 'A' && c <= 'F' ||
+    c >= 'a' && c <= 'f'
+}
+
+#[inline]
+fn decode_base16_char(c: char) -> u8 {
+    if c >= '0' && c <= '9' {
+        c as u8 - '0' as u8
+    } else if c >= 'A' && c <= 'F' {
+        10 + (c as u8 - 'A' as u8)
+    } else if c >= 'a' && c <= 'f' {
+        10 + (c as u8 - 'a' as u8)
+    } else {
+      unreachable!()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::{SliceOutput, StrOutput};
+    use super::*;
+
+    fn assert_transcodes(encoded: &str, decoded: &[u8], alphabet: Base16Alphabet) {
+        let mut buffer = [0u8; 1024];
+        let decoder = Base16Decoder::new(SliceOutput::new(&mut buffer));
+        assert_eq!(decoder.consume(&mut encoded.as_input()).unwrap(), decoded);
+        let mut buffer = [0u8; 1024];
+        let encoder = Base16Encoder::new(decoded.as_input(), alphabet);
+        assert_eq!(encoder.produce(StrOutput::new(&mut buffer)).unwrap(), encoded);
+    }
+
+    fn assert_decode_fails(encoded: &str, error: Base16Error) {
+        let mut buffer = [0u8; 1024];
+        let decoder = Base16Decoder::new(SliceOutput::new(&mut buffer));
+        assert_eq!(decoder.consume(&mut encoded.as_input()).unwrap_err(), error);
+    }
+
+    #[test]
+    fn test_base16_rfc4648_vectors() {
+        assert_transcodes("", b"", Base16);
+        assert_transcodes("66", b"f", Base16);
+        assert_transcodes("666F", b"fo", Base16);
+        assert_transcodes("666F6F", b"foo", Base16);
+        assert_transcodes("666F6F62", b"foob", Base16);
+        assert_transcodes("666F6F6261", b"fooba", Base16);
+        assert_transcodes("666F6F626172", b"foobar", Base16);
+    }
+
+    #[test]
+    fn test_base16_transcode() {
+        assert_transcodes("00", &[0], Base16);
+        assert_transcodes("00FF7F80", &[0, 255, 127, 128], Base16);
+        assert_transcodes("0123456789abcdef", &[0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF], Base16Lower);
+        assert_transcodes("DEADBEEF", &[0xDE, 0xAD, 0xBE, 0xEF], Base16);
+    }
+
+    #[test]
+    fn test_base16_decode_mixed_case() {
+        let mut buffer = [0u8; 16];
+        let decoder = Base16Decoder::new(SliceOutput::new(&mut buffer));
+        assert_eq!(decoder.consume(&mut "dEaDbEeF".as_input()).unwrap(), &[0xDE, 0xAD, 0xBE, 0xEF]);
+    }
+
+    #[test]
+    fn test_base16_decode_odd_length() {
+        assert_decode_fails("ABC", Base16Error::Unexpected);
+        assert_decode_fails("A", Base16Error::Unexpected);
+    }
+}
@@ -0,0 +1,464 @@
+use core::fmt;
+use core::marker::PhantomData;
+use crate::step::{In, Out, Over};
+use crate::then::{Then, Cont, Done, Fail};
+use crate::input::{Input, AsInput};
+use crate::output::{Output, IntoOutput};
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+
+pub trait DecodeBase32: Sized {
+    fn decode_base32_input<I>(input: &mut I, alphabet: Base32Alphabet) -> Result<Self, Base32Error> where I: Input<Token=char>;
+
+    fn decode_base32(string: &str) -> Result<Self, Base32Error> {
+        Self::decode_base32_input(&mut string.as_input(), Base32)
+    }
+}
+
+pub trait EncodeBase32 {
+    fn encode_base32_output<O>(&self, output: O, alphabet: Base32Alphabet)
+        -> Result<O::Out, O::Err> where O: Output<Token=char>;
+
+    fn encode_base32<I, O>(&self, output: I) -> O::Out
+        where I: IntoOutput<IntoOut=O>, O: Output<Token=char>, O::Err: fmt::Debug {
+        let output = output.into_output();
+        self.encode_base32_output(output, Base32).unwrap()
+    }
+}
+
+/// Base32 digit alphabets.
+///
+/// `Base32` and `Base32Hex` are the RFC 4648 alphabets. `Crockford` is
+/// Douglas Crockford's alphabet, which excludes the easily confused letters
+/// `I`, `L`, `O` and `U`; when decoding, `I` and `L` read as `1`, `O` reads
+/// as `0`, and hyphens are ignored. All alphabets decode case-insensitively.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Base32Alphabet {
+    Base32,
+    Base32Hex,
+    Crockford,
+}
+pub use self::Base32Alphabet::{Base32, Base32Hex, Crockford};
+
+impl Base32Alphabet {
+    pub fn as_str(self) -> &'static [u8; 32] {
+        match self {
+            Base32    => b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567",
+            Base32Hex => b"0123456789ABCDEFGHIJKLMNOPQRSTUV",
+            Crockford => b"0123456789ABCDEFGHJKMNPQRSTVWXYZ",
+        }
+    }
+
+    /// Returns `true` if this alphabet conventionally pads its final quantum.
+    pub fn is_padded(self) -> bool {
+        match self {
+            Base32 | Base32Hex => true,
+            Crockford => false,
+        }
+    }
+
+    /// Returns the 5-bit value of digit `c`, or `None` if `c` is not a digit
+    /// of this alphabet.
+    pub(crate) fn decode_digit(self, c: char) -> Option<u8> {
+        let c = c.to_ascii_uppercase();
+        match self {
+            Base32 => {
+                if c >= 'A' && c <= 'Z' {
+                    Some(c as u8 - 'A' as u8)
+                } else if c >= '2' && c <= '7' {
+                    Some(26 + (c as u8 - '2' as u8))
+                } else {
+                    None
+                }
+            },
+            Base32Hex => {
+                if c >= '0' && c <= '9' {
+                    Some(c as u8 - '0' as u8)
+                } else if c >= 'A' && c <= 'V' {
+                    Some(10 + (c as u8 - 'A' as u8))
+                } else {
+                    None
+                }
+            },
+            Crockford => {
+                match c {
+                    '0'..='9' => Some(c as u8 - '0' as u8),
+                    'O' => Some(0),
+                    'I' | 'L' => Some(1),
+                    'A'..='H' => Some(10 + (c as u8 - 'A' as u8)),
+                    'J' | 'K' => Some(18 + (c as u8 - 'J' as u8)),
+                    'M' | 'N' => Some(20 + (c as u8 - 'M' as u8)),
+                    'P'..='T' => Some(22 + (c as u8 - 'P' as u8)),
+                    'V'..='Z' => Some(27 + (c as u8 - 'V' as u8)),
+                    _ => None,
+                }
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Base32Error {
+    Unexpected,
+    Unpadded,
+}
+
+pub struct Base32Decoder<I: Input<Token=char>, O: Output<Token=u8>> {
+    alphabet: Base32Alphabet,
+    pub output: O,
+    /// Bits of the current quantum, most significant digit first.
+    bits: u64,
+    /// Number of digits read in the current quantum.
+    digits: u8,
+    /// Number of padding characters read in the current quantum.
+    pads: u8,
+    padded: bool,
+    state: u32,
+    input: PhantomData<I>,
+}
+
+pub struct Base32Encoder<I: Input<Token=u8>, O: Output<Token=char>> {
+    alphabet: &'static [u8; 32],
+    pub input: I,
+    /// Bytes of the current quantum, most significant byte first.
+    bits: u64,
+    /// Number of bytes read in the current quantum.
+    bytes: u8,
+    /// Number of digits to write for the current quantum.
+    digits: u8,
+    /// Number of characters written for the current quantum.
+    index: u8,
+    padded: bool,
+    state: u32,
+    output: PhantomData<O>,
+}
+
+impl<I, O> Base32Decoder<I, O> where I: Input<Token=char>, O: Output<Token=u8> {
+    pub fn new(output: O, alphabet: Base32Alphabet) -> Self {
+        Self {
+            alphabet: alphabet,
+            output: output,
+            bits: 0,
+            digits: 0,
+            pads: 0,
+            padded: alphabet.is_padded(),
+            state: 1,
+            input: PhantomData,
+        }
+    }
+
+    pub fn padded(mut self, padded: bool) -> Self {
+        self.padded = padded;
+        self
+    }
+
+    pub fn consume(mut self, input: &mut I) -> Result<O::Out, Base32Error> where O::Err: fmt::Debug {
+        loop {
+            match self.decode(input) {
+                Done(output) => return Ok(output),
+                Fail(error) => return Err(error),
+                Cont(next) => {
+                    if input.is_out() {
+                        input.over();
+                        self = next;
+                    } else {
+                        return Err(Base32Error::Unexpected);
+                    }
+                },
+            }
+        }
+    }
+
+    /// Writes the bytes encoded by a quantum with the given number of digits.
+    fn flush(&mut self) {
+        let bytes = match self.digits {
+            8 => 5,
+            7 => 4,
+            5 => 3,
+            4 => 2,
+            2 => 1,
+            _ => unreachable!(),
+        };
+        let bits = self.bits << (5 * (8 - self.digits as u32));
+        for i in 0..bytes {
+            self.output.push((bits >> (32 - 8 * i)) as u8);
+        }
+        self.bits = 0;
+        self.digits = 0;
+        self.pads = 0;
+    }
+}
+
+impl<I, O> Decoder for Base32Decoder<I, O>
+    where I: Input<Token=char>,
+          O: Output<Token=u8>,
+          O::Err: fmt::Debug {
+
+    type Input = I;
+    type Output = O::Out;
+    type Error = Base32Error;
+
+    fn decode(mut self, input: &mut I) -> Then<Self, O::Out, Base32Error> {
+        loop {
+            match self.state {
+                1 => {
+                    let head = input.head();
+                    if let In(c) = head {
+                        if let Some(x) = self.alphabet.decode_digit(c) {
+                            input.step();
+                            self.bits = self.bits << 5 | x as u64;
+                            self.digits += 1;
+                            if self.digits == 8 {
+                                self.flush();
+                            }
+                            continue;
+                        } else if c == '-' && self.alphabet == Crockford {
+                            input.step();
+                            continue;
+                        } else if c == '=' && is_final_quantum(self.digits) {
+                            input.step();
+                            self.pads = 1;
+                            self.state = 2;
+                            continue;
+                        }
+                    }
+                    match head {
+                        Out => return Cont(self),
+                        _ if self.digits == 0 => return Done(self.output.take_out().unwrap()),
+                        _ if !is_final_quantum(self.digits) => return Fail(Base32Error::Unexpected),
+                        _ if self.padded => return Fail(Base32Error::Unpadded),
+                        _ => {
+                            self.flush();
+                            return Done(self.output.take_out().unwrap());
+                        },
+                    }
+                },
+                2 => {
+                    if self.digits + self.pads == 8 {
+                        self.flush();
+                        return Done(self.output.take_out().unwrap());
+                    }
+                    match input.head() {
+                        In('=') => {
+                            input.step();
+                            self.pads += 1;
+                        },
+                        In(_) | Over => return Fail(Base32Error::Unpadded),
+                        Out => return Cont(self),
+                    };
+                },
+                _ => unreachable!(),
+            };
+        }
+    }
+}
+
+impl<I, O> Base32Encoder<I, O> where I: Input<Token=u8>, O: Output<Token=char> {
+    pub fn new(input: I, alphabet: Base32Alphabet) -> Self {
+        Self {
+            alphabet: alphabet.as_str(),
+            input: input,
+            bits: 0,
+            bytes: 0,
+            digits: 0,
+            index: 0,
+            padded: alphabet.is_padded(),
+            state: 1,
+            output: PhantomData,
+        }
+    }
+
+    pub fn padded(mut self, padded: bool) -> Self {
+        self.padded = padded;
+        self
+    }
+
+    pub fn produce(mut self, mut output: O) -> Result<O::Out, O::Err> {
+        loop {
+            match self.encode(&mut output) {
+                Done(_) => return output.take_out(),
+                Fail(_) => unreachable!(),
+                Cont(next) => {
+                    self = next;
+                    self.input.over();
+                }
+            }
+        }
+    }
+
+    fn encode_base32_digit(&self, x: u8) -> char {
+        debug_assert!(x < 32);
+        unsafe { (*self.alphabet.get_unchecked(x as usize)) as char }
+    }
+}
+
+impl<I, O> Encoder for Base32Encoder<I, O> where I: Input<Token=u8>, O: Output<Token=char> {
+    type Input = I;
+    type Output = O;
+    type Error = ();
+
+    fn encode(mut self, output: &mut O) -> Then<Self, I, ()> {
+        while !output.is_full() {
+            match self.state {
+                1 => {
+                    match self.input.head() {
+                        In(x) => {
+                            self.input.step();
+                            self.bits = self.bits << 8 | x as u64;
+                            self.bytes += 1;
+                            if self.bytes == 5 {
+                                self.digits = 8;
+                                self.state = 2;
+                            }
+                        },
+                        Over if self.bytes == 0 => return Done(self.input),
+                        Over => {
+                            self.bits <<= 8 * (5 - self.bytes as u32);
+                            self.digits = match self.bytes {
+                                1 => 2,
+                                2 => 4,
+                                3 => 5,
+                                4 => 7,
+                                _ => unreachable!(),
+                            };
+                            self.state = 2;
+                        },
+                        Out => break,
+                    };
+                },
+                2 => {
+                    let x = (self.bits >> (35 - 5 * self.index as u32)) as u8 & 0x1F;
+                    output.push(self.encode_base32_digit(x));
+                    self.index += 1;
+                    if self.index == self.digits {
+                        if self.digits == 8 {
+                            self.bits = 0;
+                            self.bytes = 0;
+                            self.digits = 0;
+                            self.index = 0;
+                            self.state = 1;
+                        } else if self.padded {
+                            self.state = 3;
+                        } else {
+                            return Done(self.input);
+                        }
+                    }
+                },
+                3 => {
+                    output.push('=');
+                    self.index += 1;
+                    if self.index == 8 {
+                        return Done(self.input);
+                    }
+                },
+                _ => unreachable!(),
+            };
+        }
+        return Cont(self);
+    }
+}
+
+/// Returns `true` if a quantum with the given number of digits can be the
+/// final, partial quantum of an encoding.
+#[inline]
+fn is_final_quantum(digits: u8) -> bool {
+    digits == 2 || digits == 4 || digits == 5 || digits == 7
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::{SliceOutput, StrOutput};
+    use super::*;
+
+    fn assert_transcodes(encoded: &str, decoded: &[u8], alphabet: Base32Alphabet) {
+        let mut buffer = [0u8; 1024];
+        let decoder = Base32Decoder::new(SliceOutput::new(&mut buffer), alphabet);
+        assert_eq!(decoder.consume(&mut encoded.as_input()).unwrap(), decoded);
+        let mut buffer = [0u8; 1024];
+        let encoder = Base32Encoder::new(decoded.as_input(), alphabet);
+        assert_eq!(encoder.produce(StrOutput::new(&mut buffer)).unwrap(), encoded);
+    }
+
+    fn assert_decodes(encoded: &str, decoded: &[u8], alphabet: Base32Alphabet, padded: bool) {
+        let mut buffer = [0u8; 1024];
+        let decoder = Base32Decoder::new(SliceOutput::new(&mut buffer), alphabet).padded(padded);
+        assert_eq!(decoder.consume(&mut encoded.as_input()).unwrap(), decoded);
+    }
+
+    fn assert_decode_fails(encoded: &str, alphabet: Base32Alphabet, error: Base32Error) {
+        let mut buffer = [0u8; 1024];
+        let decoder = Base32Decoder::new(SliceOutput::new(&mut buffer), alphabet);
+        assert_eq!(decoder.consume(&mut encoded.as_input()).unwrap_err(), error);
+    }
+
+    #[test]
+    fn test_base32_rfc4648_vectors() {
+        assert_transcodes("", b"", Base32);
+        assert_transcodes("MY======", b"f", Base32);
+        assert_transcodes("MZXQ====", b"fo", Base32);
+        assert_transcodes("MZXW6===", b"foo", Base32);
+        assert_transcodes("MZXW6YQ=", b"foob", Base32);
+        assert_transcodes("MZXW6YTB", b"fooba", Base32);
+        assert_transcodes("MZXW6YTBOI======", b"foobar", Base32);
+    }
+
+    #[test]
+    fn test_base32hex_rfc4648_vectors() {
+        assert_transcodes("", b"", Base32Hex);
+        assert_transcodes("CO======", b"f", Base32Hex);
+        assert_transcodes("CPNG====", b"fo", Base32Hex);
+        assert_transcodes("CPNMU===", b"foo", Base32Hex);
+        assert_transcodes("CPNMUOG=", b"foob", Base32Hex);
+        assert_transcodes("CPNMUOJ1", b"fooba", Base32Hex);
+        assert_transcodes("CPNMUOJ1E8======", b"foobar", Base32Hex);
+    }
+
+    #[test]
+    fn test_crockford_transcode() {
+        assert_transcodes("", b"", Crockford);
+        assert_transcodes("CR", b"f", Crockford);
+        assert_transcodes("CSQG", b"fo", Crockford);
+        assert_transcodes("CSQPY", b"foo", Crockford);
+        assert_transcodes("CSQPYRG", b"foob", Crockford);
+        assert_transcodes("CSQPYRK1", b"fooba", Crockford);
+        assert_transcodes("CSQPYRK1E8", b"foobar", Crockford);
+        assert_transcodes("0000000000", &[0, 0, 0, 0, 0, 0], Crockford);
+        assert_transcodes("ZZZZZZZZ", &[255, 255, 255, 255, 255], Crockford);
+    }
+
+    #[test]
+    fn test_crockford_decode_aliases() {
+        assert_decodes("csqpyrk1e8", b"foobar", Crockford, false);
+        assert_decodes("CSQP-YRK1-E8", b"foobar", Crockford, false);
+        assert_decodes("oOoOoOoO", &[0, 0, 0, 0, 0], Crockford, false);
+        assert_decodes("0000000l", &[0, 0, 0, 0, 1], Crockford, false);
+        assert_decodes("0000000I", &[0, 0, 0, 0, 1], Crockford, false);
+    }
+
+    #[test]
+    fn test_base32_decode_unpadded() {
+        assert_decodes("MY", b"f", Base32, false);
+        assert_decodes("MZXQ", b"fo", Base32, false);
+        assert_decodes("MZXW6", b"foo", Base32, false);
+        assert_decodes("MZXW6YQ", b"foob", Base32, false);
+        assert_decodes("MZXW6YTBOI", b"foobar", Base32, false);
+        assert_decodes("mzxw6ytboi======", b"foobar", Base32, true);
+    }
+
+    #[test]
+    fn test_base32_decode_errors() {
+        assert_decode_fails("MY", Base32, Base32Error::Unpadded);
+        assert_decode_fails("MY=====", Base32, Base32Error::Unpadded);
+        assert_decode_fails("M", Base32, Base32Error::Unexpected);
+        assert_decode_fails("MZX", Base32, Base32Error::Unexpected);
+        assert_decode_fails("MZX=====", Base32, Base32Error::Unexpected);
+        assert_decode_fails("MZXW6Y", Base32, Base32Error::Unexpected);
+    }
+
+    #[test]
+    fn test_base32_encode_unpadded() {
+        let mut buffer = [0u8; 1024];
+        let encoder = Base32Encoder::new(b"foob".as_input(), Base32).padded(false);
+        assert_eq!(encoder.produce(StrOutput::new(&mut buffer)).unwrap(), "MZXW6YQ");
+    }
+}
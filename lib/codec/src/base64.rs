@@ -48,6 +48,23 @@ impl Base64Alphabet {
 pub enum Base64Error {
     Unexpected,
     Unpadded,
+    MixedAlphabet,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LineBreak {
+    Lf,
+    CrLf,
+}
+pub use self::LineBreak::{Lf, CrLf};
+
+impl LineBreak {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Lf   => "\n",
+            CrLf => "\r\n",
+        }
+    }
 }
 
 pub struct Base64Decoder<I: Input<Token=char>, O: Output<Token=u8>> {
@@ -56,6 +73,9 @@ pub struct Base64Decoder<I: Input<Token=char>, O: Output<Token=u8>> {
     q: u8,
     r: u8,
     padded: bool,
+    mime: bool,
+    alphabet: Option<Base64Alphabet>,
+    restricted: bool,
     state: u32,
     input: PhantomData<I>,
 }
@@ -67,6 +87,10 @@ pub struct Base64Encoder<I: Input<Token=u8>, O: Output<Token=char>> {
     y: u8,
     z: u8,
     padded: bool,
+    wrap: usize,
+    line_break: LineBreak,
+    column: usize,
+    breaks: usize,
     state: u32,
     output: PhantomData<O>,
 }
@@ -79,6 +103,9 @@ impl<I, O> Base64Decoder<I, O> where I: Input<Token=char>, O: Output<Token=u8> {
             q: 0,
             r: 0,
             padded: true,
+            mime: false,
+            alphabet: None,
+            restricted: false,
             state: 1,
             input: PhantomData,
         }
@@ -89,6 +116,45 @@ impl<I, O> Base64Decoder<I, O> where I: Input<Token=char>, O: Output<Token=u8> {
         self
     }
 
+    /// Skips whitespace and line breaks between digits, as found in
+    /// MIME bodies and PEM blobs.
+    pub fn mime(mut self, mime: bool) -> Self {
+        self.mime = mime;
+        self
+    }
+
+    /// Restricts decoding to a single `alphabet`; digits specific to the
+    /// other alphabet end the input instead of being decoded.
+    pub fn alphabet(mut self, alphabet: Base64Alphabet) -> Self {
+        self.alphabet = Some(alphabet);
+        self.restricted = true;
+        self
+    }
+
+    fn is_digit(&self, c: char) -> bool {
+        match c {
+            '+' | '/' => !self.restricted || self.alphabet == Some(Base64),
+            '-' | '_' => !self.restricted || self.alphabet == Some(Base64Url),
+            _ => is_base64_char(c),
+        }
+    }
+
+    fn detect(&mut self, c: char) -> Result<(), Base64Error> {
+        let alphabet = match c {
+            '+' | '/' => Base64,
+            '-' | '_' => Base64Url,
+            _ => return Ok(()),
+        };
+        match self.alphabet {
+            None => {
+                self.alphabet = Some(alphabet);
+                Ok(())
+            },
+            Some(detected) if detected == alphabet => Ok(()),
+            Some(_) => Err(Base64Error::MixedAlphabet),
+        }
+    }
+
     pub fn consume(mut self, input: &mut I) -> Result<O::Out, Base64Error> where O::Err: fmt::Debug {
         loop {
             match self.decode(input) {
@@ -118,10 +184,21 @@ impl<I, O> Decoder for Base64Decoder<I, O>
 
     fn decode(mut self, input: &mut I) -> Then<Self, O::Out, Base64Error> {
         loop {
+            if self.mime {
+                while let In(c) = input.head() {
+                    if !is_base64_space(c) {
+                        break;
+                    }
+                    input.step();
+                }
+            }
             match self.state {
                 1 => {
                     match input.head() {
-                        In(c) if is_base64_char(c) => {
+                        In(c) if self.is_digit(c) => {
+                            if let Err(error) = self.detect(c) {
+                                return Fail(error);
+                            }
                             input.step();
                             self.p = decode_base64_char(c);
                             self.state = 2;
@@ -132,7 +209,10 @@ impl<I, O> Decoder for Base64Decoder<I, O>
                 },
                 2 => {
                     match input.head() {
-                        In(c) if is_base64_char(c) => {
+                        In(c) if self.is_digit(c) => {
+                            if let Err(error) = self.detect(c) {
+                                return Fail(error);
+                            }
                             input.step();
                             self.q = decode_base64_char(c);
                             self.state = 3;
@@ -143,7 +223,10 @@ impl<I, O> Decoder for Base64Decoder<I, O>
                 },
                 3 => {
                     match input.head() {
-                        In(c) if is_base64_char(c) || c == '=' => {
+                        In(c) if self.is_digit(c) || c == '=' => {
+                            if let Err(error) = self.detect(c) {
+                                return Fail(error);
+                            }
                             input.step();
                             self.r = decode_base64_char(c);
                             if c != '=' {
@@ -162,7 +245,10 @@ impl<I, O> Decoder for Base64Decoder<I, O>
                 },
                 4 => {
                     match input.head() {
-                        In(c) if is_base64_char(c) || c == '=' => {
+                        In(c) if self.is_digit(c) || c == '=' => {
+                            if let Err(error) = self.detect(c) {
+                                return Fail(error);
+                            }
                             input.step();
                             let s = decode_base64_char(c);
                             decode_base64_quantum(self.p, self.q, self.r, s, &mut self.output);
@@ -212,6 +298,10 @@ impl<I, O> Base64Encoder<I, O> where I: Input<Token=u8>, O: Output<Token=char> {
             y: 0,
             z: 0,
             padded: true,
+            wrap: 0,
+            line_break: CrLf,
+            column: 0,
+            breaks: 0,
             state: 1,
             output: PhantomData,
         }
@@ -222,6 +312,14 @@ impl<I, O> Base64Encoder<I, O> where I: Input<Token=u8>, O: Output<Token=char> {
         self
     }
 
+    /// Breaks lines after every `columns` digits; a `columns` of zero
+    /// disables wrapping. No line break follows the final line.
+    pub fn wrap(mut self, columns: usize, line_break: LineBreak) -> Self {
+        self.wrap = columns;
+        self.line_break = line_break;
+        self
+    }
+
     pub fn produce(mut self, mut output: O) -> Result<O::Out, O::Err> {
         loop {
             match self.encode(&mut output) {
@@ -239,6 +337,11 @@ impl<I, O> Base64Encoder<I, O> where I: Input<Token=u8>, O: Output<Token=char> {
         debug_assert!(x < 64);
         unsafe { (*self.alphabet.get_unchecked(x as usize)) as char }
     }
+
+    fn push_digit(&mut self, output: &mut O, c: char) {
+        output.push(c);
+        self.column += 1;
+    }
 }
 
 impl<I, O> Encoder for Base64Encoder<I, O> where I: Input<Token=u8>, O: Output<Token=char> {
@@ -248,6 +351,17 @@ impl<I, O> Encoder for Base64Encoder<I, O> where I: Input<Token=u8>, O: Output<T
 
     fn encode(mut self, output: &mut O) -> Then<Self, I, ()> {
         while !output.is_full() {
+            if self.breaks != 0 {
+                let line_break = self.line_break.as_str().as_bytes();
+                output.push(line_break[line_break.len() - self.breaks] as char);
+                self.breaks -= 1;
+                continue;
+            } else if self.wrap != 0 && self.column == self.wrap
+                   && (self.state != 1 || self.input.is_in()) {
+                self.column = 0;
+                self.breaks = self.line_break.as_str().len();
+                continue;
+            }
             match self.state {
                 1 => {
                     match self.input.head() {
@@ -255,7 +369,7 @@ impl<I, O> Encoder for Base64Encoder<I, O> where I: Input<Token=u8>, O: Output<T
                             self.input.step();
                             self.x = x;
                             self.state = 2;
-                            output.push(self.encode_base64_digit(x >> 2));
+                            self.push_digit(output, self.encode_base64_digit(x >> 2));
                         },
                         Over => return Done(self.input),
                         Out => break,
@@ -267,10 +381,10 @@ impl<I, O> Encoder for Base64Encoder<I, O> where I: Input<Token=u8>, O: Output<T
                             self.input.step();
                             self.y = y;
                             self.state = 3;
-                            output.push(self.encode_base64_digit((self.x << 4 | y >> 4) & 0x3F));
+                            self.push_digit(output, self.encode_base64_digit((self.x << 4 | y >> 4) & 0x3F));
                         },
                         Over => {
-                            output.push(self.encode_base64_digit(self.x << 4 & 0x3F));
+                            self.push_digit(output, self.encode_base64_digit(self.x << 4 & 0x3F));
                             if self.padded {
                                 self.state = 5;
                             } else {
@@ -286,10 +400,10 @@ impl<I, O> Encoder for Base64Encoder<I, O> where I: Input<Token=u8>, O: Output<T
                             self.input.step();
                             self.z = z;
                             self.state = 4;
-                            output.push(self.encode_base64_digit((self.y << 2 | z >> 6) & 0x3F));
+                            self.push_digit(output, self.encode_base64_digit((self.y << 2 | z >> 6) & 0x3F));
                         },
                         Over => {
-                            output.push(self.encode_base64_digit(self.y << 2 & 0x3F));
+                            self.push_digit(output, self.encode_base64_digit(self.y << 2 & 0x3F));
                             if self.padded {
                                 self.state = 6;
                             } else {
@@ -300,18 +414,18 @@ impl<I, O> Encoder for Base64Encoder<I, O> where I: Input<Token=u8>, O: Output<T
                     }
                 },
                 4 => {
-                    output.push(self.encode_base64_digit(self.z & 0x3F));
+                    self.push_digit(output, self.encode_base64_digit(self.z & 0x3F));
                     self.z = 0;
                     self.y = 0;
                     self.x = 0;
                     self.state = 1;
                 },
                 5 => {
-                    output.push('=');
+                    self.push_digit(output, '=');
                     self.state = 6;
                 },
                 6 => {
-                    output.push('=');
+                    self.push_digit(output, '=');
                     return Done(self.input);
                 },
                 _ => unreachable!(),
@@ -330,6 +444,11 @@ fn is_base64_char(c: char) -> bool {
     c == '/' || c == '_'
 }
 
+#[inline]
+fn is_base64_space(c: char) -> bool {
+    c == ' ' || c == '\t' || c == '\r' || c == '\n'
+}
+
 #[inline]
 fn decode_base64_char(c: char) -> u8 {
     if c >= 'A' && c <= 'Z' {
@@ -369,6 +488,7 @@ fn decode_base64_quantum<O>(p: u8, q: u8, r: u8, s: u8, output: &mut O)
 #[cfg(test)]
 mod tests {
     use crate::output::{SliceOutput, StrOutput};
+    use crate::testing::{for_each_chunking, for_each_output_size, decode_chunked, encode_chunked};
     use super::*;
 
     fn assert_transcodes(encoded: &str, decoded: &[u8]) {
@@ -380,6 +500,17 @@ mod tests {
         assert_eq!(encoder.produce(StrOutput::new(&mut buffer)).unwrap(), encoded);
     }
 
+    #[test]
+    fn test_base64_rfc4648_vectors() {
+        assert_transcodes("", b"");
+        assert_transcodes("Zg==", b"f");
+        assert_transcodes("Zm8=", b"fo");
+        assert_transcodes("Zm9v", b"foo");
+        assert_transcodes("Zm9vYg==", b"foob");
+        assert_transcodes("Zm9vYmE=", b"fooba");
+        assert_transcodes("Zm9vYmFy", b"foobar");
+    }
+
     #[test]
     fn test_base64_transcode() {
         assert_transcodes("AA==", &[0]);
@@ -395,4 +526,85 @@ mod tests {
                             146, 89, 167, 162, 154, 171, 178, 219, 175, 195, 28, 179, 211,
                             93, 183, 227, 158, 187, 243, 223, 254]);
     }
+
+    #[test]
+    fn test_base64_decode_chunked() {
+        fn assert_decodes_chunked(encoded: &str, decoded: &[u8], mime: bool) {
+            for_each_chunking(encoded, |chunking| {
+                let mut buffer = [0u8; 64];
+                let decoder = Base64Decoder::new(SliceOutput::new(&mut buffer)).mime(mime);
+                assert_eq!(decode_chunked(decoder, encoded, chunking).unwrap(), decoded, "{:?}", chunking);
+            });
+        }
+        assert_decodes_chunked("", b"", false);
+        assert_decodes_chunked("Zg==", b"f", false);
+        assert_decodes_chunked("Zm9vYmE=", b"fooba", false);
+        assert_decodes_chunked("Zm9vYmFy", b"foobar", false);
+        assert_decodes_chunked("-_-_", &[251, 255, 191], false);
+        assert_decodes_chunked(" Zm9v\tYm\r\nE =\r\n", b"fooba", true);
+    }
+
+    #[test]
+    fn test_base64_encode_chunked() {
+        fn assert_encodes_chunked(decoded: &[u8], encoded: &str, wrap: usize) {
+            for_each_output_size(|size| {
+                let mut buffer = ['\0'; 64];
+                let encoder = Base64Encoder::new(decoded.as_input(), Base64).wrap(wrap, CrLf);
+                let output = encode_chunked(encoder, size, &mut buffer, |encoder| encoder.input.over()).unwrap();
+                assert!(output.iter().cloned().eq(encoded.chars()), "size {}", size);
+            });
+        }
+        assert_encodes_chunked(b"", "", 0);
+        assert_encodes_chunked(b"f", "Zg==", 0);
+        assert_encodes_chunked(b"fooba", "Zm9vYmE=", 0);
+        assert_encodes_chunked(b"foobar", "Zm9vYmFy", 0);
+        assert_encodes_chunked(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9], "AAEC\r\nAwQF\r\nBgcI\r\nCQ==", 4);
+    }
+
+    #[test]
+    fn test_base64_decode_mime() {
+        let mut buffer = [0u8; 64];
+        let decoder = Base64Decoder::new(SliceOutput::new(&mut buffer)).mime(true);
+        let encoded = "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8gISIjJCUmJygpKissLS4vMDEyMzQ1Njc4\r\n\
+                       OTo7\r\n";
+        assert_eq!(decoder.consume(&mut encoded.as_input()).unwrap(), &[
+            0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19,
+            20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39,
+            40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59][..]);
+
+        let mut buffer = [0u8; 16];
+        let decoder = Base64Decoder::new(SliceOutput::new(&mut buffer)).mime(true);
+        assert_eq!(decoder.consume(&mut " Zm9v\tYm\nE =\n".as_input()).unwrap(), b"fooba");
+
+        let mut buffer = [0u8; 16];
+        let decoder = Base64Decoder::new(SliceOutput::new(&mut buffer));
+        assert_eq!(decoder.consume(&mut "Zm9v\nYmFy".as_input()).unwrap(), b"foo");
+    }
+
+    #[test]
+    fn test_base64_encode_wrap() {
+        let decoded = &[0u8, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let mut buffer = [0u8; 64];
+        let encoder = Base64Encoder::new(decoded.as_input(), Base64).wrap(4, CrLf);
+        assert_eq!(encoder.produce(StrOutput::new(&mut buffer)).unwrap(), "AAEC\r\nAwQF\r\nBgcI\r\nCQ==");
+        let mut buffer = [0u8; 64];
+        let encoder = Base64Encoder::new(decoded.as_input(), Base64).wrap(8, Lf);
+        assert_eq!(encoder.produce(StrOutput::new(&mut buffer)).unwrap(), "AAECAwQF\nBgcICQ==");
+        let mut buffer = [0u8; 64];
+        let encoder = Base64Encoder::new(decoded[..6].as_input(), Base64).wrap(4, Lf);
+        assert_eq!(encoder.produce(StrOutput::new(&mut buffer)).unwrap(), "AAEC\nAwQF");
+    }
+
+    #[test]
+    fn test_base64_decode_mixed_alphabet() {
+        let mut buffer = [0u8; 16];
+        let decoder = Base64Decoder::new(SliceOutput::new(&mut buffer));
+        assert_eq!(decoder.consume(&mut "-_+/".as_input()).unwrap_err(), Base64Error::MixedAlphabet);
+        let mut buffer = [0u8; 16];
+        let decoder = Base64Decoder::new(SliceOutput::new(&mut buffer));
+        assert_eq!(decoder.consume(&mut "-_-_".as_input()).unwrap(), &[251, 255, 191]);
+        let mut buffer = [0u8; 16];
+        let decoder = Base64Decoder::new(SliceOutput::new(&mut buffer)).alphabet(Base64);
+        assert_eq!(decoder.consume(&mut "Zm9v-_".as_input()).unwrap(), b"foo");
+    }
 }
@@ -0,0 +1,381 @@
+use core::fmt;
+use core::marker::PhantomData;
+use crate::step::{In, Out, Over};
+use crate::then::{Then, Cont, Done, Fail};
+use crate::input::{Input, AsInput};
+use crate::output::{Output, IntoOutput};
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+
+pub trait DecodeBase85: Sized {
+    fn decode_base85_input<I>(input: &mut I, alphabet: Base85Alphabet) -> Result<Self, Base85Error> where I: Input<Token=char>;
+
+    fn decode_base85(string: &str) -> Result<Self, Base85Error> {
+        Self::decode_base85_input(&mut string.as_input(), Ascii85)
+    }
+}
+
+pub trait EncodeBase85 {
+    fn encode_base85_output<O>(&self, output: O, alphabet: Base85Alphabet)
+        -> Result<O::Out, O::Err> where O: Output<Token=char>;
+
+    fn encode_base85<I, O>(&self, output: I) -> O::Out
+        where I: IntoOutput<IntoOut=O>, O: Output<Token=char>, O::Err: fmt::Debug {
+        let output = output.into_output();
+        self.encode_base85_output(output, Ascii85).unwrap()
+    }
+}
+
+/// Base85 digit alphabets.
+///
+/// `Ascii85` is the Adobe/btoa alphabet of characters `!` through `u`, which
+/// abbreviates a group of four zero bytes as `z`. `Z85` is the ZeroMQ
+/// alphabet, which avoids quote and backslash characters so that encoded
+/// strings can be embedded in source code.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Base85Alphabet {
+    Ascii85,
+    Z85,
+}
+pub use self::Base85Alphabet::{Ascii85, Z85};
+
+impl Base85Alphabet {
+    pub fn as_str(self) -> &'static [u8; 85] {
+        match self {
+            Ascii85 => b"!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstu",
+            Z85     => b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#",
+        }
+    }
+
+    /// Returns `true` if this alphabet requires encoded data to consist of
+    /// whole 5 digit groups.
+    pub fn is_padded(self) -> bool {
+        match self {
+            Ascii85 => false,
+            Z85 => true,
+        }
+    }
+
+    /// Returns the value of digit `c`, or `None` if `c` is not a digit of this
+    /// alphabet.
+    fn decode_digit(self, c: char) -> Option<u8> {
+        match self {
+            Ascii85 => {
+                if c >= '!' && c <= 'u' {
+                    Some(c as u8 - '!' as u8)
+                } else {
+                    None
+                }
+            },
+            Z85 => {
+                if c >= ' ' && c <= '}' {
+                    let x = Z85_DECODE[(c as u8 - ' ' as u8) as usize];
+                    if x != 0xFF { Some(x) } else { None }
+                } else {
+                    None
+                }
+            },
+        }
+    }
+}
+
+/// Z85 digit values, indexed by character code minus 0x20.
+const Z85_DECODE: [u8; 94] = [
+    0xFF, 0x44, 0xFF, 0x54, 0x53, 0x52, 0x48, 0xFF, 0x4B, 0x4C, 0x46, 0x41, 0xFF, 0x3F, 0x3E, 0x45,
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x40, 0xFF, 0x49, 0x42, 0x4A, 0x47,
+    0x51, 0x24, 0x25, 0x26, 0x27, 0x28, 0x29, 0x2A, 0x2B, 0x2C, 0x2D, 0x2E, 0x2F, 0x30, 0x31, 0x32,
+    0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3A, 0x3B, 0x3C, 0x3D, 0x4D, 0xFF, 0x4E, 0x43, 0xFF,
+    0xFF, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18,
+    0x19, 0x1A, 0x1B, 0x1C, 0x1D, 0x1E, 0x1F, 0x20, 0x21, 0x22, 0x23, 0x4F, 0xFF, 0x50,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Base85Error {
+    Unexpected,
+    Unpadded,
+    Overflow,
+}
+
+pub struct Base85Decoder<I: Input<Token=char>, O: Output<Token=u8>> {
+    alphabet: Base85Alphabet,
+    pub output: O,
+    /// Accumulated value of the current group.
+    value: u64,
+    /// Number of digits read in the current group.
+    digits: u8,
+    padded: bool,
+    input: PhantomData<I>,
+}
+
+pub struct Base85Encoder<I: Input<Token=u8>, O: Output<Token=char>> {
+    alphabet: Base85Alphabet,
+    pub input: I,
+    /// Value of the current group.
+    value: u32,
+    /// Number of bytes read in the current group.
+    bytes: u8,
+    /// Number of digits to write for the current group.
+    digits: u8,
+    /// Number of digits written for the current group.
+    index: u8,
+    state: u32,
+    output: PhantomData<O>,
+}
+
+impl<I, O> Base85Decoder<I, O> where I: Input<Token=char>, O: Output<Token=u8> {
+    pub fn new(output: O, alphabet: Base85Alphabet) -> Self {
+        Self {
+            alphabet: alphabet,
+            output: output,
+            value: 0,
+            digits: 0,
+            padded: alphabet.is_padded(),
+            input: PhantomData,
+        }
+    }
+
+    pub fn padded(mut self, padded: bool) -> Self {
+        self.padded = padded;
+        self
+    }
+
+    pub fn consume(mut self, input: &mut I) -> Result<O::Out, Base85Error> where O::Err: fmt::Debug {
+        loop {
+            match self.decode(input) {
+                Done(output) => return Ok(output),
+                Fail(error) => return Err(error),
+                Cont(next) => {
+                    if input.is_out() {
+                        input.over();
+                        self = next;
+                    } else {
+                        return Err(Base85Error::Unexpected);
+                    }
+                },
+            }
+        }
+    }
+
+    /// Writes the bytes encoded by the current group; partial groups get
+    /// padded with the highest valued digit, and truncated.
+    fn flush(&mut self) -> Result<(), Base85Error> {
+        let bytes = self.digits - 1;
+        while self.digits < 5 {
+            self.value = self.value * 85 + 84;
+            self.digits += 1;
+        }
+        if self.value > 0xFFFFFFFF {
+            return Err(Base85Error::Overflow);
+        }
+        for i in 0..bytes {
+            self.output.push((self.value >> (24 - 8 * i)) as u8);
+        }
+        self.value = 0;
+        self.digits = 0;
+        Ok(())
+    }
+}
+
+impl<I, O> Decoder for Base85Decoder<I, O>
+    where I: Input<Token=char>,
+          O: Output<Token=u8>,
+          O::Err: fmt::Debug {
+
+    type Input = I;
+    type Output = O::Out;
+    type Error = Base85Error;
+
+    fn decode(mut self, input: &mut I) -> Then<Self, O::Out, Base85Error> {
+        loop {
+            let head = input.head();
+            if let In(c) = head {
+                if let Some(x) = self.alphabet.decode_digit(c) {
+                    input.step();
+                    self.value = self.value * 85 + x as u64;
+                    self.digits += 1;
+                    if self.digits == 5 {
+                        if let Err(error) = self.flush() {
+                            return Fail(error);
+                        }
+                    }
+                    continue;
+                } else if c == 'z' && self.alphabet == Ascii85 && self.digits == 0 {
+                    input.step();
+                    self.output.push(0);
+                    self.output.push(0);
+                    self.output.push(0);
+                    self.output.push(0);
+                    continue;
+                }
+            }
+            return match head {
+                Out => Cont(self),
+                _ if self.digits == 0 => Done(self.output.take_out().unwrap()),
+                _ if self.digits == 1 => Fail(Base85Error::Unexpected),
+                _ if self.padded => Fail(Base85Error::Unpadded),
+                _ => match self.flush() {
+                    Ok(()) => Done(self.output.take_out().unwrap()),
+                    Err(error) => Fail(error),
+                },
+            };
+        }
+    }
+}
+
+impl<I, O> Base85Encoder<I, O> where I: Input<Token=u8>, O: Output<Token=char> {
+    pub fn new(input: I, alphabet: Base85Alphabet) -> Self {
+        Self {
+            alphabet: alphabet,
+            input: input,
+            value: 0,
+            bytes: 0,
+            digits: 0,
+            index: 0,
+            state: 1,
+            output: PhantomData,
+        }
+    }
+
+    pub fn produce(mut self, mut output: O) -> Result<O::Out, O::Err> {
+        loop {
+            match self.encode(&mut output) {
+                Done(_) => return output.take_out(),
+                Fail(_) => unreachable!(),
+                Cont(next) => {
+                    self = next;
+                    self.input.over();
+                }
+            }
+        }
+    }
+
+    fn encode_base85_digit(&self, x: u32) -> char {
+        debug_assert!(x < 85);
+        unsafe { (*self.alphabet.as_str().get_unchecked(x as usize)) as char }
+    }
+}
+
+impl<I, O> Encoder for Base85Encoder<I, O> where I: Input<Token=u8>, O: Output<Token=char> {
+    type Input = I;
+    type Output = O;
+    type Error = ();
+
+    fn encode(mut self, output: &mut O) -> Then<Self, I, ()> {
+        while !output.is_full() {
+            match self.state {
+                1 => {
+                    match self.input.head() {
+                        In(x) => {
+                            self.input.step();
+                            self.value = self.value << 8 | x as u32;
+                            self.bytes += 1;
+                            if self.bytes == 4 {
+                                if self.value == 0 && self.alphabet == Ascii85 {
+                                    output.push('z');
+                                    self.bytes = 0;
+                                } else {
+                                    self.digits = 5;
+                                    self.state = 2;
+                                }
+                            }
+                        },
+                        Over if self.bytes == 0 => return Done(self.input),
+                        Over => {
+                            self.value <<= 8 * (4 - self.bytes as u32);
+                            self.digits = self.bytes + 1;
+                            self.state = 2;
+                        },
+                        Out => break,
+                    };
+                },
+                2 => {
+                    let x = self.value / POW85[self.index as usize] % 85;
+                    output.push(self.encode_base85_digit(x));
+                    self.index += 1;
+                    if self.index == self.digits {
+                        if self.digits == 5 {
+                            self.value = 0;
+                            self.bytes = 0;
+                            self.digits = 0;
+                            self.index = 0;
+                            self.state = 1;
+                        } else {
+                            return Done(self.input);
+                        }
+                    }
+                },
+                _ => unreachable!(),
+            };
+        }
+        return Cont(self);
+    }
+}
+
+/// Place values of the digits of a 5 digit group, most significant first.
+const POW85: [u32; 5] = [85 * 85 * 85 * 85, 85 * 85 * 85, 85 * 85, 85, 1];
+
+#[cfg(test)]
+mod tests {
+    use crate::output::{SliceOutput, StrOutput};
+    use super::*;
+
+    fn assert_transcodes(encoded: &str, decoded: &[u8], alphabet: Base85Alphabet) {
+        let mut buffer = [0u8; 1024];
+        let decoder = Base85Decoder::new(SliceOutput::new(&mut buffer), alphabet);
+        assert_eq!(decoder.consume(&mut encoded.as_input()).unwrap(), decoded);
+        let mut buffer = [0u8; 1024];
+        let encoder = Base85Encoder::new(decoded.as_input(), alphabet);
+        assert_eq!(encoder.produce(StrOutput::new(&mut buffer)).unwrap(), encoded);
+    }
+
+    fn assert_decode_fails(encoded: &str, alphabet: Base85Alphabet, error: Base85Error) {
+        let mut buffer = [0u8; 1024];
+        let decoder = Base85Decoder::new(SliceOutput::new(&mut buffer), alphabet);
+        assert_eq!(decoder.consume(&mut encoded.as_input()).unwrap_err(), error);
+    }
+
+    #[test]
+    fn test_ascii85_transcode() {
+        assert_transcodes("", b"", Ascii85);
+        assert_transcodes("Ac", b"f", Ascii85);
+        assert_transcodes("Ao@", b"fo", Ascii85);
+        assert_transcodes("AoDS", b"foo", Ascii85);
+        assert_transcodes("AoDTs", b"foob", Ascii85);
+        assert_transcodes("AoDTs@/", b"fooba", Ascii85);
+        assert_transcodes("AoDTs@<)", b"foobar", Ascii85);
+        assert_transcodes("z", &[0, 0, 0, 0], Ascii85);
+        assert_transcodes("zz!!", &[0, 0, 0, 0, 0, 0, 0, 0, 0], Ascii85);
+        assert_transcodes("s8W-!", &[255, 255, 255, 255], Ascii85);
+        assert_transcodes("9jqo^BlbD-BleB1DJ+*+F(f,q", b"Man is distinguished", Ascii85);
+    }
+
+    #[test]
+    fn test_z85_rfc32_vectors() {
+        assert_transcodes("HelloWorld", &[0x86, 0x4F, 0xD2, 0x6F, 0xB5, 0x59, 0xF7, 0x5B], Z85);
+        assert_transcodes("JTKVSB%%)wK0E.X)V>+}o?pNmC{O&4W4b!Ni{Lh6",
+                          &[0x8E, 0x0B, 0xDD, 0x69, 0x76, 0x28, 0xB9, 0x1D,
+                            0x8F, 0x24, 0x55, 0x87, 0xEE, 0x95, 0xC5, 0xB0,
+                            0x4D, 0x48, 0x96, 0x3F, 0x79, 0x25, 0x98, 0x77,
+                            0xB4, 0x9C, 0xD9, 0x06, 0x3A, 0xEA, 0xD3, 0xB7], Z85);
+        assert_transcodes("00000", &[0, 0, 0, 0], Z85);
+    }
+
+    #[test]
+    fn test_z85_unpadded() {
+        let mut buffer = [0u8; 1024];
+        let decoder = Base85Decoder::new(SliceOutput::new(&mut buffer), Z85).padded(false);
+        assert_eq!(decoder.consume(&mut "Hello".as_input()).unwrap(), &[0x86, 0x4F, 0xD2, 0x6F]);
+        let mut buffer = [0u8; 1024];
+        let decoder = Base85Decoder::new(SliceOutput::new(&mut buffer), Z85).padded(false);
+        assert_eq!(decoder.consume(&mut "HelloW".as_input()).unwrap_err(), Base85Error::Unexpected);
+        assert_decode_fails("HelloWor", Z85, Base85Error::Unpadded);
+    }
+
+    #[test]
+    fn test_base85_decode_errors() {
+        assert_decode_fails("A", Ascii85, Base85Error::Unexpected);
+        assert_decode_fails("Az", Ascii85, Base85Error::Unexpected);
+        assert_decode_fails("s8W-\"", Ascii85, Base85Error::Overflow);
+        assert_decode_fails("#####", Z85, Base85Error::Overflow);
+    }
+}
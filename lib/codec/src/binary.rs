@@ -0,0 +1,756 @@
+use core::fmt;
+use core::marker::PhantomData;
+use swim_core::f16;
+use crate::step::{In, Out, Over};
+use crate::then::{Then, Cont, Done, Fail};
+use crate::input::Input;
+use crate::output::Output;
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ByteOrder {
+    BigEndian,
+    LittleEndian,
+}
+pub use self::ByteOrder::{BigEndian, LittleEndian};
+
+/// How the length of a byte string is written ahead of its bytes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LengthPrefix {
+    Varint,
+    U8,
+    U16(ByteOrder),
+    U32(ByteOrder),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BinaryError {
+    Unexpected,
+    Overflow,
+    TooLong,
+}
+
+/// A primitive value with a fixed-width binary encoding of up to 8 bytes.
+pub trait FixedWidth: Copy {
+    const SIZE: usize;
+
+    fn to_bits(self) -> u64;
+
+    fn from_bits(bits: u64) -> Self;
+}
+
+macro_rules! impl_fixed_width_int {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl FixedWidth for $t {
+                const SIZE: usize = core::mem::size_of::<$t>();
+
+                #[inline]
+                fn to_bits(self) -> u64 {
+                    self as $u as u64
+                }
+
+                #[inline]
+                fn from_bits(bits: u64) -> $t {
+                    bits as $u as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_fixed_width_int!(u8 => u8, u16 => u16, u32 => u32, u64 => u64,
+                      i8 => u8, i16 => u16, i32 => u32, i64 => u64);
+
+macro_rules! impl_fixed_width_float {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl FixedWidth for $t {
+                const SIZE: usize = core::mem::size_of::<$t>();
+
+                #[inline]
+                fn to_bits(self) -> u64 {
+                    <$t>::to_bits(self) as u64
+                }
+
+                #[inline]
+                fn from_bits(bits: u64) -> $t {
+                    <$t>::from_bits(bits as $u)
+                }
+            }
+        )*
+    };
+}
+
+impl_fixed_width_float!(f16 => u16, f32 => u32, f64 => u64);
+
+/// Maps signed integers to unsigned integers so that values of small
+/// magnitude have small encodings: 0, -1, 1, -2, ... become 0, 1, 2, 3, ...
+#[inline]
+pub fn zigzag_encode(n: i64) -> u64 {
+    (n << 1 ^ n >> 63) as u64
+}
+
+#[inline]
+pub fn zigzag_decode(n: u64) -> i64 {
+    (n >> 1) as i64 ^ -((n & 1) as i64)
+}
+
+/// Decodes an unsigned LEB128 varint.
+pub struct VarintDecoder<I: Input<Token=u8>> {
+    value: u64,
+    shift: u32,
+    input: PhantomData<I>,
+}
+
+/// Decodes a signed LEB128 varint.
+pub struct SignedVarintDecoder<I: Input<Token=u8>> {
+    value: u64,
+    shift: u32,
+    input: PhantomData<I>,
+}
+
+pub struct VarintEncoder<O: Output<Token=u8>> {
+    value: u64,
+    rest: u64,
+    done: bool,
+    output: PhantomData<O>,
+}
+
+pub struct SignedVarintEncoder<O: Output<Token=u8>> {
+    value: i64,
+    rest: i64,
+    done: bool,
+    output: PhantomData<O>,
+}
+
+pub struct FixedDecoder<T: FixedWidth, I: Input<Token=u8>> {
+    order: ByteOrder,
+    bits: u64,
+    count: usize,
+    value: PhantomData<T>,
+    input: PhantomData<I>,
+}
+
+pub struct FixedEncoder<T: FixedWidth, O: Output<Token=u8>> {
+    value: T,
+    order: ByteOrder,
+    count: usize,
+    output: PhantomData<O>,
+}
+
+/// Default maximum length of a byte string accepted by a `BytesDecoder`.
+pub const MAX_LEN: u64 = 16 * 1024 * 1024;
+
+/// Decodes a length-prefixed byte string into an output.
+pub struct BytesDecoder<I: Input<Token=u8>, O: Output<Token=u8>> {
+    pub output: O,
+    prefix: LengthPrefix,
+    max_len: u64,
+    len: u64,
+    count: usize,
+    state: u32,
+    input: PhantomData<I>,
+}
+
+pub struct BytesEncoder<'b, O: Output<Token=u8>> {
+    bytes: &'b [u8],
+    prefix: LengthPrefix,
+    header: [u8; 10],
+    header_len: usize,
+    offset: usize,
+    output: PhantomData<O>,
+}
+
+impl ByteOrder {
+    #[inline]
+    fn shift(self, size: usize, index: usize) -> usize {
+        match self {
+            BigEndian => 8 * (size - 1 - index),
+            LittleEndian => 8 * index,
+        }
+    }
+}
+
+impl LengthPrefix {
+    pub(crate) fn max_len(self) -> u64 {
+        match self {
+            LengthPrefix::Varint => u64::max_value(),
+            LengthPrefix::U8 => 0xFF,
+            LengthPrefix::U16(_) => 0xFFFF,
+            LengthPrefix::U32(_) => 0xFFFF_FFFF,
+        }
+    }
+
+    /// Adds the next `byte` of a prefix to `len`, of which `count` bytes
+    /// have already been read; returns `true` once the prefix is complete.
+    pub(crate) fn read(self, len: &mut u64, count: &mut usize, byte: u8) -> Result<bool, BinaryError> {
+        let (size, order) = match self {
+            LengthPrefix::Varint => {
+                let shift = 7 * *count;
+                if shift == 63 && byte > 1 {
+                    return Err(BinaryError::Overflow);
+                }
+                *len |= ((byte & 0x7F) as u64) << shift;
+                *count += 1;
+                return Ok(byte & 0x80 == 0);
+            },
+            LengthPrefix::U8 => (1, BigEndian),
+            LengthPrefix::U16(order) => (2, order),
+            LengthPrefix::U32(order) => (4, order),
+        };
+        *len |= (byte as u64) << order.shift(size, *count);
+        *count += 1;
+        Ok(*count == size)
+    }
+
+    /// Writes `len` into `header`, returning the number of bytes written.
+    fn write(self, len: u64, header: &mut [u8; 10]) -> usize {
+        let (size, order) = match self {
+            LengthPrefix::Varint => {
+                let mut rest = len;
+                let mut count = 0;
+                loop {
+                    let byte = (rest & 0x7F) as u8;
+                    rest >>= 7;
+                    if rest == 0 {
+                        header[count] = byte;
+                        return count + 1;
+                    }
+                    header[count] = byte | 0x80;
+                    count += 1;
+                }
+            },
+            LengthPrefix::U8 => (1, BigEndian),
+            LengthPrefix::U16(order) => (2, order),
+            LengthPrefix::U32(order) => (4, order),
+        };
+        for index in 0..size {
+            header[index] = (len >> order.shift(size, index)) as u8;
+        }
+        size
+    }
+}
+
+macro_rules! impl_consume {
+    () => {
+        pub fn consume(mut self, input: &mut I) -> Result<<Self as Decoder>::Output, BinaryError> {
+            loop {
+                match self.decode(input) {
+                    Done(value) => return Ok(value),
+                    Fail(error) => return Err(error),
+                    Cont(next) => {
+                        if input.is_out() {
+                            input.over();
+                            self = next;
+                        } else {
+                            return Err(BinaryError::Unexpected);
+                        }
+                    },
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_produce {
+    () => {
+        pub fn produce(mut self, mut output: O) -> Result<O::Out, O::Err> {
+            loop {
+                match self.encode(&mut output) {
+                    Done(_) => return output.take_out(),
+                    Fail(_) => unreachable!(),
+                    Cont(next) => self = next,
+                }
+            }
+        }
+    };
+}
+
+impl<I> VarintDecoder<I> where I: Input<Token=u8> {
+    pub fn new() -> Self {
+        Self {
+            value: 0,
+            shift: 0,
+            input: PhantomData,
+        }
+    }
+
+    impl_consume!();
+}
+
+impl<I> Decoder for VarintDecoder<I> where I: Input<Token=u8> {
+    type Input = I;
+    type Output = u64;
+    type Error = BinaryError;
+
+    fn decode(mut self, input: &mut I) -> Then<Self, u64, BinaryError> {
+        loop {
+            match input.head() {
+                In(byte) => {
+                    input.step();
+                    // The tenth byte holds only the most significant bit.
+                    if self.shift == 63 && byte > 1 {
+                        return Fail(BinaryError::Overflow);
+                    }
+                    self.value |= ((byte & 0x7F) as u64) << self.shift;
+                    if byte & 0x80 == 0 {
+                        return Done(self.value);
+                    }
+                    self.shift += 7;
+                },
+                Out => return Cont(self),
+                Over => return Fail(BinaryError::Unexpected),
+            };
+        }
+    }
+}
+
+impl<I> SignedVarintDecoder<I> where I: Input<Token=u8> {
+    pub fn new() -> Self {
+        Self {
+            value: 0,
+            shift: 0,
+            input: PhantomData,
+        }
+    }
+
+    impl_consume!();
+}
+
+impl<I> Decoder for SignedVarintDecoder<I> where I: Input<Token=u8> {
+    type Input = I;
+    type Output = i64;
+    type Error = BinaryError;
+
+    fn decode(mut self, input: &mut I) -> Then<Self, i64, BinaryError> {
+        loop {
+            match input.head() {
+                In(byte) => {
+                    input.step();
+                    // The tenth byte may only extend the sign bit.
+                    if self.shift == 63 && byte != 0 && byte != 0x7F {
+                        return Fail(BinaryError::Overflow);
+                    }
+                    self.value |= ((byte & 0x7F) as u64) << self.shift;
+                    self.shift += 7;
+                    if byte & 0x80 == 0 {
+                        if self.shift < 64 && byte & 0x40 != 0 {
+                            self.value |= !0u64 << self.shift;
+                        }
+                        return Done(self.value as i64);
+                    }
+                },
+                Out => return Cont(self),
+                Over => return Fail(BinaryError::Unexpected),
+            };
+        }
+    }
+}
+
+impl<O> VarintEncoder<O> where O: Output<Token=u8> {
+    pub fn new(value: u64) -> Self {
+        Self {
+            value: value,
+            rest: value,
+            done: false,
+            output: PhantomData,
+        }
+    }
+
+    impl_produce!();
+}
+
+impl<O> Encoder for VarintEncoder<O> where O: Output<Token=u8> {
+    type Input = u64;
+    type Output = O;
+    type Error = ();
+
+    fn encode(mut self, output: &mut O) -> Then<Self, u64, ()> {
+        while !self.done && !output.is_full() {
+            let byte = (self.rest & 0x7F) as u8;
+            self.rest >>= 7;
+            self.done = self.rest == 0;
+            output.push(if self.done { byte } else { byte | 0x80 });
+        }
+        if self.done {
+            Done(self.value)
+        } else {
+            Cont(self)
+        }
+    }
+}
+
+impl<O> SignedVarintEncoder<O> where O: Output<Token=u8> {
+    pub fn new(value: i64) -> Self {
+        Self {
+            value: value,
+            rest: value,
+            done: false,
+            output: PhantomData,
+        }
+    }
+
+    impl_produce!();
+}
+
+impl<O> Encoder for SignedVarintEncoder<O> where O: Output<Token=u8> {
+    type Input = i64;
+    type Output = O;
+    type Error = ();
+
+    fn encode(mut self, output: &mut O) -> Then<Self, i64, ()> {
+        while !self.done && !output.is_full() {
+            let byte = (self.rest & 0x7F) as u8;
+            self.rest >>= 7;
+            self.done = self.rest == 0 && byte & 0x40 == 0 || self.rest == -1 && byte & 0x40 != 0;
+            output.push(if self.done { byte } else { byte | 0x80 });
+        }
+        if self.done {
+            Done(self.value)
+        } else {
+            Cont(self)
+        }
+    }
+}
+
+impl<T, I> FixedDecoder<T, I> where T: FixedWidth, I: Input<Token=u8> {
+    pub fn new(order: ByteOrder) -> Self {
+        Self {
+            order: order,
+            bits: 0,
+            count: 0,
+            value: PhantomData,
+            input: PhantomData,
+        }
+    }
+
+    impl_consume!();
+}
+
+impl<T, I> Decoder for FixedDecoder<T, I> where T: FixedWidth, I: Input<Token=u8> {
+    type Input = I;
+    type Output = T;
+    type Error = BinaryError;
+
+    fn decode(mut self, input: &mut I) -> Then<Self, T, BinaryError> {
+        while self.count < T::SIZE {
+            match input.head() {
+                In(byte) => {
+                    input.step();
+                    self.bits |= (byte as u64) << self.order.shift(T::SIZE, self.count);
+                    self.count += 1;
+                },
+                Out => return Cont(self),
+                Over => return Fail(BinaryError::Unexpected),
+            };
+        }
+        Done(T::from_bits(self.bits))
+    }
+}
+
+impl<T, O> FixedEncoder<T, O> where T: FixedWidth, O: Output<Token=u8> {
+    pub fn new(value: T, order: ByteOrder) -> Self {
+        Self {
+            value: value,
+            order: order,
+            count: 0,
+            output: PhantomData,
+        }
+    }
+
+    impl_produce!();
+}
+
+impl<T, O> Encoder for FixedEncoder<T, O> where T: FixedWidth, O: Output<Token=u8> {
+    type Input = T;
+    type Output = O;
+    type Error = ();
+
+    fn encode(mut self, output: &mut O) -> Then<Self, T, ()> {
+        let bits = self.value.to_bits();
+        while self.count < T::SIZE && !output.is_full() {
+            output.push((bits >> self.order.shift(T::SIZE, self.count)) as u8);
+            self.count += 1;
+        }
+        if self.count == T::SIZE {
+            Done(self.value)
+        } else {
+            Cont(self)
+        }
+    }
+}
+
+impl<I, O> BytesDecoder<I, O> where I: Input<Token=u8>, O: Output<Token=u8> {
+    pub fn new(output: O, prefix: LengthPrefix) -> Self {
+        Self {
+            output: output,
+            prefix: prefix,
+            max_len: MAX_LEN,
+            len: 0,
+            count: 0,
+            state: 1,
+            input: PhantomData,
+        }
+    }
+
+    /// Rejects byte strings longer than `max_len`, which defaults to
+    /// `MAX_LEN`, before reading them. Byte strings that overrun the output
+    /// also fail with `TooLong`.
+    pub fn max_len(mut self, max_len: u64) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    pub fn consume(mut self, input: &mut I) -> Result<O::Out, BinaryError> where O::Err: fmt::Debug {
+        loop {
+            match self.decode(input) {
+                Done(output) => return Ok(output),
+                Fail(error) => return Err(error),
+                Cont(next) => {
+                    if input.is_out() {
+                        input.over();
+                        self = next;
+                    } else {
+                        return Err(BinaryError::Unexpected);
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl<I, O> Decoder for BytesDecoder<I, O>
+    where I: Input<Token=u8>,
+          O: Output<Token=u8>,
+          O::Err: fmt::Debug {
+
+    type Input = I;
+    type Output = O::Out;
+    type Error = BinaryError;
+
+    fn decode(mut self, input: &mut I) -> Then<Self, O::Out, BinaryError> {
+        loop {
+            match self.state {
+                1 => {
+                    let byte = match input.head() {
+                        In(byte) => {
+                            input.step();
+                            byte
+                        },
+                        Out => return Cont(self),
+                        Over => return Fail(BinaryError::Unexpected),
+                    };
+                    match self.prefix.read(&mut self.len, &mut self.count, byte) {
+                        Ok(true) => {},
+                        Ok(false) => continue,
+                        Err(error) => return Fail(error),
+                    };
+                    if self.len > self.max_len {
+                        return Fail(BinaryError::TooLong);
+                    }
+                    self.state = 2;
+                },
+                2 => {
+                    while self.len > 0 {
+                        match input.head() {
+                            In(_) if self.output.is_full() => return Fail(BinaryError::TooLong),
+                            In(byte) => {
+                                input.step();
+                                self.output.push(byte);
+                                self.len -= 1;
+                            },
+                            Out => return Cont(self),
+                            Over => return Fail(BinaryError::Unexpected),
+                        };
+                    }
+                    return Done(self.output.take_out().unwrap());
+                },
+                _ => unreachable!(),
+            };
+        }
+    }
+}
+
+impl<'b, O> BytesEncoder<'b, O> where O: Output<Token=u8> {
+    /// Returns an encoder for `bytes`, or a `TooLong` error if their length
+    /// doesn't fit in `prefix`.
+    pub fn new(bytes: &'b [u8], prefix: LengthPrefix) -> Result<Self, BinaryError> {
+        let len = bytes.len() as u64;
+        if len > prefix.max_len() {
+            return Err(BinaryError::TooLong);
+        }
+        let mut header = [0u8; 10];
+        let header_len = prefix.write(len, &mut header);
+        Ok(Self {
+            bytes: bytes,
+            prefix: prefix,
+            header: header,
+            header_len: header_len,
+            offset: 0,
+            output: PhantomData,
+        })
+    }
+
+    pub fn prefix(&self) -> LengthPrefix {
+        self.prefix
+    }
+
+    impl_produce!();
+}
+
+impl<'b, O> Encoder for BytesEncoder<'b, O> where O: Output<Token=u8> {
+    type Input = &'b [u8];
+    type Output = O;
+    type Error = ();
+
+    fn encode(mut self, output: &mut O) -> Then<Self, &'b [u8], ()> {
+        let total = self.header_len + self.bytes.len();
+        while self.offset < total && !output.is_full() {
+            if self.offset < self.header_len {
+                output.push(self.header[self.offset]);
+            } else {
+                output.push(self.bytes[self.offset - self.header_len]);
+            }
+            self.offset += 1;
+        }
+        if self.offset == total {
+            Done(self.bytes)
+        } else {
+            Cont(self)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::{AsInput, SliceInput};
+    use crate::output::SliceOutput;
+    use crate::testing::{for_each_chunking, decode_chunked};
+    use super::*;
+
+    /// Decodes `encoded` fed in every chunking.
+    fn assert_decodes_chunked<'a, D, F>(encoded: &'a [u8], new: F, expected: D::Output)
+        where D: Decoder<Input=SliceInput<'a, u8>, Error=BinaryError>,
+              D::Output: PartialEq + fmt::Debug,
+              F: Fn() -> D {
+        for_each_chunking(encoded, |chunking| {
+            assert_eq!(decode_chunked(new(), encoded, chunking).unwrap(), expected, "{:?}", chunking);
+        });
+    }
+
+    #[test]
+    fn test_varint_transcode() {
+        let cases: [(u64, &[u8]); 6] = [
+            (0, &[0x00]),
+            (1, &[0x01]),
+            (127, &[0x7F]),
+            (128, &[0x80, 0x01]),
+            (624485, &[0xE5, 0x8E, 0x26]),
+            (u64::max_value(), &[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]),
+        ];
+        for &(value, encoded) in cases.iter() {
+            let mut buffer = [0u8; 10];
+            assert_eq!(VarintEncoder::new(value).produce(SliceOutput::new(&mut buffer)).unwrap(), encoded);
+            assert_decodes_chunked(encoded, VarintDecoder::new, value);
+        }
+    }
+
+    #[test]
+    fn test_signed_varint_transcode() {
+        let cases: [(i64, &[u8]); 7] = [
+            (0, &[0x00]),
+            (-1, &[0x7F]),
+            (63, &[0x3F]),
+            (-64, &[0x40]),
+            (64, &[0xC0, 0x00]),
+            (-123456, &[0xC0, 0xBB, 0x78]),
+            (i64::min_value(), &[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7F]),
+        ];
+        for &(value, encoded) in cases.iter() {
+            let mut buffer = [0u8; 10];
+            assert_eq!(SignedVarintEncoder::new(value).produce(SliceOutput::new(&mut buffer)).unwrap(), encoded);
+            assert_decodes_chunked(encoded, SignedVarintDecoder::new, value);
+        }
+    }
+
+    #[test]
+    fn test_zigzag() {
+        let cases = [(0i64, 0u64), (-1, 1), (1, 2), (-2, 3), (i64::max_value(), u64::max_value() - 1), (i64::min_value(), u64::max_value())];
+        for &(signed, unsigned) in cases.iter() {
+            assert_eq!(zigzag_encode(signed), unsigned);
+            assert_eq!(zigzag_decode(unsigned), signed);
+        }
+    }
+
+    #[test]
+    fn test_fixed_transcode() {
+        fn assert_transcodes<T>(value: T, order: ByteOrder, encoded: &[u8])
+            where T: FixedWidth + PartialEq + fmt::Debug {
+            let mut buffer = [0u8; 8];
+            assert_eq!(FixedEncoder::new(value, order).produce(SliceOutput::new(&mut buffer)).unwrap(), encoded);
+            assert_decodes_chunked(encoded, || FixedDecoder::<T, _>::new(order), value);
+        }
+        assert_transcodes(0xABu8, BigEndian, &[0xAB]);
+        assert_transcodes(-2i8, LittleEndian, &[0xFE]);
+        assert_transcodes(0x1234u16, BigEndian, &[0x12, 0x34]);
+        assert_transcodes(0x1234u16, LittleEndian, &[0x34, 0x12]);
+        assert_transcodes(-2i32, BigEndian, &[0xFF, 0xFF, 0xFF, 0xFE]);
+        assert_transcodes(0x0102030405060708u64, LittleEndian, &[8, 7, 6, 5, 4, 3, 2, 1]);
+        assert_transcodes(i64::min_value(), BigEndian, &[0x80, 0, 0, 0, 0, 0, 0, 0]);
+        assert_transcodes(f16::from(1.5f32), BigEndian, &[0x3E, 0x00]);
+        assert_transcodes(-2.0f32, LittleEndian, &[0x00, 0x00, 0x00, 0xC0]);
+        assert_transcodes(0.1f64, BigEndian, &[0x3F, 0xB9, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9A]);
+    }
+
+    #[test]
+    fn test_bytes_transcode() {
+        fn assert_transcodes(bytes: &[u8], prefix: LengthPrefix, header: &[u8]) {
+            let mut encoded = [0u8; 300];
+            let encoded = BytesEncoder::new(bytes, prefix).unwrap().produce(SliceOutput::new(&mut encoded)).unwrap();
+            assert_eq!(&encoded[..header.len()], header);
+            assert_eq!(&encoded[header.len()..], bytes);
+            for split in 0..=encoded.len() {
+                let mut buffer = [0u8; 300];
+                let decoder = BytesDecoder::new(SliceOutput::new(&mut buffer), prefix);
+                let decoded = match decoder.decode(&mut encoded[..split].as_input()) {
+                    Cont(decoder) => decoder.consume(&mut encoded[split..].as_input()).unwrap(),
+                    Done(decoded) => decoded,
+                    Fail(error) => panic!("{:?}", error),
+                };
+                assert_eq!(decoded, bytes);
+            }
+        }
+        let long = [0x5Au8; 200];
+        assert_transcodes(b"", LengthPrefix::Varint, &[0x00]);
+        assert_transcodes(b"swim", LengthPrefix::Varint, &[0x04]);
+        assert_transcodes(&long, LengthPrefix::Varint, &[0xC8, 0x01]);
+        assert_transcodes(b"swim", LengthPrefix::U8, &[0x04]);
+        assert_transcodes(&long, LengthPrefix::U16(BigEndian), &[0x00, 0xC8]);
+        assert_transcodes(b"swim", LengthPrefix::U32(LittleEndian), &[0x04, 0x00, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_binary_decode_malformed() {
+        let overlong = [0xFFu8, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x02];
+        assert_eq!(VarintDecoder::new().consume(&mut overlong.as_input()), Err(BinaryError::Overflow));
+        assert_eq!(SignedVarintDecoder::new().consume(&mut overlong.as_input()), Err(BinaryError::Overflow));
+        assert_eq!(VarintDecoder::new().consume(&mut [0x80u8, 0x80].as_input()), Err(BinaryError::Unexpected));
+        assert_eq!(FixedDecoder::<u32, _>::new(BigEndian).consume(&mut [0u8; 3].as_input()), Err(BinaryError::Unexpected));
+
+        let mut buffer = [0u8; 8];
+        let decoder = BytesDecoder::new(SliceOutput::new(&mut buffer), LengthPrefix::U8).max_len(8);
+        assert_eq!(decoder.consume(&mut [9u8, 1, 2, 3].as_input()).unwrap_err(), BinaryError::TooLong);
+        let mut buffer = [0u8; 8];
+        let decoder = BytesDecoder::new(SliceOutput::new(&mut buffer), LengthPrefix::U8);
+        assert_eq!(decoder.consume(&mut [4u8, 1, 2].as_input()).unwrap_err(), BinaryError::Unexpected);
+        let mut buffer = [0u8; 8];
+        let decoder = BytesDecoder::new(SliceOutput::new(&mut buffer), LengthPrefix::U8);
+        assert_eq!(decoder.consume(&mut [9u8, 1, 2, 3, 4, 5, 6, 7, 8, 9].as_input()).unwrap_err(), BinaryError::TooLong);
+        let mut buffer = [0u8; 8];
+        let decoder = BytesDecoder::new(SliceOutput::new(&mut buffer), LengthPrefix::Varint);
+        assert_eq!(decoder.consume(&mut [0x81u8, 0x80, 0x80, 0x08, 1].as_input()).unwrap_err(), BinaryError::TooLong);
+        assert_eq!(BytesEncoder::<SliceOutput<u8>>::new(&[0u8; 256], LengthPrefix::U8).err(), Some(BinaryError::TooLong));
+    }
+}
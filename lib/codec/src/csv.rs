@@ -0,0 +1,731 @@
+use core::fmt::{self, Write};
+use core::marker::PhantomData;
+use core::str;
+use swim_core::num::float::write_float;
+use swim_mem::alloc::{Hold, Holder, HoldError};
+use swim_mem::lease::RawBuf;
+use swim_structure::item::{Item, Value, Record, Text, Num};
+use crate::step::{In, Out, Over};
+use crate::then::{Then, Cont, Done, Fail};
+use crate::input::Input;
+use crate::output::Output;
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+
+/// Delimiter, quoting, and header conventions of a delimited text format.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CsvFormat {
+    delimiter: char,
+    quote: Option<char>,
+    header: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CsvError {
+    /// Unexpected character after a closing quote, or unterminated quote.
+    Unexpected,
+    /// Value that can't be written as a field of the format.
+    Field,
+    Hold(HoldError),
+}
+
+impl From<HoldError> for CsvError {
+    fn from(error: HoldError) -> CsvError {
+        CsvError::Hold(error)
+    }
+}
+
+/// Decoding state that persists across the rows of a document, including
+/// the column names read from its header row.
+pub struct CsvReader<'a> {
+    format: CsvFormat,
+    header: Record<'a>,
+    field: RawBuf<'a, u8>,
+    has_header: bool,
+    skip_lf: bool,
+}
+
+/// Decodes the next row of a document into a `Record`, or into `None` once
+/// the input is over. Fields become slots keyed by column name when the
+/// format has a header row, and plain values otherwise; unquoted fields
+/// that look like numbers become `Num`s, and empty unquoted fields become
+/// `Extant`.
+pub struct CsvDecoder<'b, 'a: 'b, I: Input<Token=char>> {
+    reader: &'b mut CsvReader<'a>,
+    row: Option<Record<'a>>,
+    quoted: bool,
+    state: u32,
+    input: PhantomData<I>,
+}
+
+/// Encoding state that persists across the rows of a document.
+pub struct CsvWriter<'a> {
+    format: CsvFormat,
+    line: RawBuf<'a, u8>,
+    has_header: bool,
+}
+
+/// Streams one row of a document, preceded by the header row if the
+/// writer hasn't written one yet.
+pub struct CsvEncoder<'b, 'a: 'b, 'r, O: Output<Token=char>> {
+    writer: &'b mut CsvWriter<'a>,
+    row: &'r Record<'a>,
+    offset: usize,
+    output: PhantomData<O>,
+}
+
+/// Adapts a `RawBuf` to `fmt::Write`, remembering the allocation error
+/// that aborted a write.
+struct CsvBuffer<'a, 'r> {
+    string: &'r mut RawBuf<'a, u8>,
+    error: Option<HoldError>,
+}
+
+impl CsvFormat {
+    /// RFC 4180 comma separated values with a header row.
+    pub const fn csv() -> CsvFormat {
+        CsvFormat { delimiter: ',', quote: Some('"'), header: true }
+    }
+
+    /// Tab separated values with a header row, and no quoting.
+    pub const fn tsv() -> CsvFormat {
+        CsvFormat { delimiter: '\t', quote: None, header: true }
+    }
+
+    pub fn delimiter(mut self, delimiter: char) -> CsvFormat {
+        self.delimiter = delimiter;
+        self
+    }
+
+    pub fn quote(mut self, quote: Option<char>) -> CsvFormat {
+        self.quote = quote;
+        self
+    }
+
+    pub fn header(mut self, header: bool) -> CsvFormat {
+        self.header = header;
+        self
+    }
+
+    /// Returns `true` if `string` must be quoted to be read back as the
+    /// same text.
+    fn needs_quote(self, string: &str) -> bool {
+        string.is_empty() || is_numeric(string) || string.chars().any(|c| {
+            c == self.delimiter || Some(c) == self.quote || c == '\r' || c == '\n'
+        })
+    }
+}
+
+impl<'a> CsvReader<'a> {
+    pub fn try_hold(hold: &dyn Hold<'a>, format: CsvFormat) -> Result<Self, HoldError> {
+        Ok(Self {
+            format: format,
+            header: Record::try_hold_slice(hold, &[])?,
+            field: RawBuf::try_hold_empty(hold)?,
+            has_header: false,
+            skip_lf: false,
+        })
+    }
+
+    pub fn hold(hold: &dyn Hold<'a>, format: CsvFormat) -> Self {
+        Self::try_hold(hold, format).unwrap()
+    }
+
+    pub fn format(&self) -> CsvFormat {
+        self.format
+    }
+
+    /// Returns the column names read from the header row.
+    pub fn header(&self) -> &[Item<'a>] {
+        &self.header
+    }
+
+    /// Forgets the header row, and expects a new document.
+    pub fn reset(&mut self) {
+        self.header.clear();
+        self.field.truncate(0);
+        self.has_header = false;
+        self.skip_lf = false;
+    }
+
+    /// Returns a decoder for the next row of the document.
+    pub fn decoder<I>(&mut self) -> CsvDecoder<'_, 'a, I> where I: Input<Token=char> {
+        CsvDecoder::new(self)
+    }
+
+    #[inline]
+    fn in_header(&self) -> bool {
+        self.format.header && !self.has_header
+    }
+}
+
+impl<'b, 'a, I> CsvDecoder<'b, 'a, I> where I: Input<Token=char> {
+    pub fn new(reader: &'b mut CsvReader<'a>) -> Self {
+        reader.field.truncate(0);
+        Self {
+            reader: reader,
+            row: None,
+            quoted: false,
+            state: 0,
+            input: PhantomData,
+        }
+    }
+
+    pub fn consume(mut self, input: &mut I) -> Result<Option<Record<'a>>, CsvError> {
+        loop {
+            match self.decode(input) {
+                Done(row) => return Ok(row),
+                Fail(error) => return Err(error),
+                Cont(next) => {
+                    if input.is_out() {
+                        input.over();
+                        self = next;
+                    } else {
+                        return Err(CsvError::Unexpected);
+                    }
+                },
+            }
+        }
+    }
+
+    fn push_char(&mut self, c: char) -> Result<(), HoldError> {
+        let mut bytes = [0u8; 4];
+        self.reader.field.try_extend_from_slice(c.encode_utf8(&mut bytes).as_bytes())
+    }
+
+    /// Appends the buffered field to the current row.
+    fn end_field(&mut self) -> Result<(), HoldError> {
+        let reader = &mut *self.reader;
+        let hold = reader.field.holder();
+        let string = unsafe { str::from_utf8_unchecked(&reader.field) };
+        let row = match self.row {
+            Some(ref mut row) => row,
+            None => {
+                self.row = Some(Record::try_hold_slice(hold, &[])?);
+                self.row.as_mut().unwrap()
+            },
+        };
+        let item = if reader.format.header && !reader.has_header {
+            Text::try_hold_str(hold, string)?.into_item()
+        } else {
+            let value = field_value(hold, string, self.quoted)?;
+            match reader.header.get(row.len()) {
+                Some(name) => Item::slot(Value::try_hold_str(hold, name.as_text().as_str())?, value),
+                None => value.into_item(),
+            }
+        };
+        row.try_push(item)?;
+        reader.field.truncate(0);
+        self.quoted = false;
+        Ok(())
+    }
+
+    /// Completes the current row; returns `true` if the row is a data row,
+    /// and `false` if it was taken as the header row.
+    fn end_row(&mut self) -> bool {
+        if self.reader.in_header() {
+            self.reader.header = self.row.take().unwrap();
+            self.reader.has_header = true;
+            false
+        } else {
+            true
+        }
+    }
+}
+
+impl<'b, 'a, I> Decoder for CsvDecoder<'b, 'a, I> where I: Input<Token=char> {
+    type Input = I;
+    type Output = Option<Record<'a>>;
+    type Error = CsvError;
+
+    fn decode(mut self, input: &mut I) -> Then<Self, Option<Record<'a>>, CsvError> {
+        let delimiter = self.reader.format.delimiter;
+        let quote = self.reader.format.quote;
+        loop {
+            let c = match input.head() {
+                In(c) => c,
+                Out => return Cont(self),
+                Over => {
+                    if self.state == 2 {
+                        return Fail(CsvError::Unexpected);
+                    } else if self.state == 0 && self.row.is_none() {
+                        return Done(None);
+                    } else if let Err(error) = self.end_field() {
+                        return Fail(CsvError::Hold(error));
+                    } else if self.end_row() {
+                        return Done(self.row.take());
+                    }
+                    return Done(None);
+                },
+            };
+            input.step();
+            if self.reader.skip_lf {
+                // Completes a CRLF line break split across two rows.
+                self.reader.skip_lf = false;
+                if c == '\n' {
+                    continue;
+                }
+            }
+            let result = match self.state {
+                // Field start
+                0 => {
+                    if Some(c) == quote {
+                        self.quoted = true;
+                        self.state = 2;
+                        Ok(())
+                    } else if c == delimiter {
+                        self.end_field()
+                    } else if c == '\r' || c == '\n' {
+                        if self.row.is_none() {
+                            // Skip blank lines.
+                            continue;
+                        }
+                        self.end_field()
+                    } else {
+                        self.state = 1;
+                        self.push_char(c)
+                    }
+                },
+                // Unquoted field
+                1 => {
+                    if c == delimiter || c == '\r' || c == '\n' {
+                        self.state = 0;
+                        self.end_field()
+                    } else {
+                        self.push_char(c)
+                    }
+                },
+                // Quoted field
+                2 => {
+                    if Some(c) == quote {
+                        self.state = 3;
+                        Ok(())
+                    } else {
+                        self.push_char(c)
+                    }
+                },
+                // Quote in quoted field
+                3 => {
+                    if Some(c) == quote {
+                        self.state = 2;
+                        self.push_char(c)
+                    } else if c == delimiter || c == '\r' || c == '\n' {
+                        self.state = 0;
+                        self.end_field()
+                    } else {
+                        return Fail(CsvError::Unexpected);
+                    }
+                },
+                _ => unreachable!(),
+            };
+            if let Err(error) = result {
+                return Fail(CsvError::Hold(error));
+            }
+            if self.state == 0 && (c == '\r' || c == '\n') {
+                self.reader.skip_lf = c == '\r';
+                if self.end_row() {
+                    return Done(self.row.take());
+                }
+            }
+        }
+    }
+}
+
+impl<'a> CsvWriter<'a> {
+    pub fn try_hold(hold: &dyn Hold<'a>, format: CsvFormat) -> Result<Self, HoldError> {
+        Ok(Self {
+            format: format,
+            line: RawBuf::try_hold_empty(hold)?,
+            has_header: false,
+        })
+    }
+
+    pub fn hold(hold: &dyn Hold<'a>, format: CsvFormat) -> Self {
+        Self::try_hold(hold, format).unwrap()
+    }
+
+    pub fn format(&self) -> CsvFormat {
+        self.format
+    }
+
+    /// Expects a new document, starting with a header row.
+    pub fn reset(&mut self) {
+        self.has_header = false;
+    }
+
+    /// Returns an encoder for the next `row` of the document; returns an
+    /// error if the row contains attributes, nested records or data, or
+    /// text that the format can't represent.
+    pub fn try_encoder<'r, O>(&mut self, row: &'r Record<'a>) -> Result<CsvEncoder<'_, 'a, 'r, O>, CsvError>
+        where O: Output<Token=char> {
+        CsvEncoder::try_new(self, row)
+    }
+
+    pub fn encoder<'r, O>(&mut self, row: &'r Record<'a>) -> CsvEncoder<'_, 'a, 'r, O>
+        where O: Output<Token=char> {
+        self.try_encoder(row).unwrap()
+    }
+
+    /// Writes the column names of `row`, taken from the keys of its slots.
+    fn write_header(&mut self, row: &Record<'a>) -> Result<(), CsvError> {
+        let format = self.format;
+        let mut buffer = CsvBuffer { string: &mut self.line, error: None };
+        for (index, item) in row.iter().enumerate() {
+            if index != 0 {
+                buffer.push(format.delimiter)?;
+            }
+            if let Some(slot) = item.cast_as_slot() {
+                match slot.get_key().cast_as_text() {
+                    Some(name) => buffer.write_field(format, name.as_str())?,
+                    None => return Err(CsvError::Field),
+                };
+            }
+        }
+        buffer.write_str("\r\n").map_err(|_| buffer.take_error())
+    }
+
+    fn write_row(&mut self, row: &Record<'a>) -> Result<(), CsvError> {
+        let format = self.format;
+        let mut buffer = CsvBuffer { string: &mut self.line, error: None };
+        for (index, item) in row.iter().enumerate() {
+            if index != 0 {
+                buffer.push(format.delimiter)?;
+            }
+            if item.is_attr() {
+                return Err(CsvError::Field);
+            }
+            let value = item.get_val();
+            if let Some(text) = value.cast_as_text() {
+                buffer.write_field(format, text.as_str())?;
+            } else if let Some(num) = value.cast_as_num() {
+                write_num(num, &mut buffer).map_err(|_| buffer.take_error())?;
+            } else if let Some(value) = value.cast_as_bool() {
+                buffer.write_str(if value.to_bool() { "true" } else { "false" }).map_err(|_| buffer.take_error())?;
+            } else if value.is_record() || value.is_data() {
+                return Err(CsvError::Field);
+            }
+        }
+        buffer.write_str("\r\n").map_err(|_| buffer.take_error())
+    }
+}
+
+impl<'b, 'a, 'r, O> CsvEncoder<'b, 'a, 'r, O> where O: Output<Token=char> {
+    pub fn try_new(writer: &'b mut CsvWriter<'a>, row: &'r Record<'a>) -> Result<Self, CsvError> {
+        writer.line.truncate(0);
+        if writer.format.header && !writer.has_header {
+            writer.write_header(row)?;
+            writer.has_header = true;
+        }
+        writer.write_row(row)?;
+        Ok(Self {
+            writer: writer,
+            row: row,
+            offset: 0,
+            output: PhantomData,
+        })
+    }
+
+    pub fn produce(mut self, mut output: O) -> Result<O::Out, O::Err> {
+        loop {
+            match self.encode(&mut output) {
+                Done(_) => return output.take_out(),
+                Fail(_) => unreachable!(),
+                Cont(next) => self = next,
+            }
+        }
+    }
+}
+
+impl<'b, 'a, 'r, O> Encoder for CsvEncoder<'b, 'a, 'r, O> where O: Output<Token=char> {
+    type Input = &'r Record<'a>;
+    type Output = O;
+    type Error = ();
+
+    fn encode(mut self, output: &mut O) -> Then<Self, &'r Record<'a>, ()> {
+        let string = unsafe { str::from_utf8_unchecked(&self.writer.line) };
+        while !output.is_full() {
+            match string[self.offset..].chars().next() {
+                Some(c) => {
+                    output.push(c);
+                    self.offset += c.len_utf8();
+                },
+                None => return Done(self.row),
+            };
+        }
+        Cont(self)
+    }
+}
+
+impl<'a, 'r> CsvBuffer<'a, 'r> {
+    fn take_error(&mut self) -> CsvError {
+        CsvError::Hold(self.error.take().unwrap())
+    }
+
+    fn push(&mut self, c: char) -> Result<(), CsvError> {
+        let mut bytes = [0u8; 4];
+        Ok(self.string.try_extend_from_slice(c.encode_utf8(&mut bytes).as_bytes())?)
+    }
+
+    /// Writes `string` as a field, quoting it if necessary.
+    fn write_field(&mut self, format: CsvFormat, string: &str) -> Result<(), CsvError> {
+        if !format.needs_quote(string) {
+            return Ok(self.string.try_extend_from_slice(string.as_bytes())?);
+        }
+        match format.quote {
+            Some(quote) => {
+                self.push(quote)?;
+                for c in string.chars() {
+                    if c == quote {
+                        self.push(quote)?;
+                    }
+                    self.push(c)?;
+                }
+                self.push(quote)
+            },
+            // Without quoting, numeric and empty text reads back as a
+            // number or as extant, but delimiters and line breaks can't
+            // be written at all.
+            None if string.chars().any(|c| c == format.delimiter || c == '\r' || c == '\n') => {
+                Err(CsvError::Field)
+            },
+            None => Ok(self.string.try_extend_from_slice(string.as_bytes())?),
+        }
+    }
+}
+
+impl<'a, 'r> Write for CsvBuffer<'a, 'r> {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        match self.string.try_extend_from_slice(string.as_bytes()) {
+            Ok(()) => Ok(()),
+            Err(error) => {
+                self.error = Some(error);
+                Err(fmt::Error)
+            },
+        }
+    }
+}
+
+/// Converts the text of a field into a value.
+fn field_value<'a>(hold: &dyn Hold<'a>, string: &str, quoted: bool) -> Result<Value<'a>, HoldError> {
+    if !quoted {
+        if string.is_empty() {
+            return Ok(Value::extant());
+        } else if is_numeric(string) {
+            if let Ok(value) = string.parse::<i64>() {
+                return Ok(if value as i32 as i64 == value {
+                    Value::from_i32(value as i32)
+                } else {
+                    Value::from_i64(value)
+                });
+            } else if let Ok(value) = string.parse::<u64>() {
+                return Ok(Value::from_u64(value));
+            } else if let Ok(value) = string.parse::<f64>() {
+                return Ok(Value::from_f64(value));
+            }
+        }
+    }
+    Value::try_hold_str(hold, string)
+}
+
+/// Returns `true` if `string` starts like a decimal number, and contains
+/// only the characters of one.
+fn is_numeric(string: &str) -> bool {
+    let bytes = string.as_bytes();
+    let digits = match bytes.first() {
+        Some(b'-') | Some(b'+') => &bytes[1..],
+        _ => bytes,
+    };
+    match digits.first() {
+        Some(b) if b.is_ascii_digit() || *b == b'.' => (),
+        _ => return false,
+    };
+    digits.iter().all(|&b| b.is_ascii_digit() || b == b'.' || b == b'e' || b == b'E' || b == b'+' || b == b'-')
+}
+
+pub(crate) fn write_num<W: Write>(num: &Num, output: &mut W) -> fmt::Result {
+    if num.is_u8() || num.is_u16() || num.is_u32() || num.is_u64() {
+        write!(output, "{}", num.as_u64())
+    } else if num.is_i8() || num.is_i16() || num.is_i32() || num.is_i64() {
+        write!(output, "{}", num.as_i64())
+    } else if num.is_f16() {
+        write_float(num.as_f16(), output)
+    } else if num.is_bf16() {
+        write_float(num.as_bf16(), output)
+    } else if num.is_f32() {
+        write_float(num.as_f32(), output)
+    } else {
+        write_float(num.as_f64(), output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use swim_mem::block::Block;
+    use swim_mem::alloc::Pack;
+    use crate::input::AsInput;
+    use crate::output::StrOutput;
+    use super::*;
+
+    fn decode_all<'a>(reader: &mut CsvReader<'a>, csv: &str, rows: &mut [Option<Record<'a>>]) -> usize {
+        let mut input = csv.as_input();
+        let mut count = 0;
+        while let Some(row) = reader.decoder().consume(&mut input).unwrap() {
+            rows[count] = Some(row);
+            count += 1;
+        }
+        count
+    }
+
+    #[test]
+    fn test_csv_decode_header() {
+        static mut TEST_AREA: [u8; 16384] = [0; 16384];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let slot = |key: &str, value: Value<'static>| {
+            Item::slot(Value::hold_str(pack, key), value)
+        };
+        let mut reader = CsvReader::hold(pack, CsvFormat::csv());
+        let mut rows = [None, None, None];
+        let csv = "name,count,ratio\r\nfoo,42,0.5\r\n\"bar, \"\"baz\"\"\",-8589934592,\r\n\"007\",18446744073709551615,1e3";
+        assert_eq!(decode_all(&mut reader, csv, &mut rows), 3);
+        assert_eq!(reader.header(), &[
+            Value::hold_str(pack, "name").into_item(),
+            Value::hold_str(pack, "count").into_item(),
+            Value::hold_str(pack, "ratio").into_item(),
+        ][..]);
+        assert_eq!(rows[0].as_ref().unwrap(), &Record::hold_slice(pack, &[
+            slot("name", Value::hold_str(pack, "foo")),
+            slot("count", Value::from_i32(42)),
+            slot("ratio", Value::from_f64(0.5)),
+        ]));
+        assert_eq!(rows[1].as_ref().unwrap(), &Record::hold_slice(pack, &[
+            slot("name", Value::hold_str(pack, "bar, \"baz\"")),
+            slot("count", Value::from_i64(-8589934592)),
+            slot("ratio", Value::extant()),
+        ]));
+        assert_eq!(rows[2].as_ref().unwrap(), &Record::hold_slice(pack, &[
+            slot("name", Value::hold_str(pack, "007")),
+            slot("count", Value::from_u64(18446744073709551615)),
+            slot("ratio", Value::from_f64(1000.0)),
+        ]));
+    }
+
+    #[test]
+    fn test_csv_decode_split() {
+        static mut TEST_AREA: [u8; 65536] = [0; 65536];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let csv = "a,b\r\n1,\"x\r\ny\"\r\n\r\n2,z\n";
+        let expected = [
+            Record::hold_slice(pack, &[
+                Item::slot(Value::hold_str(pack, "a"), Value::from_i32(1)),
+                Item::slot(Value::hold_str(pack, "b"), Value::hold_str(pack, "x\r\ny")),
+            ]),
+            Record::hold_slice(pack, &[
+                Item::slot(Value::hold_str(pack, "a"), Value::from_i32(2)),
+                Item::slot(Value::hold_str(pack, "b"), Value::hold_str(pack, "z")),
+            ]),
+        ];
+        for split in 0..=csv.len() {
+            let mut reader = CsvReader::hold(pack, CsvFormat::csv());
+            let mut input = csv[..split].as_input();
+            let mut rest = true;
+            let mut count = 0;
+            let mut decoder = reader.decoder();
+            loop {
+                match decoder.decode(&mut input) {
+                    Cont(next) => {
+                        decoder = next;
+                        if rest {
+                            input = csv[split..].as_input();
+                            rest = false;
+                        } else if input.is_out() {
+                            input.over();
+                        } else {
+                            panic!("incomplete at split {}", split);
+                        }
+                    },
+                    Done(Some(row)) => {
+                        assert_eq!(row, expected[count], "split {}", split);
+                        count += 1;
+                        decoder = reader.decoder();
+                    },
+                    Done(None) => break,
+                    Fail(error) => panic!("{:?} at split {}", error, split),
+                };
+            }
+            assert_eq!(count, expected.len());
+        }
+    }
+
+    #[test]
+    fn test_csv_encode() {
+        static mut TEST_AREA: [u8; 16384] = [0; 16384];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let slot = |key: &str, value: Value<'static>| {
+            Item::slot(Value::hold_str(pack, key), value)
+        };
+        let rows = [
+            Record::hold_slice(pack, &[
+                slot("name", Value::hold_str(pack, "bar, \"baz\"")),
+                slot("count", Value::from_i32(-7)),
+                slot("ratio", Value::from_f64(0.5)),
+            ]),
+            Record::hold_slice(pack, &[
+                slot("name", Value::hold_str(pack, "007")),
+                slot("count", Value::from_u64(18446744073709551615)),
+                slot("ratio", Value::extant()),
+            ]),
+        ];
+        let mut writer = CsvWriter::hold(pack, CsvFormat::csv());
+        let mut buffer = [0u8; 64];
+        assert_eq!(writer.encoder(&rows[0]).produce(StrOutput::new(&mut buffer)).unwrap(),
+                   "name,count,ratio\r\n\"bar, \"\"baz\"\"\",-7,0.5\r\n");
+        let mut buffer = [0u8; 64];
+        assert_eq!(writer.encoder(&rows[1]).produce(StrOutput::new(&mut buffer)).unwrap(),
+                   "\"007\",18446744073709551615,\r\n");
+
+        let csv = "name,count,ratio\r\n\"bar, \"\"baz\"\"\",-7,0.5\r\n\"007\",18446744073709551615,\r\n";
+        let mut reader = CsvReader::hold(pack, CsvFormat::csv());
+        let mut decoded = [None, None, None];
+        assert_eq!(decode_all(&mut reader, csv, &mut decoded), 2);
+        assert_eq!(decoded[0].as_ref(), Some(&rows[0]));
+        assert_eq!(decoded[1].as_ref(), Some(&rows[1]));
+    }
+
+    #[test]
+    fn test_tsv_transcode() {
+        static mut TEST_AREA: [u8; 16384] = [0; 16384];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let format = CsvFormat::tsv().header(false);
+        let row = Record::hold_slice(pack, &[
+            Value::hold_str(pack, "a \"quoted\" word").into_item(),
+            Item::from_i32(3),
+            Item::from_bool(true),
+        ]);
+        let mut writer = CsvWriter::hold(pack, format);
+        let mut buffer = [0u8; 64];
+        assert_eq!(writer.encoder(&row).produce(StrOutput::new(&mut buffer)).unwrap(),
+                   "a \"quoted\" word\t3\ttrue\r\n");
+        let mut reader = CsvReader::hold(pack, format);
+        let decoded = reader.decoder().consume(&mut "a \"quoted\" word\t3\ttrue\n".as_input()).unwrap().unwrap();
+        assert_eq!(decoded, Record::hold_slice(pack, &[
+            Value::hold_str(pack, "a \"quoted\" word").into_item(),
+            Item::from_i32(3),
+            Value::hold_str(pack, "true").into_item(),
+        ]));
+        let tabbed = Record::hold_slice(pack, &[Value::hold_str(pack, "a\tb").into_item()]);
+        assert_eq!(writer.try_encoder::<StrOutput>(&tabbed).err(), Some(CsvError::Field));
+    }
+
+    #[test]
+    fn test_csv_decode_malformed() {
+        static mut TEST_AREA: [u8; 4096] = [0; 4096];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let format = CsvFormat::csv().header(false);
+        let mut reader = CsvReader::hold(pack, format);
+        assert_eq!(reader.decoder().consume(&mut "\"abc".as_input()).unwrap_err(), CsvError::Unexpected);
+        let mut reader = CsvReader::hold(pack, format);
+        assert_eq!(reader.decoder().consume(&mut "\"a\"b,c".as_input()).unwrap_err(), CsvError::Unexpected);
+        let mut reader = CsvReader::hold(pack, format);
+        assert_eq!(reader.decoder().consume(&mut "\r\n\n".as_input()).unwrap(), None);
+    }
+}
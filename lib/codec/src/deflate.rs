@@ -0,0 +1,1750 @@
+use core::cmp;
+use core::fmt;
+use core::marker::PhantomData;
+use swim_mem::alloc::{Hold, HoldError};
+use swim_mem::lease::RawBuf;
+use crate::step::{In, Out, Over};
+use crate::then::{Then, Cont, Done, Fail};
+use crate::input::Input;
+use crate::output::Output;
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+use crate::checksum::{Adler32, Crc32};
+
+const WINDOW_BITS: u32 = 15;
+const WINDOW_SIZE: usize = 1 << WINDOW_BITS;
+const WINDOW_MASK: usize = WINDOW_SIZE - 1;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+/// Lookahead needed to find a maximal match and hash the bytes after it.
+const MIN_LOOKAHEAD: usize = MAX_MATCH + MIN_MATCH + 1;
+const MAX_DIST: usize = WINDOW_SIZE - MIN_LOOKAHEAD;
+/// Minimal matches farther than this cost more than their literals.
+const TOO_FAR: usize = 4096;
+const HASH_BITS: u32 = 15;
+const HASH_SIZE: usize = 1 << HASH_BITS;
+const SYMBOL_CAP: usize = 8192;
+const MAX_STORED: usize = 65535;
+const MAX_BITS: usize = 15;
+const END_BLOCK: usize = 256;
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31,
+    35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2,
+    3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193,
+    257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6,
+    7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13,
+];
+/// Order in which code length code lengths are transmitted.
+const CODE_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+/// Match search parameters per compression level: lazy match threshold,
+/// good enough match length, and maximum hash chain length.
+const LEVELS: [(usize, usize, usize); 10] = [
+    (0, 0, 0),
+    (4, 8, 4),
+    (5, 16, 8),
+    (6, 32, 32),
+    (4, 16, 16),
+    (16, 32, 32),
+    (16, 128, 128),
+    (32, 128, 256),
+    (128, 258, 1024),
+    (258, 258, 4096),
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DeflateFormat {
+    /// Bare DEFLATE blocks, as used by WebSocket permessage-deflate.
+    Raw,
+    /// DEFLATE blocks with a zlib header and Adler-32 trailer.
+    Zlib,
+    /// DEFLATE blocks with a gzip header and CRC-32 trailer.
+    Gzip,
+}
+
+/// How a `DeflateEncoder` ends its message once its input is over.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DeflateFlush {
+    /// Ends the stream with a final block and the format's trailer.
+    Finish,
+    /// Ends the message on a byte boundary with an empty stored block,
+    /// leaving the stream open for subsequent messages. permessage-deflate
+    /// strips the trailing `00 00 FF FF` of the empty block from each
+    /// message, and the receiver appends it back before inflating.
+    Sync,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DeflateError {
+    Unexpected,
+    Header,
+    Checksum,
+    Distance,
+    Oversized,
+}
+
+/// Decompression state that persists across the messages of a stream,
+/// including the sliding window of recent output.
+pub struct Inflate<'a> {
+    format: DeflateFormat,
+    takeover: bool,
+    window: RawBuf<'a, u8>,
+    wpos: usize,
+    whave: usize,
+    adler: Adler32,
+    crc: Crc32,
+    size: u32,
+    started: bool,
+    finished: bool,
+}
+
+/// Compression state that persists across the messages of a stream,
+/// including the sliding window of recent input and its match index.
+pub struct Deflate<'a> {
+    format: DeflateFormat,
+    level: usize,
+    wbits: u32,
+    max_dist: usize,
+    takeover: bool,
+    window: RawBuf<'a, u8>,
+    head: RawBuf<'a, u16>,
+    prev: RawBuf<'a, u16>,
+    symbols: RawBuf<'a, u32>,
+    pending: RawBuf<'a, u8>,
+    offset: usize,
+    strstart: usize,
+    lookahead: usize,
+    block_start: usize,
+    match_len: usize,
+    match_dist: usize,
+    match_available: bool,
+    bits: u64,
+    nbits: u32,
+    adler: Adler32,
+    crc: Crc32,
+    size: u32,
+    started: bool,
+    finished: bool,
+}
+
+/// Canonical Huffman decoding table: the number of codes of each length,
+/// and the symbols ordered by code.
+struct Huffman<S> {
+    count: [u16; MAX_BITS + 1],
+    symbol: S,
+}
+
+pub struct InflateDecoder<'b, 'a: 'b, I: Input<Token=u8>, O: Output<Token=u8>> {
+    inflate: &'b mut Inflate<'a>,
+    pub output: O,
+    lencode: Huffman<[u16; 288]>,
+    distcode: Huffman<[u16; 30]>,
+    lengths: [u8; 320],
+    nlen: usize,
+    ndist: usize,
+    ncode: usize,
+    index: usize,
+    len: usize,
+    last: bool,
+    bits: u64,
+    nbits: u32,
+    state: u32,
+    input: PhantomData<I>,
+}
+
+pub struct DeflateEncoder<'b, 'a: 'b, I: Input<Token=u8>, O: Output<Token=u8>> {
+    deflate: &'b mut Deflate<'a>,
+    pub input: I,
+    flush: DeflateFlush,
+    state: u32,
+    output: PhantomData<O>,
+}
+
+impl<'a> Inflate<'a> {
+    pub fn try_hold(hold: &dyn Hold<'a>, format: DeflateFormat) -> Result<Self, HoldError> {
+        let mut window = RawBuf::try_hold_cap(hold, WINDOW_SIZE)?;
+        window.try_extend((0..WINDOW_SIZE).map(|_| 0))?;
+        Ok(Self {
+            format: format,
+            takeover: true,
+            window: window,
+            wpos: 0,
+            whave: 0,
+            adler: Adler32::new(),
+            crc: Crc32::new(),
+            size: 0,
+            started: false,
+            finished: false,
+        })
+    }
+
+    pub fn hold(hold: &dyn Hold<'a>, format: DeflateFormat) -> Self {
+        Self::try_hold(hold, format).unwrap()
+    }
+
+    /// Sets whether back references may reach into the output of previous
+    /// messages; enabled by default.
+    pub fn context_takeover(mut self, takeover: bool) -> Self {
+        self.takeover = takeover;
+        self
+    }
+
+    pub fn format(&self) -> DeflateFormat {
+        self.format
+    }
+
+    /// Returns `true` once the final block and trailer of the stream have
+    /// been decoded.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Forgets the window and checksums, and expects a new stream.
+    pub fn reset(&mut self) {
+        self.wpos = 0;
+        self.whave = 0;
+        self.adler = Adler32::new();
+        self.crc = Crc32::new();
+        self.size = 0;
+        self.started = false;
+        self.finished = false;
+    }
+
+    /// Returns a decoder for the next message of the stream.
+    pub fn decoder<I, O>(&mut self, output: O) -> InflateDecoder<'_, 'a, I, O>
+        where I: Input<Token=u8>, O: Output<Token=u8> {
+        InflateDecoder::new(self, output)
+    }
+
+    fn emit<O>(&mut self, byte: u8, output: &mut O) where O: Output<Token=u8> {
+        output.push(byte);
+        self.window[self.wpos] = byte;
+        self.wpos = (self.wpos + 1) & WINDOW_MASK;
+        if self.whave < WINDOW_SIZE {
+            self.whave += 1;
+        }
+        match self.format {
+            DeflateFormat::Raw => (),
+            DeflateFormat::Zlib => self.adler.push(byte),
+            DeflateFormat::Gzip => {
+                self.crc.push(byte);
+                self.size = self.size.wrapping_add(1);
+            },
+        };
+    }
+}
+
+impl<'b, 'a, I, O> InflateDecoder<'b, 'a, I, O> where I: Input<Token=u8>, O: Output<Token=u8> {
+    pub fn new(inflate: &'b mut Inflate<'a>, output: O) -> Self {
+        if inflate.finished {
+            inflate.reset();
+        } else if !inflate.takeover {
+            inflate.wpos = 0;
+            inflate.whave = 0;
+        }
+        let state = if inflate.started {
+            1
+        } else {
+            match inflate.format {
+                DeflateFormat::Raw => 1,
+                DeflateFormat::Zlib => 10,
+                DeflateFormat::Gzip => 20,
+            }
+        };
+        Self {
+            inflate: inflate,
+            output: output,
+            lencode: Huffman { count: [0; MAX_BITS + 1], symbol: [0; 288] },
+            distcode: Huffman { count: [0; MAX_BITS + 1], symbol: [0; 30] },
+            lengths: [0; 320],
+            nlen: 0,
+            ndist: 0,
+            ncode: 0,
+            index: 0,
+            len: 0,
+            last: false,
+            bits: 0,
+            nbits: 0,
+            state: state,
+            input: PhantomData,
+        }
+    }
+
+    pub fn consume(mut self, input: &mut I) -> Result<O::Out, DeflateError> where O::Err: fmt::Debug {
+        loop {
+            match self.decode(input) {
+                Done(output) => return Ok(output),
+                Fail(error) => return Err(error),
+                Cont(next) => {
+                    if input.is_out() {
+                        input.over();
+                        self = next;
+                    } else {
+                        return Err(DeflateError::Unexpected);
+                    }
+                },
+            }
+        }
+    }
+
+    /// Loads input bytes until at least `need` bits are buffered; returns
+    /// `false` if the input runs out first.
+    fn pull(&mut self, input: &mut I, need: u32) -> bool {
+        while self.nbits < need {
+            match input.head() {
+                In(byte) => {
+                    input.step();
+                    self.bits |= (byte as u64) << self.nbits;
+                    self.nbits += 8;
+                },
+                _ => return false,
+            };
+        }
+        true
+    }
+
+    fn take(&mut self, n: u32) -> u32 {
+        let value = (self.bits & ((1u64 << n) - 1)) as u32;
+        self.bits >>= n;
+        self.nbits -= n;
+        value
+    }
+
+    fn align(&mut self) {
+        let n = self.nbits & 7;
+        self.take(n);
+    }
+
+    /// Decodes a symbol, followed by up to `extra(symbol)` bits, without
+    /// consuming any bits until all of them are available.
+    fn symbol<F>(&mut self, input: &mut I, dist: bool, extra: F) -> Result<Option<(usize, u32)>, DeflateError>
+        where F: Fn(usize) -> u32 {
+        loop {
+            let decoded = if dist {
+                self.distcode.decode(self.bits, self.nbits)
+            } else {
+                self.lencode.decode(self.bits, self.nbits)
+            };
+            match decoded {
+                Ok(Some((symbol, n))) => {
+                    let m = extra(symbol);
+                    if !self.pull(input, n + m) {
+                        return self.starve(input);
+                    }
+                    self.take(n);
+                    return Ok(Some((symbol, self.take(m))));
+                },
+                Ok(None) => {
+                    let need = self.nbits + 8;
+                    if !self.pull(input, need) {
+                        return self.starve(input);
+                    }
+                },
+                Err(error) => return Err(error),
+            };
+        }
+    }
+
+    fn starve<T>(&self, input: &mut I) -> Result<Option<T>, DeflateError> {
+        if input.is_over() {
+            Err(DeflateError::Unexpected)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<'b, 'a, I, O> Decoder for InflateDecoder<'b, 'a, I, O>
+    where I: Input<Token=u8>,
+          O: Output<Token=u8>,
+          O::Err: fmt::Debug {
+
+    type Input = I;
+    type Output = O::Out;
+    type Error = DeflateError;
+
+    fn decode(mut self, input: &mut I) -> Then<Self, O::Out, DeflateError> {
+        loop {
+            match self.state {
+                1 => {
+                    if self.last {
+                        self.align();
+                        self.state = 9;
+                        continue;
+                    }
+                    // A raw message may end on any block boundary; no block
+                    // fits in the padding bits of a final partial byte. Zlib
+                    // and gzip streams must end with a final block and trailer.
+                    if self.nbits < 8 && input.is_over() {
+                        return if self.inflate.format == DeflateFormat::Raw {
+                            Done(self.output.take_out().unwrap())
+                        } else {
+                            Fail(DeflateError::Unexpected)
+                        };
+                    }
+                    if !self.pull(input, 3) {
+                        return if input.is_over() { Fail(DeflateError::Unexpected) } else { Cont(self) };
+                    }
+                    self.last = self.take(1) != 0;
+                    match self.take(2) {
+                        0 => {
+                            self.align();
+                            self.state = 2;
+                        },
+                        1 => {
+                            let mut lengths = [0u8; 318];
+                            for (symbol, length) in lengths.iter_mut().enumerate() {
+                                *length = match symbol {
+                                    0..=143 => 8,
+                                    144..=255 => 9,
+                                    256..=279 => 7,
+                                    280..=287 => 8,
+                                    _ => 5,
+                                };
+                            }
+                            self.lencode.build(&lengths[..288], false);
+                            self.distcode.build(&lengths[288..], false);
+                            self.state = 7;
+                        },
+                        2 => self.state = 4,
+                        _ => return Fail(DeflateError::Unexpected),
+                    };
+                },
+                2 => {
+                    if !self.pull(input, 32) {
+                        return if input.is_over() { Fail(DeflateError::Unexpected) } else { Cont(self) };
+                    }
+                    let len = self.take(16);
+                    let nlen = self.take(16);
+                    if len != !nlen & 0xFFFF {
+                        return Fail(DeflateError::Unexpected);
+                    }
+                    self.len = len as usize;
+                    self.state = 3;
+                },
+                3 => {
+                    while self.len > 0 {
+                        if self.output.is_full() {
+                            return Fail(DeflateError::Oversized);
+                        }
+                        let byte = if self.nbits >= 8 {
+                            self.take(8) as u8
+                        } else {
+                            match input.head() {
+                                In(byte) => {
+                                    input.step();
+                                    byte
+                                },
+                                Out => return Cont(self),
+                                Over => return Fail(DeflateError::Unexpected),
+                            }
+                        };
+                        self.inflate.emit(byte, &mut self.output);
+                        self.len -= 1;
+                    }
+                    self.state = 1;
+                },
+                4 => {
+                    if !self.pull(input, 14) {
+                        return if input.is_over() { Fail(DeflateError::Unexpected) } else { Cont(self) };
+                    }
+                    self.nlen = self.take(5) as usize + 257;
+                    self.ndist = self.take(5) as usize + 1;
+                    self.ncode = self.take(4) as usize + 4;
+                    if self.nlen > 286 || self.ndist > 30 {
+                        return Fail(DeflateError::Unexpected);
+                    }
+                    self.lengths = [0; 320];
+                    self.index = 0;
+                    self.state = 5;
+                },
+                5 => {
+                    while self.index < self.ncode {
+                        if !self.pull(input, 3) {
+                            return if input.is_over() { Fail(DeflateError::Unexpected) } else { Cont(self) };
+                        }
+                        self.lengths[CODE_ORDER[self.index]] = self.take(3) as u8;
+                        self.index += 1;
+                    }
+                    let lengths = self.lengths;
+                    if !self.lencode.build(&lengths[..19], true) {
+                        return Fail(DeflateError::Unexpected);
+                    }
+                    self.lengths = [0; 320];
+                    self.index = 0;
+                    self.state = 6;
+                },
+                6 => {
+                    let total = self.nlen + self.ndist;
+                    while self.index < total {
+                        let extra = |symbol| match symbol {
+                            16 => 2,
+                            17 => 3,
+                            18 => 7,
+                            _ => 0,
+                        };
+                        let (symbol, extra) = match self.symbol(input, false, extra) {
+                            Ok(Some(decoded)) => decoded,
+                            Ok(None) => return Cont(self),
+                            Err(error) => return Fail(error),
+                        };
+                        let (length, repeat) = match symbol {
+                            0..=15 => (symbol as u8, 1),
+                            16 if self.index > 0 => (self.lengths[self.index - 1], 3 + extra as usize),
+                            17 => (0, 3 + extra as usize),
+                            18 => (0, 11 + extra as usize),
+                            _ => return Fail(DeflateError::Unexpected),
+                        };
+                        if self.index + repeat > total {
+                            return Fail(DeflateError::Unexpected);
+                        }
+                        for _ in 0..repeat {
+                            self.lengths[self.index] = length;
+                            self.index += 1;
+                        }
+                    }
+                    if self.lengths[END_BLOCK] == 0 {
+                        return Fail(DeflateError::Unexpected);
+                    }
+                    let lengths = self.lengths;
+                    if !self.lencode.build(&lengths[..self.nlen], false) ||
+                       !self.distcode.build(&lengths[self.nlen..total], false) {
+                        return Fail(DeflateError::Unexpected);
+                    }
+                    self.state = 7;
+                },
+                7 => {
+                    let extra = |symbol: usize| if symbol > END_BLOCK && symbol < 286 {
+                        LENGTH_EXTRA[symbol - 257] as u32
+                    } else {
+                        0
+                    };
+                    let (symbol, extra) = match self.symbol(input, false, extra) {
+                        Ok(Some(decoded)) => decoded,
+                        Ok(None) => return Cont(self),
+                        Err(error) => return Fail(error),
+                    };
+                    if symbol < END_BLOCK {
+                        if self.output.is_full() {
+                            return Fail(DeflateError::Oversized);
+                        }
+                        self.inflate.emit(symbol as u8, &mut self.output);
+                    } else if symbol == END_BLOCK {
+                        self.state = 1;
+                    } else if symbol < 286 {
+                        self.len = LENGTH_BASE[symbol - 257] as usize + extra as usize;
+                        self.state = 8;
+                    } else {
+                        return Fail(DeflateError::Unexpected);
+                    }
+                },
+                8 => {
+                    let extra = |symbol: usize| if symbol < 30 { DIST_EXTRA[symbol] as u32 } else { 0 };
+                    let (symbol, extra) = match self.symbol(input, true, extra) {
+                        Ok(Some(decoded)) => decoded,
+                        Ok(None) => return Cont(self),
+                        Err(error) => return Fail(error),
+                    };
+                    if symbol >= 30 {
+                        return Fail(DeflateError::Unexpected);
+                    }
+                    let dist = DIST_BASE[symbol] as usize + extra as usize;
+                    if dist > self.inflate.whave {
+                        return Fail(DeflateError::Distance);
+                    }
+                    for _ in 0..self.len {
+                        if self.output.is_full() {
+                            return Fail(DeflateError::Oversized);
+                        }
+                        let byte = self.inflate.window[(self.inflate.wpos + WINDOW_SIZE - dist) & WINDOW_MASK];
+                        self.inflate.emit(byte, &mut self.output);
+                    }
+                    self.state = 7;
+                },
+                9 => {
+                    match self.inflate.format {
+                        DeflateFormat::Raw => (),
+                        DeflateFormat::Zlib => {
+                            if !self.pull(input, 32) {
+                                return if input.is_over() { Fail(DeflateError::Unexpected) } else { Cont(self) };
+                            }
+                            let adler = self.take(32).swap_bytes();
+                            if adler != self.inflate.adler.digest() {
+                                return Fail(DeflateError::Checksum);
+                            }
+                        },
+                        DeflateFormat::Gzip => {
+                            if !self.pull(input, 64) {
+                                return if input.is_over() { Fail(DeflateError::Unexpected) } else { Cont(self) };
+                            }
+                            let crc = self.take(32);
+                            let size = self.take(32);
+                            if crc != self.inflate.crc.digest() || size != self.inflate.size {
+                                return Fail(DeflateError::Checksum);
+                            }
+                        },
+                    };
+                    self.inflate.started = true;
+                    self.inflate.finished = true;
+                    return Done(self.output.take_out().unwrap());
+                },
+                10 => {
+                    if !self.pull(input, 16) {
+                        return if input.is_over() { Fail(DeflateError::Unexpected) } else { Cont(self) };
+                    }
+                    let cmf = self.take(8);
+                    let flg = self.take(8);
+                    // Preset dictionaries are not supported.
+                    if cmf & 0x0F != 8 || cmf >> 4 > 7 || (cmf << 8 | flg) % 31 != 0 || flg & 0x20 != 0 {
+                        return Fail(DeflateError::Header);
+                    }
+                    self.inflate.started = true;
+                    self.state = 1;
+                },
+                20 => {
+                    if !self.pull(input, 32) {
+                        return if input.is_over() { Fail(DeflateError::Unexpected) } else { Cont(self) };
+                    }
+                    let magic = self.take(16);
+                    let method = self.take(8);
+                    if magic != 0x8B1F || method != 8 {
+                        return Fail(DeflateError::Header);
+                    }
+                    // Keep the header flags in the length register.
+                    self.len = self.take(8) as usize;
+                    self.state = 21;
+                },
+                21 => {
+                    // Skip the modification time, extra flags, and OS.
+                    if !self.pull(input, 48) {
+                        return if input.is_over() { Fail(DeflateError::Unexpected) } else { Cont(self) };
+                    }
+                    self.take(48);
+                    self.state = if self.len & 0x04 != 0 { 22 } else { 24 };
+                },
+                22 => {
+                    if !self.pull(input, 16) {
+                        return if input.is_over() { Fail(DeflateError::Unexpected) } else { Cont(self) };
+                    }
+                    self.index = self.take(16) as usize;
+                    self.state = 23;
+                },
+                23 => {
+                    while self.index > 0 {
+                        if !self.pull(input, 8) {
+                            return if input.is_over() { Fail(DeflateError::Unexpected) } else { Cont(self) };
+                        }
+                        self.take(8);
+                        self.index -= 1;
+                    }
+                    self.state = 24;
+                },
+                24 | 25 => {
+                    // Skip the zero-terminated file name and comment.
+                    let flag = if self.state == 24 { 0x08 } else { 0x10 };
+                    if self.len & flag != 0 {
+                        loop {
+                            if !self.pull(input, 8) {
+                                return if input.is_over() { Fail(DeflateError::Unexpected) } else { Cont(self) };
+                            }
+                            if self.take(8) == 0 {
+                                break;
+                            }
+                        }
+                    }
+                    self.state += 1;
+                },
+                26 => {
+                    if self.len & 0x02 != 0 {
+                        if !self.pull(input, 16) {
+                            return if input.is_over() { Fail(DeflateError::Unexpected) } else { Cont(self) };
+                        }
+                        self.take(16);
+                    }
+                    self.inflate.started = true;
+                    self.state = 1;
+                },
+                _ => unreachable!(),
+            };
+        }
+    }
+}
+
+impl<S: AsRef<[u16]> + AsMut<[u16]>> Huffman<S> {
+    /// Builds the decoding table for a canonical code with the given code
+    /// `lengths`; returns `false` if the code is over-subscribed, or is
+    /// incomplete when it must be `complete` or has codes longer than one bit.
+    fn build(&mut self, lengths: &[u8], complete: bool) -> bool {
+        self.count = [0; MAX_BITS + 1];
+        for &length in lengths {
+            self.count[length as usize] += 1;
+        }
+        let mut left = 1i32;
+        for len in 1..=MAX_BITS {
+            left = (left << 1) - self.count[len] as i32;
+            if left < 0 {
+                return false;
+            }
+        }
+        let mut offsets = [0u16; MAX_BITS + 1];
+        for len in 1..MAX_BITS {
+            offsets[len + 1] = offsets[len] + self.count[len];
+        }
+        let symbol = self.symbol.as_mut();
+        for (index, &length) in lengths.iter().enumerate() {
+            if length != 0 {
+                symbol[offsets[length as usize] as usize] = index as u16;
+                offsets[length as usize] += 1;
+            }
+        }
+        let codes = lengths.len() as u16 - self.count[0];
+        left == 0 || !complete && codes == self.count[1]
+    }
+
+    /// Decodes the next symbol from the low `nbits` of `bits`, returning
+    /// the symbol and its code length, or `None` if more bits are needed.
+    fn decode(&self, bits: u64, nbits: u32) -> Result<Option<(usize, u32)>, DeflateError> {
+        let symbol = self.symbol.as_ref();
+        let mut code = 0i32;
+        let mut first = 0i32;
+        let mut index = 0i32;
+        for len in 1..=MAX_BITS {
+            if len as u32 > nbits {
+                return Ok(None);
+            }
+            code |= (bits >> (len - 1)) as i32 & 1;
+            let count = self.count[len] as i32;
+            if code - first < count {
+                return Ok(Some((symbol[(index + code - first) as usize] as usize, len as u32)));
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(DeflateError::Unexpected)
+    }
+}
+
+impl<'a> Deflate<'a> {
+    /// Allocates the windows and match index of a stream compressed at
+    /// `level`, from 0 (stored) to 9 (smallest).
+    pub fn try_hold(hold: &dyn Hold<'a>, format: DeflateFormat, level: u32) -> Result<Self, HoldError> {
+        let mut window = RawBuf::try_hold_cap(hold, 2 * WINDOW_SIZE)?;
+        window.try_extend((0..2 * WINDOW_SIZE).map(|_| 0))?;
+        let mut head = RawBuf::try_hold_cap(hold, HASH_SIZE)?;
+        head.try_extend((0..HASH_SIZE).map(|_| 0))?;
+        let mut prev = RawBuf::try_hold_cap(hold, WINDOW_SIZE)?;
+        prev.try_extend((0..WINDOW_SIZE).map(|_| 0))?;
+        Ok(Self {
+            format: format,
+            level: cmp::min(level, 9) as usize,
+            wbits: WINDOW_BITS,
+            max_dist: MAX_DIST,
+            takeover: true,
+            window: window,
+            head: head,
+            prev: prev,
+            symbols: RawBuf::try_hold_cap(hold, SYMBOL_CAP)?,
+            pending: RawBuf::try_hold_cap(hold, 2 * WINDOW_SIZE + 64)?,
+            offset: 0,
+            strstart: 0,
+            lookahead: 0,
+            block_start: 0,
+            match_len: MIN_MATCH - 1,
+            match_dist: 0,
+            match_available: false,
+            bits: 0,
+            nbits: 0,
+            adler: Adler32::new(),
+            crc: Crc32::new(),
+            size: 0,
+            started: false,
+            finished: false,
+        })
+    }
+
+    pub fn hold(hold: &dyn Hold<'a>, format: DeflateFormat, level: u32) -> Self {
+        Self::try_hold(hold, format, level).unwrap()
+    }
+
+    /// Limits back references to a window of `2^bits` bytes, for peers
+    /// that negotiated a smaller window; `bits` ranges from 8 to 15.
+    pub fn window_bits(mut self, bits: u32) -> Self {
+        self.wbits = cmp::max(8, cmp::min(bits, WINDOW_BITS));
+        self.max_dist = cmp::min(1 << self.wbits, MAX_DIST);
+        self
+    }
+
+    /// Sets whether back references may reach into the input of previous
+    /// messages; enabled by default.
+    pub fn context_takeover(mut self, takeover: bool) -> Self {
+        self.takeover = takeover;
+        self
+    }
+
+    pub fn format(&self) -> DeflateFormat {
+        self.format
+    }
+
+    pub fn level(&self) -> u32 {
+        self.level as u32
+    }
+
+    /// Forgets the window and checksums, and starts a new stream.
+    pub fn reset(&mut self) {
+        self.reset_window();
+        self.bits = 0;
+        self.nbits = 0;
+        self.adler = Adler32::new();
+        self.crc = Crc32::new();
+        self.size = 0;
+        self.started = false;
+        self.finished = false;
+    }
+
+    /// Returns an encoder that compresses `input` as the next message of
+    /// the stream.
+    pub fn encoder<I, O>(&mut self, input: I) -> DeflateEncoder<'_, 'a, I, O>
+        where I: Input<Token=u8>, O: Output<Token=u8> {
+        DeflateEncoder::new(self, input)
+    }
+
+    fn reset_window(&mut self) {
+        for entry in self.head.iter_mut() {
+            *entry = 0;
+        }
+        self.strstart = 0;
+        self.lookahead = 0;
+        self.block_start = 0;
+        self.match_len = MIN_MATCH - 1;
+        self.match_dist = 0;
+        self.match_available = false;
+    }
+
+    /// Writes pending bytes to `output`; returns `true` once none remain.
+    fn drain<O>(&mut self, output: &mut O) -> bool where O: Output<Token=u8> {
+        while self.offset < self.pending.len() {
+            if output.is_full() {
+                return false;
+            }
+            output.push(self.pending[self.offset]);
+            self.offset += 1;
+        }
+        self.pending.truncate(0);
+        self.offset = 0;
+        true
+    }
+
+    fn put_bits(&mut self, value: u32, n: u32) {
+        self.bits |= (value as u64) << self.nbits;
+        self.nbits += n;
+        while self.nbits >= 8 {
+            self.pending.push(self.bits as u8);
+            self.bits >>= 8;
+            self.nbits -= 8;
+        }
+    }
+
+    fn align(&mut self) {
+        if self.nbits > 0 {
+            self.pending.push(self.bits as u8);
+            self.bits = 0;
+            self.nbits = 0;
+        }
+    }
+
+    fn put_bytes(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.pending.push(byte);
+        }
+    }
+
+    fn write_header(&mut self) {
+        match self.format {
+            DeflateFormat::Raw => (),
+            DeflateFormat::Zlib => {
+                let cmf = ((self.wbits - 8) << 4 | 8) as u16;
+                let flevel = match self.level {
+                    0 | 1 => 0,
+                    2..=5 => 1,
+                    6 => 2,
+                    _ => 3,
+                };
+                let mut flg = flevel << 6;
+                flg += (31 - (cmf << 8 | flg) % 31) % 31;
+                self.put_bytes(&[cmf as u8, flg as u8]);
+            },
+            DeflateFormat::Gzip => {
+                let xfl = match self.level {
+                    9 => 2,
+                    1 => 4,
+                    _ => 0,
+                };
+                self.put_bytes(&[0x1F, 0x8B, 8, 0, 0, 0, 0, 0, xfl, 255]);
+            },
+        };
+    }
+
+    fn write_trailer(&mut self) {
+        self.align();
+        match self.format {
+            DeflateFormat::Raw => (),
+            DeflateFormat::Zlib => {
+                let adler = self.adler.digest();
+                self.put_bytes(&[(adler >> 24) as u8, (adler >> 16) as u8, (adler >> 8) as u8, adler as u8]);
+            },
+            DeflateFormat::Gzip => {
+                let crc = self.crc.digest();
+                let size = self.size;
+                self.put_bytes(&[crc as u8, (crc >> 8) as u8, (crc >> 16) as u8, (crc >> 24) as u8]);
+                self.put_bytes(&[size as u8, (size >> 8) as u8, (size >> 16) as u8, (size >> 24) as u8]);
+            },
+        };
+    }
+
+    /// Returns `true` if the window must slide before more input fits.
+    fn needs_slide(&self) -> bool {
+        self.strstart + self.lookahead >= 2 * WINDOW_SIZE && self.lookahead < MIN_LOOKAHEAD
+    }
+
+    /// Returns `true` if the current block has any symbols or pending bytes.
+    fn has_block(&self) -> bool {
+        !self.symbols.is_empty() || self.block_start < self.block_end()
+    }
+
+    /// Returns the window position of the end of the current block, which
+    /// excludes a literal deferred by lazy matching.
+    fn block_end(&self) -> usize {
+        self.strstart - self.match_available as usize
+    }
+
+    /// Moves the upper half of the window down, dropping input that is
+    /// now out of reach.
+    fn slide(&mut self) {
+        let (lower, upper) = self.window.split_at_mut(WINDOW_SIZE);
+        lower.copy_from_slice(upper);
+        self.strstart -= WINDOW_SIZE;
+        self.block_start -= WINDOW_SIZE;
+        for entry in self.head.iter_mut().chain(self.prev.iter_mut()) {
+            *entry = if *entry as usize >= WINDOW_SIZE { *entry - WINDOW_SIZE as u16 } else { 0 };
+        }
+    }
+
+    /// Appends input bytes to the window until it fills or the input runs out.
+    fn fill<I>(&mut self, input: &mut I) where I: Input<Token=u8> {
+        while self.strstart + self.lookahead < 2 * WINDOW_SIZE {
+            match input.head() {
+                In(byte) => {
+                    input.step();
+                    self.window[self.strstart + self.lookahead] = byte;
+                    self.lookahead += 1;
+                    match self.format {
+                        DeflateFormat::Raw => (),
+                        DeflateFormat::Zlib => self.adler.push(byte),
+                        DeflateFormat::Gzip => {
+                            self.crc.push(byte);
+                            self.size = self.size.wrapping_add(1);
+                        },
+                    };
+                },
+                _ => return,
+            };
+        }
+    }
+
+    /// Indexes the three bytes at `pos` and returns the previous position
+    /// with the same hash, or `0` if none.
+    fn insert(&mut self, pos: usize) -> usize {
+        let window = &self.window;
+        let hash = ((window[pos] as usize) << 10 ^ (window[pos + 1] as usize) << 5 ^ window[pos + 2] as usize) & (HASH_SIZE - 1);
+        let head = self.head[hash] as usize;
+        self.prev[pos & WINDOW_MASK] = head as u16;
+        self.head[hash] = pos as u16;
+        head
+    }
+
+    /// Searches the hash chain from `candidate` for a match longer than
+    /// `best`, returning its length and distance.
+    fn longest_match(&self, mut candidate: usize, best: usize) -> (usize, usize) {
+        let (_, nice, mut chain) = LEVELS[self.level];
+        let scan = self.strstart;
+        let limit = if scan > self.max_dist { scan - self.max_dist } else { 0 };
+        let max_len = cmp::min(MAX_MATCH, self.lookahead);
+        let window = &self.window[..];
+        let mut best_len = best;
+        let mut best_dist = 0;
+        while candidate > limit && chain > 0 && best_len < max_len {
+            if best_len < MIN_MATCH || window[candidate + best_len] == window[scan + best_len] {
+                let mut len = 0;
+                while len < max_len && window[candidate + len] == window[scan + len] {
+                    len += 1;
+                }
+                if len > best_len {
+                    best_len = len;
+                    best_dist = scan - candidate;
+                    if len >= nice {
+                        break;
+                    }
+                }
+            }
+            candidate = self.prev[candidate & WINDOW_MASK] as usize;
+            chain -= 1;
+        }
+        (best_len, best_dist)
+    }
+
+    fn tally(&mut self, len: usize, dist: usize) {
+        self.symbols.push((dist << 9 | len) as u32);
+    }
+
+    /// Finds matches in the buffered input until the symbol buffer fills,
+    /// or too little lookahead remains; returns `true` if a block should
+    /// be emitted.
+    fn compress(&mut self, over: bool) -> bool {
+        let (max_lazy, _, _) = LEVELS[self.level];
+        let lazy = self.level >= 4;
+        while (self.lookahead >= MIN_LOOKAHEAD || over && self.lookahead > 0) && self.symbols.len() < SYMBOL_CAP {
+            let candidate = if self.level != 0 && self.lookahead >= MIN_MATCH {
+                self.insert(self.strstart)
+            } else {
+                0
+            };
+            if !lazy {
+                let (len, dist) = if candidate != 0 && self.strstart - candidate <= self.max_dist {
+                    self.longest_match(candidate, MIN_MATCH - 1)
+                } else {
+                    (0, 0)
+                };
+                if len >= MIN_MATCH {
+                    self.tally(len, dist);
+                    let end = self.strstart + self.lookahead;
+                    for pos in self.strstart + 1..self.strstart + len {
+                        if pos + MIN_MATCH <= end {
+                            self.insert(pos);
+                        }
+                    }
+                    self.strstart += len;
+                    self.lookahead -= len;
+                } else {
+                    let byte = self.window[self.strstart];
+                    self.tally(byte as usize, 0);
+                    self.strstart += 1;
+                    self.lookahead -= 1;
+                }
+                continue;
+            }
+            let prev_len = self.match_len;
+            let prev_dist = self.match_dist;
+            self.match_len = MIN_MATCH - 1;
+            if candidate != 0 && prev_len < max_lazy && self.strstart - candidate <= self.max_dist {
+                let (len, dist) = self.longest_match(candidate, prev_len);
+                if len > prev_len && !(len == MIN_MATCH && dist > TOO_FAR) {
+                    self.match_len = len;
+                    self.match_dist = dist;
+                }
+            }
+            if prev_len >= MIN_MATCH && self.match_len <= prev_len {
+                // The previous match is at least as good; emit it.
+                let end = self.strstart + self.lookahead;
+                self.tally(prev_len, prev_dist);
+                for pos in self.strstart + 1..self.strstart + prev_len - 1 {
+                    if pos + MIN_MATCH <= end {
+                        self.insert(pos);
+                    }
+                }
+                self.strstart += prev_len - 1;
+                self.lookahead -= prev_len - 1;
+                self.match_available = false;
+                self.match_len = MIN_MATCH - 1;
+            } else {
+                if self.match_available {
+                    let byte = self.window[self.strstart - 1];
+                    self.tally(byte as usize, 0);
+                }
+                self.match_available = true;
+                self.strstart += 1;
+                self.lookahead -= 1;
+            }
+        }
+        if over && self.lookahead == 0 && self.match_available && self.symbols.len() < SYMBOL_CAP {
+            let byte = self.window[self.strstart - 1];
+            self.tally(byte as usize, 0);
+            self.match_available = false;
+        }
+        self.symbols.len() >= SYMBOL_CAP
+    }
+
+    /// Writes the symbols of the current block with whichever of the
+    /// stored, fixed, or dynamic encodings is smallest.
+    fn emit_block(&mut self, last: bool) {
+        let mut lit_freqs = [0u32; 286];
+        let mut dist_freqs = [0u32; 30];
+        for &symbol in self.symbols.iter() {
+            let (len, dist) = ((symbol & 0x1FF) as usize, (symbol >> 9) as usize);
+            if dist == 0 {
+                lit_freqs[len] += 1;
+            } else {
+                lit_freqs[257 + length_code(len)] += 1;
+                dist_freqs[dist_code(dist)] += 1;
+            }
+        }
+        lit_freqs[END_BLOCK] = 1;
+
+        let mut fixed_lit = [0u8; 288];
+        for (symbol, length) in fixed_lit.iter_mut().enumerate() {
+            *length = match symbol {
+                0..=143 => 8,
+                144..=255 => 9,
+                256..=279 => 7,
+                _ => 8,
+            };
+        }
+        let fixed_dist = [5u8; 30];
+
+        let mut lit_lens = [0u8; 286];
+        let mut dist_lens = [0u8; 30];
+        build_lengths(&lit_freqs, &mut lit_lens, MAX_BITS as u8);
+        build_lengths(&dist_freqs, &mut dist_lens, MAX_BITS as u8);
+        let nlen = cmp::max(257, 286 - lit_lens.iter().rev().take_while(|&&len| len == 0).count());
+        let ndist = cmp::max(1, 30 - dist_lens.iter().rev().take_while(|&&len| len == 0).count());
+
+        // Run-length encode the code lengths, as symbol and extra bits.
+        let mut all_lens = [0u8; 316];
+        all_lens[..nlen].copy_from_slice(&lit_lens[..nlen]);
+        all_lens[nlen..nlen + ndist].copy_from_slice(&dist_lens[..ndist]);
+        let mut runs = [(0u8, 0u8); 316];
+        let mut nruns = 0;
+        let mut index = 0;
+        while index < nlen + ndist {
+            let len = all_lens[index];
+            let mut run = 1;
+            while index + run < nlen + ndist && all_lens[index + run] == len {
+                run += 1;
+            }
+            if len == 0 && run >= 3 {
+                let run = cmp::min(run, 138);
+                runs[nruns] = if run >= 11 { (18, (run - 11) as u8) } else { (17, (run - 3) as u8) };
+                nruns += 1;
+                index += run;
+            } else if len != 0 && run >= 4 {
+                let run = cmp::min(run - 1, 6);
+                runs[nruns] = (len, 0);
+                runs[nruns + 1] = (16, (run - 3) as u8);
+                nruns += 2;
+                index += 1 + run;
+            } else {
+                runs[nruns] = (len, 0);
+                nruns += 1;
+                index += 1;
+            }
+        }
+        let mut code_freqs = [0u32; 19];
+        for &(symbol, _) in runs[..nruns].iter() {
+            code_freqs[symbol as usize] += 1;
+        }
+        let mut code_lens = [0u8; 19];
+        build_lengths(&code_freqs, &mut code_lens, 7);
+        let ncode = cmp::max(4, 19 - CODE_ORDER.iter().rev().take_while(|&&symbol| code_lens[symbol] == 0).count());
+
+        let data_bits = |lit_lens: &[u8], dist_lens: &[u8]| -> usize {
+            let mut bits = 0;
+            for (symbol, &freq) in lit_freqs.iter().enumerate() {
+                let extra = if symbol > END_BLOCK { LENGTH_EXTRA[symbol - 257] as usize } else { 0 };
+                bits += freq as usize * (lit_lens[symbol] as usize + extra);
+            }
+            for (symbol, &freq) in dist_freqs.iter().enumerate() {
+                bits += freq as usize * (dist_lens[symbol] as usize + DIST_EXTRA[symbol] as usize);
+            }
+            bits
+        };
+        let fixed_bits = 3 + data_bits(&fixed_lit, &fixed_dist);
+        let mut dynamic_bits = 3 + 14 + 3 * ncode + data_bits(&lit_lens, &dist_lens);
+        for &(symbol, _) in runs[..nruns].iter() {
+            dynamic_bits += code_lens[symbol as usize] as usize + match symbol {
+                16 => 2,
+                17 => 3,
+                18 => 7,
+                _ => 0,
+            };
+        }
+        let stored_len = self.block_end() - self.block_start;
+        let stored_bits = 8 * (stored_len + 5 * cmp::max(1, (stored_len + MAX_STORED - 1) / MAX_STORED)) + 7;
+
+        if self.level == 0 || stored_bits <= cmp::min(fixed_bits, dynamic_bits) {
+            let mut start = self.block_start;
+            let end = self.block_end();
+            loop {
+                let len = cmp::min(end - start, MAX_STORED);
+                let last = last && start + len == end;
+                self.put_bits(last as u32, 3);
+                self.align();
+                self.put_bytes(&[len as u8, (len >> 8) as u8, !len as u8, (!len >> 8) as u8]);
+                for index in start..start + len {
+                    self.pending.push(self.window[index]);
+                }
+                start += len;
+                if start == end {
+                    break;
+                }
+            }
+        } else {
+            let mut lit_codes = [0u16; 288];
+            let mut dist_codes = [0u16; 30];
+            if fixed_bits <= dynamic_bits {
+                self.put_bits(last as u32 | 1 << 1, 3);
+                build_codes(&fixed_lit, &mut lit_codes);
+                build_codes(&fixed_dist, &mut dist_codes);
+                self.write_symbols(&fixed_lit, &lit_codes, &fixed_dist, &dist_codes);
+            } else {
+                self.put_bits(last as u32 | 2 << 1, 3);
+                self.put_bits((nlen - 257) as u32, 5);
+                self.put_bits((ndist - 1) as u32, 5);
+                self.put_bits((ncode - 4) as u32, 4);
+                for &symbol in CODE_ORDER[..ncode].iter() {
+                    self.put_bits(code_lens[symbol] as u32, 3);
+                }
+                let mut code_codes = [0u16; 19];
+                build_codes(&code_lens, &mut code_codes);
+                for &(symbol, extra) in runs[..nruns].iter() {
+                    self.put_bits(code_codes[symbol as usize] as u32, code_lens[symbol as usize] as u32);
+                    match symbol {
+                        16 => self.put_bits(extra as u32, 2),
+                        17 => self.put_bits(extra as u32, 3),
+                        18 => self.put_bits(extra as u32, 7),
+                        _ => (),
+                    };
+                }
+                build_codes(&lit_lens, &mut lit_codes);
+                build_codes(&dist_lens, &mut dist_codes);
+                self.write_symbols(&lit_lens, &lit_codes, &dist_lens, &dist_codes);
+            }
+        }
+        self.symbols.truncate(0);
+        self.block_start = self.block_end();
+    }
+
+    fn write_symbols(&mut self, lit_lens: &[u8], lit_codes: &[u16], dist_lens: &[u8], dist_codes: &[u16]) {
+        for index in 0..self.symbols.len() {
+            let symbol = self.symbols[index];
+            let (len, dist) = ((symbol & 0x1FF) as usize, (symbol >> 9) as usize);
+            if dist == 0 {
+                self.put_bits(lit_codes[len] as u32, lit_lens[len] as u32);
+            } else {
+                let code = length_code(len);
+                self.put_bits(lit_codes[257 + code] as u32, lit_lens[257 + code] as u32);
+                self.put_bits((len - LENGTH_BASE[code] as usize) as u32, LENGTH_EXTRA[code] as u32);
+                let code = dist_code(dist);
+                self.put_bits(dist_codes[code] as u32, dist_lens[code] as u32);
+                self.put_bits((dist - DIST_BASE[code] as usize) as u32, DIST_EXTRA[code] as u32);
+            }
+        }
+        self.put_bits(lit_codes[END_BLOCK] as u32, lit_lens[END_BLOCK] as u32);
+    }
+}
+
+impl<'b, 'a, I, O> DeflateEncoder<'b, 'a, I, O> where I: Input<Token=u8>, O: Output<Token=u8> {
+    pub fn new(deflate: &'b mut Deflate<'a>, input: I) -> Self {
+        if deflate.finished {
+            deflate.reset();
+        } else if !deflate.takeover {
+            deflate.reset_window();
+        }
+        Self {
+            deflate: deflate,
+            input: input,
+            flush: DeflateFlush::Finish,
+            state: 1,
+            output: PhantomData,
+        }
+    }
+
+    pub fn flush(mut self, flush: DeflateFlush) -> Self {
+        self.flush = flush;
+        self
+    }
+
+    pub fn produce(mut self, mut output: O) -> Result<O::Out, O::Err> {
+        loop {
+            match self.encode(&mut output) {
+                Done(_) => return output.take_out(),
+                Fail(_) => unreachable!(),
+                Cont(next) => {
+                    self = next;
+                    self.input.over();
+                }
+            }
+        }
+    }
+}
+
+impl<'b, 'a, I, O> Encoder for DeflateEncoder<'b, 'a, I, O> where I: Input<Token=u8>, O: Output<Token=u8> {
+    type Input = I;
+    type Output = O;
+    type Error = DeflateError;
+
+    fn encode(mut self, output: &mut O) -> Then<Self, I, DeflateError> {
+        loop {
+            if !self.deflate.drain(output) {
+                return Cont(self);
+            }
+            match self.state {
+                1 => {
+                    if !self.deflate.started {
+                        self.deflate.write_header();
+                        self.deflate.started = true;
+                    }
+                    self.state = 2;
+                },
+                2 => {
+                    if self.deflate.needs_slide() {
+                        if self.deflate.has_block() {
+                            self.deflate.emit_block(false);
+                            continue;
+                        }
+                        self.deflate.slide();
+                    }
+                    self.deflate.fill(&mut self.input);
+                    let over = self.input.is_over();
+                    if !over && self.deflate.lookahead < MIN_LOOKAHEAD && !self.deflate.needs_slide() {
+                        return Cont(self);
+                    }
+                    if self.deflate.compress(over) {
+                        self.deflate.emit_block(false);
+                    } else if over && self.deflate.lookahead == 0 {
+                        self.state = 3;
+                    }
+                },
+                3 => {
+                    match self.flush {
+                        DeflateFlush::Finish => {
+                            self.deflate.emit_block(true);
+                            self.deflate.write_trailer();
+                            self.deflate.finished = true;
+                        },
+                        DeflateFlush::Sync => {
+                            if self.deflate.has_block() {
+                                self.deflate.emit_block(false);
+                            }
+                            self.deflate.put_bits(0, 3);
+                            self.deflate.align();
+                            self.deflate.put_bytes(&[0x00, 0x00, 0xFF, 0xFF]);
+                        },
+                    };
+                    self.state = 4;
+                },
+                4 => return Done(self.input),
+                _ => unreachable!(),
+            };
+        }
+    }
+}
+
+fn length_code(len: usize) -> usize {
+    LENGTH_BASE.iter().rposition(|&base| base as usize <= len).unwrap()
+}
+
+fn dist_code(dist: usize) -> usize {
+    DIST_BASE.iter().rposition(|&base| base as usize <= dist).unwrap()
+}
+
+/// Computes Huffman code lengths for symbol `freqs`, no longer than
+/// `limit` bits. Frequencies are flattened until the tree fits. Always
+/// assigns at least two codes, so that the resulting code is complete.
+fn build_lengths(freqs: &[u32], lengths: &mut [u8], limit: u8) {
+    let n = freqs.len();
+    let mut weights = [0u32; 286];
+    weights[..n].copy_from_slice(freqs);
+    loop {
+        for length in lengths.iter_mut() {
+            *length = 0;
+        }
+        let mut leaves = [0u16; 286];
+        let mut count = 0;
+        for symbol in 0..n {
+            if weights[symbol] != 0 {
+                leaves[count] = symbol as u16;
+                count += 1;
+            }
+        }
+        if count < 2 {
+            let first = if count == 1 { leaves[0] as usize } else { 0 };
+            lengths[first] = 1;
+            lengths[if first == 0 { 1 } else { 0 }] = 1;
+            return;
+        }
+        leaves[..count].sort_unstable_by_key(|&symbol| weights[symbol as usize]);
+
+        // Leaves occupy the first `count` nodes in weight order; internal
+        // nodes are created in non-decreasing weight order after them.
+        let mut node_weights = [0u32; 571];
+        let mut parents = [0u16; 571];
+        for (node, &symbol) in leaves[..count].iter().enumerate() {
+            node_weights[node] = weights[symbol as usize];
+        }
+        let (mut leaf, mut internal) = (0, count);
+        for node in count..2 * count - 1 {
+            let mut pick = || {
+                if leaf < count && (internal >= node || node_weights[leaf] <= node_weights[internal]) {
+                    leaf += 1;
+                    leaf - 1
+                } else {
+                    internal += 1;
+                    internal - 1
+                }
+            };
+            let (a, b) = (pick(), pick());
+            node_weights[node] = node_weights[a] + node_weights[b];
+            parents[a] = node as u16;
+            parents[b] = node as u16;
+        }
+        let mut depths = [0u8; 571];
+        for node in (0..2 * count - 2).rev() {
+            depths[node] = depths[parents[node] as usize] + 1;
+        }
+        let max = depths[..count].iter().cloned().max().unwrap();
+        if max <= limit {
+            for (node, &symbol) in leaves[..count].iter().enumerate() {
+                lengths[symbol as usize] = depths[node];
+            }
+            return;
+        }
+        for weight in weights.iter_mut() {
+            if *weight != 0 {
+                *weight = *weight >> 1 | 1;
+            }
+        }
+    }
+}
+
+/// Assigns canonical codes to code `lengths`, bit-reversed for writing
+/// least significant bit first.
+fn build_codes(lengths: &[u8], codes: &mut [u16]) {
+    let mut counts = [0u16; MAX_BITS + 1];
+    for &length in lengths {
+        counts[length as usize] += 1;
+    }
+    counts[0] = 0;
+    let mut next = [0u16; MAX_BITS + 1];
+    let mut code = 0u16;
+    for bits in 1..=MAX_BITS {
+        code = (code + counts[bits - 1]) << 1;
+        next[bits] = code;
+    }
+    for (symbol, &length) in lengths.iter().enumerate() {
+        if length != 0 {
+            let mut code = next[length as usize];
+            next[length as usize] += 1;
+            let mut reversed = 0u16;
+            for _ in 0..length {
+                reversed = reversed << 1 | code & 1;
+                code >>= 1;
+            }
+            codes[symbol] = reversed;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use swim_mem::block::Block;
+    use swim_mem::alloc::Pack;
+    use crate::input::AsInput;
+    use crate::output::SliceOutput;
+    use super::*;
+
+    const HELLO: &[u8] = b"Hello, Hello, Hello, world!";
+
+    /// Output that holds a few bytes at a time.
+    struct ChunkOutput {
+        chunk: [u8; 7],
+        len: usize,
+    }
+
+    impl Output for ChunkOutput {
+        type Token = u8;
+        type Out = ([u8; 7], usize);
+        type Err = ();
+
+        fn is_full(&self) -> bool {
+            self.len == self.chunk.len()
+        }
+
+        fn push(&mut self, token: u8) {
+            self.chunk[self.len] = token;
+            self.len += 1;
+        }
+
+        fn take_out(self) -> Result<([u8; 7], usize), ()> {
+            Ok((self.chunk, self.len))
+        }
+    }
+
+    /// Fills `buffer` with alternating runs of repetitive text and noise.
+    fn sample(buffer: &mut [u8]) {
+        let text = b"swim lanes link agents to streams ";
+        let mut seed = 0x2545F491u32;
+        for (index, byte) in buffer.iter_mut().enumerate() {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            *byte = if index / 4096 % 3 == 2 { seed as u8 } else { text[index % text.len()] };
+        }
+    }
+
+    fn deflate<'x>(deflate: &mut Deflate, decoded: &[u8], flush: DeflateFlush, buffer: &'x mut [u8]) -> &'x mut [u8] {
+        deflate.encoder(decoded.as_input()).flush(flush).produce(SliceOutput::new(buffer)).unwrap()
+    }
+
+    fn inflate<'x>(inflate: &mut Inflate, encoded: &[u8], buffer: &'x mut [u8]) -> Result<&'x mut [u8], DeflateError> {
+        inflate.decoder(SliceOutput::new(buffer)).consume(&mut encoded.as_input())
+    }
+
+    #[test]
+    fn test_inflate_captured() {
+        static mut TEST_AREA: [u8; 131072] = [0; 131072];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let mut buffer = [0u8; 64];
+        let mut raw = Inflate::hold(pack, DeflateFormat::Raw);
+        assert_eq!(inflate(&mut raw, &[
+            0xF3, 0x48, 0xCD, 0xC9, 0xC9, 0xD7, 0x51, 0xF0, 0x40, 0xA1, 0xCA, 0xF3,
+            0x8B, 0x72, 0x52, 0x14, 0x01,
+        ], &mut buffer).unwrap(), HELLO);
+        let mut zlib = Inflate::hold(pack, DeflateFormat::Zlib);
+        assert_eq!(inflate(&mut zlib, &[
+            0x78, 0xDA, 0xF3, 0x48, 0xCD, 0xC9, 0xC9, 0xD7, 0x51, 0xF0, 0x40, 0xA1,
+            0xCA, 0xF3, 0x8B, 0x72, 0x52, 0x14, 0x01, 0x7E, 0x7A, 0x09, 0x0A,
+        ], &mut buffer).unwrap(), HELLO);
+        assert!(zlib.is_finished());
+        let mut gzip = Inflate::hold(pack, DeflateFormat::Gzip);
+        assert_eq!(inflate(&mut gzip, &[
+            0x1F, 0x8B, 0x08, 0x08, 0x00, 0x00, 0x00, 0x00, 0x02, 0xFF, 0x68, 0x65,
+            0x6C, 0x6C, 0x6F, 0x2E, 0x74, 0x78, 0x74, 0x00, 0xF3, 0x48, 0xCD, 0xC9,
+            0xC9, 0xD7, 0x51, 0xF0, 0x40, 0xA1, 0xCA, 0xF3, 0x8B, 0x72, 0x52, 0x14,
+            0x01, 0xD7, 0xC4, 0x74, 0x7B, 0x1B, 0x00, 0x00, 0x00,
+        ], &mut buffer).unwrap(), HELLO);
+    }
+
+    #[test]
+    fn test_deflate_transcode() {
+        let mut decoded = [0u8; 100000];
+        sample(&mut decoded);
+        for &format in [DeflateFormat::Raw, DeflateFormat::Zlib, DeflateFormat::Gzip].iter() {
+            for &level in [0, 1, 4, 6, 9].iter() {
+                let mut test_area = [0u8; 393216];
+                let pack = Pack::new(Block::from_slice(&mut test_area));
+                let mut encoder = Deflate::hold(pack, format, level);
+                let mut decoder = Inflate::hold(pack, format);
+                let mut encoded = [0u8; 110000];
+                let encoded = deflate(&mut encoder, &decoded, DeflateFlush::Finish, &mut encoded);
+                if level != 0 {
+                    assert!(encoded.len() < decoded.len() / 2, "level {}: {} bytes", level, encoded.len());
+                }
+                let mut buffer = [0u8; 100000];
+                assert_eq!(&inflate(&mut decoder, encoded, &mut buffer).unwrap()[..], &decoded[..]);
+                assert!(decoder.is_finished());
+            }
+        }
+    }
+
+    #[test]
+    fn test_inflate_incremental() {
+        static mut TEST_AREA: [u8; 65536] = [0; 65536];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let encoded = [
+            0x1F, 0x8B, 0x08, 0x08, 0x00, 0x00, 0x00, 0x00, 0x02, 0xFF, 0x68, 0x65,
+            0x6C, 0x6C, 0x6F, 0x2E, 0x74, 0x78, 0x74, 0x00, 0xF3, 0x48, 0xCD, 0xC9,
+            0xC9, 0xD7, 0x51, 0xF0, 0x40, 0xA1, 0xCA, 0xF3, 0x8B, 0x72, 0x52, 0x14,
+            0x01, 0xD7, 0xC4, 0x74, 0x7B, 0x1B, 0x00, 0x00, 0x00,
+        ];
+        let mut gzip = Inflate::hold(pack, DeflateFormat::Gzip);
+        for split in 0..=encoded.len() {
+            gzip.reset();
+            let mut buffer = [0u8; 64];
+            let decoder = gzip.decoder(SliceOutput::new(&mut buffer));
+            let decoded = match decoder.decode(&mut encoded[..split].as_input()) {
+                Cont(decoder) => decoder.consume(&mut encoded[split..].as_input()).unwrap(),
+                Done(decoded) => decoded,
+                Fail(error) => panic!("{:?}", error),
+            };
+            assert_eq!(decoded, HELLO);
+        }
+    }
+
+    #[test]
+    fn test_deflate_incremental() {
+        static mut TEST_AREA: [u8; 393216] = [0; 393216];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let mut decoded = [0u8; 20000];
+        sample(&mut decoded);
+        let mut deflate = Deflate::hold(pack, DeflateFormat::Zlib, 6);
+        let mut encoder = deflate.encoder(decoded[..0].as_input());
+        let mut encoded = [0u8; 20000];
+        let mut len = 0;
+        let mut offset = 0;
+        loop {
+            let mut output = ChunkOutput { chunk: [0; 7], len: 0 };
+            let then = encoder.encode(&mut output);
+            let (chunk, n) = output.take_out().unwrap();
+            encoded[len..len + n].copy_from_slice(&chunk[..n]);
+            len += n;
+            match then {
+                Cont(next) => {
+                    encoder = next;
+                    if offset < decoded.len() && encoder.input.is_out() {
+                        let end = cmp::min(offset + 1000, decoded.len());
+                        encoder.input = decoded[offset..end].as_input();
+                        offset = end;
+                    } else if offset == decoded.len() {
+                        encoder.input.over();
+                    }
+                },
+                Done(_) => break,
+                Fail(error) => panic!("{:?}", error),
+            };
+        }
+        let mut inflate = Inflate::hold(pack, DeflateFormat::Zlib);
+        let mut buffer = [0u8; 20000];
+        assert_eq!(&self::inflate(&mut inflate, &encoded[..len], &mut buffer).unwrap()[..], &decoded[..]);
+    }
+
+    #[test]
+    fn test_deflate_context_takeover() {
+        static mut TEST_AREA: [u8; 786432] = [0; 786432];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let message = b"{\"node\":\"/unit/foo\",\"lane\":\"info\",\"body\":\"swim\"}";
+        let mut deflate = Deflate::hold(pack, DeflateFormat::Raw, 6);
+        let mut inflate = Inflate::hold(pack, DeflateFormat::Raw);
+        let mut sizes = [0usize; 2];
+        for size in sizes.iter_mut() {
+            let mut encoded = [0u8; 128];
+            let len = {
+                let encoded = self::deflate(&mut deflate, message, DeflateFlush::Sync, &mut encoded);
+                assert_eq!(&encoded[encoded.len() - 4..], &[0x00, 0x00, 0xFF, 0xFF]);
+                encoded.len()
+            };
+            *size = len - 4;
+            let mut buffer = [0u8; 128];
+            assert_eq!(&self::inflate(&mut inflate, &encoded[..len], &mut buffer).unwrap()[..], &message[..]);
+        }
+        assert!(sizes[1] < sizes[0] / 4, "{:?}", sizes);
+
+        let mut deflate = Deflate::hold(pack, DeflateFormat::Raw, 6).context_takeover(false);
+        let mut inflate = Inflate::hold(pack, DeflateFormat::Raw).context_takeover(false);
+        for size in sizes.iter_mut() {
+            let mut encoded = [0u8; 128];
+            let len = self::deflate(&mut deflate, message, DeflateFlush::Sync, &mut encoded).len();
+            *size = len;
+            let mut buffer = [0u8; 128];
+            assert_eq!(&self::inflate(&mut inflate, &encoded[..len], &mut buffer).unwrap()[..], &message[..]);
+        }
+        assert_eq!(sizes[0], sizes[1]);
+    }
+
+    #[test]
+    fn test_inflate_truncated() {
+        let mut decoded = [0u8; 1000];
+        sample(&mut decoded);
+        for &format in [DeflateFormat::Zlib, DeflateFormat::Gzip].iter() {
+            let mut test_area = [0u8; 393216];
+            let pack = Pack::new(Block::from_slice(&mut test_area));
+            // A stream cut after a sync flush ends on a block boundary, but
+            // lacks the final block and trailer.
+            let mut deflate = Deflate::hold(pack, format, 6);
+            let mut encoded = [0u8; 2048];
+            let encoded = self::deflate(&mut deflate, &decoded, DeflateFlush::Sync, &mut encoded);
+            let mut inflate = Inflate::hold(pack, format);
+            let mut buffer = [0u8; 2048];
+            assert_eq!(self::inflate(&mut inflate, encoded, &mut buffer).unwrap_err(),
+                       DeflateError::Unexpected, "{:?}", format);
+            assert!(!inflate.is_finished());
+
+            // A complete stream with its trailer cut short.
+            deflate.reset();
+            let mut encoded = [0u8; 2048];
+            let encoded = self::deflate(&mut deflate, &decoded, DeflateFlush::Finish, &mut encoded);
+            inflate.reset();
+            assert_eq!(self::inflate(&mut inflate, &encoded[..encoded.len() - 1], &mut buffer).unwrap_err(),
+                       DeflateError::Unexpected, "{:?}", format);
+            assert!(!inflate.is_finished());
+        }
+        static mut TEST_AREA: [u8; 65536] = [0; 65536];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let mut gzip = Inflate::hold(pack, DeflateFormat::Gzip);
+        let mut buffer = [0u8; 64];
+        assert_eq!(inflate(&mut gzip, &[
+            0x1F, 0x8B, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF,
+        ], &mut buffer).unwrap_err(), DeflateError::Unexpected);
+    }
+
+    #[test]
+    fn test_inflate_malformed() {
+        static mut TEST_AREA: [u8; 524288] = [0; 524288];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let mut buffer = [0u8; 64];
+        let mut zlib = Inflate::hold(pack, DeflateFormat::Zlib);
+        assert_eq!(inflate(&mut zlib, &[
+            0x78, 0xDA, 0xF3, 0x48, 0xCD, 0xC9, 0xC9, 0xD7, 0x51, 0xF0, 0x40, 0xA1,
+            0xCA, 0xF3, 0x8B, 0x72, 0x52, 0x14, 0x01, 0x7E, 0x7A, 0x09, 0x0B,
+        ], &mut buffer).unwrap_err(), DeflateError::Checksum);
+        zlib.reset();
+        assert_eq!(inflate(&mut zlib, &[0x78, 0xDB, 0x03, 0x00], &mut buffer).unwrap_err(), DeflateError::Header);
+        zlib.reset();
+        assert_eq!(inflate(&mut zlib, &[0x78, 0xDA, 0xF3, 0x48], &mut buffer).unwrap_err(), DeflateError::Unexpected);
+        zlib.reset();
+        assert_eq!(inflate(&mut zlib, &[0x78, 0xDA], &mut buffer).unwrap_err(), DeflateError::Unexpected);
+        assert!(!zlib.is_finished());
+
+        // A message that refers back into a previous message's window.
+        let mut deflate = Deflate::hold(pack, DeflateFormat::Raw, 6);
+        let mut encoded = [0u8; 64];
+        self::deflate(&mut deflate, HELLO, DeflateFlush::Sync, &mut encoded);
+        let mut encoded = [0u8; 64];
+        let encoded = self::deflate(&mut deflate, HELLO, DeflateFlush::Sync, &mut encoded);
+        let mut raw = Inflate::hold(pack, DeflateFormat::Raw);
+        assert_eq!(inflate(&mut raw, encoded, &mut buffer).unwrap_err(), DeflateError::Distance);
+    }
+
+    #[test]
+    fn test_inflate_oversized() {
+        static mut TEST_AREA: [u8; 524288] = [0; 524288];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        // Literals, then a match that runs past the end of the output.
+        let mut buffer = [0u8; 16];
+        let mut raw = Inflate::hold(pack, DeflateFormat::Raw);
+        assert_eq!(inflate(&mut raw, &[
+            0xF3, 0x48, 0xCD, 0xC9, 0xC9, 0xD7, 0x51, 0xF0, 0x40, 0xA1, 0xCA, 0xF3,
+            0x8B, 0x72, 0x52, 0x14, 0x01,
+        ], &mut buffer).unwrap_err(), DeflateError::Oversized);
+        // Literals that overrun the output.
+        let mut buffer = [0u8; 4];
+        raw.reset();
+        assert_eq!(inflate(&mut raw, &[
+            0xF3, 0x48, 0xCD, 0xC9, 0xC9, 0xD7, 0x51, 0xF0, 0x40, 0xA1, 0xCA, 0xF3,
+            0x8B, 0x72, 0x52, 0x14, 0x01,
+        ], &mut buffer).unwrap_err(), DeflateError::Oversized);
+        // A stored block longer than the output.
+        raw.reset();
+        assert_eq!(inflate(&mut raw, &[0x01, 0x05, 0x00, 0xFA, 0xFF, b'h', b'e', b'l', b'l', b'o'],
+                           &mut buffer).unwrap_err(), DeflateError::Oversized);
+
+        // A highly compressible stream that inflates far beyond its output.
+        let decoded = [0u8; 65536];
+        let mut deflate = Deflate::hold(pack, DeflateFormat::Zlib, 9);
+        let mut encoded = [0u8; 1024];
+        let encoded = self::deflate(&mut deflate, &decoded, DeflateFlush::Finish, &mut encoded);
+        let mut zlib = Inflate::hold(pack, DeflateFormat::Zlib);
+        let mut buffer = [0u8; 1024];
+        assert_eq!(inflate(&mut zlib, encoded, &mut buffer).unwrap_err(), DeflateError::Oversized);
+        assert!(!zlib.is_finished());
+    }
+}
@@ -0,0 +1,383 @@
+use core::marker::PhantomData;
+use crate::step::{In, Out, Over};
+use crate::then::{Then, Cont, Done, Fail};
+use crate::input::Input;
+use crate::decoder::Decoder;
+
+/// Cryptographic hash function that digests a message incrementally.
+pub trait Digest {
+    /// Size in bytes of the blocks the message is digested in; at most 128.
+    const BLOCK_SIZE: usize;
+
+    type Output: AsRef<[u8]>;
+
+    fn push(&mut self, byte: u8);
+
+    fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.push(byte);
+        }
+    }
+
+    /// Pads the message and returns its digest.
+    fn finish(self) -> Self::Output;
+}
+
+/// SHA-1 hash, as specified by FIPS 180-4. Broken for collision
+/// resistance; use only where a protocol requires it.
+#[derive(Clone)]
+pub struct Sha1 {
+    h: [u32; 5],
+    block: [u8; 64],
+    len: u64,
+}
+
+/// SHA-256 hash, as specified by FIPS 180-4.
+#[derive(Clone)]
+pub struct Sha256 {
+    h: [u32; 8],
+    block: [u8; 64],
+    len: u64,
+}
+
+/// Keyed-hash message authentication code, as specified by RFC 2104.
+#[derive(Clone)]
+pub struct Hmac<H: Digest> {
+    inner: H,
+    outer: H,
+}
+
+/// Digests the bytes of an input up to the point where it's over.
+pub struct DigestDecoder<I: Input<Token=u8>, H: Digest> {
+    pub digest: H,
+    input: PhantomData<I>,
+}
+
+impl Sha1 {
+    pub fn new() -> Self {
+        Self {
+            h: [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0],
+            block: [0; 64],
+            len: 0,
+        }
+    }
+
+    fn compress(&mut self) {
+        let mut w = [0u32; 80];
+        for i in 0..16 {
+            w[i] = (self.block[4 * i] as u32) << 24 | (self.block[4 * i + 1] as u32) << 16 |
+                   (self.block[4 * i + 2] as u32) << 8 | self.block[4 * i + 3] as u32;
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = self.h;
+        for i in 0..80 {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let t = a.rotate_left(5).wrapping_add(f).wrapping_add(e).wrapping_add(k).wrapping_add(w[i]);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = t;
+        }
+        self.h[0] = self.h[0].wrapping_add(a);
+        self.h[1] = self.h[1].wrapping_add(b);
+        self.h[2] = self.h[2].wrapping_add(c);
+        self.h[3] = self.h[3].wrapping_add(d);
+        self.h[4] = self.h[4].wrapping_add(e);
+    }
+}
+
+impl Default for Sha1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Sha1 {
+    const BLOCK_SIZE: usize = 64;
+
+    type Output = [u8; 20];
+
+    fn push(&mut self, byte: u8) {
+        self.block[(self.len & 63) as usize] = byte;
+        self.len += 1;
+        if self.len & 63 == 0 {
+            self.compress();
+        }
+    }
+
+    fn finish(mut self) -> [u8; 20] {
+        let bits = self.len << 3;
+        pad(&mut self, bits);
+        let mut digest = [0u8; 20];
+        for i in 0..20 {
+            digest[i] = (self.h[i >> 2] >> (24 - 8 * (i & 3))) as u8;
+        }
+        digest
+    }
+}
+
+impl Sha256 {
+    pub fn new() -> Self {
+        Self {
+            h: [0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19],
+            block: [0; 64],
+            len: 0,
+        }
+    }
+
+    fn compress(&mut self) {
+        let mut w = [0u32; 64];
+        for i in 0..16 {
+            w[i] = (self.block[4 * i] as u32) << 24 | (self.block[4 * i + 1] as u32) << 16 |
+                   (self.block[4 * i + 2] as u32) << 8 | self.block[4 * i + 3] as u32;
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ w[i - 15] >> 3;
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ w[i - 2] >> 10;
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.h;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(SHA256_K[i]).wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+        for (h, x) in self.h.iter_mut().zip([a, b, c, d, e, f, g, h].iter()) {
+            *h = h.wrapping_add(*x);
+        }
+    }
+}
+
+impl Default for Sha256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Sha256 {
+    const BLOCK_SIZE: usize = 64;
+
+    type Output = [u8; 32];
+
+    fn push(&mut self, byte: u8) {
+        self.block[(self.len & 63) as usize] = byte;
+        self.len += 1;
+        if self.len & 63 == 0 {
+            self.compress();
+        }
+    }
+
+    fn finish(mut self) -> [u8; 32] {
+        let bits = self.len << 3;
+        pad(&mut self, bits);
+        let mut digest = [0u8; 32];
+        for i in 0..32 {
+            digest[i] = (self.h[i >> 2] >> (24 - 8 * (i & 3))) as u8;
+        }
+        digest
+    }
+}
+
+/// Appends the padding and message length in `bits` that complete the last
+/// block of a Merkle–Damgård hash with 64 byte blocks.
+fn pad<H: Digest>(digest: &mut H, bits: u64) {
+    let len = bits >> 3;
+    digest.push(0x80);
+    for _ in 0..(119 - (len & 63)) & 63 {
+        digest.push(0);
+    }
+    for i in 0..8 {
+        digest.push((bits >> (56 - 8 * i)) as u8);
+    }
+}
+
+impl<H> Hmac<H> where H: Digest + Default {
+    pub fn new(key: &[u8]) -> Self {
+        let mut block = [0u8; 128];
+        if key.len() > H::BLOCK_SIZE {
+            let mut hash = H::default();
+            hash.update(key);
+            let digest = hash.finish();
+            let digest = digest.as_ref();
+            block[..digest.len()].copy_from_slice(digest);
+        } else {
+            block[..key.len()].copy_from_slice(key);
+        }
+        let mut inner = H::default();
+        let mut outer = H::default();
+        for &byte in &block[..H::BLOCK_SIZE] {
+            inner.push(byte ^ 0x36);
+            outer.push(byte ^ 0x5C);
+        }
+        Self { inner: inner, outer: outer }
+    }
+}
+
+impl<H> Digest for Hmac<H> where H: Digest {
+    const BLOCK_SIZE: usize = H::BLOCK_SIZE;
+
+    type Output = H::Output;
+
+    fn push(&mut self, byte: u8) {
+        self.inner.push(byte);
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        self.inner.update(bytes);
+    }
+
+    fn finish(mut self) -> H::Output {
+        self.outer.update(self.inner.finish().as_ref());
+        self.outer.finish()
+    }
+}
+
+impl<I, H> DigestDecoder<I, H> where I: Input<Token=u8>, H: Digest {
+    pub fn new(digest: H) -> Self {
+        Self {
+            digest: digest,
+            input: PhantomData,
+        }
+    }
+
+    pub fn consume(self, input: &mut I) -> H::Output {
+        let next = match self.decode(input) {
+            Done(digest) => return digest,
+            Cont(next) => next,
+            Fail(_) => unreachable!(),
+        };
+        input.over();
+        match next.decode(input) {
+            Done(digest) => digest,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<I, H> Decoder for DigestDecoder<I, H> where I: Input<Token=u8>, H: Digest {
+    type Input = I;
+    type Output = H::Output;
+    type Error = ();
+
+    fn decode(mut self, input: &mut I) -> Then<Self, H::Output, ()> {
+        loop {
+            match input.head() {
+                In(byte) => {
+                    input.step();
+                    self.digest.push(byte);
+                },
+                Out => return Cont(self),
+                Over => return Done(self.digest.finish()),
+            };
+        }
+    }
+}
+
+static SHA256_K: [u32; 64] = [
+    0x428A2F98, 0x71374491, 0xB5C0FBCF, 0xE9B5DBA5, 0x3956C25B, 0x59F111F1, 0x923F82A4, 0xAB1C5ED5,
+    0xD807AA98, 0x12835B01, 0x243185BE, 0x550C7DC3, 0x72BE5D74, 0x80DEB1FE, 0x9BDC06A7, 0xC19BF174,
+    0xE49B69C1, 0xEFBE4786, 0x0FC19DC6, 0x240CA1CC, 0x2DE92C6F, 0x4A7484AA, 0x5CB0A9DC, 0x76F988DA,
+    0x983E5152, 0xA831C66D, 0xB00327C8, 0xBF597FC7, 0xC6E00BF3, 0xD5A79147, 0x06CA6351, 0x14292967,
+    0x27B70A85, 0x2E1B2138, 0x4D2C6DFC, 0x53380D13, 0x650A7354, 0x766A0ABB, 0x81C2C92E, 0x92722C85,
+    0xA2BFE8A1, 0xA81A664B, 0xC24B8B70, 0xC76C51A3, 0xD192E819, 0xD6990624, 0xF40E3585, 0x106AA070,
+    0x19A4C116, 0x1E376C08, 0x2748774C, 0x34B0BCB5, 0x391C0CB3, 0x4ED8AA4A, 0x5B9CCA4F, 0x682E6FF3,
+    0x748F82EE, 0x78A5636F, 0x84C87814, 0x8CC70208, 0x90BEFFFA, 0xA4506CEB, 0xBEF9A3F7, 0xC67178F2,
+];
+
+#[cfg(test)]
+mod tests {
+    use crate::input::AsInput;
+    use crate::testing::{for_each_chunking, decode_chunked};
+    use super::*;
+
+    fn assert_digest(digest: &[u8], hex: &str) {
+        assert_eq!(digest.len() * 2, hex.len());
+        for (i, &byte) in digest.iter().enumerate() {
+            assert_eq!(byte, u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap(), "{}", hex);
+        }
+    }
+
+    fn digest<H: Digest>(mut hash: H, message: &[u8]) -> H::Output {
+        hash.update(message);
+        hash.finish()
+    }
+
+    fn digest_million_a<H: Digest>(mut hash: H) -> H::Output {
+        for _ in 0..1000 {
+            hash.update(&[b'a'; 1000]);
+        }
+        hash.finish()
+    }
+
+    #[test]
+    fn test_sha1() {
+        assert_digest(&digest(Sha1::new(), b""), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+        assert_digest(&digest(Sha1::new(), b"abc"), "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_digest(&digest(Sha1::new(), b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+                      "84983e441c3bd26ebaae4aa1f95129e5e54670f1");
+        assert_digest(&digest_million_a(Sha1::new()), "34aa973cd4c4daa4f61eeb2bdbad27316534016f");
+    }
+
+    #[test]
+    fn test_sha256() {
+        assert_digest(&digest(Sha256::new(), b""),
+                      "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_digest(&digest(Sha256::new(), b"abc"),
+                      "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_digest(&digest(Sha256::new(), b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+                      "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1");
+        assert_digest(&digest_million_a(Sha256::new()),
+                      "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0");
+    }
+
+    #[test]
+    fn test_hmac_sha1() {
+        assert_digest(&digest(Hmac::<Sha1>::new(&[0x0B; 20]), b"Hi There"),
+                      "b617318655057264e28bc0b6fb378c8ef146be00");
+        assert_digest(&digest(Hmac::<Sha1>::new(b"Jefe"), b"what do ya want for nothing?"),
+                      "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79");
+        assert_digest(&digest(Hmac::<Sha1>::new(&[0xAA; 80]), b"Test Using Larger Than Block-Size Key - Hash Key First"),
+                      "aa4ae5e15272d00e95705637ce8a3b55ed402112");
+    }
+
+    #[test]
+    fn test_hmac_sha256() {
+        assert_digest(&digest(Hmac::<Sha256>::new(&[0x0B; 20]), b"Hi There"),
+                      "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7");
+        assert_digest(&digest(Hmac::<Sha256>::new(b"Jefe"), b"what do ya want for nothing?"),
+                      "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+        assert_digest(&digest(Hmac::<Sha256>::new(&[0xAA; 131]), b"Test Using Larger Than Block-Size Key - Hash Key First"),
+                      "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54");
+    }
+
+    #[test]
+    fn test_digest_decode_chunked() {
+        let message = &b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"[..];
+        for_each_chunking(message, |chunking| {
+            let decoded = decode_chunked(DigestDecoder::new(Sha256::new()), message, chunking).unwrap();
+            assert_digest(&decoded, "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1");
+        });
+        let decoder = DigestDecoder::new(Hmac::<Sha1>::new(b"Jefe"));
+        assert_digest(&decoder.consume(&mut b"what do ya want for nothing?".as_input()),
+                      "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79");
+    }
+}
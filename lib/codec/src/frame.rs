@@ -0,0 +1,463 @@
+use core::fmt;
+use crate::step::{Step, In, Out, Over};
+use crate::then::{Then, Cont, Done, Fail};
+use crate::input::Input;
+use crate::output::Output;
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+use crate::binary::{LengthPrefix, BytesEncoder};
+
+/// Largest frame accepted by a framing decoder, unless overridden with
+/// `max_frame`.
+pub const DEFAULT_MAX_FRAME: u64 = 1 << 20;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FrameError<E> {
+    /// Input over in the middle of a frame, or inner decoder unfinished at
+    /// the end of its frame.
+    Unexpected,
+    /// Frame longer than the decoder's maximum frame size.
+    TooLong,
+    Inner(E),
+}
+
+/// Input over the tokens of the current frame of an enclosing input, which
+/// reports `Over` at the end of the frame.
+///
+/// A `FrameInput` is only handed out by reference for the duration of a
+/// framing decoder's `decode` call, during which it has exclusive use of
+/// the enclosing input.
+pub struct FrameInput<I: Input> {
+    input: *mut I,
+    remaining: u64,
+    delimited: bool,
+    too_long: bool,
+    over: bool,
+}
+
+/// Decodes a frame preceded by its length in bytes with an inner decoder,
+/// which sees the frame as an input that's over at the end of the frame.
+/// Bytes of the frame left over by the inner decoder are skipped.
+pub struct LengthDelimitedDecoder<I: Input<Token=u8>, D: Decoder<Input=FrameInput<I>>> {
+    decoder: Option<D>,
+    output: Option<D::Output>,
+    prefix: LengthPrefix,
+    max_frame: u64,
+    len: u64,
+    count: usize,
+    state: u32,
+}
+
+/// Decodes a frame terminated by a line feed with an inner decoder, which
+/// sees the frame, without its line feed, as an input that's over at the
+/// end of the line. Tokens of the line left over by the inner decoder are
+/// skipped.
+pub struct LineDelimitedDecoder<I: Input, D: Decoder<Input=FrameInput<I>>> {
+    decoder: Option<D>,
+    output: Option<D::Output>,
+    max_frame: u64,
+    remaining: u64,
+    state: u32,
+}
+
+/// Encodes a frame preceded by its length in bytes.
+pub type LengthDelimitedEncoder<'b, O> = BytesEncoder<'b, O>;
+
+/// Encodes a frame with an inner encoder, and terminates it with a line
+/// feed. The inner encoder must not write line feeds of its own.
+pub struct LineDelimitedEncoder<E: Encoder> {
+    encoder: Option<E>,
+    input: Option<E::Input>,
+}
+
+impl<I> FrameInput<I> where I: Input, I::Token: From<u8> + PartialEq {
+    fn new(input: &mut I, remaining: u64, delimited: bool) -> Self {
+        Self {
+            input: input,
+            remaining: remaining,
+            delimited: delimited,
+            too_long: false,
+            over: false,
+        }
+    }
+
+    fn outer(&mut self) -> &mut I {
+        unsafe { &mut *self.input }
+    }
+}
+
+impl<I> Input for FrameInput<I> where I: Input, I::Token: From<u8> + PartialEq {
+    type Token = I::Token;
+
+    fn head(&mut self) -> Step<I::Token> {
+        if self.over || !self.delimited && self.remaining == 0 {
+            return Over;
+        }
+        let delimited = self.delimited;
+        let remaining = self.remaining;
+        match self.outer().head() {
+            In(ref token) if delimited && *token == I::Token::from(b'\n') => Over,
+            In(_) if remaining == 0 => {
+                self.too_long = true;
+                Over
+            },
+            step => step,
+        }
+    }
+
+    fn step(&mut self) {
+        if self.is_in() {
+            self.outer().step();
+            self.remaining -= 1;
+        }
+    }
+
+    fn over(&mut self) {
+        self.over = true;
+    }
+}
+
+impl<I, D> LengthDelimitedDecoder<I, D> where I: Input<Token=u8>, D: Decoder<Input=FrameInput<I>> {
+    pub fn new(decoder: D, prefix: LengthPrefix) -> Self {
+        Self {
+            decoder: Some(decoder),
+            output: None,
+            prefix: prefix,
+            max_frame: DEFAULT_MAX_FRAME,
+            len: 0,
+            count: 0,
+            state: 1,
+        }
+    }
+
+    /// Rejects frames longer than `max_frame` bytes before reading them.
+    pub fn max_frame(mut self, max_frame: u64) -> Self {
+        self.max_frame = max_frame;
+        self
+    }
+
+    pub fn consume(mut self, input: &mut I) -> Result<D::Output, FrameError<D::Error>> {
+        loop {
+            match self.decode(input) {
+                Done(output) => return Ok(output),
+                Fail(error) => return Err(error),
+                Cont(next) => {
+                    if input.is_out() {
+                        input.over();
+                        self = next;
+                    } else {
+                        return Err(FrameError::Unexpected);
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl<I, D> Decoder for LengthDelimitedDecoder<I, D> where I: Input<Token=u8>, D: Decoder<Input=FrameInput<I>> {
+    type Input = I;
+    type Output = D::Output;
+    type Error = FrameError<D::Error>;
+
+    fn decode(mut self, input: &mut I) -> Then<Self, D::Output, FrameError<D::Error>> {
+        loop {
+            match self.state {
+                1 => {
+                    let byte = match input.head() {
+                        In(byte) => {
+                            input.step();
+                            byte
+                        },
+                        Out => return Cont(self),
+                        Over => return Fail(FrameError::Unexpected),
+                    };
+                    match self.prefix.read(&mut self.len, &mut self.count, byte) {
+                        Ok(true) => {},
+                        Ok(false) => continue,
+                        Err(_) => return Fail(FrameError::TooLong),
+                    };
+                    if self.len > self.max_frame {
+                        return Fail(FrameError::TooLong);
+                    }
+                    self.state = 2;
+                },
+                2 => {
+                    let mut frame = FrameInput::new(input, self.len, false);
+                    let result = self.decoder.take().unwrap().decode(&mut frame);
+                    let ended = frame.is_over();
+                    self.len = frame.remaining;
+                    match result {
+                        Done(output) => {
+                            self.output = Some(output);
+                            self.state = 3;
+                        },
+                        Fail(error) => {
+                            if self.len != 0 && input.is_over() {
+                                return Fail(FrameError::Unexpected);
+                            }
+                            return Fail(FrameError::Inner(error));
+                        },
+                        Cont(next) => {
+                            if ended {
+                                return Fail(FrameError::Unexpected);
+                            }
+                            self.decoder = Some(next);
+                            return Cont(self);
+                        },
+                    };
+                },
+                3 => {
+                    while self.len > 0 {
+                        match input.head() {
+                            In(_) => {
+                                input.step();
+                                self.len -= 1;
+                            },
+                            Out => return Cont(self),
+                            Over => return Fail(FrameError::Unexpected),
+                        };
+                    }
+                    return Done(self.output.take().unwrap());
+                },
+                _ => unreachable!(),
+            };
+        }
+    }
+}
+
+impl<I, D> LineDelimitedDecoder<I, D> where I: Input, I::Token: From<u8> + PartialEq, D: Decoder<Input=FrameInput<I>> {
+    pub fn new(decoder: D) -> Self {
+        Self {
+            decoder: Some(decoder),
+            output: None,
+            max_frame: DEFAULT_MAX_FRAME,
+            remaining: DEFAULT_MAX_FRAME,
+            state: 1,
+        }
+    }
+
+    /// Rejects lines longer than `max_frame` tokens, not counting their line
+    /// feed, as soon as they exceed it.
+    pub fn max_frame(mut self, max_frame: u64) -> Self {
+        self.max_frame = max_frame;
+        self.remaining = max_frame;
+        self
+    }
+
+    pub fn consume(mut self, input: &mut I) -> Result<D::Output, FrameError<D::Error>> {
+        loop {
+            match self.decode(input) {
+                Done(output) => return Ok(output),
+                Fail(error) => return Err(error),
+                Cont(next) => {
+                    if input.is_out() {
+                        input.over();
+                        self = next;
+                    } else {
+                        return Err(FrameError::Unexpected);
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl<I, D> Decoder for LineDelimitedDecoder<I, D>
+    where I: Input,
+          I::Token: From<u8> + PartialEq,
+          D: Decoder<Input=FrameInput<I>> {
+
+    type Input = I;
+    type Output = D::Output;
+    type Error = FrameError<D::Error>;
+
+    fn decode(mut self, input: &mut I) -> Then<Self, D::Output, FrameError<D::Error>> {
+        if self.state == 1 {
+            let mut frame = FrameInput::new(input, self.remaining, true);
+            let result = self.decoder.take().unwrap().decode(&mut frame);
+            let ended = frame.is_over();
+            let too_long = frame.too_long;
+            self.remaining = frame.remaining;
+            if too_long {
+                return Fail(FrameError::TooLong);
+            }
+            match result {
+                Done(output) => {
+                    self.output = Some(output);
+                    self.state = 2;
+                },
+                Fail(error) => {
+                    if input.is_over() {
+                        return Fail(FrameError::Unexpected);
+                    }
+                    return Fail(FrameError::Inner(error));
+                },
+                Cont(next) => {
+                    if ended {
+                        return Fail(FrameError::Unexpected);
+                    }
+                    self.decoder = Some(next);
+                    return Cont(self);
+                },
+            };
+        }
+        loop {
+            match input.head() {
+                In(ref token) if *token == I::Token::from(b'\n') => {
+                    input.step();
+                    return Done(self.output.take().unwrap());
+                },
+                In(_) => {
+                    if self.remaining == 0 {
+                        return Fail(FrameError::TooLong);
+                    }
+                    input.step();
+                    self.remaining -= 1;
+                },
+                Out => return Cont(self),
+                Over => return Fail(FrameError::Unexpected),
+            };
+        }
+    }
+}
+
+impl<E> LineDelimitedEncoder<E> where E: Encoder {
+    pub fn new(encoder: E) -> Self {
+        Self {
+            encoder: Some(encoder),
+            input: None,
+        }
+    }
+
+    /// Returns the inner encoder, until it has finished encoding the line.
+    pub fn encoder_mut(&mut self) -> Option<&mut E> {
+        self.encoder.as_mut()
+    }
+}
+
+impl<E, O> LineDelimitedEncoder<E> where E: Encoder<Output=O>, O: Output, O::Token: From<u8> {
+    pub fn produce(mut self, mut output: O) -> Result<O::Out, O::Err> where E::Error: fmt::Debug {
+        loop {
+            match self.encode(&mut output) {
+                Done(_) => return output.take_out(),
+                Fail(error) => panic!("{:?}", error),
+                Cont(next) => self = next,
+            }
+        }
+    }
+}
+
+impl<E, O> Encoder for LineDelimitedEncoder<E> where E: Encoder<Output=O>, O: Output, O::Token: From<u8> {
+    type Input = E::Input;
+    type Output = O;
+    type Error = E::Error;
+
+    fn encode(mut self, output: &mut O) -> Then<Self, E::Input, E::Error> {
+        if let Some(encoder) = self.encoder.take() {
+            match encoder.encode(output) {
+                Done(input) => self.input = Some(input),
+                Fail(error) => return Fail(error),
+                Cont(next) => {
+                    self.encoder = Some(next);
+                    return Cont(self);
+                },
+            };
+        }
+        if output.is_full() {
+            return Cont(self);
+        }
+        output.push(O::Token::from(b'\n'));
+        Done(self.input.take().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::AsInput;
+    use crate::output::SliceOutput;
+    use crate::decoder::Decoder;
+    use crate::then::Done;
+    use crate::binary::{BigEndian, BinaryError, LengthPrefix, VarintDecoder};
+    use crate::base64::{Base64Decoder, Base64Encoder, Base64Error};
+    use crate::base64::Base64Alphabet::Base64;
+    use crate::testing::{for_each_chunking, for_each_output_size, decode_chunked, encode_chunked};
+    use super::*;
+
+    #[test]
+    fn test_length_delimited_decode_chunked() {
+        fn assert_decodes_chunked(encoded: &[u8], value: u64) {
+            for_each_chunking(encoded, |chunking| {
+                let decoder = LengthDelimitedDecoder::new(VarintDecoder::new(), LengthPrefix::U32(BigEndian));
+                assert_eq!(decode_chunked(decoder, encoded, chunking), Ok(value), "{:?}", chunking);
+            });
+        }
+        assert_decodes_chunked(&[0, 0, 0, 1, 0x7F], 127);
+        assert_decodes_chunked(&[0, 0, 0, 2, 0xAC, 0x02], 300);
+        assert_decodes_chunked(&[0, 0, 0, 4, 0xAC, 0x02, 0xFF, 0xFF], 300);
+    }
+
+    #[test]
+    fn test_length_delimited_decode_frames() {
+        let mut input = [1, 0x01, 3, 0xAC, 0x02, 0x00, 1, 0x02].as_input();
+        for &value in &[1, 300, 2] {
+            let decoder = LengthDelimitedDecoder::new(VarintDecoder::new(), LengthPrefix::Varint);
+            match decoder.decode(&mut input) {
+                Done(decoded) => assert_eq!(decoded, value),
+                _ => panic!(),
+            }
+        }
+        assert!(input.is_out());
+    }
+
+    #[test]
+    fn test_length_delimited_decode_errors() {
+        let decoder = LengthDelimitedDecoder::new(VarintDecoder::new(), LengthPrefix::U8).max_frame(4);
+        assert_eq!(decoder.consume(&mut [5, 0, 0, 0, 0, 0].as_input()), Err(FrameError::TooLong));
+        let decoder = LengthDelimitedDecoder::new(VarintDecoder::new(), LengthPrefix::U8);
+        assert_eq!(decoder.consume(&mut [3, 0xAC].as_input()), Err(FrameError::Unexpected));
+        let decoder = LengthDelimitedDecoder::new(VarintDecoder::new(), LengthPrefix::U8);
+        assert_eq!(decoder.consume(&mut [1, 0xAC, 0x02].as_input()), Err(FrameError::Inner(BinaryError::Unexpected)));
+    }
+
+    #[test]
+    fn test_line_delimited_decode_chunked() {
+        fn assert_decodes_chunked(encoded: &str, decoded: &[u8]) {
+            for_each_chunking(encoded, |chunking| {
+                let mut buffer = [0u8; 16];
+                let decoder = LineDelimitedDecoder::new(Base64Decoder::new(SliceOutput::new(&mut buffer)));
+                let output = decode_chunked(decoder, encoded, chunking).unwrap();
+                assert_eq!(output, decoded, "{:?}", chunking);
+            });
+        }
+        assert_decodes_chunked("\n", b"");
+        assert_decodes_chunked("Zm9vYmFy\n", b"foobar");
+        assert_decodes_chunked("Zm9v\nYmFy\n", b"foo");
+    }
+
+    #[test]
+    fn test_line_delimited_decode_errors() {
+        let mut buffer = [0u8; 16];
+        let decoder = LineDelimitedDecoder::new(Base64Decoder::new(SliceOutput::new(&mut buffer))).max_frame(4);
+        assert_eq!(decoder.consume(&mut "Zm9vYmFy\n".as_input()), Err(FrameError::TooLong));
+        let mut buffer = [0u8; 16];
+        let decoder = LineDelimitedDecoder::new(Base64Decoder::new(SliceOutput::new(&mut buffer)));
+        assert_eq!(decoder.consume(&mut "Zm9vYmFy".as_input()), Err(FrameError::Unexpected));
+        let mut buffer = [0u8; 16];
+        let decoder = LineDelimitedDecoder::new(Base64Decoder::new(SliceOutput::new(&mut buffer)));
+        assert_eq!(decoder.consume(&mut "Zm9vY\n".as_input()), Err(FrameError::Inner(Base64Error::Unexpected)));
+    }
+
+    #[test]
+    fn test_line_delimited_encode_chunked() {
+        for_each_output_size(|size| {
+            let mut buffer = ['\0'; 16];
+            let encoder = LineDelimitedEncoder::new(Base64Encoder::new(b"foobar".as_input(), Base64));
+            let output = encode_chunked(encoder, size, &mut buffer, |line| {
+                if let Some(encoder) = line.encoder_mut() {
+                    encoder.input.over();
+                }
+            }).unwrap();
+            assert!(output.iter().cloned().eq("Zm9vYmFy\n".chars()), "size {}", size);
+        });
+    }
+}
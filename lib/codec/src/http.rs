@@ -0,0 +1,1413 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::str;
+use swim_mem::alloc::{Hold, HoldError};
+use swim_mem::lease::RawBuf;
+use crate::step::{In, Out, Over};
+use crate::then::{Then, Cont, Done, Fail};
+use crate::input::Input;
+use crate::output::Output;
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+
+const CHUNK_SIZE: usize = 256;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HttpVersion {
+    Http10,
+    Http11,
+}
+pub use self::HttpVersion::{Http10, Http11};
+
+impl HttpVersion {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Http10 => "HTTP/1.0",
+            Http11 => "HTTP/1.1",
+        }
+    }
+
+    /// Parses an `HTTP/1.x` version; minor versions above 1 are treated as
+    /// HTTP/1.1, the highest version we speak.
+    fn parse(bytes: &[u8]) -> Result<HttpVersion, HttpError> {
+        if bytes.len() != 8 || &bytes[..5] != b"HTTP/" || bytes[6] != b'.' ||
+           !is_digit(bytes[5]) || !is_digit(bytes[7]) {
+            Err(HttpError::Unexpected)
+        } else if bytes[5] != b'1' {
+            Err(HttpError::Version)
+        } else if bytes[7] == b'0' {
+            Ok(Http10)
+        } else {
+            Ok(Http11)
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HttpError {
+    Unexpected,
+    Version,
+    Status,
+    LineTooLong,
+    TooManyHeaders,
+    HeaderTooLarge,
+    BodyTooLarge,
+    ContentLength,
+    TransferEncoding,
+    Chunk,
+    Truncated,
+    Hold(HoldError),
+}
+
+impl From<HoldError> for HttpError {
+    fn from(error: HoldError) -> HttpError {
+        HttpError::Hold(error)
+    }
+}
+
+/// How the body of a message is delimited, as determined by its head.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HttpBody {
+    Empty,
+    Length(u64),
+    Chunked,
+    Close,
+}
+
+#[derive(Clone, Copy)]
+struct HttpField {
+    name: usize,
+    value: usize,
+    end: usize,
+}
+
+/// Header fields of a message, with names and values packed into a single
+/// buffer allocated in a `Hold`.
+pub struct HttpHeaders<'a> {
+    bytes: RawBuf<'a, u8>,
+    fields: RawBuf<'a, HttpField>,
+}
+
+pub struct HttpHeadersIter<'b, 'a: 'b> {
+    headers: &'b HttpHeaders<'a>,
+    index: usize,
+}
+
+pub struct HttpRequest<'a> {
+    line: RawBuf<'a, u8>,
+    method_end: usize,
+    version: HttpVersion,
+    headers: HttpHeaders<'a>,
+}
+
+pub struct HttpResponse<'a> {
+    line: RawBuf<'a, u8>,
+    version: HttpVersion,
+    status: u16,
+    headers: HttpHeaders<'a>,
+}
+
+/// A message head that can be written by an `HttpHeadEncoder`, one byte
+/// segment at a time.
+pub trait HttpHead {
+    fn head_segment(&self, index: usize) -> Option<&[u8]>;
+}
+
+/// Parser state shared by request and response head decoders.
+struct HttpHeadParser<'a> {
+    response: bool,
+    line: RawBuf<'a, u8>,
+    marks: [usize; 2],
+    headers: HttpHeaders<'a>,
+    name: usize,
+    value: usize,
+    max_line: usize,
+    max_headers: usize,
+    max_header_size: usize,
+    state: u32,
+}
+
+pub struct HttpRequestDecoder<'a, I: Input<Token=u8>> {
+    parser: HttpHeadParser<'a>,
+    input: PhantomData<I>,
+}
+
+pub struct HttpResponseDecoder<'a, I: Input<Token=u8>> {
+    parser: HttpHeadParser<'a>,
+    input: PhantomData<I>,
+}
+
+/// Decodes a message body into `output`; fails with `BodyTooLarge` if the
+/// body doesn't fit.
+pub struct HttpBodyDecoder<I: Input<Token=u8>, O: Output<Token=u8>> {
+    pub output: O,
+    body: HttpBody,
+    size: u64,
+    count: usize,
+    state: u32,
+    input: PhantomData<I>,
+}
+
+pub struct HttpHeadEncoder<'b, H: HttpHead, O: Output<Token=u8>> {
+    head: &'b H,
+    index: usize,
+    offset: usize,
+    output: PhantomData<O>,
+}
+
+pub struct HttpBodyEncoder<I: Input<Token=u8>, O: Output<Token=u8>> {
+    pub input: I,
+    body: HttpBody,
+    size: u64,
+    buffer: [u8; CHUNK_SIZE],
+    len: usize,
+    scratch: [u8; 18],
+    scratch_len: usize,
+    count: usize,
+    state: u32,
+    output: PhantomData<O>,
+}
+
+impl<'a> HttpHeaders<'a> {
+    pub fn try_hold_empty(hold: &dyn Hold<'a>) -> Result<HttpHeaders<'a>, HoldError> {
+        Ok(HttpHeaders {
+            bytes: RawBuf::try_hold_empty(hold)?,
+            fields: RawBuf::try_hold_cap(hold, 8)?,
+        })
+    }
+
+    pub fn hold_empty(hold: &dyn Hold<'a>) -> HttpHeaders<'a> {
+        HttpHeaders::try_hold_empty(hold).unwrap()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn iter<'b>(&'b self) -> HttpHeadersIter<'b, 'a> {
+        HttpHeadersIter { headers: self, index: 0 }
+    }
+
+    /// Returns the value of the first field named `name`, ignoring case.
+    pub fn get(&self, name: &str) -> Option<&[u8]> {
+        self.iter().find(|&(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, value)| value)
+    }
+
+    pub fn get_str(&self, name: &str) -> Option<&str> {
+        self.get(name).and_then(|value| str::from_utf8(value).ok())
+    }
+
+    /// Returns `true` if any comma-separated element of any field named
+    /// `name` equals `token`, ignoring case.
+    pub fn has_token(&self, name: &str, token: &str) -> bool {
+        self.iter().filter(|&(key, _)| key.eq_ignore_ascii_case(name)).any(|(_, value)| {
+            value.split(|&b| b == b',').any(|element| trim_ows(element).eq_ignore_ascii_case(token.as_bytes()))
+        })
+    }
+
+    pub fn try_push(&mut self, name: &str, value: &[u8]) -> Result<(), HttpError> {
+        if name.is_empty() || !name.bytes().all(is_tchar) || !value.iter().all(|&b| is_field_char(b)) {
+            return Err(HttpError::Unexpected);
+        }
+        let start = self.bytes.len();
+        self.bytes.try_extend_from_slice(name.as_bytes())?;
+        self.bytes.try_extend_from_slice(value)?;
+        self.fields.try_push(HttpField { name: start, value: start + name.len(), end: self.bytes.len() })?;
+        Ok(())
+    }
+
+    pub fn push(&mut self, name: &str, value: &[u8]) {
+        self.try_push(name, value).unwrap();
+    }
+
+    /// Returns the message's `Content-Length`, if any; repeated fields must
+    /// agree.
+    pub fn content_length(&self) -> Result<Option<u64>, HttpError> {
+        let mut length = None;
+        for (key, value) in self.iter() {
+            if key.eq_ignore_ascii_case("content-length") {
+                let n = parse_decimal(value).ok_or(HttpError::ContentLength)?;
+                if length.is_some() && length != Some(n) {
+                    return Err(HttpError::ContentLength);
+                }
+                length = Some(n);
+            }
+        }
+        Ok(length)
+    }
+
+    fn has_transfer_encoding(&self) -> bool {
+        self.get("transfer-encoding").is_some()
+    }
+
+    /// Returns `true` if `chunked` is the final transfer coding.
+    pub fn is_chunked(&self) -> bool {
+        let mut chunked = false;
+        for (key, value) in self.iter() {
+            if key.eq_ignore_ascii_case("transfer-encoding") {
+                if let Some(coding) = value.split(|&b| b == b',').map(trim_ows).filter(|c| !c.is_empty()).last() {
+                    chunked = coding.eq_ignore_ascii_case(b"chunked");
+                }
+            }
+        }
+        chunked
+    }
+
+    fn keep_alive(&self, version: HttpVersion) -> bool {
+        if self.has_token("connection", "close") {
+            false
+        } else {
+            version == Http11 || self.has_token("connection", "keep-alive")
+        }
+    }
+
+    fn field(&self, index: usize) -> (&str, &[u8]) {
+        let field = self.fields[index];
+        let name = unsafe { str::from_utf8_unchecked(&self.bytes[field.name..field.value]) };
+        (name, &self.bytes[field.value..field.end])
+    }
+
+    fn segment(&self, index: usize) -> Option<&[u8]> {
+        if index >= 4 * self.len() {
+            return None;
+        }
+        let (name, value) = self.field(index / 4);
+        Some(match index % 4 {
+            0 => name.as_bytes(),
+            1 => b": ",
+            2 => value,
+            _ => b"\r\n",
+        })
+    }
+}
+
+impl<'b, 'a: 'b> Iterator for HttpHeadersIter<'b, 'a> {
+    type Item = (&'b str, &'b [u8]);
+
+    fn next(&mut self) -> Option<(&'b str, &'b [u8])> {
+        if self.index < self.headers.len() {
+            let field = self.headers.field(self.index);
+            self.index += 1;
+            Some(field)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a> HttpRequest<'a> {
+    pub fn try_hold(hold: &dyn Hold<'a>, method: &str, target: &str, version: HttpVersion)
+        -> Result<HttpRequest<'a>, HttpError> {
+        if method.is_empty() || !method.bytes().all(is_tchar) ||
+           target.is_empty() || !target.bytes().all(is_target_char) {
+            return Err(HttpError::Unexpected);
+        }
+        let mut line = RawBuf::try_hold_cap(hold, method.len() + target.len())?;
+        line.try_extend_from_slice(method.as_bytes())?;
+        line.try_extend_from_slice(target.as_bytes())?;
+        Ok(HttpRequest {
+            line: line,
+            method_end: method.len(),
+            version: version,
+            headers: HttpHeaders::try_hold_empty(hold)?,
+        })
+    }
+
+    pub fn method(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(&self.line[..self.method_end]) }
+    }
+
+    pub fn target(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(&self.line[self.method_end..]) }
+    }
+
+    pub fn version(&self) -> HttpVersion {
+        self.version
+    }
+
+    pub fn headers(&self) -> &HttpHeaders<'a> {
+        &self.headers
+    }
+
+    pub fn headers_mut(&mut self) -> &mut HttpHeaders<'a> {
+        &mut self.headers
+    }
+
+    /// Determines how the request body is delimited. Requests that carry
+    /// both `Transfer-Encoding` and `Content-Length` are rejected, since
+    /// intermediaries may disagree on which one frames the body.
+    pub fn body(&self) -> Result<HttpBody, HttpError> {
+        if self.headers.has_transfer_encoding() {
+            if self.headers.content_length()?.is_some() {
+                Err(HttpError::ContentLength)
+            } else if self.headers.is_chunked() {
+                Ok(HttpBody::Chunked)
+            } else {
+                Err(HttpError::TransferEncoding)
+            }
+        } else {
+            match self.headers.content_length()? {
+                Some(0) | None => Ok(HttpBody::Empty),
+                Some(n) => Ok(HttpBody::Length(n)),
+            }
+        }
+    }
+
+    /// Returns `true` if the connection may be reused after this request.
+    pub fn keep_alive(&self) -> bool {
+        self.headers.keep_alive(self.version)
+    }
+}
+
+impl<'a> HttpHead for HttpRequest<'a> {
+    fn head_segment(&self, index: usize) -> Option<&[u8]> {
+        match index {
+            0 => Some(self.method().as_bytes()),
+            1 => Some(b" "),
+            2 => Some(self.target().as_bytes()),
+            3 => Some(b" "),
+            4 => Some(self.version.as_str().as_bytes()),
+            5 => Some(b"\r\n"),
+            _ => match self.headers.segment(index - 6) {
+                None if index - 6 == 4 * self.headers.len() => Some(b"\r\n"),
+                segment => segment,
+            },
+        }
+    }
+}
+
+impl<'a> HttpResponse<'a> {
+    pub fn try_hold(hold: &dyn Hold<'a>, version: HttpVersion, status: u16, reason: &str)
+        -> Result<HttpResponse<'a>, HttpError> {
+        if status < 100 || status > 999 || !reason.bytes().all(is_field_char) {
+            return Err(HttpError::Status);
+        }
+        let mut line = RawBuf::try_hold_cap(hold, 3 + reason.len())?;
+        line.try_extend_from_slice(&[b'0' + (status / 100) as u8,
+                                     b'0' + (status / 10 % 10) as u8,
+                                     b'0' + (status % 10) as u8])?;
+        line.try_extend_from_slice(reason.as_bytes())?;
+        Ok(HttpResponse {
+            line: line,
+            version: version,
+            status: status,
+            headers: HttpHeaders::try_hold_empty(hold)?,
+        })
+    }
+
+    pub fn version(&self) -> HttpVersion {
+        self.version
+    }
+
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    pub fn reason(&self) -> &[u8] {
+        &self.line[3..]
+    }
+
+    pub fn headers(&self) -> &HttpHeaders<'a> {
+        &self.headers
+    }
+
+    pub fn headers_mut(&mut self) -> &mut HttpHeaders<'a> {
+        &mut self.headers
+    }
+
+    /// Determines how the body of a response to a request with the given
+    /// `method` is delimited.
+    pub fn body(&self, method: &str) -> Result<HttpBody, HttpError> {
+        if method == "HEAD" || self.status < 200 || self.status == 204 || self.status == 304 {
+            Ok(HttpBody::Empty)
+        } else if method == "CONNECT" && self.status < 300 {
+            Ok(HttpBody::Close)
+        } else if self.headers.has_transfer_encoding() {
+            if self.headers.is_chunked() {
+                Ok(HttpBody::Chunked)
+            } else {
+                Ok(HttpBody::Close)
+            }
+        } else {
+            match self.headers.content_length()? {
+                Some(0) => Ok(HttpBody::Empty),
+                Some(n) => Ok(HttpBody::Length(n)),
+                None => Ok(HttpBody::Close),
+            }
+        }
+    }
+
+    /// Returns `true` if the connection may be reused after this response
+    /// to a request with the given `method`.
+    pub fn keep_alive(&self, method: &str) -> bool {
+        self.headers.keep_alive(self.version) && self.body(method).ok() != Some(HttpBody::Close)
+    }
+}
+
+impl<'a> HttpHead for HttpResponse<'a> {
+    fn head_segment(&self, index: usize) -> Option<&[u8]> {
+        match index {
+            0 => Some(self.version.as_str().as_bytes()),
+            1 => Some(b" "),
+            2 => Some(&self.line[..3]),
+            3 => Some(b" "),
+            4 => Some(self.reason()),
+            5 => Some(b"\r\n"),
+            _ => match self.headers.segment(index - 6) {
+                None if index - 6 == 4 * self.headers.len() => Some(b"\r\n"),
+                segment => segment,
+            },
+        }
+    }
+}
+
+impl<'a> HttpHeadParser<'a> {
+    fn try_hold(hold: &dyn Hold<'a>, response: bool) -> Result<HttpHeadParser<'a>, HoldError> {
+        Ok(HttpHeadParser {
+            response: response,
+            line: RawBuf::try_hold_empty(hold)?,
+            marks: [0; 2],
+            headers: HttpHeaders::try_hold_empty(hold)?,
+            name: 0,
+            value: 0,
+            max_line: 8192,
+            max_headers: 100,
+            max_header_size: 8192,
+            state: 1,
+        })
+    }
+
+    fn push_line(&mut self, b: u8) -> Result<(), HttpError> {
+        if self.line.len() >= self.max_line {
+            return Err(HttpError::LineTooLong);
+        }
+        self.line.try_push(b)?;
+        Ok(())
+    }
+
+    fn push_field(&mut self, b: u8) -> Result<(), HttpError> {
+        if self.headers.bytes.len() - self.name >= self.max_header_size {
+            return Err(HttpError::HeaderTooLarge);
+        }
+        self.headers.bytes.try_push(b)?;
+        Ok(())
+    }
+
+    fn end_field(&mut self) -> Result<(), HttpError> {
+        while let Some(&b) = self.headers.bytes.last() {
+            if self.headers.bytes.len() > self.value && (b == b' ' || b == b'\t') {
+                self.headers.bytes.pop();
+            } else {
+                break;
+            }
+        }
+        let field = HttpField { name: self.name, value: self.value, end: self.headers.bytes.len() };
+        self.headers.fields.try_push(field)?;
+        Ok(())
+    }
+
+    /// Advances through the head; returns `Done` once the empty line that
+    /// terminates the header section has been consumed.
+    fn parse<I: Input<Token=u8>>(&mut self, input: &mut I) -> Then<(), (), HttpError> {
+        loop {
+            let b = match input.head() {
+                In(b) => b,
+                Over => return Fail(HttpError::Truncated),
+                Out => return Cont(()),
+            };
+            let result = match self.state {
+                1 => { // leading empty lines
+                    if b == b'\r' || b == b'\n' {
+                        Ok(())
+                    } else {
+                        self.state = 2;
+                        continue;
+                    }
+                },
+                2 => { // method or version
+                    if b == b' ' && !self.line.is_empty() {
+                        self.marks[0] = self.line.len();
+                        self.state = 3;
+                        Ok(())
+                    } else if is_tchar(b) || self.response && b == b'/' {
+                        self.push_line(b)
+                    } else {
+                        Err(HttpError::Unexpected)
+                    }
+                },
+                3 => { // target or status
+                    if b == b' ' && self.line.len() > self.marks[0] {
+                        self.marks[1] = self.line.len();
+                        self.state = 4;
+                        Ok(())
+                    } else if self.response && b == b'\r' {
+                        self.marks[1] = self.line.len();
+                        self.state = 5;
+                        Ok(())
+                    } else if self.response && b == b'\n' {
+                        self.marks[1] = self.line.len();
+                        self.state = 6;
+                        self.start_line()
+                    } else if self.response && is_digit(b) || !self.response && is_target_char(b) {
+                        self.push_line(b)
+                    } else {
+                        Err(HttpError::Unexpected)
+                    }
+                },
+                4 => { // version or reason
+                    if b == b'\r' {
+                        self.state = 5;
+                        Ok(())
+                    } else if b == b'\n' {
+                        self.state = 6;
+                        self.start_line()
+                    } else if self.response && is_field_char(b) || !self.response && b > b' ' && b < 0x7F {
+                        self.push_line(b)
+                    } else {
+                        Err(HttpError::Unexpected)
+                    }
+                },
+                5 => { // start line LF
+                    if b == b'\n' {
+                        self.state = 6;
+                        self.start_line()
+                    } else {
+                        Err(HttpError::Unexpected)
+                    }
+                },
+                6 => { // field name start
+                    if b == b'\r' {
+                        self.state = 10;
+                        Ok(())
+                    } else if b == b'\n' {
+                        input.step();
+                        return Done(());
+                    } else if !is_tchar(b) {
+                        Err(HttpError::Unexpected)
+                    } else if self.headers.len() >= self.max_headers {
+                        Err(HttpError::TooManyHeaders)
+                    } else {
+                        self.name = self.headers.bytes.len();
+                        self.state = 7;
+                        self.push_field(b)
+                    }
+                },
+                7 => { // field name
+                    if b == b':' {
+                        self.value = self.headers.bytes.len();
+                        self.state = 8;
+                        Ok(())
+                    } else if is_tchar(b) {
+                        self.push_field(b)
+                    } else {
+                        Err(HttpError::Unexpected)
+                    }
+                },
+                8 => { // leading whitespace
+                    if b == b' ' || b == b'\t' {
+                        Ok(())
+                    } else {
+                        self.state = 9;
+                        continue;
+                    }
+                },
+                9 => { // field value
+                    if b == b'\r' {
+                        self.state = 11;
+                        Ok(())
+                    } else if b == b'\n' {
+                        self.state = 6;
+                        self.end_field()
+                    } else if is_field_char(b) {
+                        self.push_field(b)
+                    } else {
+                        Err(HttpError::Unexpected)
+                    }
+                },
+                10 => { // final LF
+                    if b == b'\n' {
+                        input.step();
+                        return Done(());
+                    } else {
+                        Err(HttpError::Unexpected)
+                    }
+                },
+                11 => { // field LF
+                    if b == b'\n' {
+                        self.state = 6;
+                        self.end_field()
+                    } else {
+                        Err(HttpError::Unexpected)
+                    }
+                },
+                _ => unreachable!(),
+            };
+            if let Err(error) = result {
+                return Fail(error);
+            }
+            input.step();
+        }
+    }
+
+    fn start_line(&mut self) -> Result<(), HttpError> {
+        if self.response {
+            HttpVersion::parse(&self.line[..self.marks[0]])?;
+            if self.marks[1] - self.marks[0] != 3 {
+                return Err(HttpError::Status);
+            }
+        } else {
+            if self.line.len() == self.marks[1] {
+                return Err(HttpError::Unexpected);
+            }
+            HttpVersion::parse(&self.line[self.marks[1]..])?;
+        }
+        Ok(())
+    }
+
+    fn into_request(self) -> HttpRequest<'a> {
+        let HttpHeadParser { mut line, marks, headers, .. } = self;
+        let version = HttpVersion::parse(&line[marks[1]..]).unwrap();
+        line.truncate(marks[1]);
+        HttpRequest {
+            line: line,
+            method_end: marks[0],
+            version: version,
+            headers: headers,
+        }
+    }
+
+    fn into_response(self) -> HttpResponse<'a> {
+        let HttpHeadParser { mut line, marks, headers, .. } = self;
+        let version = HttpVersion::parse(&line[..marks[0]]).unwrap();
+        let status = line[marks[0]..marks[1]].iter().fold(0, |n, &b| 10 * n + (b - b'0') as u16);
+        line.drain(..marks[0]);
+        HttpResponse {
+            line: line,
+            version: version,
+            status: status,
+            headers: headers,
+        }
+    }
+}
+
+macro_rules! impl_http_head_decoder {
+    ($decoder:ident, $head:ident, $response:expr, $into:ident) => {
+        impl<'a, I> $decoder<'a, I> where I: Input<Token=u8> {
+            pub fn try_hold(hold: &dyn Hold<'a>) -> Result<Self, HoldError> {
+                Ok(Self {
+                    parser: HttpHeadParser::try_hold(hold, $response)?,
+                    input: PhantomData,
+                })
+            }
+
+            pub fn hold(hold: &dyn Hold<'a>) -> Self {
+                Self::try_hold(hold).unwrap()
+            }
+
+            /// Limits the length of the start line, excluding separators.
+            pub fn max_line(mut self, max_line: usize) -> Self {
+                self.parser.max_line = max_line;
+                self
+            }
+
+            /// Limits the number of header fields.
+            pub fn max_headers(mut self, max_headers: usize) -> Self {
+                self.parser.max_headers = max_headers;
+                self
+            }
+
+            /// Limits the combined name and value length of each header field.
+            pub fn max_header_size(mut self, max_header_size: usize) -> Self {
+                self.parser.max_header_size = max_header_size;
+                self
+            }
+
+            pub fn consume(mut self, input: &mut I) -> Result<$head<'a>, HttpError> {
+                loop {
+                    match self.decode(input) {
+                        Done(head) => return Ok(head),
+                        Fail(error) => return Err(error),
+                        Cont(next) => {
+                            if input.is_out() {
+                                input.over();
+                                self = next;
+                            } else {
+                                return Err(HttpError::Unexpected);
+                            }
+                        },
+                    }
+                }
+            }
+        }
+
+        impl<'a, I> Decoder for $decoder<'a, I> where I: Input<Token=u8> {
+            type Input = I;
+            type Output = $head<'a>;
+            type Error = HttpError;
+
+            fn decode(mut self, input: &mut I) -> Then<Self, $head<'a>, HttpError> {
+                match self.parser.parse(input) {
+                    Cont(()) => Cont(self),
+                    Done(()) => Done(self.parser.$into()),
+                    Fail(error) => Fail(error),
+                }
+            }
+        }
+    };
+}
+
+impl_http_head_decoder!(HttpRequestDecoder, HttpRequest, false, into_request);
+impl_http_head_decoder!(HttpResponseDecoder, HttpResponse, true, into_response);
+
+impl<I, O> HttpBodyDecoder<I, O> where I: Input<Token=u8>, O: Output<Token=u8> {
+    pub fn new(output: O, body: HttpBody) -> Self {
+        Self {
+            output: output,
+            body: body,
+            size: 0,
+            count: 0,
+            state: 1,
+            input: PhantomData,
+        }
+    }
+
+    pub fn consume(mut self, input: &mut I) -> Result<O::Out, HttpError> where O::Err: fmt::Debug {
+        loop {
+            match self.decode(input) {
+                Done(output) => return Ok(output),
+                Fail(error) => return Err(error),
+                Cont(next) => {
+                    if input.is_out() {
+                        input.over();
+                        self = next;
+                    } else {
+                        return Err(HttpError::Unexpected);
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl<I, O> Decoder for HttpBodyDecoder<I, O>
+    where I: Input<Token=u8>,
+          O: Output<Token=u8>,
+          O::Err: fmt::Debug {
+
+    type Input = I;
+    type Output = O::Out;
+    type Error = HttpError;
+
+    fn decode(mut self, input: &mut I) -> Then<Self, O::Out, HttpError> {
+        loop {
+            match (self.body, self.state) {
+                (HttpBody::Empty, _) => return Done(self.output.take_out().unwrap()),
+                (HttpBody::Length(len), _) => {
+                    while self.size < len {
+                        match input.head() {
+                            In(_) if self.output.is_full() => return Fail(HttpError::BodyTooLarge),
+                            In(b) => {
+                                input.step();
+                                self.output.push(b);
+                                self.size += 1;
+                            },
+                            Over => return Fail(HttpError::Truncated),
+                            Out => return Cont(self),
+                        };
+                    }
+                    return Done(self.output.take_out().unwrap());
+                },
+                (HttpBody::Close, _) => {
+                    loop {
+                        match input.head() {
+                            In(_) if self.output.is_full() => return Fail(HttpError::BodyTooLarge),
+                            In(b) => {
+                                input.step();
+                                self.output.push(b);
+                            },
+                            Over => return Done(self.output.take_out().unwrap()),
+                            Out => return Cont(self),
+                        };
+                    }
+                },
+                (HttpBody::Chunked, 1) => { // chunk size
+                    match input.head() {
+                        In(b) if is_hex_digit(b) => {
+                            input.step();
+                            if self.count == 16 {
+                                return Fail(HttpError::Chunk);
+                            }
+                            self.size = self.size << 4 | decode_hex_digit(b) as u64;
+                            self.count += 1;
+                        },
+                        In(_) if self.count == 0 => return Fail(HttpError::Chunk),
+                        In(_) => {
+                            self.count = 0;
+                            self.state = 2;
+                        },
+                        Over => return Fail(HttpError::Truncated),
+                        Out => return Cont(self),
+                    };
+                },
+                (HttpBody::Chunked, 2) => { // chunk extensions
+                    match input.head() {
+                        In(b'\n') => {
+                            input.step();
+                            self.count = 0;
+                            self.state = if self.size != 0 { 3 } else { 5 };
+                        },
+                        In(b) if b == b'\r' || b == b'\t' || b >= b' ' && b != 0x7F => {
+                            input.step();
+                            self.count += 1;
+                            if self.count > 1024 {
+                                return Fail(HttpError::Chunk);
+                            }
+                        },
+                        In(_) => return Fail(HttpError::Chunk),
+                        Over => return Fail(HttpError::Truncated),
+                        Out => return Cont(self),
+                    };
+                },
+                (HttpBody::Chunked, 3) => { // chunk data
+                    while self.size != 0 {
+                        match input.head() {
+                            In(_) if self.output.is_full() => return Fail(HttpError::BodyTooLarge),
+                            In(b) => {
+                                input.step();
+                                self.output.push(b);
+                                self.size -= 1;
+                            },
+                            Over => return Fail(HttpError::Truncated),
+                            Out => return Cont(self),
+                        };
+                    }
+                    self.state = 4;
+                },
+                (HttpBody::Chunked, 4) => { // chunk data CRLF
+                    match input.head() {
+                        In(b'\r') if self.count == 0 => {
+                            input.step();
+                            self.count = 1;
+                        },
+                        In(b'\n') => {
+                            input.step();
+                            self.count = 0;
+                            self.state = 1;
+                        },
+                        In(_) => return Fail(HttpError::Chunk),
+                        Over => return Fail(HttpError::Truncated),
+                        Out => return Cont(self),
+                    };
+                },
+                (HttpBody::Chunked, 5) => { // trailer fields, discarded
+                    match input.head() {
+                        In(b'\n') => {
+                            input.step();
+                            if self.size == 0 {
+                                return Done(self.output.take_out().unwrap());
+                            }
+                            self.size = 0;
+                        },
+                        In(b) => {
+                            input.step();
+                            if b != b'\r' {
+                                self.size += 1;
+                            }
+                            self.count += 1;
+                            if self.count > 8192 {
+                                return Fail(HttpError::HeaderTooLarge);
+                            }
+                        },
+                        Over => return Fail(HttpError::Truncated),
+                        Out => return Cont(self),
+                    };
+                },
+                _ => unreachable!(),
+            };
+        }
+    }
+}
+
+impl<'b, H, O> HttpHeadEncoder<'b, H, O> where H: HttpHead, O: Output<Token=u8> {
+    pub fn new(head: &'b H) -> Self {
+        Self {
+            head: head,
+            index: 0,
+            offset: 0,
+            output: PhantomData,
+        }
+    }
+
+    pub fn produce(mut self, mut output: O) -> Result<O::Out, O::Err> {
+        loop {
+            match self.encode(&mut output) {
+                Done(_) => return output.take_out(),
+                Fail(_) => unreachable!(),
+                Cont(next) => self = next,
+            }
+        }
+    }
+}
+
+impl<'b, H, O> Encoder for HttpHeadEncoder<'b, H, O> where H: HttpHead, O: Output<Token=u8> {
+    type Input = &'b H;
+    type Output = O;
+    type Error = ();
+
+    fn encode(mut self, output: &mut O) -> Then<Self, &'b H, ()> {
+        while !output.is_full() {
+            match self.head.head_segment(self.index) {
+                Some(segment) if self.offset < segment.len() => {
+                    output.push(segment[self.offset]);
+                    self.offset += 1;
+                },
+                Some(_) => {
+                    self.index += 1;
+                    self.offset = 0;
+                },
+                None => return Done(self.head),
+            };
+        }
+        if self.head.head_segment(self.index).is_none() {
+            return Done(self.head);
+        }
+        return Cont(self);
+    }
+}
+
+impl<I, O> HttpBodyEncoder<I, O> where I: Input<Token=u8>, O: Output<Token=u8> {
+    pub fn new(input: I, body: HttpBody) -> Self {
+        Self {
+            input: input,
+            body: body,
+            size: 0,
+            buffer: [0; CHUNK_SIZE],
+            len: 0,
+            scratch: [0; 18],
+            scratch_len: 0,
+            count: 0,
+            state: 1,
+            output: PhantomData,
+        }
+    }
+
+    pub fn produce(mut self, mut output: O) -> Result<O::Out, HttpError> where O::Err: fmt::Debug {
+        loop {
+            match self.encode(&mut output) {
+                Done(_) => return Ok(output.take_out().unwrap()),
+                Fail(error) => return Err(error),
+                Cont(next) => {
+                    self = next;
+                    self.input.over();
+                }
+            }
+        }
+    }
+
+    fn chunk_header(&mut self, len: usize) {
+        let mut n = 0;
+        let mut shift = 4 * (2 * core::mem::size_of::<usize>() - 1);
+        while shift > 0 && len >> shift == 0 {
+            shift -= 4;
+        }
+        loop {
+            self.scratch[n] = encode_hex_digit((len >> shift & 0xF) as u8);
+            n += 1;
+            if shift == 0 {
+                break;
+            }
+            shift -= 4;
+        }
+        self.scratch[n] = b'\r';
+        self.scratch[n + 1] = b'\n';
+        self.scratch_len = n + 2;
+        self.count = 0;
+    }
+}
+
+impl<I, O> Encoder for HttpBodyEncoder<I, O> where I: Input<Token=u8>, O: Output<Token=u8> {
+    type Input = I;
+    type Output = O;
+    type Error = HttpError;
+
+    fn encode(mut self, output: &mut O) -> Then<Self, I, HttpError> {
+        loop {
+            match (self.body, self.state) {
+                (HttpBody::Empty, _) => return Done(self.input),
+                (HttpBody::Length(len), _) => {
+                    while self.size < len && !output.is_full() {
+                        match self.input.head() {
+                            In(b) => {
+                                self.input.step();
+                                output.push(b);
+                                self.size += 1;
+                            },
+                            Over => return Fail(HttpError::Truncated),
+                            Out => return Cont(self),
+                        };
+                    }
+                    if self.size == len {
+                        return Done(self.input);
+                    }
+                    return Cont(self);
+                },
+                (HttpBody::Close, _) => {
+                    while !output.is_full() {
+                        match self.input.head() {
+                            In(b) => {
+                                self.input.step();
+                                output.push(b);
+                            },
+                            Over => return Done(self.input),
+                            Out => return Cont(self),
+                        };
+                    }
+                    return Cont(self);
+                },
+                (HttpBody::Chunked, 1) => { // buffer chunk
+                    while self.len < CHUNK_SIZE {
+                        match self.input.head() {
+                            In(b) => {
+                                self.input.step();
+                                self.buffer[self.len] = b;
+                                self.len += 1;
+                            },
+                            _ => break,
+                        };
+                    }
+                    if self.len != 0 {
+                        let len = self.len;
+                        self.chunk_header(len);
+                        self.state = 2;
+                    } else if self.input.is_over() {
+                        self.scratch[..5].copy_from_slice(b"0\r\n\r\n");
+                        self.scratch_len = 5;
+                        self.count = 0;
+                        self.state = 5;
+                    } else {
+                        return Cont(self);
+                    }
+                },
+                (HttpBody::Chunked, state) => {
+                    if output.is_full() {
+                        return Cont(self);
+                    }
+                    match state {
+                        2 | 5 if self.count < self.scratch_len => {
+                            output.push(self.scratch[self.count]);
+                            self.count += 1;
+                        },
+                        2 => {
+                            self.count = 0;
+                            self.state = 3;
+                        },
+                        3 if self.count < self.len => {
+                            output.push(self.buffer[self.count]);
+                            self.count += 1;
+                        },
+                        3 => {
+                            self.count = 0;
+                            self.state = 4;
+                        },
+                        4 if self.count < 2 => {
+                            output.push(b"\r\n"[self.count]);
+                            self.count += 1;
+                        },
+                        4 => {
+                            self.len = 0;
+                            self.count = 0;
+                            self.state = 1;
+                        },
+                        5 => return Done(self.input),
+                        _ => unreachable!(),
+                    };
+                },
+            };
+        }
+    }
+}
+
+#[inline]
+fn is_digit(b: u8) -> bool {
+    b >= b'0' && b <= b'9'
+}
+
+#[inline]
+fn is_hex_digit(b: u8) -> bool {
+    is_digit(b) || b >= b'A' && b <= b'F' || b >= b'a' && b <= b'f'
+}
+
+#[inline]
+fn decode_hex_digit(b: u8) -> u8 {
+    if is_digit(b) {
+        b - b'0'
+    } else if b >= b'A' && b <= b'F' {
+        10 + (b - b'A')
+    } else {
+        10 + (b - b'a')
+    }
+}
+
+#[inline]
+fn encode_hex_digit(x: u8) -> u8 {
+    b"0123456789ABCDEF"[x as usize]
+}
+
+#[inline]
+fn is_tchar(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || match b {
+        b'!' | b'#' | b'$' | b'%' | b'&' | b'\'' | b'*' | b'+' |
+        b'-' | b'.' | b'^' | b'_' | b'`' | b'|' | b'~' => true,
+        _ => false,
+    }
+}
+
+#[inline]
+fn is_target_char(b: u8) -> bool {
+    b > b' ' && b < 0x7F
+}
+
+#[inline]
+fn is_field_char(b: u8) -> bool {
+    b == b'\t' || b >= b' ' && b != 0x7F
+}
+
+fn trim_ows(mut bytes: &[u8]) -> &[u8] {
+    while let Some((&b, rest)) = bytes.split_first() {
+        if b != b' ' && b != b'\t' {
+            break;
+        }
+        bytes = rest;
+    }
+    while let Some((&b, rest)) = bytes.split_last() {
+        if b != b' ' && b != b'\t' {
+            break;
+        }
+        bytes = rest;
+    }
+    bytes
+}
+
+fn parse_decimal(bytes: &[u8]) -> Option<u64> {
+    if bytes.is_empty() {
+        return None;
+    }
+    let mut n = 0u64;
+    for &b in bytes {
+        if !is_digit(b) {
+            return None;
+        }
+        n = n.checked_mul(10)?.checked_add((b - b'0') as u64)?;
+    }
+    Some(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use swim_mem::block::Block;
+    use swim_mem::alloc::Pack;
+    use crate::input::AsInput;
+    use crate::output::SliceOutput;
+    use super::*;
+
+    fn decode_request<'a>(hold: &dyn Hold<'a>, encoded: &[u8]) -> Result<HttpRequest<'a>, HttpError> {
+        HttpRequestDecoder::hold(hold).consume(&mut encoded.as_input())
+    }
+
+    #[test]
+    fn test_http_decode_request() {
+        let encoded = b"GET /index.html?q=1 HTTP/1.1\r\n\
+                        Host: example.com\r\n\
+                        Accept:  text/html , */*  \r\n\
+                        X-Empty:\r\n\
+                        \r\n";
+        for split in 0..=encoded.len() {
+            let mut test_area = [0u8; 4096];
+            let pack = Pack::new(Block::from_slice(&mut test_area));
+            let decoder = HttpRequestDecoder::hold(pack);
+            let request = match decoder.decode(&mut encoded[..split].as_input()) {
+                Cont(decoder) => decoder.consume(&mut encoded[split..].as_input()).unwrap(),
+                Done(request) => request,
+                Fail(error) => panic!("{:?}", error),
+            };
+            assert_eq!(request.method(), "GET");
+            assert_eq!(request.target(), "/index.html?q=1");
+            assert_eq!(request.version(), Http11);
+            assert_eq!(request.headers().len(), 3);
+            assert_eq!(request.headers().get("host"), Some(&b"example.com"[..]));
+            assert_eq!(request.headers().get_str("ACCEPT"), Some("text/html , */*"));
+            assert_eq!(request.headers().get("x-empty"), Some(&b""[..]));
+            assert_eq!(request.headers().get("missing"), None);
+            assert_eq!(request.body(), Ok(HttpBody::Empty));
+            assert!(request.keep_alive());
+        }
+    }
+
+    #[test]
+    fn test_http_decode_response() {
+        static mut TEST_AREA: [u8; 16384] = [0; 16384];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let encoded = b"HTTP/1.0 404 Not Found\nContent-Length: 5\nConnection: keep-alive\n\nhello";
+        let mut input = encoded[..].as_input();
+        let response = HttpResponseDecoder::hold(pack).decode(&mut input);
+        let response = match response { Done(response) => response, _ => panic!() };
+        assert_eq!(response.version(), Http10);
+        assert_eq!(response.status(), 404);
+        assert_eq!(response.reason(), b"Not Found");
+        assert_eq!(response.body("GET"), Ok(HttpBody::Length(5)));
+        assert_eq!(response.body("HEAD"), Ok(HttpBody::Empty));
+        assert!(response.keep_alive("GET"));
+
+        let mut buffer = [0u8; 16];
+        let body = HttpBodyDecoder::new(SliceOutput::new(&mut buffer), response.body("GET").unwrap());
+        assert_eq!(body.consume(&mut input).unwrap(), b"hello");
+
+        let response = HttpResponseDecoder::hold(pack).consume(&mut b"HTTP/1.1 204\r\n\r\n"[..].as_input()).unwrap();
+        assert_eq!((response.status(), response.reason()), (204, &b""[..]));
+    }
+
+    #[test]
+    fn test_http_keep_alive() {
+        static mut TEST_AREA: [u8; 16384] = [0; 16384];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        assert!(!decode_request(pack, b"GET / HTTP/1.0\r\n\r\n").unwrap().keep_alive());
+        assert!(decode_request(pack, b"GET / HTTP/1.0\r\nConnection: Keep-Alive\r\n\r\n").unwrap().keep_alive());
+        assert!(!decode_request(pack, b"GET / HTTP/1.1\r\nConnection: upgrade, close\r\n\r\n").unwrap().keep_alive());
+        let response = HttpResponseDecoder::hold(pack).consume(&mut b"HTTP/1.1 200 OK\r\n\r\n"[..].as_input()).unwrap();
+        assert_eq!(response.body("GET"), Ok(HttpBody::Close));
+        assert!(!response.keep_alive("GET"));
+    }
+
+    #[test]
+    fn test_http_request_body_framing() {
+        static mut TEST_AREA: [u8; 16384] = [0; 16384];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let request = decode_request(pack, b"POST / HTTP/1.1\r\nTransfer-Encoding: gzip, chunked\r\n\r\n").unwrap();
+        assert_eq!(request.body(), Ok(HttpBody::Chunked));
+        let request = decode_request(pack, b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\nContent-Length: 4\r\n\r\n").unwrap();
+        assert_eq!(request.body(), Err(HttpError::ContentLength));
+        let request = decode_request(pack, b"POST / HTTP/1.1\r\nTransfer-Encoding: gzip\r\n\r\n").unwrap();
+        assert_eq!(request.body(), Err(HttpError::TransferEncoding));
+        let request = decode_request(pack, b"POST / HTTP/1.1\r\nContent-Length: 4\r\nContent-Length: 5\r\n\r\n").unwrap();
+        assert_eq!(request.body(), Err(HttpError::ContentLength));
+        let request = decode_request(pack, b"POST / HTTP/1.1\r\nContent-Length: 4\r\ncontent-length: 4\r\n\r\n").unwrap();
+        assert_eq!(request.body(), Ok(HttpBody::Length(4)));
+    }
+
+    #[test]
+    fn test_http_decode_malformed() {
+        static mut TEST_AREA: [u8; 16384] = [0; 16384];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        assert_eq!(decode_request(pack, b"GET / HTTP/2.0\r\n\r\n").err(), Some(HttpError::Version));
+        assert_eq!(decode_request(pack, b"GET / HTTX/1.1\r\n\r\n").err(), Some(HttpError::Unexpected));
+        assert_eq!(decode_request(pack, b"GET /\r\n\r\n").err(), Some(HttpError::Unexpected));
+        assert_eq!(decode_request(pack, b"G(T / HTTP/1.1\r\n\r\n").err(), Some(HttpError::Unexpected));
+        assert_eq!(decode_request(pack, b"GET / HTTP/1.1\r\nHost : x\r\n\r\n").err(), Some(HttpError::Unexpected));
+        assert_eq!(decode_request(pack, b"GET / HTTP/1.1\r\nA: b\r\n folded\r\n\r\n").err(), Some(HttpError::Unexpected));
+        assert_eq!(decode_request(pack, b"GET / HTTP/1.1\r\nHost: x\r\n").err(), Some(HttpError::Truncated));
+        let response = HttpResponseDecoder::hold(pack).consume(&mut b"HTTP/1.1 20 OK\r\n\r\n"[..].as_input());
+        assert_eq!(response.err(), Some(HttpError::Status));
+    }
+
+    #[test]
+    fn test_http_decode_limits() {
+        static mut TEST_AREA: [u8; 16384] = [0; 16384];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let encoded = b"GET / HTTP/1.1\r\nA: 1\r\nB: 2\r\nC: 3\r\n\r\n";
+        let decoder = HttpRequestDecoder::hold(pack).max_headers(2);
+        assert_eq!(decoder.consume(&mut encoded[..].as_input()).err(), Some(HttpError::TooManyHeaders));
+        let decoder = HttpRequestDecoder::hold(pack).max_header_size(4);
+        assert_eq!(decoder.consume(&mut b"GET / HTTP/1.1\r\nAbc: 12\r\n\r\n"[..].as_input()).err(),
+                   Some(HttpError::HeaderTooLarge));
+        let decoder = HttpRequestDecoder::hold(pack).max_line(8);
+        assert_eq!(decoder.consume(&mut b"GET /abcdef HTTP/1.1\r\n\r\n"[..].as_input()).err(),
+                   Some(HttpError::LineTooLong));
+    }
+
+    #[test]
+    fn test_http_decode_chunked_body() {
+        let encoded = b"4\r\nWiki\r\n5;name=value\r\npedia\r\nE\r\n in\r\n\r\nchunks.\r\n0\r\nExpires: never\r\n\r\nrest";
+        for split in 0..=encoded.len() {
+            let mut buffer = [0u8; 64];
+            let decoder = HttpBodyDecoder::new(SliceOutput::new(&mut buffer), HttpBody::Chunked);
+            let mut input = encoded[split..].as_input();
+            let body = match decoder.decode(&mut encoded[..split].as_input()) {
+                Cont(decoder) => decoder.consume(&mut input).unwrap(),
+                Done(body) => body,
+                Fail(error) => panic!("{:?}", error),
+            };
+            assert_eq!(body, &b"Wikipedia in\r\n\r\nchunks."[..]);
+        }
+        let mut buffer = [0u8; 64];
+        let decoder = HttpBodyDecoder::new(SliceOutput::new(&mut buffer), HttpBody::Chunked);
+        assert_eq!(decoder.consume(&mut b"x\r\n"[..].as_input()).err(), Some(HttpError::Chunk));
+        let mut buffer = [0u8; 64];
+        let decoder = HttpBodyDecoder::new(SliceOutput::new(&mut buffer), HttpBody::Chunked);
+        assert_eq!(decoder.consume(&mut b"2\r\nabc\r\n"[..].as_input()).err(), Some(HttpError::Chunk));
+    }
+
+    #[test]
+    fn test_http_decode_body_too_large() {
+        let mut buffer = [0u8; 4];
+        let decoder = HttpBodyDecoder::new(SliceOutput::new(&mut buffer), HttpBody::Length(5));
+        assert_eq!(decoder.consume(&mut b"hello"[..].as_input()).err(), Some(HttpError::BodyTooLarge));
+        let mut buffer = [0u8; 5];
+        let decoder = HttpBodyDecoder::new(SliceOutput::new(&mut buffer), HttpBody::Length(5));
+        assert_eq!(decoder.consume(&mut b"hello"[..].as_input()).unwrap(), b"hello");
+        let mut buffer = [0u8; 8];
+        let decoder = HttpBodyDecoder::new(SliceOutput::new(&mut buffer), HttpBody::Chunked);
+        let encoded = b"4\r\nWiki\r\n5\r\npedia\r\n0\r\n\r\n";
+        assert_eq!(decoder.consume(&mut encoded[..].as_input()).err(), Some(HttpError::BodyTooLarge));
+        let mut buffer = [0u8; 4];
+        let decoder = HttpBodyDecoder::new(SliceOutput::new(&mut buffer), HttpBody::Close);
+        assert_eq!(decoder.consume(&mut b"hello"[..].as_input()).err(), Some(HttpError::BodyTooLarge));
+    }
+
+    #[test]
+    fn test_http_encode_head() {
+        static mut TEST_AREA: [u8; 16384] = [0; 16384];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let mut request = HttpRequest::try_hold(pack, "PUT", "/lane", Http11).unwrap();
+        request.headers_mut().push("Host", b"swim.ai");
+        request.headers_mut().push("Transfer-Encoding", b"chunked");
+        let mut buffer = [0u8; 128];
+        let encoded = HttpHeadEncoder::new(&request).produce(SliceOutput::new(&mut buffer)).unwrap();
+        assert_eq!(encoded, &b"PUT /lane HTTP/1.1\r\nHost: swim.ai\r\nTransfer-Encoding: chunked\r\n\r\n"[..]);
+        let decoded = decode_request(pack, encoded).unwrap();
+        assert_eq!((decoded.method(), decoded.target()), ("PUT", "/lane"));
+        assert_eq!(decoded.body(), Ok(HttpBody::Chunked));
+
+        let response = HttpResponse::try_hold(pack, Http11, 101, "Switching Protocols").unwrap();
+        let mut buffer = [0u8; 128];
+        let encoded = HttpHeadEncoder::new(&response).produce(SliceOutput::new(&mut buffer)).unwrap();
+        assert_eq!(encoded, &b"HTTP/1.1 101 Switching Protocols\r\n\r\n"[..]);
+
+        assert_eq!(request.headers_mut().try_push("Bad Name", b"x").err(), Some(HttpError::Unexpected));
+        assert_eq!(request.headers_mut().try_push("X", b"a\r\nb").err(), Some(HttpError::Unexpected));
+    }
+
+    #[test]
+    fn test_http_encode_body() {
+        let decoded = [0x61u8; 300];
+        let mut buffer = [0u8; 512];
+        let encoder = HttpBodyEncoder::new(decoded[..].as_input(), HttpBody::Chunked);
+        let encoded = encoder.produce(SliceOutput::new(&mut buffer)).unwrap();
+        assert_eq!(&encoded[..5], b"100\r\n");
+        assert_eq!(&encoded[261..267], b"\r\n2C\r\n");
+        assert_eq!(&encoded[encoded.len() - 7..], b"\r\n0\r\n\r\n");
+        let mut output = [0u8; 512];
+        let decoder = HttpBodyDecoder::new(SliceOutput::new(&mut output), HttpBody::Chunked);
+        assert_eq!(decoder.consume(&mut (&*encoded).as_input()).unwrap(), &decoded[..]);
+
+        let mut buffer = [0u8; 16];
+        let encoder = HttpBodyEncoder::new(b"hello"[..].as_input(), HttpBody::Length(5));
+        assert_eq!(encoder.produce(SliceOutput::new(&mut buffer)).unwrap(), b"hello");
+        let mut buffer = [0u8; 16];
+        let encoder = HttpBodyEncoder::new(b"hell"[..].as_input(), HttpBody::Length(5));
+        assert_eq!(encoder.produce(SliceOutput::new(&mut buffer)).err(), Some(HttpError::Truncated));
+    }
+}
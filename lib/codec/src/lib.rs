@@ -4,10 +4,40 @@
 
 #![feature(const_fn)]
 
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "futures")]
+extern crate futures_io;
+
+extern crate swim_core;
+extern crate swim_mem;
+extern crate swim_structure;
+
 pub mod step;
 pub mod then;
 pub mod input;
 pub mod output;
 pub mod decoder;
 pub mod encoder;
+pub mod base16;
+pub mod base32;
 pub mod base64;
+pub mod base85;
+pub mod binary;
+pub mod checksum;
+pub mod csv;
+pub mod deflate;
+pub mod digest;
+pub mod frame;
+pub mod http;
+pub mod pem;
+pub mod percent;
+#[cfg(feature = "futures")]
+pub mod stream;
+#[cfg(test)]
+pub mod testing;
+pub mod ulid;
+pub mod uuid;
+pub mod websocket;
+pub mod xml;
@@ -0,0 +1,300 @@
+use core::fmt;
+use core::marker::PhantomData;
+use crate::step::{In, Out, Over};
+use crate::then::{Then, Cont, Done, Fail};
+use crate::input::Input;
+use crate::output::Output;
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+use crate::base64::{Base64, Lf, Base64Error, Base64Decoder, Base64Encoder};
+
+const BEGIN_PREFIX: &'static str = "-----BEGIN ";
+const END_PREFIX: &'static str = "\n-----END ";
+const LINE_SUFFIX: &'static str = "-----";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PemError {
+    Unexpected,
+    Base64(Base64Error),
+}
+
+/// Decodes the base64 body of a PEM block with the given `label`, such as
+/// `CERTIFICATE` in `-----BEGIN CERTIFICATE-----`.
+pub struct PemDecoder<'a, I: Input<Token=char>, O: Output<Token=u8>> {
+    label: &'a str,
+    base64: Option<Base64Decoder<I, O>>,
+    output: Option<O::Out>,
+    index: usize,
+    state: u32,
+}
+
+/// Encodes a base64 body, wrapped at 64 columns, between PEM armor lines
+/// with the given `label`.
+pub struct PemEncoder<'a, I: Input<Token=u8>, O: Output<Token=char>> {
+    label: &'a str,
+    base64: Option<Base64Encoder<I, O>>,
+    input: Option<I>,
+    index: usize,
+    state: u32,
+    output: PhantomData<O>,
+}
+
+impl<'a, I, O> PemDecoder<'a, I, O> where I: Input<Token=char>, O: Output<Token=u8> {
+    pub fn new(output: O, label: &'a str) -> Self {
+        Self {
+            label: label,
+            base64: Some(Base64Decoder::new(output).mime(true).alphabet(Base64)),
+            output: None,
+            index: 0,
+            state: 1,
+        }
+    }
+
+    pub fn consume(mut self, input: &mut I) -> Result<O::Out, PemError> where O::Err: fmt::Debug {
+        loop {
+            match self.decode(input) {
+                Done(output) => return Ok(output),
+                Fail(error) => return Err(error),
+                Cont(next) => {
+                    if input.is_out() {
+                        input.over();
+                        self = next;
+                    } else {
+                        return Err(PemError::Unexpected);
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl<'a, I, O> Decoder for PemDecoder<'a, I, O>
+    where I: Input<Token=char>,
+          O: Output<Token=u8>,
+          O::Err: fmt::Debug {
+
+    type Input = I;
+    type Output = O::Out;
+    type Error = PemError;
+
+    fn decode(mut self, input: &mut I) -> Then<Self, O::Out, PemError> {
+        loop {
+            match self.state {
+                1 | 4 => {
+                    while let In(c) = input.head() {
+                        if !is_pem_space(c) {
+                            break;
+                        }
+                        input.step();
+                    }
+                    match input.head() {
+                        In(_) => self.state += 1,
+                        Over => return Fail(PemError::Unexpected),
+                        Out => return Cont(self),
+                    };
+                },
+                2 | 5 => {
+                    let prefix = if self.state == 2 { BEGIN_PREFIX } else { &END_PREFIX[1..] };
+                    match pem_line_char(prefix, self.label, self.index) {
+                        Some(expected) => {
+                            match input.head() {
+                                In(c) if c == expected => {
+                                    input.step();
+                                    self.index += 1;
+                                },
+                                In(_) | Over => return Fail(PemError::Unexpected),
+                                Out => return Cont(self),
+                            };
+                        },
+                        None if self.state == 2 => {
+                            self.index = 0;
+                            self.state = 3;
+                        },
+                        None => return Done(self.output.take().unwrap()),
+                    };
+                },
+                3 => {
+                    match self.base64.take().unwrap().decode(input) {
+                        Cont(next) => {
+                            self.base64 = Some(next);
+                            return Cont(self);
+                        },
+                        Done(output) => {
+                            self.output = Some(output);
+                            self.state = 4;
+                        },
+                        Fail(error) => return Fail(PemError::Base64(error)),
+                    };
+                },
+                _ => unreachable!(),
+            };
+        }
+    }
+}
+
+impl<'a, I, O> PemEncoder<'a, I, O> where I: Input<Token=u8>, O: Output<Token=char> {
+    pub fn new(input: I, label: &'a str) -> Self {
+        Self {
+            label: label,
+            base64: Some(Base64Encoder::new(input, Base64).wrap(64, Lf)),
+            input: None,
+            index: 0,
+            state: 1,
+            output: PhantomData,
+        }
+    }
+
+    pub fn produce(mut self, mut output: O) -> Result<O::Out, O::Err> {
+        loop {
+            match self.encode(&mut output) {
+                Done(_) => return output.take_out(),
+                Fail(_) => unreachable!(),
+                Cont(next) => {
+                    self = next;
+                    match self.base64 {
+                        Some(ref mut base64) => base64.input.over(),
+                        None => self.input.as_mut().unwrap().over(),
+                    };
+                }
+            }
+        }
+    }
+}
+
+impl<'a, I, O> Encoder for PemEncoder<'a, I, O> where I: Input<Token=u8>, O: Output<Token=char> {
+    type Input = I;
+    type Output = O;
+    type Error = ();
+
+    fn encode(mut self, output: &mut O) -> Then<Self, I, ()> {
+        while !output.is_full() {
+            match self.state {
+                1 => {
+                    match pem_line_char(BEGIN_PREFIX, self.label, self.index) {
+                        Some(c) => {
+                            output.push(c);
+                            self.index += 1;
+                        },
+                        None => {
+                            output.push('\n');
+                            self.index = 0;
+                            self.state = 2;
+                        },
+                    };
+                },
+                2 => {
+                    match self.base64.as_mut().unwrap().input.head() {
+                        In(_) => self.state = 3,
+                        Over => {
+                            self.input = Some(self.base64.take().unwrap().input);
+                            self.index = 1;
+                            self.state = 4;
+                        },
+                        Out => break,
+                    };
+                },
+                3 => {
+                    match self.base64.take().unwrap().encode(output) {
+                        Cont(next) => {
+                            self.base64 = Some(next);
+                            return Cont(self);
+                        },
+                        Done(input) => {
+                            self.input = Some(input);
+                            self.state = 4;
+                        },
+                        Fail(_) => unreachable!(),
+                    };
+                },
+                4 => {
+                    match pem_line_char(END_PREFIX, self.label, self.index) {
+                        Some(c) => {
+                            output.push(c);
+                            self.index += 1;
+                        },
+                        None => {
+                            output.push('\n');
+                            return Done(self.input.take().unwrap());
+                        },
+                    };
+                },
+                _ => unreachable!(),
+            };
+        }
+        return Cont(self);
+    }
+}
+
+#[inline]
+fn is_pem_space(c: char) -> bool {
+    c == ' ' || c == '\t' || c == '\r' || c == '\n'
+}
+
+fn pem_line_char(prefix: &str, label: &str, index: usize) -> Option<char> {
+    let (prefix, label, suffix) = (prefix.as_bytes(), label.as_bytes(), LINE_SUFFIX.as_bytes());
+    if index < prefix.len() {
+        Some(prefix[index] as char)
+    } else if index < prefix.len() + label.len() {
+        Some(label[index - prefix.len()] as char)
+    } else if index < prefix.len() + label.len() + suffix.len() {
+        Some(suffix[index - prefix.len() - label.len()] as char)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::AsInput;
+    use crate::output::{SliceOutput, StrOutput};
+    use super::*;
+
+    fn assert_transcodes(encoded: &str, decoded: &[u8], label: &str) {
+        let mut buffer = [0u8; 1024];
+        let decoder = PemDecoder::new(SliceOutput::new(&mut buffer), label);
+        assert_eq!(decoder.consume(&mut encoded.as_input()).unwrap(), decoded);
+        let mut buffer = [0u8; 1024];
+        let encoder = PemEncoder::new(decoded.as_input(), label);
+        assert_eq!(encoder.produce(StrOutput::new(&mut buffer)).unwrap(), encoded);
+    }
+
+    #[test]
+    fn test_pem_transcode() {
+        assert_transcodes("-----BEGIN TEST-----\n-----END TEST-----\n", b"", "TEST");
+        assert_transcodes("-----BEGIN TEST-----\nZm9vYmFy\n-----END TEST-----\n", b"foobar", "TEST");
+        assert_transcodes("-----BEGIN PUBLIC KEY-----\n\
+                           AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8gISIjJCUmJygpKissLS4v\n\
+                           MDEyMzQ1Njc4OTo7PD0+P0BB\n\
+                           -----END PUBLIC KEY-----\n",
+                          &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+                            16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31,
+                            32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47,
+                            48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63,
+                            64, 65],
+                          "PUBLIC KEY");
+    }
+
+    #[test]
+    fn test_pem_decode_crlf() {
+        let mut buffer = [0u8; 16];
+        let decoder = PemDecoder::new(SliceOutput::new(&mut buffer), "TEST");
+        let encoded = "\r\n-----BEGIN TEST-----\r\nZm9v\r\nYmFy\r\n-----END TEST-----\r\n";
+        assert_eq!(decoder.consume(&mut encoded.as_input()).unwrap(), b"foobar");
+    }
+
+    #[test]
+    fn test_pem_decode_mismatched_label() {
+        let mut buffer = [0u8; 16];
+        let decoder = PemDecoder::new(SliceOutput::new(&mut buffer), "TEST");
+        let encoded = "-----BEGIN TEST-----\nZm9vYmFy\n-----END OTHER-----\n";
+        assert_eq!(decoder.consume(&mut encoded.as_input()).unwrap_err(), PemError::Unexpected);
+    }
+
+    #[test]
+    fn test_pem_decode_truncated() {
+        let mut buffer = [0u8; 16];
+        let decoder = PemDecoder::new(SliceOutput::new(&mut buffer), "TEST");
+        let encoded = "-----BEGIN TEST-----\nZm9vYmFy\n";
+        assert_eq!(decoder.consume(&mut encoded.as_input()).unwrap_err(), PemError::Unexpected);
+    }
+}
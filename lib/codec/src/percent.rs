@@ -0,0 +1,301 @@
+use core::fmt;
+use core::marker::PhantomData;
+use crate::step::{In, Out, Over};
+use crate::then::{Then, Cont, Done, Fail};
+use crate::input::Input;
+use crate::output::Output;
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+
+/// The URI component being percent-encoded, which determines the reserved
+/// characters that may appear unescaped.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PercentSet {
+    /// Escapes everything but unreserved characters.
+    Component,
+    UserInfo,
+    Host,
+    Path,
+    Segment,
+    Query,
+    /// A key or value of an `application/x-www-form-urlencoded` query.
+    QueryParam,
+    Fragment,
+}
+
+impl PercentSet {
+    pub fn is_literal(self, b: u8) -> bool {
+        if is_unreserved(b) {
+            return true;
+        }
+        match self {
+            PercentSet::Component => false,
+            PercentSet::UserInfo => is_sub_delim(b) || b == b':',
+            PercentSet::Host => is_sub_delim(b),
+            PercentSet::Path => is_sub_delim(b) || b == b':' || b == b'@' || b == b'/',
+            PercentSet::Segment => is_sub_delim(b) || b == b':' || b == b'@',
+            PercentSet::Query | PercentSet::Fragment => {
+                is_sub_delim(b) || b == b':' || b == b'@' || b == b'/' || b == b'?'
+            },
+            PercentSet::QueryParam => {
+                is_sub_delim(b) && b != b'&' && b != b'=' && b != b'+' ||
+                b == b':' || b == b'@' || b == b'/' || b == b'?'
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PercentError {
+    Unexpected,
+}
+
+pub struct PercentDecoder<I: Input<Token=char>, O: Output<Token=u8>> {
+    pub output: O,
+    plus: bool,
+    p: u8,
+    state: u32,
+    input: PhantomData<I>,
+}
+
+pub struct PercentEncoder<I: Input<Token=u8>, O: Output<Token=char>> {
+    pub input: I,
+    set: PercentSet,
+    x: u8,
+    state: u32,
+    output: PhantomData<O>,
+}
+
+impl<I, O> PercentDecoder<I, O> where I: Input<Token=char>, O: Output<Token=u8> {
+    pub fn new(output: O) -> Self {
+        Self {
+            output: output,
+            plus: false,
+            p: 0,
+            state: 1,
+            input: PhantomData,
+        }
+    }
+
+    /// Decodes `+` as a space, as in form-encoded query parameters.
+    pub fn plus(mut self, plus: bool) -> Self {
+        self.plus = plus;
+        self
+    }
+
+    pub fn consume(mut self, input: &mut I) -> Result<O::Out, PercentError> where O::Err: fmt::Debug {
+        loop {
+            match self.decode(input) {
+                Done(output) => return Ok(output),
+                Fail(error) => return Err(error),
+                Cont(next) => {
+                    if input.is_out() {
+                        input.over();
+                        self = next;
+                    } else {
+                        return Err(PercentError::Unexpected);
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl<I, O> Decoder for PercentDecoder<I, O>
+    where I: Input<Token=char>,
+          O: Output<Token=u8>,
+          O::Err: fmt::Debug {
+
+    type Input = I;
+    type Output = O::Out;
+    type Error = PercentError;
+
+    fn decode(mut self, input: &mut I) -> Then<Self, O::Out, PercentError> {
+        loop {
+            match self.state {
+                1 => {
+                    match input.head() {
+                        In('%') => {
+                            input.step();
+                            self.state = 2;
+                        },
+                        In('+') if self.plus => {
+                            input.step();
+                            self.output.push(b' ');
+                        },
+                        In(c) => {
+                            input.step();
+                            let mut bytes = [0u8; 4];
+                            for &b in c.encode_utf8(&mut bytes).as_bytes() {
+                                self.output.push(b);
+                            }
+                        },
+                        Over => return Done(self.output.take_out().unwrap()),
+                        Out => return Cont(self),
+                    };
+                },
+                2 => {
+                    match input.head() {
+                        In(c) if c.is_ascii_hexdigit() => {
+                            input.step();
+                            self.p = decode_hex_char(c);
+                            self.state = 3;
+                        },
+                        In(_) | Over => return Fail(PercentError::Unexpected),
+                        Out => return Cont(self),
+                    };
+                },
+                3 => {
+                    match input.head() {
+                        In(c) if c.is_ascii_hexdigit() => {
+                            input.step();
+                            self.output.push(self.p << 4 | decode_hex_char(c));
+                            self.p = 0;
+                            self.state = 1;
+                        },
+                        In(_) | Over => return Fail(PercentError::Unexpected),
+                        Out => return Cont(self),
+                    };
+                },
+                _ => unreachable!(),
+            };
+        }
+    }
+}
+
+impl<I, O> PercentEncoder<I, O> where I: Input<Token=u8>, O: Output<Token=char> {
+    pub fn new(input: I, set: PercentSet) -> Self {
+        Self {
+            input: input,
+            set: set,
+            x: 0,
+            state: 1,
+            output: PhantomData,
+        }
+    }
+
+    pub fn produce(mut self, mut output: O) -> Result<O::Out, O::Err> {
+        loop {
+            match self.encode(&mut output) {
+                Done(_) => return output.take_out(),
+                Fail(_) => unreachable!(),
+                Cont(next) => {
+                    self = next;
+                    self.input.over();
+                }
+            }
+        }
+    }
+}
+
+impl<I, O> Encoder for PercentEncoder<I, O> where I: Input<Token=u8>, O: Output<Token=char> {
+    type Input = I;
+    type Output = O;
+    type Error = ();
+
+    fn encode(mut self, output: &mut O) -> Then<Self, I, ()> {
+        while !output.is_full() {
+            match self.state {
+                1 => {
+                    match self.input.head() {
+                        In(x) if self.set.is_literal(x) => {
+                            self.input.step();
+                            output.push(x as char);
+                        },
+                        In(x) => {
+                            self.input.step();
+                            self.x = x;
+                            self.state = 2;
+                            output.push('%');
+                        },
+                        Over => return Done(self.input),
+                        Out => break,
+                    };
+                },
+                2 => {
+                    output.push(encode_hex_digit(self.x >> 4));
+                    self.state = 3;
+                },
+                3 => {
+                    output.push(encode_hex_digit(self.x & 0x0F));
+                    self.x = 0;
+                    self.state = 1;
+                },
+                _ => unreachable!(),
+            };
+        }
+        return Cont(self);
+    }
+}
+
+#[inline]
+pub fn is_unreserved(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'-' || b == b'.' || b == b'_' || b == b'~'
+}
+
+#[inline]
+pub fn is_sub_delim(b: u8) -> bool {
+    match b {
+        b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+' | b',' | b';' | b'=' => true,
+        _ => false,
+    }
+}
+
+#[inline]
+fn decode_hex_char(c: char) -> u8 {
+    c.to_digit(16).unwrap() as u8
+}
+
+#[inline]
+fn encode_hex_digit(x: u8) -> char {
+    b"0123456789ABCDEF"[x as usize] as char
+}
+
+#[cfg(test)]
+mod tests {
+    use core::str;
+    use crate::input::AsInput;
+    use crate::output::{SliceOutput, StrOutput};
+    use super::*;
+
+    fn assert_transcodes(encoded: &str, decoded: &str, set: PercentSet) {
+        let mut buffer = [0u8; 256];
+        let decoder = PercentDecoder::new(SliceOutput::new(&mut buffer));
+        assert_eq!(str::from_utf8(decoder.consume(&mut encoded.as_input()).unwrap()).unwrap(), decoded);
+        let mut buffer = [0u8; 256];
+        let encoder = PercentEncoder::new(decoded.as_bytes().as_input(), set);
+        assert_eq!(encoder.produce(StrOutput::new(&mut buffer)).unwrap(), encoded);
+    }
+
+    #[test]
+    fn test_percent_transcode() {
+        assert_transcodes("", "", PercentSet::Component);
+        assert_transcodes("abc-._~123", "abc-._~123", PercentSet::Component);
+        assert_transcodes("a%20b%2Fc%3F", "a b/c?", PercentSet::Component);
+        assert_transcodes("a%20b/c%3F", "a b/c?", PercentSet::Path);
+        assert_transcodes("a%2Fb", "a/b", PercentSet::Segment);
+        assert_transcodes("k%3Dv%26w%2Bx/y?", "k=v&w+x/y?", PercentSet::QueryParam);
+        assert_transcodes("k=v&w+x", "k=v&w+x", PercentSet::Query);
+        assert_transcodes("caf%C3%A9", "caf\u{e9}", PercentSet::Component);
+    }
+
+    #[test]
+    fn test_percent_decode_lenient_chars() {
+        let mut buffer = [0u8; 16];
+        let decoder = PercentDecoder::new(SliceOutput::new(&mut buffer));
+        assert_eq!(decoder.consume(&mut "%c3%a9\u{e9}".as_input()).unwrap(), "\u{e9}\u{e9}".as_bytes());
+        let mut buffer = [0u8; 16];
+        let decoder = PercentDecoder::new(SliceOutput::new(&mut buffer)).plus(true);
+        assert_eq!(decoder.consume(&mut "a+b%2B".as_input()).unwrap(), b"a b+");
+    }
+
+    #[test]
+    fn test_percent_decode_malformed() {
+        let mut buffer = [0u8; 16];
+        let decoder = PercentDecoder::new(SliceOutput::new(&mut buffer));
+        assert_eq!(decoder.consume(&mut "%4".as_input()).unwrap_err(), PercentError::Unexpected);
+        let mut buffer = [0u8; 16];
+        let decoder = PercentDecoder::new(SliceOutput::new(&mut buffer));
+        assert_eq!(decoder.consume(&mut "%G0".as_input()).unwrap_err(), PercentError::Unexpected);
+    }
+}
@@ -0,0 +1,371 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::io;
+use std::vec::Vec;
+use futures_io::{AsyncRead, AsyncWrite};
+use crate::step::{Step, In, Out, Over};
+use crate::then::{Cont, Done, Fail};
+use crate::input::Input;
+use crate::output::Output;
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+
+#[derive(Debug)]
+pub enum StreamError<E> {
+    Io(io::Error),
+    Codec(E),
+    /// Codec stalled with input still available, or output not full; or
+    /// the source ended before the decoder finished.
+    Unexpected,
+}
+
+/// Reusable read buffer that feeds bytes from an `AsyncRead` to a decoder.
+/// Bytes read past the end of a decoded value remain buffered for the
+/// next decoder.
+pub struct StreamInput {
+    buffer: Vec<u8>,
+    offset: usize,
+    len: usize,
+    over: bool,
+}
+
+/// Reusable write buffer that collects bytes from an encoder until it
+/// fills, and then drains them to an `AsyncWrite`.
+pub struct StreamOutput {
+    buffer: Vec<u8>,
+    cap: usize,
+    offset: usize,
+}
+
+/// Future that decodes a value from an `AsyncRead`.
+pub struct DecodeStream<'i, R, D> {
+    reader: R,
+    input: &'i mut StreamInput,
+    decoder: Option<D>,
+}
+
+/// Future that encodes a value to an `AsyncWrite`, and flushes the writer;
+/// resolves to the encoder's input.
+pub struct EncodeSink<'o, W, E: Encoder> {
+    writer: W,
+    output: &'o mut StreamOutput,
+    encoder: Option<E>,
+    input: Option<E::Input>,
+}
+
+/// Returns a future that runs `decoder` over bytes read from `reader` into
+/// `input`, reading more whenever the decoder runs out of input.
+pub fn decode_stream<'i, R, D>(reader: R, input: &'i mut StreamInput, decoder: D) -> DecodeStream<'i, R, D>
+    where R: AsyncRead + Unpin, D: Decoder<Input=StreamInput> + Unpin {
+    DecodeStream {
+        reader: reader,
+        input: input,
+        decoder: Some(decoder),
+    }
+}
+
+/// Returns a future that runs `encoder` into `output`, writing the buffered
+/// bytes to `writer` whenever the output is full. The encoder resumes only
+/// once the writer has accepted the whole buffer.
+pub fn encode_sink<'o, W, E>(writer: W, output: &'o mut StreamOutput, encoder: E) -> EncodeSink<'o, W, E>
+    where W: AsyncWrite + Unpin, E: Encoder<Output=StreamOutput> + Unpin, E::Input: Unpin {
+    output.clear();
+    EncodeSink {
+        writer: writer,
+        output: output,
+        encoder: Some(encoder),
+        input: None,
+    }
+}
+
+impl StreamInput {
+    pub fn with_capacity(cap: usize) -> StreamInput {
+        StreamInput {
+            buffer: std::vec![0; cap],
+            offset: 0,
+            len: 0,
+            over: false,
+        }
+    }
+
+    /// Returns the buffered bytes not yet consumed by a decoder.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buffer[self.offset..self.len]
+    }
+
+    /// Reads more bytes from `reader`, marking the input over once the
+    /// reader reaches its end.
+    fn poll_fill<R>(&mut self, reader: &mut R, cx: &mut Context) -> Poll<io::Result<()>>
+        where R: AsyncRead + Unpin {
+        if self.offset == self.len {
+            self.offset = 0;
+            self.len = 0;
+        }
+        match Pin::new(reader).poll_read(cx, &mut self.buffer[self.len..]) {
+            Poll::Ready(Ok(0)) => {
+                self.over = true;
+                Poll::Ready(Ok(()))
+            },
+            Poll::Ready(Ok(count)) => {
+                self.len += count;
+                Poll::Ready(Ok(()))
+            },
+            Poll::Ready(Err(error)) => Poll::Ready(Err(error)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl Input for StreamInput {
+    type Token = u8;
+
+    #[inline]
+    fn head(&mut self) -> Step<u8> {
+        if self.offset < self.len {
+            In(self.buffer[self.offset])
+        } else if self.over {
+            Over
+        } else {
+            Out
+        }
+    }
+
+    #[inline]
+    fn step(&mut self) {
+        self.offset += 1;
+    }
+
+    #[inline]
+    fn over(&mut self) {
+        self.over = true;
+    }
+}
+
+impl StreamOutput {
+    pub fn with_capacity(cap: usize) -> StreamOutput {
+        StreamOutput {
+            buffer: Vec::with_capacity(cap),
+            cap: cap,
+            offset: 0,
+        }
+    }
+
+    fn clear(&mut self) {
+        self.buffer.clear();
+        self.offset = 0;
+    }
+
+    /// Writes the buffered bytes to `writer`, and empties the buffer once
+    /// they've all been written.
+    fn poll_drain<W>(&mut self, writer: &mut W, cx: &mut Context) -> Poll<io::Result<()>>
+        where W: AsyncWrite + Unpin {
+        while self.offset < self.buffer.len() {
+            match Pin::new(&mut *writer).poll_write(cx, &self.buffer[self.offset..]) {
+                Poll::Ready(Ok(0)) => return Poll::Ready(Err(io::ErrorKind::WriteZero.into())),
+                Poll::Ready(Ok(count)) => self.offset += count,
+                Poll::Ready(Err(error)) => return Poll::Ready(Err(error)),
+                Poll::Pending => return Poll::Pending,
+            };
+        }
+        self.clear();
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Output for StreamOutput {
+    type Token = u8;
+    type Out = ();
+    type Err = ();
+
+    #[inline]
+    fn is_full(&self) -> bool {
+        self.buffer.len() >= self.cap
+    }
+
+    #[inline]
+    fn push(&mut self, token: u8) {
+        assert!(!self.is_full());
+        self.buffer.push(token);
+    }
+
+    fn take_out(self) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+impl<'i, R, D> Future for DecodeStream<'i, R, D> where R: AsyncRead + Unpin, D: Decoder<Input=StreamInput> + Unpin {
+    type Output = Result<D::Output, StreamError<D::Error>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        loop {
+            let decoder = this.decoder.take().expect("polled after completion");
+            match decoder.decode(this.input) {
+                Done(output) => return Poll::Ready(Ok(output)),
+                Fail(error) => return Poll::Ready(Err(StreamError::Codec(error))),
+                Cont(next) => this.decoder = Some(next),
+            };
+            if !this.input.is_out() {
+                return Poll::Ready(Err(StreamError::Unexpected));
+            }
+            match this.input.poll_fill(&mut this.reader, cx) {
+                Poll::Ready(Ok(())) => (),
+                Poll::Ready(Err(error)) => return Poll::Ready(Err(StreamError::Io(error))),
+                Poll::Pending => return Poll::Pending,
+            };
+        }
+    }
+}
+
+impl<'o, W, E> Future for EncodeSink<'o, W, E>
+    where W: AsyncWrite + Unpin, E: Encoder<Output=StreamOutput> + Unpin, E::Input: Unpin {
+    type Output = Result<E::Input, StreamError<E::Error>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        loop {
+            if this.output.is_full() || this.encoder.is_none() {
+                match this.output.poll_drain(&mut this.writer, cx) {
+                    Poll::Ready(Ok(())) => (),
+                    Poll::Ready(Err(error)) => return Poll::Ready(Err(StreamError::Io(error))),
+                    Poll::Pending => return Poll::Pending,
+                };
+                if this.encoder.is_none() {
+                    return match Pin::new(&mut this.writer).poll_flush(cx) {
+                        Poll::Ready(Ok(())) => Poll::Ready(Ok(this.input.take().expect("polled after completion"))),
+                        Poll::Ready(Err(error)) => Poll::Ready(Err(StreamError::Io(error))),
+                        Poll::Pending => Poll::Pending,
+                    };
+                }
+            }
+            let encoder = this.encoder.take().unwrap();
+            match encoder.encode(this.output) {
+                Done(input) => this.input = Some(input),
+                Fail(error) => return Poll::Ready(Err(StreamError::Codec(error))),
+                Cont(next) => {
+                    if !this.output.is_full() {
+                        return Poll::Ready(Err(StreamError::Unexpected));
+                    }
+                    this.encoder = Some(next);
+                },
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::task::{RawWaker, RawWakerVTable, Waker};
+    use crate::binary::{BytesDecoder, BytesEncoder, LengthPrefix, VarintDecoder, BinaryError};
+    use crate::output::SliceOutput;
+    use super::*;
+
+    /// Reads at most `chunk` bytes per poll, and returns `Pending` before
+    /// every read.
+    struct ChunkReader<'a> {
+        data: &'a [u8],
+        chunk: usize,
+        ready: bool,
+    }
+
+    /// Accepts at most `chunk` bytes per poll, and returns `Pending` before
+    /// every write.
+    struct ChunkWriter {
+        data: Vec<u8>,
+        chunk: usize,
+        ready: bool,
+    }
+
+    impl<'a> AsyncRead for ChunkReader<'a> {
+        fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+            if !self.ready {
+                self.ready = true;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            self.ready = false;
+            let count = self.chunk.min(buf.len()).min(self.data.len());
+            buf[..count].copy_from_slice(&self.data[..count]);
+            self.data = &self.data[count..];
+            Poll::Ready(Ok(count))
+        }
+    }
+
+    impl AsyncWrite for ChunkWriter {
+        fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<io::Result<usize>> {
+            if !self.ready {
+                self.ready = true;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            self.ready = false;
+            let count = self.chunk.min(buf.len());
+            self.data.extend_from_slice(&buf[..count]);
+            Poll::Ready(Ok(count))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    /// Polls `future` to completion, counting the polls that return
+    /// `Pending`.
+    fn block_on<F: Future + Unpin>(mut future: F) -> (F::Output, usize) {
+        fn clone(_: *const ()) -> RawWaker {
+            RawWaker::new(core::ptr::null(), &VTABLE)
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        let waker = unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) };
+        let mut cx = Context::from_waker(&waker);
+        let mut pending = 0;
+        loop {
+            match Pin::new(&mut future).poll(&mut cx) {
+                Poll::Ready(output) => return (output, pending),
+                Poll::Pending => pending += 1,
+            };
+        }
+    }
+
+    #[test]
+    fn test_stream_transcode() {
+        let bytes: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+        let mut writer = ChunkWriter { data: Vec::new(), chunk: 7, ready: false };
+        let mut output = StreamOutput::with_capacity(16);
+        let encoder = BytesEncoder::new(&bytes, LengthPrefix::Varint).unwrap();
+        let (input, pending) = block_on(encode_sink(&mut writer, &mut output, encoder));
+        assert_eq!(input.unwrap(), &bytes[..]);
+        assert_eq!(writer.data.len(), 1002);
+        // Each full 16 byte buffer drains in three writes of at most 7
+        // bytes, and the final 10 bytes in two, each first returning Pending.
+        assert_eq!(pending, 62 * 3 + 2);
+
+        let mut reader = ChunkReader { data: &writer.data, chunk: 5, ready: false };
+        let mut input = StreamInput::with_capacity(64);
+        let mut buffer = std::vec![0u8; 1000];
+        let decoder = BytesDecoder::new(SliceOutput::new(&mut buffer), LengthPrefix::Varint);
+        let (decoded, _) = block_on(decode_stream(&mut reader, &mut input, decoder));
+        assert_eq!(decoded.unwrap(), &bytes[..]);
+    }
+
+    #[test]
+    fn test_stream_decode_remainder() {
+        let mut reader = ChunkReader { data: &[0xAC, 0x02, 0x05, 0x80], chunk: 64, ready: false };
+        let mut input = StreamInput::with_capacity(64);
+        let (value, _) = block_on(decode_stream(&mut reader, &mut input, VarintDecoder::new()));
+        assert_eq!(value.unwrap(), 300);
+        assert_eq!(input.as_slice(), &[0x05, 0x80]);
+        let (value, _) = block_on(decode_stream(&mut reader, &mut input, VarintDecoder::new()));
+        assert_eq!(value.unwrap(), 5);
+        match block_on(decode_stream(&mut reader, &mut input, VarintDecoder::new())).0 {
+            Err(StreamError::Codec(BinaryError::Unexpected)) => (),
+            result => panic!("{:?}", result),
+        };
+    }
+}
@@ -0,0 +1,231 @@
+//! Conformance harness for codecs: runs decoders over every way of
+//! splitting their input, and encoders into every small output size, so
+//! that tests can assert a codec's result doesn't depend on where it gets
+//! suspended. Only compiled for this crate's own tests.
+//!
+//! ```ignore
+//! # use swim_codec::base64::Base64Decoder;
+//! # use swim_codec::output::SliceOutput;
+//! # use swim_codec::testing::{for_each_chunking, decode_chunked};
+//! for_each_chunking("Zm9vYmFy", |chunking| {
+//!     let mut buffer = [0u8; 6];
+//!     let decoder = Base64Decoder::new(SliceOutput::new(&mut buffer));
+//!     let decoded = decode_chunked(decoder, "Zm9vYmFy", chunking).unwrap();
+//!     assert_eq!(decoded, b"foobar", "{:?}", chunking);
+//! });
+//! ```
+
+use crate::input::{Input, AsInput, SliceInput, StrInput};
+use crate::output::Output;
+use crate::then::{Cont, Done, Fail};
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+
+/// Number of pseudo-random chunkings run by `for_each_chunking`, in
+/// addition to every two-way split.
+pub const RANDOM_CHUNKINGS: u32 = 32;
+
+/// Largest output size run by `for_each_output_size`.
+pub const MAX_OUTPUT_SIZE: usize = 16;
+
+/// Input source that can be fed to a decoder in separate chunks.
+pub trait Chunk<'a>: Copy {
+    type Input: Input;
+
+    fn len(self) -> usize;
+
+    /// Returns `true` if the source may be split at `index`.
+    fn is_boundary(self, index: usize) -> bool;
+
+    /// Returns an input over the source between `start` and `end`.
+    fn input(self, start: usize, end: usize) -> Self::Input;
+}
+
+/// Where a `Chunk` gets split.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Chunking {
+    /// Two chunks, split at the given index.
+    Split(usize),
+    /// Chunks of one to eight tokens, pseudo-randomly sized from the given
+    /// seed.
+    Random(u32),
+}
+
+/// Output that collects tokens into a slice, but reports itself full after
+/// each `size` tokens until `refill`ed; simulates a sequence of small output
+/// buffers with a single output value.
+pub struct ChunkOutput<'a, T: 'a> {
+    slice: &'a mut [T],
+    offset: usize,
+    limit: usize,
+}
+
+impl<'a, T: 'a + Clone> Chunk<'a> for &'a [T] {
+    type Input = SliceInput<'a, T>;
+
+    fn len(self) -> usize {
+        <[T]>::len(self)
+    }
+
+    fn is_boundary(self, _index: usize) -> bool {
+        true
+    }
+
+    fn input(self, start: usize, end: usize) -> SliceInput<'a, T> {
+        self[start..end].as_input()
+    }
+}
+
+impl<'a> Chunk<'a> for &'a str {
+    type Input = StrInput<'a>;
+
+    fn len(self) -> usize {
+        str::len(self)
+    }
+
+    fn is_boundary(self, index: usize) -> bool {
+        self.is_char_boundary(index)
+    }
+
+    fn input(self, start: usize, end: usize) -> StrInput<'a> {
+        self[start..end].as_input()
+    }
+}
+
+impl Chunking {
+    /// Returns the end of the chunk that begins at `start`.
+    fn next_end<'a, S: Chunk<'a>>(&mut self, source: S, start: usize) -> usize {
+        let len = source.len();
+        let mut end = match *self {
+            Chunking::Split(index) if start < index => index,
+            Chunking::Split(_) => len,
+            Chunking::Random(ref mut seed) => {
+                *seed ^= *seed << 13;
+                *seed ^= *seed >> 17;
+                *seed ^= *seed << 5;
+                start + 1 + (*seed % 8) as usize
+            },
+        };
+        if end > len {
+            end = len;
+        }
+        while !source.is_boundary(end) {
+            end += 1;
+        }
+        end
+    }
+}
+
+impl<'a, T: 'a> ChunkOutput<'a, T> {
+    pub fn new(slice: &'a mut [T], size: usize) -> Self {
+        ChunkOutput {
+            slice: slice,
+            offset: 0,
+            limit: size,
+        }
+    }
+
+    /// Makes room for another `size` tokens.
+    pub fn refill(&mut self, size: usize) {
+        self.limit = self.offset + size;
+    }
+}
+
+impl<'a, T: 'a> Output for ChunkOutput<'a, T> {
+    type Token = T;
+    type Out = &'a mut [T];
+    type Err = ();
+
+    fn is_full(&self) -> bool {
+        self.offset >= self.limit || self.offset >= self.slice.len()
+    }
+
+    fn push(&mut self, token: T) {
+        assert!(self.offset < self.limit);
+        self.slice[self.offset] = token;
+        self.offset += 1;
+    }
+
+    fn take_out(self) -> Result<&'a mut [T], ()> {
+        Ok(&mut self.slice[..self.offset])
+    }
+}
+
+/// Calls `f` with every two-way split of `source`, and with
+/// `RANDOM_CHUNKINGS` pseudo-random chunkings.
+pub fn for_each_chunking<'a, S, F>(source: S, mut f: F) where S: Chunk<'a>, F: FnMut(Chunking) {
+    for index in 0..=source.len() {
+        if source.is_boundary(index) {
+            f(Chunking::Split(index));
+        }
+    }
+    for seed in 1..=RANDOM_CHUNKINGS {
+        f(Chunking::Random(seed.wrapping_mul(0x9E3779B9)));
+    }
+}
+
+/// Calls `f` with every output size from 1 to `MAX_OUTPUT_SIZE`.
+pub fn for_each_output_size<F>(mut f: F) where F: FnMut(usize) {
+    for size in 1..=MAX_OUTPUT_SIZE {
+        f(size);
+    }
+}
+
+/// Runs `decoder` over `source`, fed in the chunks given by `chunking`, and
+/// marks the input over after the last chunk.
+///
+/// # Panics
+///
+/// Panics if the decoder suspends before it has consumed its input, or
+/// after its input is over.
+pub fn decode_chunked<'a, S, D>(mut decoder: D, source: S, mut chunking: Chunking) -> Result<D::Output, D::Error>
+    where S: Chunk<'a>, D: Decoder<Input=S::Input> {
+    let len = source.len();
+    let mut end = chunking.next_end(source, 0);
+    let mut input = source.input(0, end);
+    loop {
+        decoder = match decoder.decode(&mut input) {
+            Done(output) => return Ok(output),
+            Fail(error) => return Err(error),
+            Cont(next) => next,
+        };
+        if input.is_in() {
+            panic!("decoder suspended with input remaining");
+        } else if input.is_over() {
+            panic!("decoder suspended after input over");
+        } else if end == len {
+            input.over();
+        } else {
+            let start = end;
+            end = chunking.next_end(source, start);
+            input = source.input(start, end);
+        }
+    }
+}
+
+/// Runs `encoder` into `slice`, with room for `size` more tokens each time
+/// the encoder runs; calls `refill` whenever the encoder suspends without
+/// filling its output, to give it more input or to end its input.
+///
+/// # Panics
+///
+/// Panics if `slice` fills before the encoder is done.
+pub fn encode_chunked<'b, T, E, F>(mut encoder: E, size: usize, slice: &'b mut [T], mut refill: F)
+    -> Result<&'b mut [T], E::Error>
+    where E: Encoder<Output=ChunkOutput<'b, T>>, F: FnMut(&mut E) {
+    let len = slice.len();
+    let mut output = ChunkOutput::new(slice, size);
+    loop {
+        encoder = match encoder.encode(&mut output) {
+            Done(_) => return Ok(output.take_out().unwrap()),
+            Fail(error) => return Err(error),
+            Cont(next) => next,
+        };
+        if output.offset == len {
+            panic!("output overflow");
+        } else if !output.is_full() {
+            refill(&mut encoder);
+        }
+        output.refill(size);
+    }
+}
@@ -0,0 +1,231 @@
+use core::fmt;
+use core::marker::PhantomData;
+use swim_core::ulid::Ulid;
+use swim_mem::alloc::{Hold, HoldError};
+use swim_structure::item::Text;
+use crate::step::{In, Out, Over};
+use crate::then::{Then, Cont, Done, Fail};
+use crate::input::{Input, AsInput};
+use crate::output::{Output, IntoOutput, StrOutput};
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+use crate::base32::{DecodeBase32, EncodeBase32, Base32Alphabet, Crockford, Base32Error};
+
+/// Number of base32 digits in the encoded form of a `Ulid`.
+const ULID_LEN: u32 = 26;
+
+/// Decodes a `Ulid` from its 26 base32 digits, most significant first; the
+/// leading digit carries only the top 3 bits, and so can't exceed 7.
+pub struct UlidDecoder<I: Input<Token=char>> {
+    alphabet: Base32Alphabet,
+    value: u128,
+    /// Number of digits read.
+    index: u32,
+    input: PhantomData<I>,
+}
+
+/// Encodes a `Ulid` as 26 base32 digits, most significant first.
+pub struct UlidEncoder<O: Output<Token=char>> {
+    ulid: Ulid,
+    alphabet: &'static [u8; 32],
+    /// Number of digits written.
+    index: u32,
+    output: PhantomData<O>,
+}
+
+impl<I> UlidDecoder<I> where I: Input<Token=char> {
+    pub fn new(alphabet: Base32Alphabet) -> Self {
+        Self {
+            alphabet: alphabet,
+            value: 0,
+            index: 0,
+            input: PhantomData,
+        }
+    }
+
+    pub fn consume(mut self, input: &mut I) -> Result<Ulid, Base32Error> {
+        loop {
+            match self.decode(input) {
+                Done(ulid) => return Ok(ulid),
+                Fail(error) => return Err(error),
+                Cont(next) => {
+                    if input.is_out() {
+                        input.over();
+                        self = next;
+                    } else {
+                        return Err(Base32Error::Unexpected);
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl<I> Decoder for UlidDecoder<I> where I: Input<Token=char> {
+    type Input = I;
+    type Output = Ulid;
+    type Error = Base32Error;
+
+    fn decode(mut self, input: &mut I) -> Then<Self, Ulid, Base32Error> {
+        while self.index < ULID_LEN {
+            match input.head() {
+                In(c) => {
+                    match self.alphabet.decode_digit(c) {
+                        Some(x) if self.index != 0 || x < 8 => {
+                            input.step();
+                            self.value = self.value << 5 | x as u128;
+                            self.index += 1;
+                        },
+                        _ => return Fail(Base32Error::Unexpected),
+                    }
+                },
+                Over => return Fail(Base32Error::Unexpected),
+                Out => return Cont(self),
+            };
+        }
+        Done(Ulid::from_u128(self.value))
+    }
+}
+
+impl<O> UlidEncoder<O> where O: Output<Token=char> {
+    pub fn new(ulid: Ulid, alphabet: Base32Alphabet) -> Self {
+        Self {
+            ulid: ulid,
+            alphabet: alphabet.as_str(),
+            index: 0,
+            output: PhantomData,
+        }
+    }
+
+    /// Writes the encoded form of the `Ulid` to `output`; stops short if
+    /// `output` fills up.
+    pub fn produce(self, mut output: O) -> Result<O::Out, O::Err> {
+        match self.encode(&mut output) {
+            Done(_) | Cont(_) => output.take_out(),
+            Fail(_) => unreachable!(),
+        }
+    }
+}
+
+impl<O> Encoder for UlidEncoder<O> where O: Output<Token=char> {
+    type Input = Ulid;
+    type Output = O;
+    type Error = ();
+
+    fn encode(mut self, output: &mut O) -> Then<Self, Ulid, ()> {
+        while self.index < ULID_LEN {
+            if output.is_full() {
+                return Cont(self);
+            }
+            let shift = 5 * (ULID_LEN - 1 - self.index);
+            let x = (self.ulid.to_u128() >> shift) as usize & 0x1F;
+            output.push(self.alphabet[x] as char);
+            self.index += 1;
+        }
+        Done(self.ulid)
+    }
+}
+
+impl DecodeBase32 for Ulid {
+    /// Decodes a `Ulid` from its 26 digits, which must make up the whole of
+    /// `input`.
+    fn decode_base32_input<I>(input: &mut I, alphabet: Base32Alphabet)
+        -> Result<Ulid, Base32Error> where I: Input<Token=char> {
+        let ulid = UlidDecoder::new(alphabet).consume(input)?;
+        if input.is_in() {
+            return Err(Base32Error::Unexpected);
+        }
+        Ok(ulid)
+    }
+
+    fn decode_base32(string: &str) -> Result<Ulid, Base32Error> {
+        Ulid::decode_base32_input(&mut string.as_input(), Crockford)
+    }
+}
+
+impl EncodeBase32 for Ulid {
+    fn encode_base32_output<O>(&self, output: O, alphabet: Base32Alphabet)
+        -> Result<O::Out, O::Err> where O: Output<Token=char> {
+        UlidEncoder::new(*self, alphabet).produce(output)
+    }
+
+    fn encode_base32<I, O>(&self, output: I) -> O::Out
+        where I: IntoOutput<IntoOut=O>, O: Output<Token=char>, O::Err: fmt::Debug {
+        self.encode_base32_output(output.into_output(), Crockford).unwrap()
+    }
+}
+
+/// Returns the Crockford base32 form of `ulid` as a `Text` value allocated
+/// in `hold`.
+pub fn try_hold_text<'a>(hold: &dyn Hold<'a>, ulid: &Ulid) -> Result<Text<'a>, HoldError> {
+    let mut buffer = [0u8; ULID_LEN as usize];
+    let string = UlidEncoder::new(*ulid, Crockford).produce(StrOutput::new(&mut buffer)).unwrap();
+    Text::try_hold_str(hold, string)
+}
+
+pub fn hold_text<'a>(hold: &dyn Hold<'a>, ulid: &Ulid) -> Text<'a> {
+    try_hold_text(hold, ulid).unwrap()
+}
+
+pub fn to_text<'a>(ulid: &Ulid) -> Text<'a> {
+    hold_text(Hold::global(), ulid)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::u128;
+    use swim_core::time::Timestamp;
+    use crate::output::StrOutput;
+    use crate::testing::{for_each_chunking, for_each_output_size, decode_chunked, encode_chunked};
+    use super::*;
+
+    const ULID: &str = "01ARZ3NDEKTSV4RRFFQ69G5FAV";
+
+    #[test]
+    fn test_ulid_decode() {
+        let ulid = Ulid::decode_base32(ULID).unwrap();
+        assert_eq!(ulid.timestamp(), Timestamp::from_millis(1_469_922_850_259));
+        assert_eq!(Ulid::decode_base32("01arz3ndektsv4rrffq69g5fav").unwrap(), ulid);
+        assert_eq!(Ulid::decode_base32("OLARZ3NDEKTSV4RRFFQ69G5FAV").unwrap(), ulid);
+        assert_eq!(Ulid::decode_base32("00000000000000000000000000").unwrap(), Ulid::NIL);
+        assert_eq!(Ulid::decode_base32("7ZZZZZZZZZZZZZZZZZZZZZZZZZ").unwrap(), Ulid::from_u128(u128::MAX));
+    }
+
+    #[test]
+    fn test_ulid_decode_fails() {
+        assert_eq!(Ulid::decode_base32("").unwrap_err(), Base32Error::Unexpected);
+        assert_eq!(Ulid::decode_base32("01ARZ3NDEKTSV4RRFFQ69G5FA").unwrap_err(), Base32Error::Unexpected);
+        assert_eq!(Ulid::decode_base32("01ARZ3NDEKTSV4RRFFQ69G5FAVX").unwrap_err(), Base32Error::Unexpected);
+        assert_eq!(Ulid::decode_base32("01ARZ3NDEKTSV4RRFFQ69G5FAU").unwrap_err(), Base32Error::Unexpected);
+        assert_eq!(Ulid::decode_base32("80000000000000000000000000").unwrap_err(), Base32Error::Unexpected);
+    }
+
+    #[test]
+    fn test_ulid_decode_chunked() {
+        let expected = Ulid::decode_base32(ULID).unwrap();
+        for_each_chunking(ULID, |chunking| {
+            let decoder = UlidDecoder::new(Crockford);
+            assert_eq!(decode_chunked(decoder, ULID, chunking).unwrap(), expected, "{:?}", chunking);
+        });
+    }
+
+    #[test]
+    fn test_ulid_encode() {
+        let ulid = Ulid::decode_base32(ULID).unwrap();
+        let mut buffer = [0u8; 26];
+        assert_eq!(ulid.encode_base32(StrOutput::new(&mut buffer)), ULID);
+        let mut buffer = [0u8; 26];
+        assert_eq!(Ulid::from_u128(u128::MAX).encode_base32(StrOutput::new(&mut buffer)), "7ZZZZZZZZZZZZZZZZZZZZZZZZZ");
+    }
+
+    #[test]
+    fn test_ulid_encode_chunked() {
+        let ulid = Ulid::decode_base32(ULID).unwrap();
+        for_each_output_size(|size| {
+            let mut buffer = ['\0'; 26];
+            let encoder = UlidEncoder::new(ulid, Crockford);
+            let output = encode_chunked(encoder, size, &mut buffer, |_| ()).unwrap();
+            assert!(output.iter().cloned().eq(ULID.chars()), "{}", size);
+        });
+    }
+}
@@ -0,0 +1,325 @@
+use core::fmt;
+use core::marker::PhantomData;
+use swim_core::uuid::Uuid;
+use swim_mem::alloc::{Hold, HoldError};
+use swim_structure::item::Text;
+use crate::step::{In, Out, Over};
+use crate::then::{Then, Cont, Done, Fail};
+use crate::input::{Input, AsInput};
+use crate::output::{Output, IntoOutput, StrOutput};
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+use crate::base16::{DecodeBase16, EncodeBase16, Base16Alphabet, Base16Lower, Base16Error};
+use crate::digest::{Digest, Sha1};
+
+/// Number of characters in the hyphenated form of a `Uuid`.
+const UUID_LEN: u32 = 36;
+
+/// Decodes a `Uuid` from its hyphenated form: 32 hexadecimal digits, of
+/// either case, in groups of 8-4-4-4-12 separated by hyphens.
+pub struct UuidDecoder<I: Input<Token=char>> {
+    bytes: [u8; 16],
+    /// Number of characters read, including hyphens.
+    index: u32,
+    input: PhantomData<I>,
+}
+
+/// Encodes a `Uuid` in its hyphenated form.
+pub struct UuidEncoder<O: Output<Token=char>> {
+    uuid: Uuid,
+    alphabet: &'static [u8; 16],
+    /// Number of characters written, including hyphens.
+    index: u32,
+    output: PhantomData<O>,
+}
+
+impl<I> UuidDecoder<I> where I: Input<Token=char> {
+    pub fn new() -> Self {
+        Self {
+            bytes: [0; 16],
+            index: 0,
+            input: PhantomData,
+        }
+    }
+
+    pub fn consume(mut self, input: &mut I) -> Result<Uuid, Base16Error> {
+        loop {
+            match self.decode(input) {
+                Done(uuid) => return Ok(uuid),
+                Fail(error) => return Err(error),
+                Cont(next) => {
+                    if input.is_out() {
+                        input.over();
+                        self = next;
+                    } else {
+                        return Err(Base16Error::Unexpected);
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl<I> Decoder for UuidDecoder<I> where I: Input<Token=char> {
+    type Input = I;
+    type Output = Uuid;
+    type Error = Base16Error;
+
+    fn decode(mut self, input: &mut I) -> Then<Self, Uuid, Base16Error> {
+        while self.index < UUID_LEN {
+            match input.head() {
+                In(c) => {
+                    if is_hyphen_index(self.index) {
+                        if c != '-' {
+                            return Fail(Base16Error::Unexpected);
+                        }
+                    } else if let Some(x) = c.to_digit(16) {
+                        let digit = digit_index(self.index);
+                        self.bytes[(digit / 2) as usize] |= (x as u8) << (4 * (1 - digit % 2));
+                    } else {
+                        return Fail(Base16Error::Unexpected);
+                    }
+                    input.step();
+                    self.index += 1;
+                },
+                Over => return Fail(Base16Error::Unexpected),
+                Out => return Cont(self),
+            };
+        }
+        Done(Uuid::from_bytes(self.bytes))
+    }
+}
+
+impl<O> UuidEncoder<O> where O: Output<Token=char> {
+    pub fn new(uuid: Uuid, alphabet: Base16Alphabet) -> Self {
+        Self {
+            uuid: uuid,
+            alphabet: alphabet.as_str(),
+            index: 0,
+            output: PhantomData,
+        }
+    }
+
+    /// Writes the hyphenated form of the `Uuid` to `output`; stops short if
+    /// `output` fills up.
+    pub fn produce(self, mut output: O) -> Result<O::Out, O::Err> {
+        match self.encode(&mut output) {
+            Done(_) | Cont(_) => output.take_out(),
+            Fail(_) => unreachable!(),
+        }
+    }
+}
+
+impl<O> Encoder for UuidEncoder<O> where O: Output<Token=char> {
+    type Input = Uuid;
+    type Output = O;
+    type Error = ();
+
+    fn encode(mut self, output: &mut O) -> Then<Self, Uuid, ()> {
+        while self.index < UUID_LEN {
+            if output.is_full() {
+                return Cont(self);
+            }
+            if is_hyphen_index(self.index) {
+                output.push('-');
+            } else {
+                let digit = digit_index(self.index);
+                let x = self.uuid.as_bytes()[(digit / 2) as usize] >> (4 * (1 - digit % 2)) & 0x0F;
+                output.push(self.alphabet[x as usize] as char);
+            }
+            self.index += 1;
+        }
+        Done(self.uuid)
+    }
+}
+
+impl DecodeBase16 for Uuid {
+    /// Decodes a `Uuid` from 32 hexadecimal digits, of either case, without
+    /// hyphens, which must make up the whole of `input`.
+    fn decode_base16_input<I>(input: &mut I) -> Result<Uuid, Base16Error> where I: Input<Token=char> {
+        let mut bytes = [0u8; 16];
+        for digit in 0..32 {
+            match input.head() {
+                In(c) => {
+                    let x = c.to_digit(16).ok_or(Base16Error::Unexpected)?;
+                    bytes[digit / 2] |= (x as u8) << (4 * (1 - digit % 2));
+                    input.step();
+                },
+                Over | Out => return Err(Base16Error::Unexpected),
+            };
+        }
+        if input.is_in() {
+            return Err(Base16Error::Unexpected);
+        }
+        Ok(Uuid::from_bytes(bytes))
+    }
+}
+
+impl EncodeBase16 for Uuid {
+    /// Encodes this `Uuid` as 32 hexadecimal digits, without hyphens; stops
+    /// short if `output` fills up.
+    fn encode_base16_output<O>(&self, mut output: O, alphabet: Base16Alphabet)
+        -> Result<O::Out, O::Err> where O: Output<Token=char> {
+        let alphabet = alphabet.as_str();
+        for digit in 0..32 {
+            if output.is_full() {
+                break;
+            }
+            let x = self.as_bytes()[digit / 2] >> (4 * (1 - digit % 2)) & 0x0F;
+            output.push(alphabet[x as usize] as char);
+        }
+        output.take_out()
+    }
+
+    /// Encodes this `Uuid` as 32 lowercase hexadecimal digits.
+    fn encode_base16<I, O>(&self, output: I) -> O::Out
+        where I: IntoOutput<IntoOut=O>, O: Output<Token=char>, O::Err: fmt::Debug {
+        self.encode_base16_output(output.into_output(), Base16Lower).unwrap()
+    }
+}
+
+/// Parses the hyphenated form of a `Uuid`, which must make up the whole of
+/// `string`.
+pub fn parse(string: &str) -> Result<Uuid, Base16Error> {
+    let mut input = string.as_input();
+    let uuid = UuidDecoder::new().consume(&mut input)?;
+    if input.is_in() {
+        return Err(Base16Error::Unexpected);
+    }
+    Ok(uuid)
+}
+
+/// Writes the canonical, lowercase hyphenated form of `uuid` to `output`.
+pub fn format<I, O>(uuid: &Uuid, output: I) -> O::Out
+    where I: IntoOutput<IntoOut=O>, O: Output<Token=char>, O::Err: fmt::Debug {
+    UuidEncoder::new(*uuid, Base16Lower).produce(output.into_output()).unwrap()
+}
+
+/// Returns the name-based (version 5) `Uuid` for `name` in `namespace`.
+pub fn uuid_v5(namespace: &Uuid, name: &[u8]) -> Uuid {
+    let mut sha1 = Sha1::new();
+    sha1.update(namespace.as_bytes());
+    sha1.update(name);
+    Uuid::from_sha1_digest(&sha1.finish())
+}
+
+/// Returns the canonical, lowercase hyphenated form of `uuid` as a `Text`
+/// value allocated in `hold`.
+pub fn try_hold_text<'a>(hold: &dyn Hold<'a>, uuid: &Uuid) -> Result<Text<'a>, HoldError> {
+    let mut buffer = [0u8; UUID_LEN as usize];
+    let string = UuidEncoder::new(*uuid, Base16Lower).produce(StrOutput::new(&mut buffer)).unwrap();
+    Text::try_hold_str(hold, string)
+}
+
+pub fn hold_text<'a>(hold: &dyn Hold<'a>, uuid: &Uuid) -> Text<'a> {
+    try_hold_text(hold, uuid).unwrap()
+}
+
+pub fn to_text<'a>(uuid: &Uuid) -> Text<'a> {
+    hold_text(Hold::global(), uuid)
+}
+
+/// Returns `true` if the character at `index` of a hyphenated `Uuid` is a
+/// group separator.
+#[inline]
+fn is_hyphen_index(index: u32) -> bool {
+    index == 8 || index == 13 || index == 18 || index == 23
+}
+
+/// Returns the position of the hexadecimal digit at character `index` of a
+/// hyphenated `Uuid`, not counting the separators that precede it.
+#[inline]
+fn digit_index(index: u32) -> u32 {
+    let hyphens = match index {
+        0..=7 => 0,
+        9..=12 => 1,
+        14..=17 => 2,
+        19..=22 => 3,
+        _ => 4,
+    };
+    index - hyphens
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::StrOutput;
+    use crate::testing::{for_each_chunking, for_each_output_size, decode_chunked, encode_chunked};
+    use crate::base16::Base16;
+    use super::*;
+
+    const UUID: &str = "6ba7b810-9dad-11d1-80b4-00c04fd430c8";
+
+    #[test]
+    fn test_uuid_parse() {
+        assert_eq!(parse(UUID).unwrap(), Uuid::NAMESPACE_DNS);
+        assert_eq!(parse("6BA7B810-9DAD-11D1-80B4-00C04FD430C8").unwrap(), Uuid::NAMESPACE_DNS);
+        assert_eq!(parse("00000000-0000-0000-0000-000000000000").unwrap(), Uuid::NIL);
+    }
+
+    #[test]
+    fn test_uuid_parse_fails() {
+        assert_eq!(parse("").unwrap_err(), Base16Error::Unexpected);
+        assert_eq!(parse("6ba7b810-9dad-11d1-80b4-00c04fd430c").unwrap_err(), Base16Error::Unexpected);
+        assert_eq!(parse("6ba7b810-9dad-11d1-80b4-00c04fd430c8a").unwrap_err(), Base16Error::Unexpected);
+        assert_eq!(parse("6ba7b8109dad11d180b400c04fd430c8").unwrap_err(), Base16Error::Unexpected);
+        assert_eq!(parse("6ba7b810-9dad-11d1-80b4_00c04fd430c8").unwrap_err(), Base16Error::Unexpected);
+        assert_eq!(parse("6ba7b810-9dad-11d1-80b4-00c04fd430cg").unwrap_err(), Base16Error::Unexpected);
+    }
+
+    #[test]
+    fn test_uuid_decode_base16() {
+        assert_eq!(Uuid::decode_base16("6ba7b8109dad11d180b400c04fd430c8").unwrap(), Uuid::NAMESPACE_DNS);
+        assert_eq!(Uuid::decode_base16("6BA7B8109DAD11D180B400C04FD430C8").unwrap(), Uuid::NAMESPACE_DNS);
+        assert_eq!(Uuid::decode_base16("00000000000000000000000000000000").unwrap(), Uuid::NIL);
+        assert_eq!(Uuid::decode_base16("").unwrap_err(), Base16Error::Unexpected);
+        assert_eq!(Uuid::decode_base16("6ba7b8109dad11d180b400c04fd430c").unwrap_err(), Base16Error::Unexpected);
+        assert_eq!(Uuid::decode_base16("6ba7b8109dad11d180b400c04fd430c8a").unwrap_err(), Base16Error::Unexpected);
+        assert_eq!(Uuid::decode_base16(UUID).unwrap_err(), Base16Error::Unexpected);
+        assert_eq!(Uuid::decode_base16("6ba7b8109dad11d180b400c04fd430cg").unwrap_err(), Base16Error::Unexpected);
+    }
+
+    #[test]
+    fn test_uuid_decode_chunked() {
+        for_each_chunking(UUID, |chunking| {
+            let decoder = UuidDecoder::new();
+            assert_eq!(decode_chunked(decoder, UUID, chunking).unwrap(), Uuid::NAMESPACE_DNS, "{:?}", chunking);
+        });
+    }
+
+    #[test]
+    fn test_uuid_format() {
+        let mut buffer = [0u8; 36];
+        assert_eq!(format(&Uuid::NAMESPACE_DNS, StrOutput::new(&mut buffer)), UUID);
+        let mut buffer = [0u8; 36];
+        assert_eq!(format(&Uuid::NIL, StrOutput::new(&mut buffer)), "00000000-0000-0000-0000-000000000000");
+    }
+
+    #[test]
+    fn test_uuid_encode_base16() {
+        let mut buffer = [0u8; 32];
+        assert_eq!(Uuid::NAMESPACE_DNS.encode_base16(StrOutput::new(&mut buffer)), "6ba7b8109dad11d180b400c04fd430c8");
+        let mut buffer = [0u8; 32];
+        assert_eq!(Uuid::NAMESPACE_DNS.encode_base16_output(StrOutput::new(&mut buffer), Base16).unwrap(), "6BA7B8109DAD11D180B400C04FD430C8");
+        let mut buffer = [0u8; 32];
+        assert_eq!(Uuid::NIL.encode_base16(StrOutput::new(&mut buffer)), "00000000000000000000000000000000");
+    }
+
+    #[test]
+    fn test_uuid_encode_chunked() {
+        for_each_output_size(|size| {
+            let mut buffer = ['\0'; 36];
+            let encoder = UuidEncoder::new(Uuid::NAMESPACE_DNS, Base16);
+            let output = encode_chunked(encoder, size, &mut buffer, |_| ()).unwrap();
+            let expected = "6BA7B810-9DAD-11D1-80B4-00C04FD430C8";
+            assert!(output.iter().cloned().eq(expected.chars()), "{}", size);
+        });
+    }
+
+    #[test]
+    fn test_uuid_v5() {
+        // RFC 9562, Appendix A.4
+        let uuid = uuid_v5(&Uuid::NAMESPACE_DNS, b"www.example.com");
+        assert_eq!(uuid, parse("2ed6657d-e927-568b-95e1-2665a8aea6a2").unwrap());
+        assert_eq!(uuid.version(), Some(5));
+    }
+}
@@ -0,0 +1,703 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::str;
+use crate::step::{In, Out, Over};
+use crate::then::{Then, Cont, Done, Fail};
+use crate::input::{Input, AsInput};
+use crate::output::Output;
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+use crate::base64::{Base64, Base64Encoder};
+use crate::digest::{Digest, Sha1};
+
+/// GUID appended to a client's `Sec-WebSocket-Key` to form the accept key.
+const WS_ACCEPT_GUID: &'static [u8] = b"258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WsOpcode {
+    Continuation,
+    Text,
+    Binary,
+    Close,
+    Ping,
+    Pong,
+}
+
+impl WsOpcode {
+    pub fn from_u8(code: u8) -> Option<WsOpcode> {
+        match code {
+            0x0 => Some(WsOpcode::Continuation),
+            0x1 => Some(WsOpcode::Text),
+            0x2 => Some(WsOpcode::Binary),
+            0x8 => Some(WsOpcode::Close),
+            0x9 => Some(WsOpcode::Ping),
+            0xA => Some(WsOpcode::Pong),
+            _ => None,
+        }
+    }
+
+    pub fn to_u8(self) -> u8 {
+        match self {
+            WsOpcode::Continuation => 0x0,
+            WsOpcode::Text => 0x1,
+            WsOpcode::Binary => 0x2,
+            WsOpcode::Close => 0x8,
+            WsOpcode::Ping => 0x9,
+            WsOpcode::Pong => 0xA,
+        }
+    }
+
+    pub fn is_control(self) -> bool {
+        self.to_u8() & 0x8 != 0
+    }
+}
+
+/// The side of the connection doing the framing; clients mask the frames
+/// they send, servers never do.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WsRole {
+    Client,
+    Server,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct WsStatus(pub u16);
+
+impl WsStatus {
+    pub const NORMAL: WsStatus = WsStatus(1000);
+    pub const GOING_AWAY: WsStatus = WsStatus(1001);
+    pub const PROTOCOL_ERROR: WsStatus = WsStatus(1002);
+    pub const UNSUPPORTED_DATA: WsStatus = WsStatus(1003);
+    pub const NO_STATUS: WsStatus = WsStatus(1005);
+    pub const ABNORMAL: WsStatus = WsStatus(1006);
+    pub const INVALID_PAYLOAD: WsStatus = WsStatus(1007);
+    pub const POLICY_VIOLATION: WsStatus = WsStatus(1008);
+    pub const MESSAGE_TOO_BIG: WsStatus = WsStatus(1009);
+    pub const MANDATORY_EXTENSION: WsStatus = WsStatus(1010);
+    pub const INTERNAL_ERROR: WsStatus = WsStatus(1011);
+
+    /// Returns `true` if this status may be sent in a close frame.
+    pub fn is_valid(self) -> bool {
+        match self.0 {
+            1000..=1003 | 1007..=1011 | 3000..=4999 => true,
+            _ => false,
+        }
+    }
+
+    /// Splits a close frame `payload` into its status code and UTF-8 reason;
+    /// returns `None` for an empty payload.
+    pub fn decode_close(payload: &[u8]) -> Result<Option<(WsStatus, &str)>, WsError> {
+        if payload.is_empty() {
+            return Ok(None);
+        } else if payload.len() < 2 {
+            return Err(WsError::Status);
+        }
+        let status = WsStatus((payload[0] as u16) << 8 | payload[1] as u16);
+        if !status.is_valid() {
+            return Err(WsError::Status);
+        }
+        match str::from_utf8(&payload[2..]) {
+            Ok(reason) => Ok(Some((status, reason))),
+            Err(_) => Err(WsError::Utf8),
+        }
+    }
+
+    /// Writes a close frame payload carrying this status and `reason`.
+    pub fn encode_close<O>(self, reason: &str, output: &mut O) where O: Output<Token=u8> {
+        output.push((self.0 >> 8) as u8);
+        output.push(self.0 as u8);
+        for &b in reason.as_bytes() {
+            output.push(b);
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WsError {
+    Reserved,
+    Opcode,
+    Masking,
+    Control,
+    Length,
+    Oversized,
+    Continuation,
+    Truncated,
+    Status,
+    Utf8,
+}
+
+/// Connection-level framing state that outlives individual frames: the
+/// endpoint role, the message size limit, and any fragmented message in
+/// progress.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct WsFraming {
+    role: WsRole,
+    max_message: u64,
+    fragment: Option<WsOpcode>,
+    fragment_len: u64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct WsFrame<T> {
+    pub fin: bool,
+    pub opcode: WsOpcode,
+    pub payload: T,
+}
+
+/// Decodes a single frame, writing its unmasked payload to `output`.
+pub struct WsFrameDecoder<'a, I: Input<Token=u8>, O: Output<Token=u8>> {
+    framing: &'a mut WsFraming,
+    pub output: O,
+    fin: bool,
+    opcode: WsOpcode,
+    masked: bool,
+    mask: [u8; 4],
+    len: u64,
+    min_len: u64,
+    offset: u64,
+    count: u32,
+    state: u32,
+    input: PhantomData<I>,
+}
+
+/// Encodes a single frame of `len` payload bytes read from `input`.
+pub struct WsFrameEncoder<I: Input<Token=u8>, O: Output<Token=u8>> {
+    pub input: I,
+    fin: bool,
+    opcode: WsOpcode,
+    masked: bool,
+    mask: [u8; 4],
+    len: u64,
+    offset: u64,
+    header: [u8; 14],
+    header_len: u32,
+    count: u32,
+    state: u32,
+    output: PhantomData<O>,
+}
+
+impl WsFraming {
+    pub fn new(role: WsRole) -> Self {
+        Self {
+            role: role,
+            max_message: 16 * 1024 * 1024,
+            fragment: None,
+            fragment_len: 0,
+        }
+    }
+
+    /// Limits the total payload length of a message across all of its
+    /// fragments.
+    pub fn max_message(mut self, max_message: u64) -> Self {
+        self.max_message = max_message;
+        self
+    }
+
+    pub fn role(&self) -> WsRole {
+        self.role
+    }
+
+    /// Returns the opcode of the fragmented message in progress, if any.
+    pub fn fragment(&self) -> Option<WsOpcode> {
+        self.fragment
+    }
+
+    pub fn decoder<I, O>(&mut self, output: O) -> WsFrameDecoder<'_, I, O>
+        where I: Input<Token=u8>, O: Output<Token=u8> {
+        WsFrameDecoder::new(self, output)
+    }
+
+    fn accept(&mut self, fin: bool, opcode: WsOpcode, len: u64) -> Result<(), WsError> {
+        if opcode.is_control() {
+            if !fin || len > 125 {
+                return Err(WsError::Control);
+            }
+            return Ok(());
+        }
+        let (message, message_len) = match (self.fragment, opcode) {
+            (Some(message), WsOpcode::Continuation) => (message, self.fragment_len.saturating_add(len)),
+            (None, WsOpcode::Continuation) | (Some(_), _) => return Err(WsError::Continuation),
+            (None, message) => (message, len),
+        };
+        if message_len > self.max_message {
+            return Err(WsError::Oversized);
+        }
+        if fin {
+            self.fragment = None;
+            self.fragment_len = 0;
+        } else {
+            self.fragment = Some(message);
+            self.fragment_len = message_len;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, I, O> WsFrameDecoder<'a, I, O> where I: Input<Token=u8>, O: Output<Token=u8> {
+    pub fn new(framing: &'a mut WsFraming, output: O) -> Self {
+        Self {
+            framing: framing,
+            output: output,
+            fin: false,
+            opcode: WsOpcode::Continuation,
+            masked: false,
+            mask: [0; 4],
+            len: 0,
+            min_len: 0,
+            offset: 0,
+            count: 0,
+            state: 1,
+            input: PhantomData,
+        }
+    }
+
+    pub fn consume(mut self, input: &mut I) -> Result<WsFrame<O::Out>, WsError> where O::Err: fmt::Debug {
+        loop {
+            match self.decode(input) {
+                Done(frame) => return Ok(frame),
+                Fail(error) => return Err(error),
+                Cont(next) => {
+                    if input.is_out() {
+                        input.over();
+                        self = next;
+                    } else {
+                        return Err(WsError::Truncated);
+                    }
+                },
+            }
+        }
+    }
+
+    fn accept(&mut self) -> Result<(), WsError> {
+        if self.masked != (self.framing.role == WsRole::Server) {
+            return Err(WsError::Masking);
+        }
+        self.framing.accept(self.fin, self.opcode, self.len)
+    }
+}
+
+impl<'a, I, O> Decoder for WsFrameDecoder<'a, I, O>
+    where I: Input<Token=u8>,
+          O: Output<Token=u8>,
+          O::Err: fmt::Debug {
+
+    type Input = I;
+    type Output = WsFrame<O::Out>;
+    type Error = WsError;
+
+    fn decode(mut self, input: &mut I) -> Then<Self, WsFrame<O::Out>, WsError> {
+        loop {
+            match self.state {
+                1 => {
+                    match input.head() {
+                        In(b) => {
+                            input.step();
+                            if b & 0x70 != 0 {
+                                return Fail(WsError::Reserved);
+                            }
+                            self.fin = b & 0x80 != 0;
+                            self.opcode = match WsOpcode::from_u8(b & 0x0F) {
+                                Some(opcode) => opcode,
+                                None => return Fail(WsError::Opcode),
+                            };
+                            self.state = 2;
+                        },
+                        Over => return Fail(WsError::Truncated),
+                        Out => return Cont(self),
+                    };
+                },
+                2 => {
+                    match input.head() {
+                        In(b) => {
+                            input.step();
+                            self.masked = b & 0x80 != 0;
+                            match b & 0x7F {
+                                126 => {
+                                    self.min_len = 126;
+                                    self.count = 2;
+                                    self.state = 3;
+                                },
+                                127 => {
+                                    self.min_len = 0x10000;
+                                    self.count = 8;
+                                    self.state = 3;
+                                },
+                                len => {
+                                    self.len = len as u64;
+                                    self.state = 4;
+                                },
+                            };
+                        },
+                        Over => return Fail(WsError::Truncated),
+                        Out => return Cont(self),
+                    };
+                },
+                3 => {
+                    match input.head() {
+                        In(b) => {
+                            input.step();
+                            self.len = self.len << 8 | b as u64;
+                            self.count -= 1;
+                            if self.count == 0 {
+                                if self.len < self.min_len || self.len >> 63 != 0 {
+                                    return Fail(WsError::Length);
+                                }
+                                self.state = 4;
+                            }
+                        },
+                        Over => return Fail(WsError::Truncated),
+                        Out => return Cont(self),
+                    };
+                },
+                4 => {
+                    if let Err(error) = self.accept() {
+                        return Fail(error);
+                    }
+                    self.count = 0;
+                    self.state = if self.masked { 5 } else { 6 };
+                },
+                5 => {
+                    match input.head() {
+                        In(b) => {
+                            input.step();
+                            self.mask[self.count as usize] = b;
+                            self.count += 1;
+                            if self.count == 4 {
+                                self.state = 6;
+                            }
+                        },
+                        Over => return Fail(WsError::Truncated),
+                        Out => return Cont(self),
+                    };
+                },
+                6 => {
+                    while self.offset < self.len {
+                        match input.head() {
+                            In(_) if self.output.is_full() => return Fail(WsError::Oversized),
+                            In(b) => {
+                                input.step();
+                                self.output.push(b ^ self.mask[(self.offset & 3) as usize]);
+                                self.offset += 1;
+                            },
+                            Over => return Fail(WsError::Truncated),
+                            Out => return Cont(self),
+                        };
+                    }
+                    return Done(WsFrame {
+                        fin: self.fin,
+                        opcode: self.opcode,
+                        payload: self.output.take_out().unwrap(),
+                    });
+                },
+                _ => unreachable!(),
+            };
+        }
+    }
+}
+
+impl<I, O> WsFrameEncoder<I, O> where I: Input<Token=u8>, O: Output<Token=u8> {
+    pub fn new(input: I, opcode: WsOpcode, len: u64) -> Self {
+        Self {
+            input: input,
+            fin: true,
+            opcode: opcode,
+            masked: false,
+            mask: [0; 4],
+            len: len,
+            offset: 0,
+            header: [0; 14],
+            header_len: 0,
+            count: 0,
+            state: 1,
+            output: PhantomData,
+        }
+    }
+
+    pub fn fin(mut self, fin: bool) -> Self {
+        self.fin = fin;
+        self
+    }
+
+    /// Masks the payload with `mask`, as required of client frames.
+    pub fn mask(mut self, mask: [u8; 4]) -> Self {
+        self.masked = true;
+        self.mask = mask;
+        self
+    }
+
+    pub fn produce(mut self, mut output: O) -> Result<O::Out, WsError> where O::Err: fmt::Debug {
+        loop {
+            match self.encode(&mut output) {
+                Done(_) => return Ok(output.take_out().unwrap()),
+                Fail(error) => return Err(error),
+                Cont(next) => {
+                    self = next;
+                    self.input.over();
+                }
+            }
+        }
+    }
+
+    fn encode_header(&mut self) {
+        let mut n = 0;
+        self.header[n] = if self.fin { 0x80 } else { 0 } | self.opcode.to_u8();
+        n += 1;
+        let mask_bit = if self.masked { 0x80 } else { 0 };
+        if self.len < 126 {
+            self.header[n] = mask_bit | self.len as u8;
+            n += 1;
+        } else if self.len <= 0xFFFF {
+            self.header[n] = mask_bit | 126;
+            self.header[n + 1] = (self.len >> 8) as u8;
+            self.header[n + 2] = self.len as u8;
+            n += 3;
+        } else {
+            self.header[n] = mask_bit | 127;
+            n += 1;
+            let mut shift = 56;
+            while n < 10 {
+                self.header[n] = (self.len >> shift) as u8;
+                shift -= 8;
+                n += 1;
+            }
+        }
+        if self.masked {
+            self.header[n..n + 4].copy_from_slice(&self.mask);
+            n += 4;
+        }
+        self.header_len = n as u32;
+    }
+}
+
+impl<I, O> Encoder for WsFrameEncoder<I, O> where I: Input<Token=u8>, O: Output<Token=u8> {
+    type Input = I;
+    type Output = O;
+    type Error = WsError;
+
+    fn encode(mut self, output: &mut O) -> Then<Self, I, WsError> {
+        while !output.is_full() {
+            match self.state {
+                1 => {
+                    if self.opcode.is_control() && (!self.fin || self.len > 125) {
+                        return Fail(WsError::Control);
+                    } else if self.len >> 63 != 0 {
+                        return Fail(WsError::Length);
+                    }
+                    self.encode_header();
+                    self.state = 2;
+                },
+                2 => {
+                    output.push(self.header[self.count as usize]);
+                    self.count += 1;
+                    if self.count == self.header_len {
+                        self.state = 3;
+                    }
+                },
+                3 => {
+                    if self.offset == self.len {
+                        return Done(self.input);
+                    }
+                    match self.input.head() {
+                        In(b) => {
+                            self.input.step();
+                            let m = if self.masked { self.mask[(self.offset & 3) as usize] } else { 0 };
+                            output.push(b ^ m);
+                            self.offset += 1;
+                        },
+                        Over => return Fail(WsError::Truncated),
+                        Out => break,
+                    };
+                },
+                _ => unreachable!(),
+            };
+        }
+        if self.state == 3 && self.offset == self.len {
+            return Done(self.input);
+        }
+        return Cont(self);
+    }
+}
+
+/// Computes the `Sec-WebSocket-Accept` value for a client's
+/// `Sec-WebSocket-Key`.
+pub fn ws_accept_key<O>(key: &str, output: O) -> Result<O::Out, O::Err> where O: Output<Token=char> {
+    let mut sha1 = Sha1::new();
+    sha1.update(key.as_bytes());
+    sha1.update(WS_ACCEPT_GUID);
+    let digest = sha1.finish();
+    Base64Encoder::new(digest[..].as_input(), Base64).produce(output)
+}
+
+/// Computes a `Sec-WebSocket-Key` from 16 random `nonce` bytes.
+pub fn ws_client_key<O>(nonce: &[u8; 16], output: O) -> Result<O::Out, O::Err> where O: Output<Token=char> {
+    Base64Encoder::new(nonce[..].as_input(), Base64).produce(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::SliceInput;
+    use crate::output::{SliceOutput, StrOutput};
+    use super::*;
+
+    fn assert_decodes(encoded: &[u8], role: WsRole, fin: bool, opcode: WsOpcode, payload: &[u8]) {
+        for split in 0..=encoded.len() {
+            let mut framing = WsFraming::new(role);
+            let mut buffer = [0u8; 1024];
+            let decoder = framing.decoder(SliceOutput::new(&mut buffer));
+            let frame = match decoder.decode(&mut encoded[..split].as_input()) {
+                Cont(decoder) => decoder.consume(&mut encoded[split..].as_input()).unwrap(),
+                Done(frame) => frame,
+                Fail(error) => panic!("{:?}", error),
+            };
+            assert_eq!(frame.fin, fin);
+            assert_eq!(frame.opcode, opcode);
+            assert_eq!(frame.payload, payload);
+        }
+    }
+
+    fn assert_encodes(encoded: &[u8], encoder: WsFrameEncoder<SliceInput<u8>, SliceOutput<u8>>) {
+        let mut buffer = [0u8; 1024];
+        assert_eq!(encoder.produce(SliceOutput::new(&mut buffer)).unwrap(), encoded);
+    }
+
+    fn assert_decode_fails(encoded: &[u8], framing: &mut WsFraming, error: WsError) {
+        let mut buffer = [0u8; 1024];
+        let decoder = framing.decoder(SliceOutput::new(&mut buffer));
+        assert_eq!(decoder.consume(&mut encoded.as_input()).unwrap_err(), error);
+    }
+
+    #[test]
+    fn test_ws_rfc6455_examples() {
+        let unmasked = &[0x81, 0x05, 0x48, 0x65, 0x6c, 0x6c, 0x6f];
+        let masked = &[0x81, 0x85, 0x37, 0xfa, 0x21, 0x3d, 0x7f, 0x9f, 0x4d, 0x51, 0x58];
+        assert_decodes(unmasked, WsRole::Client, true, WsOpcode::Text, b"Hello");
+        assert_decodes(masked, WsRole::Server, true, WsOpcode::Text, b"Hello");
+        assert_encodes(unmasked, WsFrameEncoder::new(b"Hello"[..].as_input(), WsOpcode::Text, 5));
+        assert_encodes(masked, WsFrameEncoder::new(b"Hello"[..].as_input(), WsOpcode::Text, 5)
+                                   .mask([0x37, 0xfa, 0x21, 0x3d]));
+
+        let ping = &[0x89, 0x05, 0x48, 0x65, 0x6c, 0x6c, 0x6f];
+        let pong = &[0x8a, 0x85, 0x37, 0xfa, 0x21, 0x3d, 0x7f, 0x9f, 0x4d, 0x51, 0x58];
+        assert_decodes(ping, WsRole::Client, true, WsOpcode::Ping, b"Hello");
+        assert_decodes(pong, WsRole::Server, true, WsOpcode::Pong, b"Hello");
+    }
+
+    #[test]
+    fn test_ws_extended_lengths() {
+        let payload = [0x5Au8; 256];
+        let mut encoded = [0u8; 260];
+        encoded[..4].copy_from_slice(&[0x82, 0x7E, 0x01, 0x00]);
+        encoded[4..].copy_from_slice(&payload);
+        assert_encodes(&encoded, WsFrameEncoder::new(payload[..].as_input(), WsOpcode::Binary, 256));
+
+        let mut framing = WsFraming::new(WsRole::Client);
+        let mut buffer = [0u8; 256];
+        let frame = framing.decoder(SliceOutput::new(&mut buffer)).consume(&mut encoded[..].as_input()).unwrap();
+        assert_eq!(frame.payload, &payload[..]);
+
+        let header = &[0x82, 0x7F, 0, 0, 0, 0, 0, 1, 0, 0];
+        let mut buffer = [0u8; 10];
+        let encoder = WsFrameEncoder::<_, SliceOutput<u8>>::new([0u8; 0][..].as_input(), WsOpcode::Binary, 0x10000);
+        match encoder.encode(&mut SliceOutput::new(&mut buffer)) {
+            Cont(_) => assert_eq!(&buffer, header),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn test_ws_non_minimal_length() {
+        let mut framing = WsFraming::new(WsRole::Client);
+        assert_decode_fails(&[0x82, 0x7E, 0x00, 0x05], &mut framing, WsError::Length);
+        let mut framing = WsFraming::new(WsRole::Client);
+        assert_decode_fails(&[0x82, 0x7F, 0, 0, 0, 0, 0, 0, 0xFF, 0xFF], &mut framing, WsError::Length);
+        let mut framing = WsFraming::new(WsRole::Client);
+        assert_decode_fails(&[0x82, 0x7F, 0x80, 0, 0, 0, 0, 0, 0, 0], &mut framing, WsError::Length);
+    }
+
+    #[test]
+    fn test_ws_fragmentation() {
+        let mut framing = WsFraming::new(WsRole::Client);
+        let mut buffer = [0u8; 16];
+        let first = framing.decoder(SliceOutput::new(&mut buffer))
+                           .consume(&mut [0x01, 0x03, 0x48, 0x65, 0x6c][..].as_input()).unwrap();
+        assert_eq!((first.fin, first.opcode, &*first.payload), (false, WsOpcode::Text, &b"Hel"[..]));
+        assert_eq!(framing.fragment(), Some(WsOpcode::Text));
+
+        let mut buffer = [0u8; 16];
+        let ping = framing.decoder(SliceOutput::new(&mut buffer))
+                          .consume(&mut [0x89, 0x00][..].as_input()).unwrap();
+        assert_eq!(ping.opcode, WsOpcode::Ping);
+        assert_eq!(framing.fragment(), Some(WsOpcode::Text));
+
+        assert_decode_fails(&[0x81, 0x00], &mut framing, WsError::Continuation);
+
+        let mut buffer = [0u8; 16];
+        let last = framing.decoder(SliceOutput::new(&mut buffer))
+                          .consume(&mut [0x80, 0x02, 0x6c, 0x6f][..].as_input()).unwrap();
+        assert_eq!((last.fin, last.opcode, &*last.payload), (true, WsOpcode::Continuation, &b"lo"[..]));
+        assert_eq!(framing.fragment(), None);
+
+        assert_decode_fails(&[0x80, 0x00], &mut framing, WsError::Continuation);
+    }
+
+    #[test]
+    fn test_ws_protocol_errors() {
+        let mut framing = WsFraming::new(WsRole::Server);
+        assert_decode_fails(&[0x81, 0x00], &mut framing, WsError::Masking);
+        let mut framing = WsFraming::new(WsRole::Client);
+        assert_decode_fails(&[0x81, 0x80, 0, 0, 0, 0], &mut framing, WsError::Masking);
+        assert_decode_fails(&[0xC1, 0x00], &mut framing, WsError::Reserved);
+        assert_decode_fails(&[0x83, 0x00], &mut framing, WsError::Opcode);
+        assert_decode_fails(&[0x09, 0x00], &mut framing, WsError::Control);
+        assert_decode_fails(&[0x89, 0x7E, 0x00, 0x7E], &mut framing, WsError::Control);
+        assert_decode_fails(&[0x81, 0x05, 0x48], &mut framing, WsError::Truncated);
+    }
+
+    #[test]
+    fn test_ws_max_message() {
+        let mut framing = WsFraming::new(WsRole::Client).max_message(4);
+        let mut buffer = [0u8; 16];
+        framing.decoder(SliceOutput::new(&mut buffer))
+               .consume(&mut [0x02, 0x03, 1, 2, 3][..].as_input()).unwrap();
+        assert_decode_fails(&[0x80, 0x02, 4, 5], &mut framing, WsError::Oversized);
+        let mut framing = WsFraming::new(WsRole::Client).max_message(4);
+        assert_decode_fails(&[0x82, 0x05, 1, 2, 3, 4, 5], &mut framing, WsError::Oversized);
+    }
+
+    #[test]
+    fn test_ws_decode_frame_too_large() {
+        let mut framing = WsFraming::new(WsRole::Client);
+        let mut buffer = [0u8; 4];
+        let decoder = framing.decoder(SliceOutput::new(&mut buffer));
+        assert_eq!(decoder.consume(&mut [0x82, 0x05, 1, 2, 3, 4, 5][..].as_input()).unwrap_err(),
+                   WsError::Oversized);
+    }
+
+    #[test]
+    fn test_ws_close_payload() {
+        let mut buffer = [0u8; 16];
+        let mut output = SliceOutput::new(&mut buffer);
+        WsStatus::NORMAL.encode_close("bye", &mut output);
+        let payload = output.take_out().unwrap();
+        assert_eq!(payload, &[0x03, 0xE8, b'b', b'y', b'e']);
+        assert_eq!(WsStatus::decode_close(payload).unwrap(), Some((WsStatus::NORMAL, "bye")));
+        assert_eq!(WsStatus::decode_close(&[]).unwrap(), None);
+        assert_eq!(WsStatus::decode_close(&[0x03]).unwrap_err(), WsError::Status);
+        assert_eq!(WsStatus::decode_close(&[0x03, 0xED]).unwrap_err(), WsError::Status);
+        assert_eq!(WsStatus::decode_close(&[0x03, 0xE8, 0xFF]).unwrap_err(), WsError::Utf8);
+
+        let mut buffer = [0u8; 16];
+        let encoder = WsFrameEncoder::<_, SliceOutput<u8>>::new(b"x"[..].as_input(), WsOpcode::Close, 1).fin(false);
+        assert_eq!(encoder.produce(SliceOutput::new(&mut buffer)).unwrap_err(), WsError::Control);
+    }
+
+    #[test]
+    fn test_ws_handshake_keys() {
+        let mut buffer = [0u8; 64];
+        assert_eq!(ws_accept_key("dGhlIHNhbXBsZSBub25jZQ==", StrOutput::new(&mut buffer)).unwrap(),
+                   "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+        let mut buffer = [0u8; 64];
+        assert_eq!(ws_client_key(b"the sample nonce", StrOutput::new(&mut buffer)).unwrap(),
+                   "dGhlIHNhbXBsZSBub25jZQ==");
+    }
+}
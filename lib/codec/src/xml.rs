@@ -0,0 +1,650 @@
+use core::char;
+use core::fmt::{self, Write};
+use core::marker::PhantomData;
+use core::str;
+use swim_mem::alloc::{Hold, Holder, HoldError};
+use swim_mem::lease::RawBuf;
+use swim_structure::item::{Item, Attr, Value, Record, Text};
+use crate::step::{In, Out, Over};
+use crate::then::{Then, Cont, Done, Fail};
+use crate::input::Input;
+use crate::output::Output;
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+use crate::csv::write_num;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum XmlError {
+    /// Malformed markup, text outside the root element, or unexpected end
+    /// of input.
+    Unexpected,
+    /// End tag that doesn't match the open element.
+    Mismatch,
+    /// Malformed or unknown entity reference.
+    Entity,
+    /// Name with an undeclared or malformed namespace prefix.
+    Namespace,
+    /// Value that doesn't have the shape of an element.
+    Shape,
+    Hold(HoldError),
+}
+
+impl From<HoldError> for XmlError {
+    fn from(error: HoldError) -> XmlError {
+        XmlError::Hold(error)
+    }
+}
+
+/// Decodes the root element of an XML 1.0 document into a `Value`, using
+/// the same mapping as Recon markup: each element becomes a record whose
+/// first item is an attribute named by the element's tag, with the
+/// element's XML attributes as slots of its parameters, followed by the
+/// element's text and child elements. Namespace prefixes are kept in the
+/// qualified names, and must be declared. Comments, processing
+/// instructions, the document type declaration, and whitespace between
+/// elements are skipped. Completes as soon as the root element ends.
+pub struct XmlDecoder<'a, I: Input<Token=char>> {
+    /// Raw text or markup since the last markup boundary.
+    raw: RawBuf<'a, u8>,
+    /// Unescaped text content not yet added to the open element.
+    text: RawBuf<'a, u8>,
+    /// Open elements, outermost first.
+    stack: Record<'a>,
+    state: u32,
+    input: PhantomData<I>,
+}
+
+/// Writes an element shaped `Value` into a buffer allocated in a `Hold`,
+/// and streams the buffered XML to a `char` output.
+pub struct XmlEncoder<'a, 'b, O: Output<Token=char>> {
+    source: &'b Value<'a>,
+    string: RawBuf<'a, u8>,
+    offset: usize,
+    output: PhantomData<O>,
+}
+
+/// Adapts a `RawBuf` to `fmt::Write`, remembering the allocation error
+/// that aborted a write.
+struct XmlBuffer<'a, 'r> {
+    string: &'r mut RawBuf<'a, u8>,
+    error: Option<HoldError>,
+}
+
+impl<'a, I> XmlDecoder<'a, I> where I: Input<Token=char> {
+    pub fn try_hold(hold: &dyn Hold<'a>) -> Result<Self, HoldError> {
+        Ok(Self {
+            raw: RawBuf::try_hold_empty(hold)?,
+            text: RawBuf::try_hold_empty(hold)?,
+            stack: Record::try_hold_slice(hold, &[])?,
+            state: 0,
+            input: PhantomData,
+        })
+    }
+
+    pub fn hold(hold: &dyn Hold<'a>) -> Self {
+        Self::try_hold(hold).unwrap()
+    }
+
+    pub fn consume(mut self, input: &mut I) -> Result<Value<'a>, XmlError> {
+        loop {
+            match self.decode(input) {
+                Done(value) => return Ok(value),
+                Fail(error) => return Err(error),
+                Cont(next) => {
+                    if input.is_out() {
+                        input.over();
+                        self = next;
+                    } else {
+                        return Err(XmlError::Unexpected);
+                    }
+                },
+            }
+        }
+    }
+
+    /// Returns `true` if the buffered markup ends before `c`.
+    fn is_markup_end(&self, c: char) -> bool {
+        let markup = &self.raw[..];
+        if c != '>' {
+            false
+        } else if markup.starts_with(b"!--") {
+            markup.len() >= 5 && markup.ends_with(b"--")
+        } else if markup.starts_with(b"![CDATA[") {
+            markup.ends_with(b"]]")
+        } else if markup.starts_with(b"!") {
+            let open = markup.iter().filter(|&&b| b == b'[').count();
+            open == markup.iter().filter(|&&b| b == b']').count()
+        } else if markup.starts_with(b"?") {
+            markup.ends_with(b"?")
+        } else {
+            let mut quote = 0;
+            for &b in markup {
+                if quote == 0 && (b == b'"' || b == b'\'') {
+                    quote = b;
+                } else if b == quote {
+                    quote = 0;
+                }
+            }
+            quote == 0
+        }
+    }
+
+    /// Processes the buffered markup; returns the root element once it
+    /// ends.
+    fn markup(&mut self) -> Result<Option<Value<'a>>, XmlError> {
+        let hold = self.raw.holder();
+        let markup = unsafe { str::from_utf8_unchecked(&self.raw) };
+        if markup.starts_with("!--") || markup.starts_with('?') || markup.starts_with("!DOCTYPE") {
+            Ok(None)
+        } else if markup.starts_with("![CDATA[") {
+            self.text.try_extend_from_slice(markup[8..markup.len() - 2].as_bytes())?;
+            Ok(None)
+        } else if markup.starts_with('!') {
+            Err(XmlError::Unexpected)
+        } else if markup.starts_with('/') {
+            let name = markup[1..].trim_end_matches(is_space);
+            flush_text(&mut self.text, &mut self.stack)?;
+            let element = match self.stack.pop() {
+                Some(element) => element.into_record(),
+                None => return Err(XmlError::Unexpected),
+            };
+            if element[0].as_attr().get_key().as_str() != name {
+                return Err(XmlError::Mismatch);
+            }
+            close(&mut self.stack, element)
+        } else {
+            let (element, empty) = parse_start_tag(hold, markup, &self.stack)?;
+            flush_text(&mut self.text, &mut self.stack)?;
+            if empty {
+                close(&mut self.stack, element)
+            } else {
+                self.stack.try_push(element.into_item())?;
+                Ok(None)
+            }
+        }
+    }
+}
+
+impl<'a, I> Decoder for XmlDecoder<'a, I> where I: Input<Token=char> {
+    type Input = I;
+    type Output = Value<'a>;
+    type Error = XmlError;
+
+    fn decode(mut self, input: &mut I) -> Then<Self, Value<'a>, XmlError> {
+        loop {
+            let c = match input.head() {
+                In(c) => c,
+                Out => return Cont(self),
+                Over => return Fail(XmlError::Unexpected),
+            };
+            input.step();
+            let result = match self.state {
+                // Text
+                0 => if c == '<' {
+                    let raw = unsafe { str::from_utf8_unchecked(&self.raw) };
+                    let result = unescape(raw, false, &mut self.text);
+                    self.raw.truncate(0);
+                    self.state = 1;
+                    result.map(|_| None)
+                } else {
+                    push_char(&mut self.raw, c).map(|_| None).map_err(XmlError::from)
+                },
+                // Markup
+                1 => if self.is_markup_end(c) {
+                    let result = self.markup();
+                    self.raw.truncate(0);
+                    self.state = 0;
+                    result
+                } else {
+                    push_char(&mut self.raw, c).map(|_| None).map_err(XmlError::from)
+                },
+                _ => unreachable!(),
+            };
+            match result {
+                Ok(Some(root)) => return Done(root),
+                Ok(None) => (),
+                Err(error) => return Fail(error),
+            };
+        }
+    }
+}
+
+impl<'a, 'b, O> XmlEncoder<'a, 'b, O> where O: Output<Token=char> {
+    pub fn try_hold(hold: &dyn Hold<'a>, source: &'b Value<'a>) -> Result<Self, XmlError> {
+        let mut string = RawBuf::try_hold_empty(hold)?;
+        let mut buffer = XmlBuffer { string: &mut string, error: None };
+        match write_element(source, &mut buffer) {
+            Ok(()) => (),
+            Err(XmlError::Unexpected) => return Err(XmlError::Hold(buffer.error.take().unwrap())),
+            Err(error) => return Err(error),
+        };
+        Ok(Self {
+            source: source,
+            string: string,
+            offset: 0,
+            output: PhantomData,
+        })
+    }
+
+    pub fn hold(hold: &dyn Hold<'a>, source: &'b Value<'a>) -> Self {
+        Self::try_hold(hold, source).unwrap()
+    }
+
+    pub fn produce(mut self, mut output: O) -> Result<O::Out, O::Err> {
+        loop {
+            match self.encode(&mut output) {
+                Done(_) => return output.take_out(),
+                Fail(_) => unreachable!(),
+                Cont(next) => self = next,
+            }
+        }
+    }
+}
+
+impl<'a, 'b, O> Encoder for XmlEncoder<'a, 'b, O> where O: Output<Token=char> {
+    type Input = &'b Value<'a>;
+    type Output = O;
+    type Error = ();
+
+    fn encode(mut self, output: &mut O) -> Then<Self, &'b Value<'a>, ()> {
+        let string = unsafe { str::from_utf8_unchecked(&self.string) };
+        while !output.is_full() {
+            match string[self.offset..].chars().next() {
+                Some(c) => {
+                    output.push(c);
+                    self.offset += c.len_utf8();
+                },
+                None => return Done(self.source),
+            };
+        }
+        Cont(self)
+    }
+}
+
+impl<'a, 'r> Write for XmlBuffer<'a, 'r> {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        match self.string.try_extend_from_slice(string.as_bytes()) {
+            Ok(()) => Ok(()),
+            Err(error) => {
+                self.error = Some(error);
+                Err(fmt::Error)
+            },
+        }
+    }
+}
+
+impl From<fmt::Error> for XmlError {
+    /// Maps a buffer write failure to `Unexpected`, which `XmlEncoder`
+    /// replaces with the allocation error that caused it.
+    fn from(_: fmt::Error) -> XmlError {
+        XmlError::Unexpected
+    }
+}
+
+/// Adds a completed element to its parent; returns the element if it's
+/// the root.
+fn close<'a>(stack: &mut Record<'a>, element: Record<'a>) -> Result<Option<Value<'a>>, XmlError> {
+    match stack.last_mut() {
+        Some(parent) => {
+            parent.as_mut_record().try_push(element.into_item())?;
+            Ok(None)
+        },
+        None => Ok(Some(element.into_value())),
+    }
+}
+
+/// Adds any buffered text, other than whitespace between elements, to
+/// the open element.
+fn flush_text<'a>(text: &mut RawBuf<'a, u8>, stack: &mut Record<'a>) -> Result<(), XmlError> {
+    let string = unsafe { str::from_utf8_unchecked(text) };
+    if !string.chars().all(|c| is_space(c) || c == '\u{FEFF}') {
+        match stack.last_mut() {
+            Some(parent) => {
+                let value = Value::try_hold_str(text.holder(), string)?;
+                parent.as_mut_record().try_push(value.into_item())?;
+            },
+            None => return Err(XmlError::Unexpected),
+        };
+    }
+    text.truncate(0);
+    Ok(())
+}
+
+/// Parses the contents of a start tag into an element record; returns
+/// `true` along with the element if the tag is self-closing. Prefixed names
+/// must be declared by the tag itself, or by one of the `open` elements.
+fn parse_start_tag<'a>(hold: &dyn Hold<'a>, markup: &str, open: &[Item<'a>])
+    -> Result<(Record<'a>, bool), XmlError> {
+    let (markup, empty) = match markup.trim_end_matches(is_space) {
+        markup if markup.ends_with('/') => (&markup[..markup.len() - 1], true),
+        markup => (markup, false),
+    };
+    let (name, mut rest) = split_name(markup)?;
+    let mut params = Record::try_hold_slice(hold, &[])?;
+    loop {
+        let trimmed = rest.trim_start_matches(is_space);
+        if trimmed.is_empty() {
+            break;
+        } else if trimmed.len() == rest.len() {
+            return Err(XmlError::Unexpected);
+        }
+        let (key, after) = split_name(trimmed)?;
+        let after = after.trim_start_matches(is_space);
+        if !after.starts_with('=') {
+            return Err(XmlError::Unexpected);
+        }
+        let after = after[1..].trim_start_matches(is_space);
+        let quote = match after.chars().next() {
+            Some(quote) if quote == '"' || quote == '\'' => quote,
+            _ => return Err(XmlError::Unexpected),
+        };
+        let end = match after[1..].find(quote) {
+            Some(end) => end + 1,
+            None => return Err(XmlError::Unexpected),
+        };
+        if params.iter().any(|item| item.as_slot().get_key().as_text().as_str() == key) {
+            return Err(XmlError::Unexpected);
+        }
+        let mut value = RawBuf::<u8>::try_hold_empty(hold)?;
+        unescape(&after[1..end], true, &mut value)?;
+        let value = unsafe { str::from_utf8_unchecked(&value) };
+        params.try_push(Item::slot(Value::try_hold_str(hold, key)?, Value::try_hold_str(hold, value)?))?;
+        rest = &after[end + 1..];
+    }
+    check_prefix(name, &params, open)?;
+    for item in params.iter() {
+        let key = item.as_slot().get_key().as_text().as_str();
+        if key != "xmlns" && !key.starts_with("xmlns:") {
+            check_prefix(key, &params, open)?;
+        }
+    }
+    let params = if params.is_empty() { Value::extant() } else { params.into_value() };
+    let mut element = Record::try_hold_slice(hold, &[])?;
+    element.try_push(Item::attr(Text::try_hold_str(hold, name)?, params))?;
+    Ok((element, empty))
+}
+
+/// Splits the XML name off the start of `string`.
+fn split_name(string: &str) -> Result<(&str, &str), XmlError> {
+    let end = string.find(|c| !is_name_char(c)).unwrap_or(string.len());
+    match string.chars().next() {
+        Some(c) if is_name_start(c) => Ok((&string[..end], &string[end..])),
+        _ => Err(XmlError::Unexpected),
+    }
+}
+
+/// Checks that the prefix of a qualified `name` is declared by `params`, or
+/// by the parameters of one of the `open` elements.
+fn check_prefix(name: &str, params: &Record, open: &[Item]) -> Result<(), XmlError> {
+    let prefix = match name.find(':') {
+        Some(index) => &name[..index],
+        None => return Ok(()),
+    };
+    let local = &name[prefix.len() + 1..];
+    if prefix.is_empty() || local.is_empty() || local.contains(':') {
+        return Err(XmlError::Namespace);
+    } else if prefix == "xml" {
+        return Ok(());
+    }
+    let declares = |params: &[Item]| params.iter().any(|item| {
+        let key = item.as_slot().get_key().as_text().as_str();
+        key.starts_with("xmlns:") && &key[6..] == prefix
+    });
+    if declares(params) || open.iter().rev().any(|element| {
+        element.as_record()[0].as_attr().get_val().cast_as_record().map_or(false, |params| declares(params))
+    }) {
+        Ok(())
+    } else {
+        Err(XmlError::Namespace)
+    }
+}
+
+/// Appends `string` to `output`, replacing entity and character references;
+/// attribute values additionally have their whitespace characters
+/// `normalize`d to spaces.
+fn unescape(string: &str, normalize: bool, output: &mut RawBuf<u8>) -> Result<(), XmlError> {
+    let mut rest = string;
+    while let Some(index) = rest.find(|c| c == '&' || (normalize && (c == '\t' || c == '\n' || c == '\r'))) {
+        output.try_extend_from_slice(rest[..index].as_bytes())?;
+        rest = &rest[index..];
+        if !rest.starts_with('&') {
+            output.try_push(b' ')?;
+            rest = &rest[1..];
+            continue;
+        }
+        let end = rest.find(';').ok_or(XmlError::Entity)?;
+        let c = match &rest[1..end] {
+            "lt" => '<',
+            "gt" => '>',
+            "amp" => '&',
+            "apos" => '\'',
+            "quot" => '"',
+            entity if entity.starts_with("#x") => {
+                let code = u32::from_str_radix(&entity[2..], 16).map_err(|_| XmlError::Entity)?;
+                char::from_u32(code).ok_or(XmlError::Entity)?
+            },
+            entity if entity.starts_with('#') => {
+                let code = entity[1..].parse::<u32>().map_err(|_| XmlError::Entity)?;
+                char::from_u32(code).ok_or(XmlError::Entity)?
+            },
+            _ => return Err(XmlError::Entity),
+        };
+        push_char(output, c)?;
+        rest = &rest[end + 1..];
+    }
+    Ok(output.try_extend_from_slice(rest.as_bytes())?)
+}
+
+fn push_char(output: &mut RawBuf<u8>, c: char) -> Result<(), HoldError> {
+    let mut bytes = [0u8; 4];
+    output.try_extend_from_slice(c.encode_utf8(&mut bytes).as_bytes())
+}
+
+fn write_element<W: Write>(value: &Value, output: &mut W) -> Result<(), XmlError> {
+    let record = value.cast_as_record().ok_or(XmlError::Shape)?;
+    let tag = match record.first().and_then(Item::cast_as_attr) {
+        Some(tag) => tag,
+        None => return Err(XmlError::Shape),
+    };
+    let name = write_start_tag(tag, output)?;
+    let body = &record[1..];
+    if body.is_empty() {
+        output.write_str("/>")?;
+        return Ok(());
+    }
+    output.write_char('>')?;
+    for item in body.iter() {
+        if item.is_field() {
+            return Err(XmlError::Shape);
+        }
+        let value = item.as_value();
+        if let Some(text) = value.cast_as_text() {
+            write_escaped(text.as_str(), false, output)?;
+        } else if let Some(num) = value.cast_as_num() {
+            write_num(num, output)?;
+        } else if let Some(value) = value.cast_as_bool() {
+            output.write_str(if value.to_bool() { "true" } else { "false" })?;
+        } else if value.is_record() {
+            write_element(value, output)?;
+        } else {
+            return Err(XmlError::Shape);
+        }
+    }
+    write!(output, "</{}>", name)?;
+    Ok(())
+}
+
+/// Writes the start of an element's tag, without its closing `>`, and
+/// returns the element's name.
+fn write_start_tag<'t, W: Write>(tag: &'t Attr, output: &mut W) -> Result<&'t str, XmlError> {
+    let name = tag.get_key().as_str();
+    if !is_name(name) {
+        return Err(XmlError::Shape);
+    }
+    write!(output, "<{}", name)?;
+    let params = tag.get_val();
+    if let Some(params) = params.cast_as_record() {
+        for param in params.iter() {
+            let (key, value) = match param.cast_as_slot() {
+                Some(slot) => slot.get_key_val(),
+                None => return Err(XmlError::Shape),
+            };
+            match key.cast_as_text() {
+                Some(key) if is_name(key.as_str()) => write!(output, " {}=\"", key)?,
+                _ => return Err(XmlError::Shape),
+            };
+            if let Some(text) = value.cast_as_text() {
+                write_escaped(text.as_str(), true, output)?;
+            } else if let Some(num) = value.cast_as_num() {
+                write_num(num, output)?;
+            } else if let Some(value) = value.cast_as_bool() {
+                output.write_str(if value.to_bool() { "true" } else { "false" })?;
+            } else if !value.is_extant() {
+                return Err(XmlError::Shape);
+            }
+            output.write_char('"')?;
+        }
+    } else if !params.is_extant() && !params.is_absent() {
+        return Err(XmlError::Shape);
+    }
+    Ok(name)
+}
+
+/// Writes `string` as character data, or as an `attribute` value.
+fn write_escaped<W: Write>(string: &str, attribute: bool, output: &mut W) -> fmt::Result {
+    for c in string.chars() {
+        match c {
+            '&' => output.write_str("&amp;")?,
+            '<' => output.write_str("&lt;")?,
+            '>' => output.write_str("&gt;")?,
+            '\r' => output.write_str("&#13;")?,
+            '"' if attribute => output.write_str("&quot;")?,
+            '\t' if attribute => output.write_str("&#9;")?,
+            '\n' if attribute => output.write_str("&#10;")?,
+            c => output.write_char(c)?,
+        };
+    }
+    Ok(())
+}
+
+#[inline]
+fn is_space(c: char) -> bool {
+    c == ' ' || c == '\t' || c == '\r' || c == '\n'
+}
+
+#[inline]
+fn is_name_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == ':'
+}
+
+#[inline]
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == ':' || c == '-' || c == '.'
+}
+
+fn is_name(string: &str) -> bool {
+    match split_name(string) {
+        Ok((_, rest)) => rest.is_empty(),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use swim_mem::block::Block;
+    use swim_mem::alloc::Pack;
+    use crate::input::AsInput;
+    use crate::output::StrOutput;
+    use super::*;
+
+    fn element<'a>(hold: &dyn Hold<'a>, name: &str, params: Value<'a>, body: &[Item<'a>]) -> Value<'a> {
+        let mut record = Record::hold_slice(hold, body);
+        record.insert(0, Item::attr(Text::hold_str(hold, name), params));
+        record.into_value()
+    }
+
+    fn attr_params<'a>(hold: &dyn Hold<'a>, attrs: &[(&str, &str)]) -> Value<'a> {
+        let mut params = Record::hold_slice(hold, &[]);
+        for &(key, value) in attrs.iter() {
+            params.push(Item::slot(Value::hold_str(hold, key), Value::hold_str(hold, value)));
+        }
+        params.into_value()
+    }
+
+    #[test]
+    fn test_xml_transcode() {
+        static mut TEST_AREA: [u8; 65536] = [0; 65536];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let xml = "<a href=\"/x?y=1&amp;z=&quot;2&quot;\">link <b>bold &lt;3</b><br/></a>";
+        let value = element(pack, "a", attr_params(pack, &[("href", "/x?y=1&z=\"2\"")]), &[
+            Value::hold_str(pack, "link ").into_item(),
+            element(pack, "b", Value::extant(), &[Value::hold_str(pack, "bold <3").into_item()]).into_item(),
+            element(pack, "br", Value::extant(), &[]).into_item(),
+        ]);
+        assert_eq!(XmlDecoder::hold(pack).consume(&mut xml.as_input()).unwrap(), value);
+        let mut buffer = [0u8; 128];
+        assert_eq!(XmlEncoder::hold(pack, &value).produce(StrOutput::new(&mut buffer)).unwrap(), xml);
+    }
+
+    #[test]
+    fn test_xml_decode_document() {
+        static mut TEST_AREA: [u8; 16384] = [0; 16384];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let xml = "<?xml version=\"1.0\"?>\n<!DOCTYPE feed [<!ENTITY x \"y\">]>\n<!-- feed -->\n\
+                   <feed xmlns:geo='urn:geo'>\n  <geo:point geo:lat = \"51.5\">\t<![CDATA[<a&b>]]> &#x263A;&#65;</geo:point>\n\
+                   <!-- skipped --><e/>\n</feed>\n";
+        let expected = element(pack, "feed", attr_params(pack, &[("xmlns:geo", "urn:geo")]), &[
+            element(pack, "geo:point", attr_params(pack, &[("geo:lat", "51.5")]), &[
+                Value::hold_str(pack, "\t<a&b> \u{263A}A").into_item(),
+            ]).into_item(),
+            element(pack, "e", Value::extant(), &[]).into_item(),
+        ]);
+        for split in 0..=xml.len() {
+            let mut test_area = [0u8; 16384];
+            let decoder = XmlDecoder::hold(Pack::new(Block::from_slice(&mut test_area)));
+            let value = match decoder.decode(&mut xml[..split].as_input()) {
+                Cont(decoder) => decoder.consume(&mut xml[split..].as_input()).unwrap(),
+                Done(value) => value,
+                Fail(error) => panic!("{:?} at split {}", error, split),
+            };
+            assert_eq!(value, expected, "split {}", split);
+        }
+    }
+
+    #[test]
+    fn test_xml_decode_malformed() {
+        static mut TEST_AREA: [u8; 16384] = [0; 16384];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let decode = |xml: &str| XmlDecoder::hold(pack).consume(&mut xml.as_input()).unwrap_err();
+        assert_eq!(decode("<a><b></a>"), XmlError::Mismatch);
+        assert_eq!(decode("<a>"), XmlError::Unexpected);
+        assert_eq!(decode("text<a/>"), XmlError::Unexpected);
+        assert_eq!(decode("<a x=1/>"), XmlError::Unexpected);
+        assert_eq!(decode("<a x='1' x='2'/>"), XmlError::Unexpected);
+        assert_eq!(decode("<a>&nbsp;</a>"), XmlError::Entity);
+        assert_eq!(decode("<a>AT&T</a>"), XmlError::Entity);
+        assert_eq!(decode("<ns:a/>"), XmlError::Namespace);
+        assert_eq!(decode("<a x:y='1'/>"), XmlError::Namespace);
+    }
+
+    #[test]
+    fn test_xml_encode_shape() {
+        static mut TEST_AREA: [u8; 16384] = [0; 16384];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let value = element(pack, "p", Record::hold_slice(pack, &[
+            Item::slot(Value::hold_str(pack, "n"), Value::from_i32(2)),
+            Item::slot(Value::hold_str(pack, "t"), Value::hold_str(pack, "a\"\n")),
+        ]).into_value(), &[Item::from_f64(0.5), Item::from_bool(true)]);
+        let mut buffer = [0u8; 64];
+        assert_eq!(XmlEncoder::hold(pack, &value).produce(StrOutput::new(&mut buffer)).unwrap(),
+                   "<p n=\"2\" t=\"a&quot;&#10;\">0.5true</p>");
+        let encode = |value: &Value<'static>| XmlEncoder::<StrOutput>::try_hold(pack, value).err();
+        assert_eq!(encode(&Value::hold_str(pack, "a")), Some(XmlError::Shape));
+        assert_eq!(encode(&Record::hold_slice(pack, &[Item::from_i32(1)]).into_value()), Some(XmlError::Shape));
+        assert_eq!(encode(&element(pack, "a b", Value::extant(), &[])), Some(XmlError::Shape));
+        assert_eq!(encode(&element(pack, "a", Value::from_i32(1), &[])), Some(XmlError::Shape));
+        assert_eq!(encode(&element(pack, "a", Value::extant(), &[
+            Item::slot(Value::hold_str(pack, "x"), Value::from_i32(1)),
+        ])), Some(XmlError::Shape));
+    }
+}
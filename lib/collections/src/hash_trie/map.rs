@@ -3,6 +3,7 @@ use core::fmt;
 use core::hash::{BuildHasher, Hash};
 use core::iter::{ExactSizeIterator, FusedIterator, TrustedLen};
 use swim_core::murmur3::Murmur3;
+use swim_core::siphash::SipHash13;
 use swim_mem::alloc::{Hold, Holder, HoldError, Stow, TryClone, CloneIntoHold};
 use crate::hash_trie::{HashTrie, HashTrieIter};
 
@@ -45,6 +46,16 @@ impl<K, V> HashTrieMap<'static, K, V> {
     }
 }
 
+impl<K, V> HashTrieMap<'static, K, V, SipHash13> {
+    /// Constructs a new `HashTrieMap` that will allocate its data in the
+    /// global `Hold`, and hash its keys using SipHash with a secret random
+    /// key. Use keyed maps to hold keys taken from untrusted input.
+    #[inline]
+    pub fn new_keyed() -> HashTrieMap<'static, K, V, SipHash13> {
+        HashTrieMap::hold_new_keyed(Hold::global())
+    }
+}
+
 impl<K, V, H> HashTrieMap<'static, K, V, H> {
     /// Constructs a new `HashTrieMap` that will allocate its data in the
     /// global `Hold`, and hash its keys using the supplied `hasher`.
@@ -64,6 +75,16 @@ impl<'a, K, V> HashTrieMap<'a, K, V> {
     }
 }
 
+impl<'a, K, V> HashTrieMap<'a, K, V, SipHash13> {
+    /// Constructs a new `HashTrieMap` that will allocate its data in `Hold`,
+    /// and hash its keys using SipHash with a secret random key, which keeps
+    /// untrusted input from flooding the trie with colliding keys.
+    #[inline]
+    pub fn hold_new_keyed(hold: &dyn Hold<'a>) -> HashTrieMap<'a, K, V, SipHash13> {
+        HashTrieMap::hold_new_hasher(hold, SipHash13::new())
+    }
+}
+
 impl<'a, K, V, H> HashTrieMap<'a, K, V, H> {
     /// Constructs a new `HashTrieMap` that will allocate its data in `Hold`,
     /// and hash its keys using the supplied `hasher`. Allocates a zero-sized
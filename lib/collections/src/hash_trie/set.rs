@@ -3,6 +3,7 @@ use core::fmt;
 use core::hash::{BuildHasher, Hash};
 use core::iter::{ExactSizeIterator, FusedIterator, TrustedLen};
 use swim_core::murmur3::Murmur3;
+use swim_core::siphash::SipHash13;
 use swim_mem::alloc::{Hold, Holder, HoldError, Stow, TryClone, CloneIntoHold};
 use crate::hash_trie::{HashTrie, HashTrieIter};
 
@@ -25,6 +26,16 @@ impl<T> HashTrieSet<'static, T> {
     }
 }
 
+impl<T> HashTrieSet<'static, T, SipHash13> {
+    /// Constructs a new `HashTrieSet` that will allocate its data in the
+    /// global `Hold`, and hash its elements using SipHash with a secret random
+    /// key. Use keyed sets to hold elements taken from untrusted input.
+    #[inline]
+    pub fn new_keyed() -> HashTrieSet<'static, T, SipHash13> {
+        HashTrieSet::hold_new_keyed(Hold::global())
+    }
+}
+
 impl<T, H> HashTrieSet<'static, T, H> {
     /// Constructs a new `HashTrieSet` that will allocate its data in the
     /// global `Hold`, and hash its keys using the supplied `hasher`.
@@ -44,6 +55,16 @@ impl<'a, T> HashTrieSet<'a, T> {
     }
 }
 
+impl<'a, T> HashTrieSet<'a, T, SipHash13> {
+    /// Constructs a new `HashTrieSet` that will allocate its data in `Hold`,
+    /// and hash its elements using SipHash with a secret random key, which
+    /// keeps untrusted input from flooding the trie with colliding elements.
+    #[inline]
+    pub fn hold_new_keyed(hold: &dyn Hold<'a>) -> HashTrieSet<'a, T, SipHash13> {
+        HashTrieSet::hold_new_hasher(hold, SipHash13::new())
+    }
+}
+
 impl<'a, T, H> HashTrieSet<'a, T, H> {
     /// Constructs a new `HashTrieSet` that will allocate its data in `Hold`,
     /// and hash its keys using the supplied `hasher`. Allocates a zero-sized
@@ -1,8 +1,11 @@
+extern crate swim_core;
 extern crate swim_mem;
 extern crate swim_collections;
+extern crate swim_c_rt;
 
 mod hash_collision;
 
+use swim_core::siphash::SipHash13;
 use swim_mem::block::Block;
 use swim_mem::alloc::{Slab, Pool};
 use swim_collections::hash_trie::HashTrieSet;
@@ -219,3 +222,21 @@ fn test_hash_trie_set_iter_collisions() {
                i => HashCollision::new(i, i >> 2),
                elem => **elem);
 }
+
+#[test]
+fn test_hash_trie_set_insert_keyed() {
+    static mut TEST_HUNK: [u8; 8*1024*1024] = [0; 8*1024*1024];
+    let slab = Slab::new(unsafe { Block::from_slice(&mut TEST_HUNK) }, 4096);
+    let pool = &Pool::new(&slab);
+
+    test_insert!(HashTrieSet::<usize, SipHash13>::hold_new_keyed(pool), 1 << 15);
+}
+
+#[test]
+fn test_hash_trie_set_remove_keyed() {
+    static mut TEST_HUNK: [u8; 16*1024*1024] = [0; 16*1024*1024];
+    let slab = Slab::new(unsafe { Block::from_slice(&mut TEST_HUNK) }, 4096);
+    let pool = &Pool::new(&slab);
+
+    test_remove!(HashTrieSet::<usize, SipHash13>::hold_new_keyed(pool), 1 << 15);
+}
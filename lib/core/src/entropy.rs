@@ -0,0 +1,20 @@
+#[allow(improper_ctypes)]
+extern "Rust" {
+    fn _swim_entropy(bytes: &mut [u8]) -> Result<(), EntropyError>;
+}
+
+/// Fills `bytes` with unpredictable random data drawn from the operating
+/// system's entropy source, as provided by the linked runtime.
+#[inline]
+pub fn fill(bytes: &mut [u8]) -> Result<(), EntropyError> {
+    unsafe { _swim_entropy(bytes) }
+}
+
+/// Entropy source error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntropyError {
+    /// No entropy source is available on this platform.
+    Unsupported,
+    /// The entropy source failed to produce the requested random bytes.
+    Unavailable,
+}
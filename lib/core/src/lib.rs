@@ -2,11 +2,20 @@
 
 #![no_std]
 
+#![feature(const_fn)]
+#![feature(const_fn_union)]
 #![feature(link_llvm_intrinsics)]
 #![feature(raw)]
 
 pub use num::f16::f16;
+pub use num::bf16::bf16;
 
 pub mod num;
+pub mod entropy;
 pub mod murmur3;
+pub mod rand;
 pub mod reify;
+pub mod siphash;
+pub mod time;
+pub mod ulid;
+pub mod uuid;
@@ -12,12 +12,41 @@ pub struct Murmur3Hasher {
     have: usize,
 }
 
+/// MurmurHash3_x86_32 hasher, which matches the 32-bit hashes of the Java
+/// implementation.
+pub struct Murmur3x86_32Hasher {
+    h1: u32,
+    k1: u32,
+    size: u32,
+    have: usize,
+}
+
+/// MurmurHash3_x86_128 hasher.
+pub struct Murmur3x86_128Hasher {
+    h: [u32; 4],
+    tail: [u8; 16],
+    size: u32,
+    have: usize,
+}
+
+/// `BuildHasher` of seeded `Murmur3Hasher`s; hash tries built with a
+/// random seed lay out their keys unpredictably.
 #[derive(Clone, Default, Debug)]
-pub struct Murmur3 {}
+pub struct Murmur3 {
+    seed: u32,
+}
 
 const C1: u64 = 0x87c37b91114253d5;
 const C2: u64 = 0x4cf5ad432745937f;
 
+const C1_32: u32 = 0xcc9e2d51;
+const C2_32: u32 = 0x1b873593;
+
+const C1_128: u32 = 0x239b961b;
+const C2_128: u32 = 0xab0e9789;
+const C3_128: u32 = 0x38b34ae5;
+const C4_128: u32 = 0xa1e38b93;
+
 macro_rules! load_int_le {
     ($buf: expr, $i: expr, $int_ty: ident) => ({
         debug_assert!($i + mem::size_of::<$int_ty>() <= $buf.len());
@@ -61,6 +90,22 @@ fn fmix64(mut k: u64) -> u64 {
     k
 }
 
+#[inline]
+fn fmix32(mut h: u32) -> u32 {
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x85ebca6b);
+    h ^= h >> 13;
+    h = h.wrapping_mul(0xc2b2ae35);
+    h ^= h >> 16;
+
+    h
+}
+
+#[inline]
+fn mix_k32(k: u32, c1: u32, r: u32, c2: u32) -> u32 {
+    k.wrapping_mul(c1).rotate_left(r).wrapping_mul(c2)
+}
+
 impl Murmur3Hasher {
     pub const fn new(seed: u32) -> Self {
         Self {
@@ -200,9 +245,187 @@ impl Hasher for Murmur3Hasher {
     }
 }
 
+impl Murmur3x86_32Hasher {
+    pub const fn new(seed: u32) -> Self {
+        Self {
+            h1: seed,
+            k1: 0,
+            size: 0,
+            have: 0,
+        }
+    }
+
+    #[inline]
+    fn block(&mut self, k1: u32) {
+        self.h1 ^= mix_k32(k1, C1_32, 15, C2_32);
+        self.h1 = self.h1.rotate_left(13);
+        self.h1 = self.h1.wrapping_mul(5).wrapping_add(0xe6546b64); // h1 = h1*5+0xe6546b64;
+    }
+
+    pub fn finalize(&self) -> u32 {
+        let mut h1 = self.h1;
+        if self.have != 0 {
+            h1 ^= mix_k32(self.k1, C1_32, 15, C2_32);
+        }
+        h1 ^= self.size;
+        fmix32(h1)
+    }
+}
+
+impl Hasher for Murmur3x86_32Hasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut offset = 0;
+        let len = bytes.len();
+        self.size = self.size.wrapping_add(len as u32);
+
+        while self.have != 0 && offset < len {
+            self.k1 |= (bytes[offset] as u32) << (8 * self.have);
+            self.have += 1;
+            offset += 1;
+            if self.have == 4 {
+                self.block(self.k1);
+                self.k1 = 0;
+                self.have = 0;
+            }
+        }
+        while offset + 4 <= len {
+            self.block(unsafe { load_int_le!(bytes, offset, u32) });
+            offset += 4;
+        }
+        while offset < len {
+            self.k1 |= (bytes[offset] as u32) << (8 * self.have);
+            self.have += 1;
+            offset += 1;
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.finalize() as u64
+    }
+}
+
+impl Murmur3x86_128Hasher {
+    pub const fn new(seed: u32) -> Self {
+        Self {
+            h: [seed; 4],
+            tail: [0; 16],
+            size: 0,
+            have: 0,
+        }
+    }
+
+    #[inline]
+    fn block(&mut self, block: &[u8]) {
+        let (k1, k2, k3, k4) = unsafe {
+            (load_int_le!(block, 0, u32), load_int_le!(block, 4, u32),
+             load_int_le!(block, 8, u32), load_int_le!(block, 12, u32))
+        };
+        let [mut h1, mut h2, mut h3, mut h4] = self.h;
+
+        h1 ^= mix_k32(k1, C1_128, 15, C2_128);
+        h1 = h1.rotate_left(19).wrapping_add(h2);
+        h1 = h1.wrapping_mul(5).wrapping_add(0x561ccd1b); // h1 = h1*5+0x561ccd1b;
+
+        h2 ^= mix_k32(k2, C2_128, 16, C3_128);
+        h2 = h2.rotate_left(17).wrapping_add(h3);
+        h2 = h2.wrapping_mul(5).wrapping_add(0x0bcaa747); // h2 = h2*5+0x0bcaa747;
+
+        h3 ^= mix_k32(k3, C3_128, 17, C4_128);
+        h3 = h3.rotate_left(15).wrapping_add(h4);
+        h3 = h3.wrapping_mul(5).wrapping_add(0x96cd1c35); // h3 = h3*5+0x96cd1c35;
+
+        h4 ^= mix_k32(k4, C4_128, 18, C1_128);
+        h4 = h4.rotate_left(13).wrapping_add(h1);
+        h4 = h4.wrapping_mul(5).wrapping_add(0x32ac3b17); // h4 = h4*5+0x32ac3b17;
+
+        self.h = [h1, h2, h3, h4];
+    }
+
+    pub fn finalize(&self) -> (u32, u32, u32, u32) {
+        let [mut h1, mut h2, mut h3, mut h4] = self.h;
+        if self.have != 0 {
+            let mut tail = [0u8; 16];
+            tail[..self.have].copy_from_slice(&self.tail[..self.have]);
+            let (k1, k2, k3, k4) = unsafe {
+                (load_int_le!(tail, 0, u32), load_int_le!(tail, 4, u32),
+                 load_int_le!(tail, 8, u32), load_int_le!(tail, 12, u32))
+            };
+            // Zero words of the tail mix to zero, so they needn't be skipped.
+            h1 ^= mix_k32(k1, C1_128, 15, C2_128);
+            h2 ^= mix_k32(k2, C2_128, 16, C3_128);
+            h3 ^= mix_k32(k3, C3_128, 17, C4_128);
+            h4 ^= mix_k32(k4, C4_128, 18, C1_128);
+        }
+
+        h1 ^= self.size;
+        h2 ^= self.size;
+        h3 ^= self.size;
+        h4 ^= self.size;
+
+        h1 = h1.wrapping_add(h2).wrapping_add(h3).wrapping_add(h4);
+        h2 = h2.wrapping_add(h1);
+        h3 = h3.wrapping_add(h1);
+        h4 = h4.wrapping_add(h1);
+
+        h1 = fmix32(h1);
+        h2 = fmix32(h2);
+        h3 = fmix32(h3);
+        h4 = fmix32(h4);
+
+        h1 = h1.wrapping_add(h2).wrapping_add(h3).wrapping_add(h4);
+        h2 = h2.wrapping_add(h1);
+        h3 = h3.wrapping_add(h1);
+        h4 = h4.wrapping_add(h1);
+
+        (h1, h2, h3, h4)
+    }
+}
+
+impl Hasher for Murmur3x86_128Hasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut offset = 0;
+        let len = bytes.len();
+        self.size = self.size.wrapping_add(len as u32);
+
+        if self.have != 0 {
+            let take = cmp::min(16 - self.have, len);
+            self.tail[self.have..self.have + take].copy_from_slice(&bytes[..take]);
+            self.have += take;
+            offset += take;
+            if self.have < 16 {
+                return;
+            }
+            let tail = self.tail;
+            self.block(&tail);
+            self.have = 0;
+        }
+        while offset + 16 <= len {
+            self.block(&bytes[offset..offset + 16]);
+            offset += 16;
+        }
+        self.have = len - offset;
+        self.tail[..self.have].copy_from_slice(&bytes[offset..]);
+    }
+
+    fn finish(&self) -> u64 {
+        let (h1, h2, _, _) = self.finalize();
+        (h2 as u64) << 32 | h1 as u64
+    }
+}
+
 impl Murmur3 {
     pub const fn new() -> Self {
-        Self {}
+        Self::with_seed(0)
+    }
+
+    pub const fn with_seed(seed: u32) -> Self {
+        Self {
+            seed: seed,
+        }
+    }
+
+    pub const fn seed(&self) -> u32 {
+        self.seed
     }
 }
 
@@ -211,10 +434,32 @@ impl BuildHasher for Murmur3 {
 
     #[inline]
     fn build_hasher(&self) -> Murmur3Hasher {
-        Murmur3Hasher::new(0)
+        Murmur3Hasher::new(self.seed)
     }
 }
 
+/// Returns the MurmurHash3_x86_32 hash of `bytes`; the same hash as the
+/// Java implementation's.
+pub fn murmur3_hash(bytes: &[u8], seed: u32) -> u32 {
+    let mut hasher = Murmur3x86_32Hasher::new(seed);
+    hasher.write(bytes);
+    hasher.finalize()
+}
+
+/// Returns the MurmurHash3_x86_128 hash of `bytes`.
+pub fn murmur3_x86_128(bytes: &[u8], seed: u32) -> (u32, u32, u32, u32) {
+    let mut hasher = Murmur3x86_128Hasher::new(seed);
+    hasher.write(bytes);
+    hasher.finalize()
+}
+
+/// Returns the MurmurHash3_x64_128 hash of `bytes`.
+pub fn murmur3_x64_128(bytes: &[u8], seed: u32) -> (u64, u64) {
+    let mut hasher = Murmur3Hasher::new(seed);
+    hasher.write(bytes);
+    hasher.finalize()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -275,4 +520,158 @@ mod tests {
         assert_hashes("the quick brown fox jumps over the lazy dog".as_bytes(), 0xc58f1a7b, 0x88ec96021b8af702, 0x640843c82e69c55c);
         assert_hashes("the quick brown fox jumps over the lazy cog".as_bytes(), 0xc58f1a7b, 0x9219b4b672765148, 0x81736d9f9f008440);
     }
+
+    fn assert_hashes_x86_32(data: &[u8], seed: u32, h1: u32) {
+        let n = data.len();
+        for i in 0..(n+1) {
+            for j in i..(n+1) {
+                let mut hasher = Murmur3x86_32Hasher::new(seed);
+                hasher.write(&data[0..i]);
+                hasher.write(&data[i..j]);
+                hasher.write(&data[j..n]);
+                assert_eq!(hasher.finalize(), h1);
+            }
+        }
+        assert_eq!(murmur3_hash(data, seed), h1);
+    }
+
+    fn assert_hashes_x86_128(data: &[u8], seed: u32, h: (u32, u32, u32, u32)) {
+        let n = data.len();
+        for i in 0..(n+1) {
+            for j in i..(n+1) {
+                let mut hasher = Murmur3x86_128Hasher::new(seed);
+                hasher.write(&data[0..i]);
+                hasher.write(&data[i..j]);
+                hasher.write(&data[j..n]);
+                assert_eq!(hasher.finalize(), h);
+            }
+        }
+        assert_eq!(murmur3_x86_128(data, seed), h);
+    }
+
+    /// SMHasher's verification code: hashes keys of the form {0, 1, ..., N-1}
+    /// with seed 256-N, for N from 0 to 255, then hashes the concatenated
+    /// little-endian hashes with seed 0, and returns the first 32 bits.
+    fn verification<F: FnMut(&[u8], u32, &mut [u8])>(size: usize, mut hash: F) -> u32 {
+        let mut key = [0u8; 256];
+        let mut hashes = [0u8; 256 * 16];
+        for i in 0..256 {
+            key[i] = i as u8;
+            hash(&key[..i], 256 - i as u32, &mut hashes[i * size..(i + 1) * size]);
+        }
+        let mut result = [0u8; 16];
+        hash(&hashes[..256 * size], 0, &mut result[..size]);
+        u32::from_le_bytes([result[0], result[1], result[2], result[3]])
+    }
+
+    #[test]
+    fn test_verification() {
+        let x86_32 = verification(4, |key, seed, out| {
+            out.copy_from_slice(&murmur3_hash(key, seed).to_le_bytes());
+        });
+        assert_eq!(x86_32, 0xB0F57EE3);
+        let x86_128 = verification(16, |key, seed, out| {
+            let (h1, h2, h3, h4) = murmur3_x86_128(key, seed);
+            out[0..4].copy_from_slice(&h1.to_le_bytes());
+            out[4..8].copy_from_slice(&h2.to_le_bytes());
+            out[8..12].copy_from_slice(&h3.to_le_bytes());
+            out[12..16].copy_from_slice(&h4.to_le_bytes());
+        });
+        assert_eq!(x86_128, 0xB3ECE62A);
+        let x64_128 = verification(16, |key, seed, out| {
+            let (h1, h2) = murmur3_x64_128(key, seed);
+            out[0..8].copy_from_slice(&h1.to_le_bytes());
+            out[8..16].copy_from_slice(&h2.to_le_bytes());
+        });
+        assert_eq!(x64_128, 0x6384BA69);
+    }
+
+    #[test]
+    fn test_seeded_build_hasher() {
+        let data = "The quick brown fox jumps over the lazy dog".as_bytes();
+        let mut hasher = Murmur3::with_seed(0x9747b28c).build_hasher();
+        hasher.write(data);
+        assert_eq!(hasher.finish(), 0x738a7f3bd2633121);
+        let mut hasher = Murmur3::new().build_hasher();
+        hasher.write(data);
+        assert_eq!(hasher.finish(), 0xe34bbc7bbc071b6c);
+    }
+
+    #[test]
+    fn test_vectors_x86_32() {
+        assert_hashes_x86_32("".as_bytes(), 0, 0x00000000);
+        assert_hashes_x86_32("a".as_bytes(), 0, 0x3c2569b2);
+        assert_hashes_x86_32("ab".as_bytes(), 0, 0x9bbfd75f);
+        assert_hashes_x86_32("abc".as_bytes(), 0, 0xb3dd93fa);
+        assert_hashes_x86_32("abcd".as_bytes(), 0, 0x43ed676a);
+        assert_hashes_x86_32("abcde".as_bytes(), 0, 0xe89b9af6);
+        assert_hashes_x86_32("abcdef".as_bytes(), 0, 0x6181c085);
+        assert_hashes_x86_32("abcdefg".as_bytes(), 0, 0x883c9b06);
+        assert_hashes_x86_32("abcdefgh".as_bytes(), 0, 0x49ddccc4);
+        assert_hashes_x86_32("abcdefghi".as_bytes(), 0, 0x421406f0);
+        assert_hashes_x86_32("abcdefghij".as_bytes(), 0, 0x88927791);
+        assert_hashes_x86_32("abcdefghijk".as_bytes(), 0, 0x5f3b25df);
+        assert_hashes_x86_32("abcdefghijkl".as_bytes(), 0, 0xa36f3d27);
+        assert_hashes_x86_32("abcdefghijklm".as_bytes(), 0, 0xf212161b);
+        assert_hashes_x86_32("abcdefghijklmn".as_bytes(), 0, 0xf8526df0);
+        assert_hashes_x86_32("abcdefghijklmno".as_bytes(), 0, 0x9d09f7d2);
+        assert_hashes_x86_32("abcdefghijklmnop".as_bytes(), 0, 0xe76291ed);
+        assert_hashes_x86_32("abcdefghijklmnopqrstuvwxyz".as_bytes(), 0, 0xa34e036d);
+        assert_hashes_x86_32("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz".as_bytes(), 0, 0x84af2740);
+        assert_hashes_x86_32("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789".as_bytes(), 0, 0xa27af39b);
+        assert_hashes_x86_32("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/".as_bytes(), 0, 0x12c644dc);
+
+        assert_hashes_x86_32("The quick brown fox jumps over the lazy dog".as_bytes(), 0x9747b28c, 0x2fa826cd);
+        assert_hashes_x86_32("The quick brown fox jumps over the lazy cog".as_bytes(), 0x9747b28c, 0x8d9c9ed4);
+        assert_hashes_x86_32("THE QUICK BROWN FOX JUMPS OVER THE LAZY DOG".as_bytes(), 0x9747b28c, 0x637b8cf5);
+        assert_hashes_x86_32("THE QUICK BROWN FOX JUMPS OVER THE LAZY COG".as_bytes(), 0x9747b28c, 0x21b8f236);
+        assert_hashes_x86_32("the quick brown fox jumps over the lazy dog".as_bytes(), 0x9747b28c, 0xedc02f1c);
+        assert_hashes_x86_32("the quick brown fox jumps over the lazy cog".as_bytes(), 0x9747b28c, 0x9781572b);
+
+        assert_hashes_x86_32("The quick brown fox jumps over the lazy dog".as_bytes(), 0xc58f1a7b, 0x75b422d6);
+        assert_hashes_x86_32("The quick brown fox jumps over the lazy cog".as_bytes(), 0xc58f1a7b, 0x27757527);
+        assert_hashes_x86_32("THE QUICK BROWN FOX JUMPS OVER THE LAZY DOG".as_bytes(), 0xc58f1a7b, 0xf24960e0);
+        assert_hashes_x86_32("THE QUICK BROWN FOX JUMPS OVER THE LAZY COG".as_bytes(), 0xc58f1a7b, 0x1ea370fc);
+        assert_hashes_x86_32("the quick brown fox jumps over the lazy dog".as_bytes(), 0xc58f1a7b, 0x14561460);
+        assert_hashes_x86_32("the quick brown fox jumps over the lazy cog".as_bytes(), 0xc58f1a7b, 0xb6d292ff);
+    }
+
+    #[test]
+    fn test_vectors_x86_128() {
+        assert_hashes_x86_128("".as_bytes(), 0, (0x00000000, 0x00000000, 0x00000000, 0x00000000));
+        assert_hashes_x86_128("a".as_bytes(), 0, (0xa794933c, 0x5556b01b, 0x5556b01b, 0x5556b01b));
+        assert_hashes_x86_128("ab".as_bytes(), 0, (0x158451df, 0x25be3010, 0x25be3010, 0x25be3010));
+        assert_hashes_x86_128("abc".as_bytes(), 0, (0x75cdc6d1, 0xa2b006a5, 0xa2b006a5, 0xa2b006a5));
+        assert_hashes_x86_128("abcd".as_bytes(), 0, (0x96b6ccaa, 0x45afc62e, 0x45afc62e, 0x45afc62e));
+        assert_hashes_x86_128("abcde".as_bytes(), 0, (0xc5402efb, 0x5d24c5bc, 0x5a720177, 0x5a720177));
+        assert_hashes_x86_128("abcdef".as_bytes(), 0, (0xe17cb90a, 0xa1af2721, 0xa9bedff9, 0xa9bedff9));
+        assert_hashes_x86_128("abcdefg".as_bytes(), 0, (0x90b541d9, 0x09863ade, 0x4a776928, 0x4a776928));
+        assert_hashes_x86_128("abcdefgh".as_bytes(), 0, (0xaef41136, 0xadb11487, 0xfa6c8092, 0xfa6c8092));
+        assert_hashes_x86_128("abcdefghi".as_bytes(), 0, (0xad058c1c, 0x2206596f, 0x14d27d10, 0xdd94417c));
+        assert_hashes_x86_128("abcdefghij".as_bytes(), 0, (0xf5d92ea7, 0x9a37900e, 0xc792aa2a, 0x692ff17f));
+        assert_hashes_x86_128("abcdefghijk".as_bytes(), 0, (0xa0c8c9dd, 0xca1f111e, 0x82fef12d, 0xbd5e9757));
+        assert_hashes_x86_128("abcdefghijkl".as_bytes(), 0, (0x738503fe, 0xf48224c0, 0x27d4dfad, 0xd28ce553));
+        assert_hashes_x86_128("abcdefghijklm".as_bytes(), 0, (0xc13aa215, 0xdbaea1ec, 0xf41c3566, 0xd92cde70));
+        assert_hashes_x86_128("abcdefghijklmn".as_bytes(), 0, (0x75ec118e, 0x605d1fd7, 0x946f45f4, 0xf1d4894d));
+        assert_hashes_x86_128("abcdefghijklmno".as_bytes(), 0, (0xee6d1d69, 0x4a1ad5ae, 0x84ce1457, 0xada761a8));
+        assert_hashes_x86_128("abcdefghijklmnop".as_bytes(), 0, (0x9fd27627, 0x90b91256, 0xe4ce8b21, 0xd193ba45));
+        assert_hashes_x86_128("abcdefghijklmnopqrstuvwxyz".as_bytes(), 0, (0x3e340613, 0x666f2f66, 0x17f6566e, 0x44e33d2c));
+        assert_hashes_x86_128("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz".as_bytes(), 0, (0x5aafee3b, 0xe6142591, 0x770573ee, 0x4c62de38));
+        assert_hashes_x86_128("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789".as_bytes(), 0, (0x2962d6e7, 0x48ddf732, 0x07ed3829, 0x12720c32));
+        assert_hashes_x86_128("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/".as_bytes(), 0, (0x621fa64a, 0x5eed08df, 0x816d205d, 0x8864fc12));
+
+        assert_hashes_x86_128("The quick brown fox jumps over the lazy dog".as_bytes(), 0x9747b28c, (0x8ad4d55e, 0x4cb86171, 0x8ea73a9c, 0xcdb6793e));
+        assert_hashes_x86_128("The quick brown fox jumps over the lazy cog".as_bytes(), 0x9747b28c, (0xcc29880e, 0x108110f9, 0xbf5e6f7c, 0x88e13334));
+        assert_hashes_x86_128("THE QUICK BROWN FOX JUMPS OVER THE LAZY DOG".as_bytes(), 0x9747b28c, (0x7192c385, 0xc601175a, 0x4abfdf82, 0x90815014));
+        assert_hashes_x86_128("THE QUICK BROWN FOX JUMPS OVER THE LAZY COG".as_bytes(), 0x9747b28c, (0x3c1bbb98, 0x654f1995, 0x6dde796e, 0x093f2466));
+        assert_hashes_x86_128("the quick brown fox jumps over the lazy dog".as_bytes(), 0x9747b28c, (0xf657c16e, 0xee9b0f79, 0x36e42b9e, 0x8af809c5));
+        assert_hashes_x86_128("the quick brown fox jumps over the lazy cog".as_bytes(), 0x9747b28c, (0xe39977ba, 0x1c9fd13b, 0x92a2493d, 0xfe882743));
+
+        assert_hashes_x86_128("The quick brown fox jumps over the lazy dog".as_bytes(), 0xc58f1a7b, (0x4aa93b4f, 0x3a378f61, 0x6a97662b, 0xe8f88fa3));
+        assert_hashes_x86_128("The quick brown fox jumps over the lazy cog".as_bytes(), 0xc58f1a7b, (0x19256508, 0x510aee1f, 0xaa1cd0bc, 0xfd88b9f5));
+        assert_hashes_x86_128("THE QUICK BROWN FOX JUMPS OVER THE LAZY DOG".as_bytes(), 0xc58f1a7b, (0xc85c9a2b, 0x2139ecc8, 0x0792ae64, 0x188b42e0));
+        assert_hashes_x86_128("THE QUICK BROWN FOX JUMPS OVER THE LAZY COG".as_bytes(), 0xc58f1a7b, (0x42b6c71f, 0xd704acb0, 0x842365bb, 0x24c8cb19));
+        assert_hashes_x86_128("the quick brown fox jumps over the lazy dog".as_bytes(), 0xc58f1a7b, (0xf555a3a0, 0x111668e2, 0xa178cf5c, 0x4e1b5d92));
+        assert_hashes_x86_128("the quick brown fox jumps over the lazy cog".as_bytes(), 0xc58f1a7b, (0xaab9226c, 0x7c16ba4b, 0x083e9d80, 0x469d1b7b));
+    }
 }
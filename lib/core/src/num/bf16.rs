@@ -0,0 +1,807 @@
+#![allow(non_camel_case_types)]
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
+use core::num::FpCategory;
+use core::str::FromStr;
+use crate::num::float::{parse_float, FloatError};
+
+/// The 16-bit brain floating point type: the upper half of an `f32`, with
+/// its full exponent range and an 8-bit significand.
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct bf16(u16);
+
+/// Basic mathematical constants.
+pub mod consts {
+    use super::bf16;
+
+    /// Euler's number (e)
+    pub const E: bf16 = bf16(0x402Eu16);
+
+    /// 1/π
+    pub const FRAC_1_PI: bf16 = bf16(0x3EA3u16);
+
+    /// 1/sqrt(2)
+    pub const FRAC_1_SQRT_2: bf16 = bf16(0x3F35u16);
+
+    /// 2/π
+    pub const FRAC_2_PI: bf16 = bf16(0x3F23u16);
+
+    /// 2/sqrt(π)
+    pub const FRAC_2_SQRT_PI: bf16 = bf16(0x3F90u16);
+
+    /// π/2
+    pub const FRAC_PI_2: bf16 = bf16(0x3FC9u16);
+
+    /// π/3
+    pub const FRAC_PI_3: bf16 = bf16(0x3F86u16);
+
+    /// π/4
+    pub const FRAC_PI_4: bf16 = bf16(0x3F49u16);
+
+    /// π/6
+    pub const FRAC_PI_6: bf16 = bf16(0x3F06u16);
+
+    /// π/8
+    pub const FRAC_PI_8: bf16 = bf16(0x3EC9u16);
+
+    /// ln(2)
+    pub const LN_2: bf16 = bf16(0x3F31u16);
+
+    /// ln(10)
+    pub const LN_10: bf16 = bf16(0x4013u16);
+
+    /// log10(e)
+    pub const LOG10_E: bf16 = bf16(0x3EDEu16);
+
+    /// log2(e)
+    pub const LOG2_E: bf16 = bf16(0x3FB9u16);
+
+    /// Archimedes' constant (π)
+    pub const PI: bf16 = bf16(0x4049u16);
+
+    /// sqrt(2)
+    pub const SQRT_2: bf16 = bf16(0x3FB5u16);
+}
+
+impl bf16 {
+    /// Approximate number of significant digits in base 10.
+    pub const DIGITS: u32 = 2;
+
+    /// Difference between 1.0 and the next largest representable number.
+    pub const EPSILON: bf16 = bf16(0x3C00u16);
+
+    /// Infinity (∞).
+    pub const INFINITY: bf16 = bf16(0x7F80u16);
+
+    /// Number of significant digits in base 2.
+    pub const MANTISSA_DIGITS: u32 = 8;
+
+    /// Largest finite `bf16` value.
+    pub const MAX: bf16 = bf16(0x7F7F);
+
+    /// Maximum possible power of 10 exponent.
+    pub const MAX_10_EXP: i32 = 38;
+
+    /// Maximum possible power of 2 exponent.
+    pub const MAX_EXP: i32 = 128;
+
+    /// Smallest finite `bf16` value.
+    pub const MIN: bf16 = bf16(0xFF7F);
+
+    /// Minimum possible normal power of 10 exponent.
+    pub const MIN_10_EXP: i32 = -37;
+
+    /// One greater than the minimum possible normal power of 2 exponent.
+    pub const MIN_EXP: i32 = -125;
+
+    /// Smallest positive normal `bf16` value.
+    pub const MIN_POSITIVE: bf16 = bf16(0x0080u16);
+
+    /// Not a Number (NaN).
+    pub const NAN: bf16 = bf16(0x7FC0u16);
+
+    /// Negative infinity (-∞).
+    pub const NEG_INFINITY: bf16 = bf16(0xFF80u16);
+
+    /// The radix or base of the internal representation of `bf16`
+    pub const RADIX: u32 = 2;
+
+    /// Raw transmutation from `u16`.
+    #[inline]
+    pub const fn from_bits(v: u16) -> bf16 {
+        bf16(v)
+    }
+
+    /// Raw transmutation to `u16`.
+    #[inline]
+    pub const fn to_bits(self) -> u16 {
+        self.0
+    }
+
+    /// Returns `true` if this value is `NaN` and `false` otherwise.
+    ///
+    /// ```
+    /// use swim_core::bf16;
+    ///
+    /// let nan = bf16::NAN;
+    /// let f = bf16::from(7.0_f32);
+    ///
+    /// assert!(nan.is_nan());
+    /// assert!(!f.is_nan());
+    /// ```
+    #[inline]
+    pub fn is_nan(self) -> bool {
+        (self.0 & 0x7F80u16 == 0x7F80u16) && (self.0 & 0x007Fu16 != 0)
+    }
+
+    /// Returns `true` if this value is positive infinity or negative infinity
+    /// and false otherwise.
+    ///
+    /// ```
+    /// use swim_core::bf16;
+    ///
+    /// let f = bf16::from(7.0f32);
+    /// let inf = bf16::INFINITY;
+    /// let neg_inf = bf16::NEG_INFINITY;
+    /// let nan = bf16::NAN;
+    ///
+    /// assert!(!f.is_infinite());
+    /// assert!(!nan.is_infinite());
+    ///
+    /// assert!(inf.is_infinite());
+    /// assert!(neg_inf.is_infinite());
+    /// ```
+    #[inline]
+    pub fn is_infinite(self) -> bool {
+        (self.0 & 0x7F80u16 == 0x7F80u16) && (self.0 & 0x007Fu16 == 0)
+    }
+
+    /// Returns `true` if this number is neither infinite nor `NaN`.
+    ///
+    /// ```rust
+    /// use swim_core::bf16;
+    ///
+    /// let f = bf16::from(7.0f32);
+    /// let inf = bf16::INFINITY;
+    /// let neg_inf = bf16::NEG_INFINITY;
+    /// let nan = bf16::NAN;
+    ///
+    /// assert!(f.is_finite());
+    ///
+    /// assert!(!nan.is_finite());
+    /// assert!(!inf.is_finite());
+    /// assert!(!neg_inf.is_finite());
+    /// ```
+    #[inline]
+    pub fn is_finite(self) -> bool {
+        self.0 & 0x7F80u16 != 0x7F80u16
+    }
+
+    /// Returns `true` if the number is neither zero, infinite, subnormal, or `NaN`.
+    ///
+    /// ```rust
+    /// use swim_core::bf16;
+    ///
+    /// let min = bf16::MIN_POSITIVE;
+    /// let max = bf16::MAX;
+    /// let lower_than_min = bf16::from(1.0e-40_f32);
+    /// let zero = bf16::from(0.0_f32);
+    ///
+    /// assert!(min.is_normal());
+    /// assert!(max.is_normal());
+    ///
+    /// assert!(!zero.is_normal());
+    /// assert!(!bf16::NAN.is_normal());
+    /// assert!(!bf16::INFINITY.is_normal());
+    /// // Values between `0` and `min` are Subnormal.
+    /// assert!(!lower_than_min.is_normal());
+    /// ```
+    #[inline]
+    pub fn is_normal(self) -> bool {
+        let exp = self.0 & 0x7F80u16;
+        exp != 0x7F80u16 && exp != 0
+    }
+
+    /// Returns the floating point category of the number. If only one property
+    /// is going to be tested, it is generally faster to use the specific
+    /// predicate instead.
+    ///
+    /// ```rust
+    /// use std::num::FpCategory;
+    /// use swim_core::bf16;
+    ///
+    /// let num = bf16::from(12.4_f32);
+    /// let inf = bf16::INFINITY;
+    ///
+    /// assert_eq!(num.classify(), FpCategory::Normal);
+    /// assert_eq!(inf.classify(), FpCategory::Infinite);
+    /// ```
+    pub fn classify(self) -> FpCategory {
+        let exp = self.0 & 0x7F80u16;
+        let man = self.0 & 0x007Fu16;
+        if exp == 0 {
+            if man == 0 {
+                FpCategory::Zero
+            } else {
+                FpCategory::Subnormal
+            }
+        } else if exp == 0x7F80u16 {
+            if man == 0 {
+                FpCategory::Infinite
+            } else {
+                FpCategory::Nan
+            }
+        } else {
+            FpCategory::Normal
+        }
+    }
+
+    /// Returns a number that represents the sign of `self`.
+    ///
+    /// - `1.0` if the number is positive, `+0.0` or `INFINITY`
+    /// - `-1.0` if the number is negative, `-0.0` or `NEG_INFINITY`
+    /// - `NAN` if the number is `NAN`
+    ///
+    /// ```rust
+    /// use swim_core::bf16;
+    ///
+    /// let f = bf16::from(3.5_f32);
+    ///
+    /// assert_eq!(f.signum(), bf16::from(1.0));
+    /// assert_eq!(bf16::NEG_INFINITY.signum(), bf16::from(-1.0));
+    ///
+    /// assert!(bf16::NAN.signum().is_nan());
+    /// ```
+    pub fn signum(self) -> bf16 {
+        if self.is_nan() {
+            self
+        } else if self.0 & 0x8000u16 != 0 {
+            bf16(0xBF80u16)
+        } else {
+            bf16(0x3F80u16)
+        }
+    }
+
+    /// Returns `true` if and only if `self` has a positive sign, including `+0.0`, `NaN`s with
+    /// positive sign bit and positive infinity.
+    ///
+    /// ```rust
+    /// use swim_core::bf16;
+    ///
+    /// let f = bf16::from(7.0_f32);
+    /// let g = bf16::from(-7.0_f32);
+    ///
+    /// assert!(f.is_sign_positive());
+    /// assert!(!g.is_sign_positive());
+    /// ```
+    #[inline]
+    pub fn is_sign_positive(self) -> bool {
+        !self.is_nan() && self.0 & 0x8000u16 == 0
+    }
+
+    /// Returns `true` if and only if `self` has a negative sign, including `-0.0`, `NaN`s with
+    /// negative sign bit and negative infinity.
+    ///
+    /// ```rust
+    /// use swim_core::bf16;
+    ///
+    /// let f = bf16::from(7.0f32);
+    /// let g = bf16::from(-7.0f32);
+    ///
+    /// assert!(!f.is_sign_negative());
+    /// assert!(g.is_sign_negative());
+    /// ```
+    #[inline]
+    pub fn is_sign_negative(self) -> bool {
+        !self.is_nan() && self.0 & 0x8000u16 != 0
+    }
+
+    /// Computes the absolute value of `self`. Returns `NAN` if the number is
+    /// `NAN`.
+    ///
+    /// ```
+    /// use swim_core::bf16;
+    ///
+    /// assert_eq!(bf16::from(-3.5_f32).abs(), bf16::from(3.5_f32));
+    /// assert!(bf16::NAN.abs().is_nan());
+    /// ```
+    #[inline]
+    pub fn abs(self) -> bf16 {
+        bf16(self.0 & 0x7FFFu16)
+    }
+
+    /// Returns the square root of a number, correctly rounded. Returns `NAN`
+    /// if `self` is a negative number.
+    ///
+    /// ```
+    /// use swim_core::bf16;
+    ///
+    /// assert_eq!(bf16::from(6.25_f32).sqrt(), bf16::from(2.5_f32));
+    /// assert!(bf16::from(-1.0_f32).sqrt().is_nan());
+    /// ```
+    #[inline]
+    pub fn sqrt(self) -> bf16 {
+        // Single precision has more than twice the precision of bfloat16,
+        // plus two bits, so rounding twice rounds correctly.
+        bf16::from(sqrt_f32(f32::from(self)))
+    }
+
+    /// Fused multiply-add. Computes `(self * a) + b` with only one rounding
+    /// error, yielding a more accurate result than an unfused multiply-add.
+    ///
+    /// ```
+    /// use swim_core::bf16;
+    ///
+    /// let x = bf16::from(10.0_f32);
+    /// let m = bf16::from(4.0_f32);
+    /// let b = bf16::from(60.0_f32);
+    ///
+    /// assert_eq!(x.mul_add(m, b), bf16::from(100.0_f32));
+    /// ```
+    pub fn mul_add(self, a: bf16, b: bf16) -> bf16 {
+        // The product of two bfloat16 numbers is exact in double precision.
+        // The sum isn't always, so it's rounded to odd, which rounds
+        // correctly when rounded again to fewer than 52 bits.
+        let product = f64::from(self) * f64::from(a);
+        let addend = f64::from(b);
+        let sum = product + addend;
+        let virtual_addend = sum - product;
+        let error = (product - (sum - virtual_addend)) + (addend - virtual_addend);
+        if sum.is_finite() && error != 0.0 && sum.to_bits() & 1 == 0 {
+            let bits = if (error > 0.0) == (sum > 0.0) { sum.to_bits() + 1 } else { sum.to_bits() - 1 };
+            return bf16::from(f64::from_bits(bits));
+        }
+        bf16::from(sum)
+    }
+
+    /// Returns the minimum of the two numbers. If one of the arguments is
+    /// `NAN`, then the other argument is returned.
+    ///
+    /// ```
+    /// use swim_core::bf16;
+    ///
+    /// let x = bf16::from(1.0_f32);
+    /// let y = bf16::from(-2.0_f32);
+    ///
+    /// assert_eq!(x.min(y), y);
+    /// assert_eq!(bf16::NAN.min(x), x);
+    /// ```
+    #[inline]
+    pub fn min(self, other: bf16) -> bf16 {
+        if self.is_nan() || other < self { other } else { self }
+    }
+
+    /// Returns the maximum of the two numbers. If one of the arguments is
+    /// `NAN`, then the other argument is returned.
+    ///
+    /// ```
+    /// use swim_core::bf16;
+    ///
+    /// let x = bf16::from(1.0_f32);
+    /// let y = bf16::from(-2.0_f32);
+    ///
+    /// assert_eq!(x.max(y), x);
+    /// assert_eq!(bf16::NAN.max(y), y);
+    /// ```
+    #[inline]
+    pub fn max(self, other: bf16) -> bf16 {
+        if self.is_nan() || other > self { other } else { self }
+    }
+
+    /// Returns the integer part of a number.
+    ///
+    /// ```
+    /// use swim_core::bf16;
+    ///
+    /// assert_eq!(bf16::from(3.7_f32).trunc(), bf16::from(3.0_f32));
+    /// assert_eq!(bf16::from(-3.7_f32).trunc(), bf16::from(-3.0_f32));
+    /// ```
+    pub fn trunc(self) -> bf16 {
+        let exp = ((self.0 & 0x7F80u16) >> 7) as i32 - 127;
+        if exp < 0 {
+            bf16(self.0 & 0x8000u16)
+        } else if exp >= 7 {
+            self
+        } else {
+            bf16(self.0 & !(0x007Fu16 >> exp))
+        }
+    }
+
+    /// Returns the largest integer less than or equal to a number.
+    ///
+    /// ```
+    /// use swim_core::bf16;
+    ///
+    /// assert_eq!(bf16::from(3.7_f32).floor(), bf16::from(3.0_f32));
+    /// assert_eq!(bf16::from(-3.7_f32).floor(), bf16::from(-4.0_f32));
+    /// ```
+    pub fn floor(self) -> bf16 {
+        let trunc = self.trunc();
+        if self.is_sign_negative() && trunc != self {
+            trunc - bf16::from(1u8)
+        } else {
+            trunc
+        }
+    }
+
+    /// Returns the smallest integer greater than or equal to a number.
+    ///
+    /// ```
+    /// use swim_core::bf16;
+    ///
+    /// assert_eq!(bf16::from(3.2_f32).ceil(), bf16::from(4.0_f32));
+    /// assert_eq!(bf16::from(-3.7_f32).ceil(), bf16::from(-3.0_f32));
+    /// ```
+    pub fn ceil(self) -> bf16 {
+        let trunc = self.trunc();
+        if self.is_sign_positive() && trunc != self {
+            trunc + bf16::from(1u8)
+        } else {
+            trunc
+        }
+    }
+
+    /// Returns the nearest integer to a number. Rounds half-way cases away
+    /// from `0.0`.
+    ///
+    /// ```
+    /// use swim_core::bf16;
+    ///
+    /// assert_eq!(bf16::from(2.5_f32).round(), bf16::from(3.0_f32));
+    /// assert_eq!(bf16::from(-2.5_f32).round(), bf16::from(-3.0_f32));
+    /// assert_eq!(bf16::from(0.25_f32).round(), bf16::from(0.0_f32));
+    /// ```
+    pub fn round(self) -> bf16 {
+        let exp = ((self.0 & 0x7F80u16) >> 7) as i32 - 127;
+        if exp < -1 {
+            bf16(self.0 & 0x8000u16)
+        } else if exp == -1 {
+            bf16(self.0 & 0x8000u16 | 0x3F80u16)
+        } else if exp >= 7 {
+            self
+        } else {
+            // Adding half a unit carries into the exponent when needed.
+            bf16((self.0 + (0x0040u16 >> exp)) & !(0x007Fu16 >> exp))
+        }
+    }
+
+    /// Returns an integer that orders like the value of a non-`NaN` number,
+    /// with both zeros equal.
+    #[inline]
+    fn order_key(self) -> i16 {
+        if self.0 & 0x8000u16 != 0 {
+            -((self.0 & 0x7FFFu16) as i16)
+        } else {
+            self.0 as i16
+        }
+    }
+
+    /// Splits a positive finite number into an integer mantissa and a
+    /// binary exponent.
+    #[inline]
+    fn decompose(self) -> (u32, i32) {
+        let exp = ((self.0 & 0x7F80u16) >> 7) as i32;
+        let man = (self.0 & 0x007Fu16) as u32;
+        if exp == 0 {
+            (man, -133)
+        } else {
+            (man | 0x0080u32, exp - 134)
+        }
+    }
+}
+
+impl PartialEq for bf16 {
+    fn eq(&self, other: &bf16) -> bool {
+        !self.is_nan() && !other.is_nan() && self.order_key() == other.order_key()
+    }
+}
+
+impl PartialOrd for bf16 {
+    fn partial_cmp(&self, other: &bf16) -> Option<Ordering> {
+        if self.is_nan() || other.is_nan() {
+            None
+        } else {
+            Some(self.order_key().cmp(&other.order_key()))
+        }
+    }
+
+    fn lt(&self, other: &bf16) -> bool {
+        !self.is_nan() && !other.is_nan() && self.order_key() < other.order_key()
+    }
+
+    fn le(&self, other: &bf16) -> bool {
+        !self.is_nan() && !other.is_nan() && self.order_key() <= other.order_key()
+    }
+
+    fn gt(&self, other: &bf16) -> bool {
+        !self.is_nan() && !other.is_nan() && self.order_key() > other.order_key()
+    }
+
+    fn ge(&self, other: &bf16) -> bool {
+        !self.is_nan() && !other.is_nan() && self.order_key() >= other.order_key()
+    }
+}
+
+// Single precision has more than twice the precision of bfloat16, plus two
+// bits, and the same exponent range, so the sum, difference, product and
+// quotient of two bfloat16 numbers, rounded to single precision and then to
+// bfloat16, are rounded correctly.
+macro_rules! impl_binary_op {
+    ($($op:ident, $op_fn:ident, $assign:ident, $assign_fn:ident;)*) => {
+        $(
+            impl $op for bf16 {
+                type Output = bf16;
+
+                #[inline]
+                fn $op_fn(self, rhs: bf16) -> bf16 {
+                    bf16::from(f32::from(self).$op_fn(f32::from(rhs)))
+                }
+            }
+
+            impl $assign for bf16 {
+                #[inline]
+                fn $assign_fn(&mut self, rhs: bf16) {
+                    *self = (*self).$op_fn(rhs);
+                }
+            }
+        )*
+    };
+}
+
+impl_binary_op! {
+    Add, add, AddAssign, add_assign;
+    Sub, sub, SubAssign, sub_assign;
+    Mul, mul, MulAssign, mul_assign;
+    Div, div, DivAssign, div_assign;
+}
+
+impl Rem for bf16 {
+    type Output = bf16;
+
+    /// Returns the remainder of truncated division, which is always exact.
+    fn rem(self, rhs: bf16) -> bf16 {
+        if self.is_nan() || rhs.is_nan() || self.is_infinite() || rhs.abs().0 == 0 {
+            return bf16::NAN;
+        } else if rhs.is_infinite() || self.abs() < rhs.abs() {
+            return self;
+        }
+        let (x, x_exp) = self.abs().decompose();
+        let (y, y_exp) = rhs.abs().decompose();
+        // The exponents span too many bits to align the mantissas, so the
+        // remainder gets reduced one doubling at a time instead.
+        let mut r = x % y;
+        for _ in y_exp..x_exp {
+            r = (r << 1) % y;
+        }
+        let scale = f64::from_bits(((1023 + y_exp) as u64) << 52);
+        let r = bf16::from(r as f64 * scale);
+        if self.is_sign_negative() { -r } else { r }
+    }
+}
+
+impl RemAssign for bf16 {
+    #[inline]
+    fn rem_assign(&mut self, rhs: bf16) {
+        *self = *self % rhs;
+    }
+}
+
+impl Neg for bf16 {
+    type Output = bf16;
+
+    #[inline]
+    fn neg(self) -> bf16 {
+        bf16(self.0 ^ 0x8000u16)
+    }
+}
+
+impl fmt::Debug for bf16 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "0x{:X}", self.0)
+    }
+}
+
+impl fmt::Display for bf16 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", f32::from(*self))
+    }
+}
+
+impl fmt::LowerExp for bf16 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{:e}", f32::from(*self))
+    }
+}
+
+impl fmt::UpperExp for bf16 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{:E}", f32::from(*self))
+    }
+}
+
+impl From<bf16> for f32 {
+    #[inline]
+    fn from(x: bf16) -> f32 {
+        bf16_to_f32(x)
+    }
+}
+
+impl From<bf16> for f64 {
+    #[inline]
+    fn from(x: bf16) -> f64 {
+        bf16_to_f32(x) as f64
+    }
+}
+
+impl From<f32> for bf16 {
+    #[inline]
+    fn from(x: f32) -> bf16 {
+        f32_to_bf16(x)
+    }
+}
+
+impl From<f64> for bf16 {
+    #[inline]
+    fn from(x: f64) -> bf16 {
+        f64_to_bf16(x)
+    }
+}
+
+impl From<i8> for bf16 {
+    #[inline]
+    fn from(x: i8) -> bf16 {
+        f32_to_bf16(f32::from(x))
+    }
+}
+
+impl From<u8> for bf16 {
+    #[inline]
+    fn from(x: u8) -> bf16 {
+        f32_to_bf16(f32::from(x))
+    }
+}
+
+impl FromStr for bf16 {
+    type Err = FloatError;
+    fn from_str(src: &str) -> Result<bf16, FloatError> {
+        parse_float(src)
+    }
+}
+
+/// Rounds to nearest, ties to even; `NaN`s stay `NaN`, and become quiet.
+#[inline]
+fn f32_to_bf16(f: f32) -> bf16 {
+    let bits = f.to_bits();
+    if bits & 0x7F800000u32 == 0x7F800000u32 && bits & 0x007FFFFFu32 != 0 {
+        return bf16((bits >> 16) as u16 | 0x0040u16);
+    }
+    // Rounding up carries into the exponent, and on to infinity, as needed.
+    let round = 0x7FFFu32 + ((bits >> 16) & 1);
+    bf16(((bits + round) >> 16) as u16)
+}
+
+#[inline]
+fn f64_to_bf16(f: f64) -> bf16 {
+    // Rounds to odd on the way to single precision, so that rounding again
+    // to bfloat16 rounds correctly.
+    let single = f as f32;
+    let bits = single.to_bits();
+    let wide = single as f64;
+    if single.is_finite() && wide != f && bits & 1 == 0 {
+        let bits = if wide.abs() < f.abs() { bits + 1 } else { bits - 1 };
+        return f32_to_bf16(f32::from_bits(bits));
+    }
+    f32_to_bf16(single)
+}
+
+#[inline(always)]
+pub fn bf16_to_f32(f: bf16) -> f32 {
+    f32::from_bits((f.0 as u32) << 16)
+}
+
+#[inline(always)]
+fn sqrt_f32(f: f32) -> f32 {
+    extern "C" {
+        #[link_name = "llvm.sqrt.f32"]
+        fn llvm_sqrt_f32(f: f32) -> f32;
+    }
+    unsafe { llvm_sqrt_f32(f) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_f32_bits(bits: u32) -> u16 {
+        bf16::from(f32::from_bits(bits)).to_bits()
+    }
+
+    #[test]
+    fn test_f32_to_bf16_round_to_nearest_even() {
+        assert_eq!(from_f32_bits(0x3F800000), 0x3F80);
+        // Below, at and above the halfway point, from an even bfloat16.
+        assert_eq!(from_f32_bits(0x3F807FFF), 0x3F80);
+        assert_eq!(from_f32_bits(0x3F808000), 0x3F80);
+        assert_eq!(from_f32_bits(0x3F808001), 0x3F81);
+        // Ties round up from an odd bfloat16.
+        assert_eq!(from_f32_bits(0x3F818000), 0x3F82);
+        assert_eq!(from_f32_bits(0xBF818000), 0xBF82);
+        // Rounding up carries into the exponent.
+        assert_eq!(from_f32_bits(0x3FFF8000), 0x4000);
+        // Subnormals round the same way.
+        assert_eq!(from_f32_bits(0x00008000), 0x0000);
+        assert_eq!(from_f32_bits(0x00018000), 0x0002);
+        assert_eq!(from_f32_bits(0x80000000), 0x8000);
+    }
+
+    #[test]
+    fn test_f32_to_bf16_overflow() {
+        assert_eq!(from_f32_bits(0x7F7F7FFF), bf16::MAX.to_bits());
+        assert_eq!(from_f32_bits(0x7F7F8000), bf16::INFINITY.to_bits());
+        assert_eq!(bf16::from(core::f32::MAX), bf16::INFINITY);
+        assert_eq!(bf16::from(core::f32::MIN), bf16::NEG_INFINITY);
+        assert_eq!(bf16::from(core::f32::INFINITY), bf16::INFINITY);
+        assert_eq!(bf16::from(1e300f64), bf16::INFINITY);
+    }
+
+    #[test]
+    fn test_f32_to_bf16_nan() {
+        // Signaling NaNs whose payload lies entirely in the truncated bits
+        // would otherwise become infinities.
+        assert_eq!(from_f32_bits(0x7F800001), 0x7FC0);
+        assert_eq!(from_f32_bits(0xFF800001), 0xFFC0);
+        assert_eq!(from_f32_bits(0x7FA00000), 0x7FE0);
+        assert_eq!(from_f32_bits(0x7FC00000), 0x7FC0);
+        assert!(bf16::from(core::f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn test_f64_to_bf16_rounds_once() {
+        // 1 + 2^-8 ± 2^-40 rounds to the tie 1 + 2^-8 in single precision,
+        // but lies just either side of it in double precision.
+        assert_eq!(bf16::from(f64::from_bits(0x3FF0100000001000)).to_bits(), 0x3F81);
+        assert_eq!(bf16::from(f64::from_bits(0x3FF00FFFFFFFF000)).to_bits(), 0x3F80);
+        assert_eq!(bf16::from(f64::from_bits(0x3FF0100000000000)).to_bits(), 0x3F80);
+    }
+
+    #[test]
+    fn test_bf16_arithmetic() {
+        let x = bf16::from(1.5f32);
+        let y = bf16::from(2.25f32);
+        assert_eq!(x + y, bf16::from(3.75f32));
+        assert_eq!(x - y, bf16::from(-0.75f32));
+        assert_eq!(x * y, bf16::from(3.375f32));
+        assert_eq!(y / x, bf16::from(1.5f32));
+        assert_eq!(bf16::from(7u8) % bf16::from(4u8), bf16::from(3u8));
+        assert_eq!(-x, bf16::from(-1.5f32));
+        // 257 needs 9 significant bits, and ties to even.
+        assert_eq!(bf16::from(256.0f32) + bf16::from(1u8), bf16::from(256.0f32));
+        assert_eq!(bf16::from(1u8) / bf16::from(3u8), bf16::from(1.0f32 / 3.0));
+        assert_eq!(bf16::MAX + bf16::MAX, bf16::INFINITY);
+        assert!((bf16::INFINITY - bf16::INFINITY).is_nan());
+        let mut z = x;
+        z += y;
+        z *= bf16::from(2u8);
+        assert_eq!(z, bf16::from(7.5f32));
+        assert_eq!(bf16::from(4u8).sqrt(), bf16::from(2u8));
+        assert_eq!(x.mul_add(y, bf16::from(1u8)), bf16::from(4.375f32));
+    }
+
+    #[test]
+    fn test_bf16_from_str() {
+        assert_eq!("1.5".parse::<bf16>(), Ok(bf16::from(1.5f32)));
+        assert_eq!("-0".parse::<bf16>().map(bf16::to_bits), Ok(0x8000));
+        assert_eq!("3.14159".parse::<bf16>(), Ok(consts::PI));
+        assert_eq!("1e39".parse::<bf16>(), Ok(bf16::INFINITY));
+        assert_eq!("-inf".parse::<bf16>(), Ok(bf16::NEG_INFINITY));
+        assert!("NaN".parse::<bf16>().unwrap().is_nan());
+        // Rounds the exact decimal value once.
+        assert_eq!("1.00390625000000001".parse::<bf16>().map(bf16::to_bits), Ok(0x3F81));
+        assert_eq!("1.00390625".parse::<bf16>().map(bf16::to_bits), Ok(0x3F80));
+        assert_eq!("".parse::<bf16>(), Err(FloatError::Empty));
+        assert_eq!("1.5x".parse::<bf16>(), Err(FloatError::Invalid));
+    }
+}
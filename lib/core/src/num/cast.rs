@@ -0,0 +1,457 @@
+use core::convert::TryFrom;
+use crate::num::f16::f16;
+use crate::num::bf16::bf16;
+
+/// Primitive number that converts to every other primitive number type
+/// whose values can exactly represent it. Conversions never round, wrap, or
+/// saturate; NaN converts to NaN in every floating point type, and to no
+/// integer type.
+pub trait NumCast: Copy {
+    /// Converts this number to a `u8` value, if exactly representable.
+    fn to_u8(self) -> Option<u8>;
+
+    /// Converts this number to an `i8` value, if exactly representable.
+    fn to_i8(self) -> Option<i8>;
+
+    /// Converts this number to a `u16` value, if exactly representable.
+    fn to_u16(self) -> Option<u16>;
+
+    /// Converts this number to an `i16` value, if exactly representable.
+    fn to_i16(self) -> Option<i16>;
+
+    /// Converts this number to a `u32` value, if exactly representable.
+    fn to_u32(self) -> Option<u32>;
+
+    /// Converts this number to an `i32` value, if exactly representable.
+    fn to_i32(self) -> Option<i32>;
+
+    /// Converts this number to a `u64` value, if exactly representable.
+    fn to_u64(self) -> Option<u64>;
+
+    /// Converts this number to an `i64` value, if exactly representable.
+    fn to_i64(self) -> Option<i64>;
+
+    /// Converts this number to a `usize` value, if exactly representable.
+    fn to_usize(self) -> Option<usize>;
+
+    /// Converts this number to an `isize` value, if exactly representable.
+    fn to_isize(self) -> Option<isize>;
+
+    /// Converts this number to an `f16` value, if exactly representable.
+    fn to_f16(self) -> Option<f16>;
+
+    /// Converts this number to a `bf16` value, if exactly representable.
+    fn to_bf16(self) -> Option<bf16>;
+
+    /// Converts this number to an `f32` value, if exactly representable.
+    fn to_f32(self) -> Option<f32>;
+
+    /// Converts this number to an `f64` value, if exactly representable.
+    fn to_f64(self) -> Option<f64>;
+
+    /// Converts this number to a `T` value, if exactly representable.
+    #[inline]
+    fn try_into_num<T: TryFromNum>(self) -> Option<T> {
+        T::try_from_num(self)
+    }
+}
+
+/// Primitive number type that can be exactly converted from any `NumCast`
+/// number whose value it can represent.
+pub trait TryFromNum: Sized {
+    /// Converts `value` to `Self`, if exactly representable.
+    fn try_from_num<N: NumCast>(value: N) -> Option<Self>;
+}
+
+macro_rules! impl_try_from_num {
+    ($($ty: ty => $to: ident),*) => ($(
+        impl TryFromNum for $ty {
+            #[inline]
+            fn try_from_num<N: NumCast>(value: N) -> Option<$ty> {
+                value.$to()
+            }
+        }
+    )*);
+}
+
+impl_try_from_num!(u8 => to_u8, i8 => to_i8, u16 => to_u16, i16 => to_i16,
+                   u32 => to_u32, i32 => to_i32, u64 => to_u64, i64 => to_i64,
+                   usize => to_usize, isize => to_isize, f16 => to_f16,
+                   bf16 => to_bf16, f32 => to_f32, f64 => to_f64);
+
+/// Number of significant binary digits, and the exponent bound of finite
+/// values, of each floating point type.
+const F16_DIGITS: u32 = 11;
+const F16_MAX_EXP: u32 = 16;
+const BF16_DIGITS: u32 = 8;
+const BF16_MAX_EXP: u32 = 128;
+const F32_DIGITS: u32 = 24;
+const F32_MAX_EXP: u32 = 128;
+const F64_DIGITS: u32 = 53;
+const F64_MAX_EXP: u32 = 1024;
+
+/// Returns `true` if the integer `value` is exactly representable by a
+/// floating point type with `digits` significant binary digits, whose finite
+/// values have magnitudes less than `2^max_exp`.
+#[inline]
+fn int_fits_float(value: i128, digits: u32, max_exp: u32) -> bool {
+    let magnitude = if value < 0 { value.wrapping_neg() as u128 } else { value as u128 };
+    if magnitude == 0 {
+        return true;
+    }
+    let bits = 128 - magnitude.leading_zeros();
+    bits - magnitude.trailing_zeros() <= digits && bits <= max_exp
+}
+
+macro_rules! impl_num_cast_int {
+    ($($ty: ty),*) => ($(
+        impl NumCast for $ty {
+            #[inline]
+            fn to_u8(self) -> Option<u8> {
+                u8::try_from(self).ok()
+            }
+
+            #[inline]
+            fn to_i8(self) -> Option<i8> {
+                i8::try_from(self).ok()
+            }
+
+            #[inline]
+            fn to_u16(self) -> Option<u16> {
+                u16::try_from(self).ok()
+            }
+
+            #[inline]
+            fn to_i16(self) -> Option<i16> {
+                i16::try_from(self).ok()
+            }
+
+            #[inline]
+            fn to_u32(self) -> Option<u32> {
+                u32::try_from(self).ok()
+            }
+
+            #[inline]
+            fn to_i32(self) -> Option<i32> {
+                i32::try_from(self).ok()
+            }
+
+            #[inline]
+            fn to_u64(self) -> Option<u64> {
+                u64::try_from(self).ok()
+            }
+
+            #[inline]
+            fn to_i64(self) -> Option<i64> {
+                i64::try_from(self).ok()
+            }
+
+            #[inline]
+            fn to_usize(self) -> Option<usize> {
+                usize::try_from(self).ok()
+            }
+
+            #[inline]
+            fn to_isize(self) -> Option<isize> {
+                isize::try_from(self).ok()
+            }
+
+            #[inline]
+            fn to_f16(self) -> Option<f16> {
+                if int_fits_float(self as i128, F16_DIGITS, F16_MAX_EXP) {
+                    Some(f16::from(self as f32))
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            fn to_bf16(self) -> Option<bf16> {
+                if int_fits_float(self as i128, BF16_DIGITS, BF16_MAX_EXP) {
+                    Some(bf16::from(self as f32))
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            fn to_f32(self) -> Option<f32> {
+                if int_fits_float(self as i128, F32_DIGITS, F32_MAX_EXP) {
+                    Some(self as f32)
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            fn to_f64(self) -> Option<f64> {
+                if int_fits_float(self as i128, F64_DIGITS, F64_MAX_EXP) {
+                    Some(self as f64)
+                } else {
+                    None
+                }
+            }
+        }
+    )*);
+}
+
+impl_num_cast_int!(u8, i8, u16, i16, u32, i32, u64, i64, usize, isize);
+
+/// Converts the float `$value` of type `$fty` to the integer type `$ity`, if
+/// the value is integral and in range.
+macro_rules! float_to_int {
+    ($value: expr, $fty: ty, $ity: ty) => ({
+        let value = $value;
+        // Both bounds are powers of two, and so exactly representable; the
+        // exclusive upper bound is computed by halves to avoid overflowing.
+        let min = <$ity>::min_value() as $fty;
+        let max = (<$ity>::max_value() / 2 + 1) as $fty * 2.0;
+        if min <= value && value < max && value as $ity as $fty == value {
+            Some(value as $ity)
+        } else {
+            None
+        }
+    });
+}
+
+macro_rules! impl_num_cast_float {
+    ($($fty: ident),*) => ($(
+        impl NumCast for $fty {
+            #[inline]
+            fn to_u8(self) -> Option<u8> {
+                float_to_int!(self, $fty, u8)
+            }
+
+            #[inline]
+            fn to_i8(self) -> Option<i8> {
+                float_to_int!(self, $fty, i8)
+            }
+
+            #[inline]
+            fn to_u16(self) -> Option<u16> {
+                float_to_int!(self, $fty, u16)
+            }
+
+            #[inline]
+            fn to_i16(self) -> Option<i16> {
+                float_to_int!(self, $fty, i16)
+            }
+
+            #[inline]
+            fn to_u32(self) -> Option<u32> {
+                float_to_int!(self, $fty, u32)
+            }
+
+            #[inline]
+            fn to_i32(self) -> Option<i32> {
+                float_to_int!(self, $fty, i32)
+            }
+
+            #[inline]
+            fn to_u64(self) -> Option<u64> {
+                float_to_int!(self, $fty, u64)
+            }
+
+            #[inline]
+            fn to_i64(self) -> Option<i64> {
+                float_to_int!(self, $fty, i64)
+            }
+
+            #[inline]
+            fn to_usize(self) -> Option<usize> {
+                float_to_int!(self, $fty, usize)
+            }
+
+            #[inline]
+            fn to_isize(self) -> Option<isize> {
+                float_to_int!(self, $fty, isize)
+            }
+
+            #[inline]
+            fn to_f16(self) -> Option<f16> {
+                let value = f16::from(self);
+                if $fty::from(value) == self || self.is_nan() {
+                    Some(value)
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            fn to_bf16(self) -> Option<bf16> {
+                let value = bf16::from(self);
+                if $fty::from(value) == self || self.is_nan() {
+                    Some(value)
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            fn to_f32(self) -> Option<f32> {
+                let value = self as f32;
+                if value as $fty == self || self.is_nan() {
+                    Some(value)
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            fn to_f64(self) -> Option<f64> {
+                Some(self as f64)
+            }
+        }
+    )*);
+}
+
+impl_num_cast_float!(f32, f64);
+
+macro_rules! impl_num_cast_half {
+    ($($hty: ident => $same: ident, $oty: ident => $other: ident),*) => ($(
+        // Half precision values widen exactly to f32, so defer to its checks.
+        impl NumCast for $hty {
+            #[inline]
+            fn to_u8(self) -> Option<u8> {
+                f32::from(self).to_u8()
+            }
+
+            #[inline]
+            fn to_i8(self) -> Option<i8> {
+                f32::from(self).to_i8()
+            }
+
+            #[inline]
+            fn to_u16(self) -> Option<u16> {
+                f32::from(self).to_u16()
+            }
+
+            #[inline]
+            fn to_i16(self) -> Option<i16> {
+                f32::from(self).to_i16()
+            }
+
+            #[inline]
+            fn to_u32(self) -> Option<u32> {
+                f32::from(self).to_u32()
+            }
+
+            #[inline]
+            fn to_i32(self) -> Option<i32> {
+                f32::from(self).to_i32()
+            }
+
+            #[inline]
+            fn to_u64(self) -> Option<u64> {
+                f32::from(self).to_u64()
+            }
+
+            #[inline]
+            fn to_i64(self) -> Option<i64> {
+                f32::from(self).to_i64()
+            }
+
+            #[inline]
+            fn to_usize(self) -> Option<usize> {
+                f32::from(self).to_usize()
+            }
+
+            #[inline]
+            fn to_isize(self) -> Option<isize> {
+                f32::from(self).to_isize()
+            }
+
+            #[inline]
+            fn $same(self) -> Option<$hty> {
+                Some(self)
+            }
+
+            #[inline]
+            fn $other(self) -> Option<$oty> {
+                f32::from(self).$other()
+            }
+
+            #[inline]
+            fn to_f32(self) -> Option<f32> {
+                Some(f32::from(self))
+            }
+
+            #[inline]
+            fn to_f64(self) -> Option<f64> {
+                Some(f64::from(self))
+            }
+        }
+    )*);
+}
+
+impl_num_cast_half!(f16 => to_f16, bf16 => to_bf16,
+                    bf16 => to_bf16, f16 => to_f16);
+
+#[cfg(test)]
+mod tests {
+    use core::{i64, u64};
+    use super::*;
+
+    #[test]
+    fn test_int_to_int() {
+        assert_eq!(255u64.to_u8(), Some(255));
+        assert_eq!(256u64.to_u8(), None);
+        assert_eq!((-1i32).to_u32(), None);
+        assert_eq!((-128i64).to_i8(), Some(-128));
+        assert_eq!((-129i64).to_i8(), None);
+        assert_eq!(u64::MAX.to_i64(), None);
+        assert_eq!(i64::MIN.try_into_num::<i64>(), Some(i64::MIN));
+        assert_eq!(u32::try_from_num(4_000_000_000u64), Some(4_000_000_000));
+    }
+
+    #[test]
+    fn test_int_to_float() {
+        assert_eq!(2048u32.to_f16(), Some(f16::from(2048.0f32)));
+        assert_eq!(2049u32.to_f16(), None);
+        assert_eq!(65504u32.to_f16(), Some(f16::from(65504.0f32)));
+        assert_eq!(65536u32.to_f16(), None);
+        assert_eq!(256i32.to_bf16(), Some(bf16::from(256.0f32)));
+        assert_eq!(257i32.to_bf16(), None);
+        assert_eq!(16_777_216u32.to_f32(), Some(16_777_216.0));
+        assert_eq!(16_777_217u32.to_f32(), None);
+        assert_eq!(u64::MAX.to_f32(), None);
+        assert_eq!(u64::MAX.to_f64(), None);
+        assert_eq!(i64::MIN.to_f64(), Some(-9_223_372_036_854_775_808.0));
+        assert_eq!((1u64 << 63).to_f32(), Some(9_223_372_036_854_775_808.0));
+    }
+
+    #[test]
+    fn test_float_to_int() {
+        assert_eq!(255.0f32.to_u8(), Some(255));
+        assert_eq!(256.0f32.to_u8(), None);
+        assert_eq!(1.5f64.to_i32(), None);
+        assert_eq!((-0.0f64).to_u32(), Some(0));
+        assert_eq!((-1.0f64).to_u32(), None);
+        assert_eq!(core::f64::NAN.to_i64(), None);
+        assert_eq!(core::f64::INFINITY.to_u64(), None);
+        assert_eq!(18_446_744_073_709_551_616.0f64.to_u64(), None);
+        assert_eq!(18_446_744_073_709_549_568.0f64.to_u64(), Some(18_446_744_073_709_549_568));
+        assert_eq!((-9_223_372_036_854_775_808.0f32).to_i64(), Some(i64::MIN));
+        assert_eq!(9_223_372_036_854_775_808.0f32.to_i64(), None);
+        assert_eq!(f16::from(-7.0f32).to_i8(), Some(-7));
+        assert_eq!(f16::from(0.5f32).to_i8(), None);
+    }
+
+    #[test]
+    fn test_float_to_float() {
+        assert_eq!(0.5f64.to_f32(), Some(0.5));
+        assert_eq!(0.1f64.to_f32(), None);
+        assert_eq!(1e300f64.to_f32(), None);
+        assert_eq!(core::f64::INFINITY.to_f32(), Some(core::f32::INFINITY));
+        assert!(core::f64::NAN.to_f32().unwrap().is_nan());
+        assert!(core::f32::NAN.to_f16().unwrap().is_nan());
+        assert_eq!(0.1f32.to_f64(), Some(0.1f32 as f64));
+        assert_eq!(0.25f32.to_f16(), Some(f16::from(0.25f32)));
+        assert_eq!(0.1f32.to_f16(), None);
+        assert_eq!(1e10f32.to_f16(), None);
+        assert_eq!(1e10f32.to_bf16(), None);
+        assert_eq!(f16::from(0.5f32).to_bf16(), Some(bf16::from(0.5f32)));
+        assert_eq!(f16::from(1.001f32).to_bf16(), None);
+        assert_eq!(bf16::from(1e10f32).to_f16(), None);
+    }
+}
@@ -2,8 +2,10 @@
 
 use core::cmp::Ordering;
 use core::fmt;
-use core::num::{FpCategory, ParseFloatError};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
+use core::num::FpCategory;
 use core::str::FromStr;
+use crate::num::float::{parse_float, FloatError};
 
 /// The 16-bit floating point type.
 #[repr(C)]
@@ -295,11 +297,204 @@ impl f16 {
     pub fn is_sign_negative(self) -> bool {
         !self.is_nan() && self.0 & 0x8000u16 != 0
     }
+
+    /// Computes the absolute value of `self`. Returns `NAN` if the number is
+    /// `NAN`.
+    ///
+    /// ```
+    /// use swim_core::f16;
+    ///
+    /// assert_eq!(f16::from(-3.5_f32).abs(), f16::from(3.5_f32));
+    /// assert!(f16::NAN.abs().is_nan());
+    /// ```
+    #[inline]
+    pub fn abs(self) -> f16 {
+        f16(self.0 & 0x7FFFu16)
+    }
+
+    /// Returns the square root of a number, correctly rounded. Returns `NAN`
+    /// if `self` is a negative number.
+    ///
+    /// ```
+    /// use swim_core::f16;
+    ///
+    /// assert_eq!(f16::from(6.25_f32).sqrt(), f16::from(2.5_f32));
+    /// assert!(f16::from(-1.0_f32).sqrt().is_nan());
+    /// ```
+    #[inline]
+    pub fn sqrt(self) -> f16 {
+        // Single precision has more than twice the precision of half
+        // precision, plus two bits, so rounding twice rounds correctly.
+        f16::from(sqrt_f32(f32::from(self)))
+    }
+
+    /// Fused multiply-add. Computes `(self * a) + b` with only one rounding
+    /// error, yielding a more accurate result than an unfused multiply-add.
+    ///
+    /// ```
+    /// use swim_core::f16;
+    ///
+    /// let x = f16::from(10.0_f32);
+    /// let m = f16::from(4.0_f32);
+    /// let b = f16::from(60.0_f32);
+    ///
+    /// assert_eq!(x.mul_add(m, b), f16::from(100.0_f32));
+    /// ```
+    pub fn mul_add(self, a: f16, b: f16) -> f16 {
+        // The product of two half precision numbers is exact in double
+        // precision. The sum isn't always, so it's rounded to odd, which
+        // rounds correctly when rounded again to fewer than 52 bits.
+        let product = f64::from(self) * f64::from(a);
+        let addend = f64::from(b);
+        let sum = product + addend;
+        let virtual_addend = sum - product;
+        let error = (product - (sum - virtual_addend)) + (addend - virtual_addend);
+        if sum.is_finite() && error != 0.0 && sum.to_bits() & 1 == 0 {
+            let bits = if (error > 0.0) == (sum > 0.0) { sum.to_bits() + 1 } else { sum.to_bits() - 1 };
+            return f16::from(f64::from_bits(bits));
+        }
+        f16::from(sum)
+    }
+
+    /// Returns the minimum of the two numbers. If one of the arguments is
+    /// `NAN`, then the other argument is returned.
+    ///
+    /// ```
+    /// use swim_core::f16;
+    ///
+    /// let x = f16::from(1.0_f32);
+    /// let y = f16::from(-2.0_f32);
+    ///
+    /// assert_eq!(x.min(y), y);
+    /// assert_eq!(f16::NAN.min(x), x);
+    /// ```
+    #[inline]
+    pub fn min(self, other: f16) -> f16 {
+        if self.is_nan() || other < self { other } else { self }
+    }
+
+    /// Returns the maximum of the two numbers. If one of the arguments is
+    /// `NAN`, then the other argument is returned.
+    ///
+    /// ```
+    /// use swim_core::f16;
+    ///
+    /// let x = f16::from(1.0_f32);
+    /// let y = f16::from(-2.0_f32);
+    ///
+    /// assert_eq!(x.max(y), x);
+    /// assert_eq!(f16::NAN.max(y), y);
+    /// ```
+    #[inline]
+    pub fn max(self, other: f16) -> f16 {
+        if self.is_nan() || other > self { other } else { self }
+    }
+
+    /// Returns the integer part of a number.
+    ///
+    /// ```
+    /// use swim_core::f16;
+    ///
+    /// assert_eq!(f16::from(3.7_f32).trunc(), f16::from(3.0_f32));
+    /// assert_eq!(f16::from(-3.7_f32).trunc(), f16::from(-3.0_f32));
+    /// ```
+    pub fn trunc(self) -> f16 {
+        let exp = ((self.0 & 0x7C00u16) >> 10) as i32 - 15;
+        if exp < 0 {
+            f16(self.0 & 0x8000u16)
+        } else if exp >= 10 {
+            self
+        } else {
+            f16(self.0 & !(0x03FFu16 >> exp))
+        }
+    }
+
+    /// Returns the largest integer less than or equal to a number.
+    ///
+    /// ```
+    /// use swim_core::f16;
+    ///
+    /// assert_eq!(f16::from(3.7_f32).floor(), f16::from(3.0_f32));
+    /// assert_eq!(f16::from(-3.7_f32).floor(), f16::from(-4.0_f32));
+    /// ```
+    pub fn floor(self) -> f16 {
+        let trunc = self.trunc();
+        if self.is_sign_negative() && trunc != self {
+            trunc - f16::from(1u8)
+        } else {
+            trunc
+        }
+    }
+
+    /// Returns the smallest integer greater than or equal to a number.
+    ///
+    /// ```
+    /// use swim_core::f16;
+    ///
+    /// assert_eq!(f16::from(3.2_f32).ceil(), f16::from(4.0_f32));
+    /// assert_eq!(f16::from(-3.7_f32).ceil(), f16::from(-3.0_f32));
+    /// ```
+    pub fn ceil(self) -> f16 {
+        let trunc = self.trunc();
+        if self.is_sign_positive() && trunc != self {
+            trunc + f16::from(1u8)
+        } else {
+            trunc
+        }
+    }
+
+    /// Returns the nearest integer to a number. Rounds half-way cases away
+    /// from `0.0`.
+    ///
+    /// ```
+    /// use swim_core::f16;
+    ///
+    /// assert_eq!(f16::from(2.5_f32).round(), f16::from(3.0_f32));
+    /// assert_eq!(f16::from(-2.5_f32).round(), f16::from(-3.0_f32));
+    /// assert_eq!(f16::from(0.25_f32).round(), f16::from(0.0_f32));
+    /// ```
+    pub fn round(self) -> f16 {
+        let exp = ((self.0 & 0x7C00u16) >> 10) as i32 - 15;
+        if exp < -1 {
+            f16(self.0 & 0x8000u16)
+        } else if exp == -1 {
+            f16(self.0 & 0x8000u16 | 0x3C00u16)
+        } else if exp >= 10 {
+            self
+        } else {
+            // Adding half a unit carries into the exponent when needed.
+            f16((self.0 + (0x0200u16 >> exp)) & !(0x03FFu16 >> exp))
+        }
+    }
+
+    /// Returns an integer that orders like the value of a non-`NaN` number,
+    /// with both zeros equal.
+    #[inline]
+    fn order_key(self) -> i16 {
+        if self.0 & 0x8000u16 != 0 {
+            -((self.0 & 0x7FFFu16) as i16)
+        } else {
+            self.0 as i16
+        }
+    }
+
+    /// Splits a positive finite number into an integer mantissa and a
+    /// binary exponent.
+    #[inline]
+    fn decompose(self) -> (u64, i32) {
+        let exp = ((self.0 & 0x7C00u16) >> 10) as i32;
+        let man = (self.0 & 0x03FFu16) as u64;
+        if exp == 0 {
+            (man, -24)
+        } else {
+            (man | 0x0400u64, exp - 25)
+        }
+    }
 }
 
 impl PartialEq for f16 {
     fn eq(&self, other: &f16) -> bool {
-        !self.is_nan() && !other.is_nan() && self.0 == other.0
+        !self.is_nan() && !other.is_nan() && self.order_key() == other.order_key()
     }
 }
 
@@ -307,29 +502,94 @@ impl PartialOrd for f16 {
     fn partial_cmp(&self, other: &f16) -> Option<Ordering> {
         if self.is_nan() || other.is_nan() {
             None
-        } else if self.0 == other.0 {
-            Some(Ordering::Equal)
-        } else if self.0 < other.0 {
-            Some(Ordering::Less)
         } else {
-            Some(Ordering::Greater)
+            Some(self.order_key().cmp(&other.order_key()))
         }
     }
 
     fn lt(&self, other: &f16) -> bool {
-        !self.is_nan() && !other.is_nan() && self.0 < other.0
+        !self.is_nan() && !other.is_nan() && self.order_key() < other.order_key()
     }
 
     fn le(&self, other: &f16) -> bool {
-        !self.is_nan() && !other.is_nan() && self.0 <= other.0
+        !self.is_nan() && !other.is_nan() && self.order_key() <= other.order_key()
     }
 
     fn gt(&self, other: &f16) -> bool {
-        !self.is_nan() && !other.is_nan() && self.0 > other.0
+        !self.is_nan() && !other.is_nan() && self.order_key() > other.order_key()
     }
 
     fn ge(&self, other: &f16) -> bool {
-        !self.is_nan() && !other.is_nan() && self.0 >= other.0
+        !self.is_nan() && !other.is_nan() && self.order_key() >= other.order_key()
+    }
+}
+
+// Single precision has more than twice the precision of half precision,
+// plus two bits, so the sum, difference, product and quotient of two half
+// precision numbers, rounded to single and then to half precision, are
+// rounded correctly.
+macro_rules! impl_binary_op {
+    ($($op:ident, $op_fn:ident, $assign:ident, $assign_fn:ident;)*) => {
+        $(
+            impl $op for f16 {
+                type Output = f16;
+
+                #[inline]
+                fn $op_fn(self, rhs: f16) -> f16 {
+                    f16::from(f32::from(self).$op_fn(f32::from(rhs)))
+                }
+            }
+
+            impl $assign for f16 {
+                #[inline]
+                fn $assign_fn(&mut self, rhs: f16) {
+                    *self = (*self).$op_fn(rhs);
+                }
+            }
+        )*
+    };
+}
+
+impl_binary_op! {
+    Add, add, AddAssign, add_assign;
+    Sub, sub, SubAssign, sub_assign;
+    Mul, mul, MulAssign, mul_assign;
+    Div, div, DivAssign, div_assign;
+}
+
+impl Rem for f16 {
+    type Output = f16;
+
+    /// Returns the remainder of truncated division, which is always exact.
+    fn rem(self, rhs: f16) -> f16 {
+        if self.is_nan() || rhs.is_nan() || self.is_infinite() || rhs.abs().0 == 0 {
+            return f16::NAN;
+        } else if rhs.is_infinite() || self.abs() < rhs.abs() {
+            return self;
+        }
+        let (x, x_exp) = self.abs().decompose();
+        let (y, y_exp) = rhs.abs().decompose();
+        let exp = if x_exp < y_exp { x_exp } else { y_exp };
+        let r = (x << (x_exp - exp)) % (y << (y_exp - exp));
+        let scale = f64::from_bits(((1023 + exp) as u64) << 52);
+        let r = f16::from(r as f64 * scale);
+        if self.is_sign_negative() { -r } else { r }
+    }
+}
+
+impl RemAssign for f16 {
+    #[inline]
+    fn rem_assign(&mut self, rhs: f16) {
+        *self = *self % rhs;
+    }
+}
+
+impl Neg for f16 {
+    type Output = f16;
+
+    #[inline]
+    fn neg(self) -> f16 {
+        f16(self.0 ^ 0x8000u16)
     }
 }
 
@@ -400,9 +660,91 @@ impl From<u8> for f16 {
 }
 
 impl FromStr for f16 {
-    type Err = ParseFloatError;
-    fn from_str(src: &str) -> Result<f16, ParseFloatError> {
-        f32::from_str(src).map(|x| f16::from(x))
+    type Err = FloatError;
+    fn from_str(src: &str) -> Result<f16, FloatError> {
+        parse_float(src)
+    }
+}
+
+/// Converts each `f16` in `src` to the `f32` at the same index in `dst`,
+/// with F16C or NEON instructions when the `simd` feature is enabled and the
+/// target has them.
+///
+/// ```
+/// use swim_core::f16;
+/// use swim_core::num::f16::convert_slice_f16_to_f32;
+///
+/// let src = [f16::from(1.5_f32), f16::NEG_INFINITY, f16::MAX];
+/// let mut dst = [0.0_f32; 3];
+/// convert_slice_f16_to_f32(&src, &mut dst);
+///
+/// assert_eq!(dst, [1.5, core::f32::NEG_INFINITY, 65504.0]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+pub fn convert_slice_f16_to_f32(src: &[f16], dst: &mut [f32]) {
+    assert_eq!(src.len(), dst.len());
+    let done = simd::f16_to_f32(src, dst);
+    for (x, y) in src[done..].iter().zip(dst[done..].iter_mut()) {
+        *y = half_to_single(x.0);
+    }
+}
+
+/// Converts each `f32` in `src` to the nearest `f16`, ties to even, at the
+/// same index in `dst`, with F16C or NEON instructions when the `simd`
+/// feature is enabled and the target has them.
+///
+/// ```
+/// use swim_core::f16;
+/// use swim_core::num::f16::convert_slice_f32_to_f16;
+///
+/// let src = [1.5_f32, 1.0e-10, 65520.0];
+/// let mut dst = [f16::default(); 3];
+/// convert_slice_f32_to_f16(&src, &mut dst);
+///
+/// assert_eq!(dst, [f16::from(1.5_f32), f16::from(0.0_f32), f16::INFINITY]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+pub fn convert_slice_f32_to_f16(src: &[f32], dst: &mut [f16]) {
+    assert_eq!(src.len(), dst.len());
+    let done = simd::f32_to_f16(src, dst);
+    for (x, y) in src[done..].iter().zip(dst[done..].iter_mut()) {
+        *y = f16(single_to_half(*x));
+    }
+}
+
+/// Converts each `f16` in `src` to the `f64` at the same index in `dst`,
+/// with F16C or NEON instructions when the `simd` feature is enabled and the
+/// target has them.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+pub fn convert_slice_f16_to_f64(src: &[f16], dst: &mut [f64]) {
+    assert_eq!(src.len(), dst.len());
+    let done = simd::f16_to_f64(src, dst);
+    for (x, y) in src[done..].iter().zip(dst[done..].iter_mut()) {
+        *y = half_to_single(x.0) as f64;
+    }
+}
+
+/// Converts each `f64` in `src` to the nearest `f16`, ties to even, at the
+/// same index in `dst`, with F16C or NEON instructions when the `simd`
+/// feature is enabled and the target has them.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+pub fn convert_slice_f64_to_f16(src: &[f64], dst: &mut [f16]) {
+    assert_eq!(src.len(), dst.len());
+    let done = simd::f64_to_f16(src, dst);
+    for (x, y) in src[done..].iter().zip(dst[done..].iter_mut()) {
+        *y = f16(single_to_half(double_to_single_odd(*x)));
     }
 }
 
@@ -441,3 +783,404 @@ pub fn f16_to_f64(f: f16) -> f64 {
     }
     unsafe { convert_from_fp16_f64(f.0) }
 }
+
+#[inline(always)]
+fn sqrt_f32(f: f32) -> f32 {
+    extern "C" {
+        #[link_name = "llvm.sqrt.f32"]
+        fn llvm_sqrt_f32(f: f32) -> f32;
+    }
+    unsafe { llvm_sqrt_f32(f) }
+}
+
+/// Branch-free half to single precision conversion; quiets `NaN`s, like
+/// the F16C and NEON instructions do.
+#[inline(always)]
+fn half_to_single(h: u16) -> f32 {
+    // Scaling by 2^112 rebiases the exponent, and normalizes subnormals.
+    let scaled = f32::from_bits(((h & 0x7FFFu16) as u32) << 13) * f32::from_bits(239 << 23);
+    let inf_nan = ((scaled >= f32::from_bits(143 << 23)) as u32) * 0x7F800000u32;
+    let quiet = (((h & 0x7FFFu16) > 0x7C00u16) as u32) * 0x00400000u32;
+    let sign = ((h & 0x8000u16) as u32) << 16;
+    f32::from_bits(scaled.to_bits() | inf_nan | quiet | sign)
+}
+
+/// Branch-free single to half precision conversion, rounding to nearest,
+/// ties to even; quiets `NaN`s, and keeps the high bits of their payloads.
+#[inline(always)]
+fn single_to_half(f: f32) -> u16 {
+    let bits = f.to_bits();
+    let abs = bits & 0x7FFFFFFFu32;
+    // Rebiases the exponent, and rounds off 13 mantissa bits; rounding up
+    // carries into the exponent, and on to infinity, as needed.
+    let normal = abs.wrapping_sub(112 << 23).wrapping_add(0x0FFF + ((abs >> 13) & 1)) >> 13;
+    // Adding 0.5 lines the subnormal mantissa up with the low bits, and
+    // lets the FPU do the rounding.
+    let subnormal = (f32::from_bits(abs) + 0.5).to_bits().wrapping_sub(126 << 23);
+    let nan = (abs > 0x7F800000u32) as u32;
+    let inf_nan = 0x7C00u32 | (nan * (0x0200u32 | ((abs >> 13) & 0x03FFu32)));
+    let big = 0u32.wrapping_sub((abs >= 143 << 23) as u32);
+    let small = 0u32.wrapping_sub((abs < 113 << 23) as u32);
+    let half = (inf_nan & big) | (subnormal & small) | (normal & !(big | small));
+    (half | ((bits >> 16) & 0x8000u32)) as u16
+}
+
+/// Branch-free double to single precision conversion, rounding to odd,
+/// which keeps enough information to round correctly again to half
+/// precision.
+#[inline(always)]
+fn double_to_single_odd(x: f64) -> f32 {
+    let single = x as f32;
+    let wide = single as f64;
+    // Truncates the nearest value towards zero, then sets the sticky bit.
+    let over = (wide.abs() > x.abs()) as u32;
+    let inexact = (wide != x) as u32;
+    f32::from_bits((single.to_bits() - over) | inexact)
+}
+
+#[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64"),
+          target_feature = "f16c", target_feature = "avx"))]
+mod simd {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+    use super::{f16, double_to_single_odd};
+
+    pub(super) fn f16_to_f32(src: &[f16], dst: &mut [f32]) -> usize {
+        let n = src.len() & !7;
+        let mut i = 0;
+        while i < n {
+            unsafe {
+                let h = _mm_loadu_si128(src.as_ptr().add(i) as *const __m128i);
+                _mm256_storeu_ps(dst.as_mut_ptr().add(i), _mm256_cvtph_ps(h));
+            }
+            i += 8;
+        }
+        n
+    }
+
+    pub(super) fn f32_to_f16(src: &[f32], dst: &mut [f16]) -> usize {
+        let n = src.len() & !7;
+        let mut i = 0;
+        while i < n {
+            unsafe {
+                let v = _mm256_loadu_ps(src.as_ptr().add(i));
+                let h = _mm256_cvtps_ph::<_MM_FROUND_TO_NEAREST_INT>(v);
+                _mm_storeu_si128(dst.as_mut_ptr().add(i) as *mut __m128i, h);
+            }
+            i += 8;
+        }
+        n
+    }
+
+    pub(super) fn f16_to_f64(src: &[f16], dst: &mut [f64]) -> usize {
+        let n = src.len() & !3;
+        let mut i = 0;
+        while i < n {
+            unsafe {
+                let h = _mm_loadl_epi64(src.as_ptr().add(i) as *const __m128i);
+                _mm256_storeu_pd(dst.as_mut_ptr().add(i), _mm256_cvtps_pd(_mm_cvtph_ps(h)));
+            }
+            i += 4;
+        }
+        n
+    }
+
+    pub(super) fn f64_to_f16(src: &[f64], dst: &mut [f16]) -> usize {
+        let n = src.len() & !7;
+        let mut i = 0;
+        while i < n {
+            let mut single = [0.0f32; 8];
+            for (x, y) in src[i..i + 8].iter().zip(single.iter_mut()) {
+                *y = double_to_single_odd(*x);
+            }
+            unsafe {
+                let h = _mm256_cvtps_ph::<_MM_FROUND_TO_NEAREST_INT>(_mm256_loadu_ps(single.as_ptr()));
+                _mm_storeu_si128(dst.as_mut_ptr().add(i) as *mut __m128i, h);
+            }
+            i += 8;
+        }
+        n
+    }
+}
+
+#[cfg(all(feature = "simd", target_arch = "aarch64", target_feature = "neon"))]
+mod simd {
+    use core::arch::aarch64::{float32x4_t, float64x2_t};
+    use core::arch::asm;
+    use core::ptr;
+    use super::f16;
+
+    pub(super) fn f16_to_f32(src: &[f16], dst: &mut [f32]) -> usize {
+        let n = src.len() & !3;
+        let mut i = 0;
+        while i < n {
+            unsafe {
+                let h = ptr::read_unaligned(src.as_ptr().add(i) as *const u64);
+                let v: float32x4_t;
+                asm!("fmov {v:d}, {h}",
+                     "fcvtl {v:v}.4s, {v:v}.4h",
+                     h = in(reg) h, v = out(vreg) v,
+                     options(pure, nomem, nostack));
+                ptr::write_unaligned(dst.as_mut_ptr().add(i) as *mut float32x4_t, v);
+            }
+            i += 4;
+        }
+        n
+    }
+
+    pub(super) fn f32_to_f16(src: &[f32], dst: &mut [f16]) -> usize {
+        let n = src.len() & !3;
+        let mut i = 0;
+        while i < n {
+            unsafe {
+                let v = ptr::read_unaligned(src.as_ptr().add(i) as *const float32x4_t);
+                let h: u64;
+                asm!("fcvtn {v:v}.4h, {v:v}.4s",
+                     "fmov {h}, {v:d}",
+                     v = inout(vreg) v => _, h = out(reg) h,
+                     options(pure, nomem, nostack));
+                ptr::write_unaligned(dst.as_mut_ptr().add(i) as *mut u64, h);
+            }
+            i += 4;
+        }
+        n
+    }
+
+    pub(super) fn f16_to_f64(src: &[f16], dst: &mut [f64]) -> usize {
+        let n = src.len() & !3;
+        let mut i = 0;
+        while i < n {
+            unsafe {
+                let h = ptr::read_unaligned(src.as_ptr().add(i) as *const u64);
+                let lo: float64x2_t;
+                let hi: float64x2_t;
+                asm!("fmov {lo:d}, {h}",
+                     "fcvtl {lo:v}.4s, {lo:v}.4h",
+                     "fcvtl2 {hi:v}.2d, {lo:v}.4s",
+                     "fcvtl {lo:v}.2d, {lo:v}.2s",
+                     h = in(reg) h, lo = out(vreg) lo, hi = out(vreg) hi,
+                     options(pure, nomem, nostack));
+                ptr::write_unaligned(dst.as_mut_ptr().add(i) as *mut float64x2_t, lo);
+                ptr::write_unaligned(dst.as_mut_ptr().add(i + 2) as *mut float64x2_t, hi);
+            }
+            i += 4;
+        }
+        n
+    }
+
+    pub(super) fn f64_to_f16(src: &[f64], dst: &mut [f16]) -> usize {
+        let n = src.len() & !3;
+        let mut i = 0;
+        while i < n {
+            unsafe {
+                let lo = ptr::read_unaligned(src.as_ptr().add(i) as *const float64x2_t);
+                let hi = ptr::read_unaligned(src.as_ptr().add(i + 2) as *const float64x2_t);
+                let h: u64;
+                // FCVTXN rounds to odd, so FCVTN rounds correctly after it.
+                asm!("fcvtxn {lo:v}.2s, {lo:v}.2d",
+                     "fcvtxn2 {lo:v}.4s, {hi:v}.2d",
+                     "fcvtn {lo:v}.4h, {lo:v}.4s",
+                     "fmov {h}, {lo:d}",
+                     lo = inout(vreg) lo => _, hi = in(vreg) hi, h = out(reg) h,
+                     options(pure, nomem, nostack));
+                ptr::write_unaligned(dst.as_mut_ptr().add(i) as *mut u64, h);
+            }
+            i += 4;
+        }
+        n
+    }
+}
+
+#[cfg(not(all(feature = "simd",
+              any(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "f16c", target_feature = "avx"),
+                  all(target_arch = "aarch64", target_feature = "neon")))))]
+mod simd {
+    use super::f16;
+
+    pub(super) fn f16_to_f32(_src: &[f16], _dst: &mut [f32]) -> usize {
+        0
+    }
+
+    pub(super) fn f32_to_f16(_src: &[f32], _dst: &mut [f16]) -> usize {
+        0
+    }
+
+    pub(super) fn f16_to_f64(_src: &[f16], _dst: &mut [f64]) -> usize {
+        0
+    }
+
+    pub(super) fn f64_to_f16(_src: &[f64], _dst: &mut [f16]) -> usize {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Odd length, so that every conversion also runs its scalar tail.
+    const CHUNK: usize = 1021;
+
+    struct Bits(u64);
+
+    impl Bits {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            self.0.wrapping_mul(0x2545F4914F6CDD1D)
+        }
+    }
+
+    // Scalar conversions needn't quiet NaNs, so NaNs only need to agree on
+    // their sign.
+
+    fn same_f16(x: f16, y: f16) -> bool {
+        x.0 == y.0 || x.is_nan() && y.is_nan() && x.0 & 0x8000 == y.0 & 0x8000
+    }
+
+    fn same_f32(x: f32, y: f32) -> bool {
+        x.to_bits() == y.to_bits() || x.is_nan() && y.is_nan() && x.is_sign_negative() == y.is_sign_negative()
+    }
+
+    fn same_f64(x: f64, y: f64) -> bool {
+        x.to_bits() == y.to_bits() || x.is_nan() && y.is_nan() && x.is_sign_negative() == y.is_sign_negative()
+    }
+
+    #[test]
+    fn test_convert_slice_f16_to_wider() {
+        let mut src = [f16(0); CHUNK];
+        let mut single = [0.0f32; CHUNK];
+        let mut double = [0.0f64; CHUNK];
+        let mut bits = 0u32;
+        while bits <= 0xFFFF {
+            for x in src.iter_mut() {
+                *x = f16(bits as u16);
+                bits += 1;
+            }
+            convert_slice_f16_to_f32(&src, &mut single);
+            convert_slice_f16_to_f64(&src, &mut double);
+            for ((x, y), z) in src.iter().zip(single.iter()).zip(double.iter()) {
+                assert!(same_f32(*y, f32::from(*x)), "{:?} {:e}", x, y);
+                assert!(same_f64(*z, f64::from(*x)), "{:?} {:e}", x, z);
+            }
+        }
+    }
+
+    #[test]
+    fn test_convert_slice_f32_to_f16() {
+        let mut random = Bits(0x9E3779B97F4A7C15);
+        let mut src = [0.0f32; CHUNK];
+        let mut dst = [f16(0); CHUNK];
+        for round in 0..300 {
+            for x in src.iter_mut() {
+                let bits = random.next();
+                let half = f32::from(f16((bits >> 48) as u16)).to_bits();
+                *x = f32::from_bits(match round % 3 {
+                    // Half precision values, and the ties just after them.
+                    0 => half.wrapping_add(0x1000 * (bits as u32 & 1)),
+                    // Exponents from below the smallest subnormal to above
+                    // the largest finite half precision value.
+                    1 => bits as u32 & 0x807FFFFF | (100 + (bits >> 32) as u32 % 45) << 23,
+                    _ => bits as u32,
+                });
+            }
+            convert_slice_f32_to_f16(&src, &mut dst);
+            for (x, y) in src.iter().zip(dst.iter()) {
+                assert!(same_f16(*y, f16::from(*x)), "{:e} {:?} {:?}", x, y, f16::from(*x));
+            }
+        }
+    }
+
+    #[test]
+    fn test_convert_slice_f64_to_f16() {
+        let mut random = Bits(0x9E3779B97F4A7C15);
+        let mut src = [0.0f64; CHUNK];
+        let mut dst = [f16(0); CHUNK];
+        for round in 0..300 {
+            for x in src.iter_mut() {
+                let bits = random.next();
+                let half = f64::from(f16((bits >> 48) as u16)).to_bits();
+                *x = f64::from_bits(match round % 3 {
+                    // Within a few ulps of the ties after half precision
+                    // values, which rounding twice would get wrong.
+                    0 => half.wrapping_add((1 << 41) - 2 + (bits & 3)),
+                    1 => bits & 0x800FFFFFFFFFFFFF | (996 + (bits >> 52) % 45) << 52,
+                    _ => bits,
+                });
+            }
+            convert_slice_f64_to_f16(&src, &mut dst);
+            for (x, y) in src.iter().zip(dst.iter()) {
+                assert!(same_f16(*y, f16::from(*x)), "{:e} {:?} {:?}", x, y, f16::from(*x));
+            }
+        }
+    }
+
+    #[test]
+    fn test_simd_f16_to_wider() {
+        let mut src = [f16(0); CHUNK];
+        let mut single = [0.0f32; CHUNK];
+        let mut double = [0.0f64; CHUNK];
+        let mut bits = 0u32;
+        while bits <= 0xFFFF {
+            for x in src.iter_mut() {
+                *x = f16(bits as u16);
+                bits += 1;
+            }
+            let done = simd::f16_to_f32(&src, &mut single);
+            for (x, y) in src[..done].iter().zip(single.iter()) {
+                assert_eq!(y.to_bits(), half_to_single(x.0).to_bits(), "{:?}", x);
+            }
+            let done = simd::f16_to_f64(&src, &mut double);
+            for (x, y) in src[..done].iter().zip(double.iter()) {
+                assert_eq!(y.to_bits(), (half_to_single(x.0) as f64).to_bits(), "{:?}", x);
+            }
+        }
+    }
+
+    #[test]
+    fn test_simd_f32_to_f16() {
+        let mut random = Bits(0x2545F4914F6CDD1D);
+        let mut src = [0.0f32; CHUNK];
+        let mut dst = [f16(0); CHUNK];
+        for round in 0..300 {
+            for x in src.iter_mut() {
+                let bits = random.next();
+                let half = f32::from(f16((bits >> 48) as u16)).to_bits();
+                *x = f32::from_bits(match round % 3 {
+                    0 => half.wrapping_add(0x1000 * (bits as u32 & 1)),
+                    1 => bits as u32 & 0x807FFFFF | (100 + (bits >> 32) as u32 % 45) << 23,
+                    _ => bits as u32,
+                });
+            }
+            let done = simd::f32_to_f16(&src, &mut dst);
+            for (x, y) in src[..done].iter().zip(dst.iter()) {
+                assert_eq!(y.0, single_to_half(*x), "{:e}", x);
+            }
+        }
+    }
+
+    #[test]
+    fn test_simd_f64_to_f16() {
+        let mut random = Bits(0x2545F4914F6CDD1D);
+        let mut src = [0.0f64; CHUNK];
+        let mut dst = [f16(0); CHUNK];
+        for round in 0..300 {
+            for x in src.iter_mut() {
+                let bits = random.next();
+                let half = f64::from(f16((bits >> 48) as u16)).to_bits();
+                *x = f64::from_bits(match round % 3 {
+                    0 => half.wrapping_add((1 << 41) - 2 + (bits & 3)),
+                    1 => bits & 0x800FFFFFFFFFFFFF | (996 + (bits >> 52) % 45) << 52,
+                    _ => bits,
+                });
+            }
+            let done = simd::f64_to_f16(&src, &mut dst);
+            for (x, y) in src[..done].iter().zip(dst.iter()) {
+                assert_eq!(y.0, single_to_half(double_to_single_odd(*x)), "{:e}", x);
+            }
+        }
+    }
+}
@@ -0,0 +1,794 @@
+//! Shortest round-trip formatting, and correctly rounded parsing, of binary
+//! floating point numbers.
+//!
+//! `write_float` writes the shortest decimal that parses back to the same
+//! value, found with the free-format algorithm of Steele & White, as
+//! refined by Burger & Dybvig. `parse_float` rounds the exact value of a
+//! decimal literal to the nearest representable value, ties to even. Both
+//! work over fixed-size big integers, so neither needs to allocate.
+//!
+//! ```
+//! use swim_core::f16;
+//! use swim_core::num::float::parse_float;
+//!
+//! let x: f16 = parse_float("0.1").unwrap();
+//! assert_eq!(x.to_bits(), 0x2E66);
+//! ```
+
+use core::cmp::Ordering;
+use core::fmt::{self, Write};
+use crate::num::bf16::bf16;
+use crate::num::f16::f16;
+
+/// Binary floating point type with an IEEE 754 interchange format.
+pub trait Float: Copy {
+    /// Number of explicitly stored mantissa bits.
+    const MANTISSA_BITS: u32;
+
+    const EXPONENT_BITS: u32;
+
+    /// Decimal exponent at or above which every value overflows to infinity.
+    const MAX_DECIMAL_EXP: i32;
+
+    /// Decimal exponent at or below which every value rounds to zero.
+    const MIN_DECIMAL_EXP: i32;
+
+    fn to_raw(self) -> u64;
+
+    fn from_raw(raw: u64) -> Self;
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FloatError {
+    Empty,
+    Invalid,
+}
+
+impl Float for f16 {
+    const MANTISSA_BITS: u32 = 10;
+    const EXPONENT_BITS: u32 = 5;
+    const MAX_DECIMAL_EXP: i32 = 5;
+    const MIN_DECIMAL_EXP: i32 = -8;
+
+    #[inline]
+    fn to_raw(self) -> u64 {
+        self.to_bits() as u64
+    }
+
+    #[inline]
+    fn from_raw(raw: u64) -> f16 {
+        f16::from_bits(raw as u16)
+    }
+}
+
+impl Float for bf16 {
+    const MANTISSA_BITS: u32 = 7;
+    const EXPONENT_BITS: u32 = 8;
+    const MAX_DECIMAL_EXP: i32 = 39;
+    const MIN_DECIMAL_EXP: i32 = -41;
+
+    #[inline]
+    fn to_raw(self) -> u64 {
+        self.to_bits() as u64
+    }
+
+    #[inline]
+    fn from_raw(raw: u64) -> bf16 {
+        bf16::from_bits(raw as u16)
+    }
+}
+
+impl Float for f32 {
+    const MANTISSA_BITS: u32 = 23;
+    const EXPONENT_BITS: u32 = 8;
+    const MAX_DECIMAL_EXP: i32 = 39;
+    const MIN_DECIMAL_EXP: i32 = -46;
+
+    #[inline]
+    fn to_raw(self) -> u64 {
+        self.to_bits() as u64
+    }
+
+    #[inline]
+    fn from_raw(raw: u64) -> f32 {
+        f32::from_bits(raw as u32)
+    }
+}
+
+impl Float for f64 {
+    const MANTISSA_BITS: u32 = 52;
+    const EXPONENT_BITS: u32 = 11;
+    const MAX_DECIMAL_EXP: i32 = 309;
+    const MIN_DECIMAL_EXP: i32 = -324;
+
+    #[inline]
+    fn to_raw(self) -> u64 {
+        self.to_bits()
+    }
+
+    #[inline]
+    fn from_raw(raw: u64) -> f64 {
+        f64::from_bits(raw)
+    }
+}
+
+/// Number of significant digits in the shortest representation of an `f64`.
+const MAX_DIGITS: usize = 17;
+
+/// Number of significant digits of a literal that affect how it rounds;
+/// the rest only matter for whether they're all zero.
+const MAX_PARSE_DIGITS: usize = 800;
+
+/// Writes `value` as the shortest decimal that parses back to it, in plain
+/// notation for decimal exponents from -4 up to 16, and in scientific
+/// notation otherwise. Integral values keep a `.0` suffix.
+pub fn write_float<F: Float, W: Write>(value: F, output: &mut W) -> fmt::Result {
+    let raw = value.to_raw();
+    let fraction = raw & ((1 << F::MANTISSA_BITS) - 1);
+    let exponent = (raw >> F::MANTISSA_BITS) & ((1 << F::EXPONENT_BITS) - 1);
+    if exponent == (1 << F::EXPONENT_BITS) - 1 && fraction != 0 {
+        return output.write_str("NaN");
+    }
+    if raw >> (F::MANTISSA_BITS + F::EXPONENT_BITS) != 0 {
+        output.write_char('-')?;
+    }
+    if exponent == (1 << F::EXPONENT_BITS) - 1 {
+        return output.write_str("inf");
+    } else if exponent == 0 && fraction == 0 {
+        return output.write_str("0.0");
+    }
+    let mut digits = [0u8; MAX_DIGITS];
+    let (len, k) = shortest::<F>(fraction, exponent, &mut digits);
+    let digits = &digits[..len];
+    let x = k - 1;
+    if x < -4 || x >= 16 {
+        output.write_char(digits[0] as char)?;
+        if len > 1 {
+            output.write_char('.')?;
+            write_digits(&digits[1..], output)?;
+        }
+        write!(output, "e{}", x)
+    } else if k <= 0 {
+        output.write_str("0.")?;
+        for _ in 0..-k {
+            output.write_char('0')?;
+        }
+        write_digits(digits, output)
+    } else if (k as usize) < len {
+        write_digits(&digits[..k as usize], output)?;
+        output.write_char('.')?;
+        write_digits(&digits[k as usize..], output)
+    } else {
+        write_digits(digits, output)?;
+        for _ in len..k as usize {
+            output.write_char('0')?;
+        }
+        output.write_str(".0")
+    }
+}
+
+fn write_digits<W: Write>(digits: &[u8], output: &mut W) -> fmt::Result {
+    for &digit in digits {
+        output.write_char(digit as char)?;
+    }
+    Ok(())
+}
+
+/// Generates the shortest digits of a positive finite value, returning the
+/// number of digits, and the decimal exponent `k` that places the decimal
+/// point, as in `0.d₁d₂…dₙ × 10ᵏ`.
+fn shortest<F: Float>(fraction: u64, exponent: u64, digits: &mut [u8; MAX_DIGITS]) -> (usize, i32) {
+    let bias = (1 << (F::EXPONENT_BITS - 1)) - 1;
+    let mbits = F::MANTISSA_BITS as i32;
+    let (f, e) = if exponent == 0 {
+        (fraction, 1 - bias - mbits)
+    } else {
+        (fraction | 1 << mbits, exponent as i32 - bias - mbits)
+    };
+    // The value is mant × 2^exp, and the half-way points to its neighbours
+    // are minus and plus units of 2^exp below and above it; the neighbour
+    // below a power of two is half as far away as the one above.
+    let (mant, minus, plus, exp) = if exponent > 1 && fraction == 0 {
+        (f << 2, 1, 2, e - 2)
+    } else {
+        (f << 1, 1, 1, e - 1)
+    };
+    // Half-way points round to even, so they read back as the value only
+    // if its mantissa is even.
+    let rounding = if f & 1 == 0 { Ordering::Greater } else { Ordering::Equal };
+    let mut k = estimate_exp10(mant + plus, exp);
+    let mut mant = Big::from_u64(mant);
+    let mut minus = Big::from_u64(minus);
+    let mut plus = Big::from_u64(plus);
+    let mut scale = Big::from_u64(1);
+    if exp < 0 {
+        scale.mul_pow2(-exp as usize);
+    } else {
+        mant.mul_pow2(exp as usize);
+        minus.mul_pow2(exp as usize);
+        plus.mul_pow2(exp as usize);
+    }
+    if k >= 0 {
+        scale.mul_pow10(k as usize);
+    } else {
+        mant.mul_pow10(-k as usize);
+        minus.mul_pow10(-k as usize);
+        plus.mul_pow10(-k as usize);
+    }
+    if scale.cmp(mant.clone().add(&plus)) < rounding {
+        k += 1;
+    } else {
+        mant.mul_small(10);
+        minus.mul_small(10);
+        plus.mul_small(10);
+    }
+    let mut len = 0;
+    let (down, up) = loop {
+        let mut digit = 0;
+        while mant.cmp(&scale) != Ordering::Less {
+            mant.sub(&scale);
+            digit += 1;
+        }
+        digits[len] = b'0' + digit;
+        len += 1;
+        let down = mant.cmp(&minus) < rounding;
+        let up = scale.cmp(mant.clone().add(&plus)) < rounding;
+        if down || up {
+            break (down, up);
+        }
+        mant.mul_small(10);
+        minus.mul_small(10);
+        plus.mul_small(10);
+    };
+    if up && (!down || mant.mul_pow2(1).cmp(&scale) != Ordering::Less) {
+        match digits[..len].iter().rposition(|&digit| digit != b'9') {
+            Some(index) => {
+                digits[index] += 1;
+                len = index + 1;
+            },
+            None => {
+                digits[0] = b'1';
+                len = 1;
+                k += 1;
+            },
+        }
+    }
+    while len > 1 && digits[len - 1] == b'0' {
+        len -= 1;
+    }
+    (len, k)
+}
+
+/// Returns `k` or `k - 1`, where `10^(k-1) <= mant × 2^exp < 10^k`.
+fn estimate_exp10(mant: u64, exp: i32) -> i32 {
+    let bits = 64 - (mant - 1).leading_zeros() as i64;
+    // 1292913986 = floor(2^32 × log10(2))
+    (((bits + exp as i64) * 1292913986) >> 32) as i32
+}
+
+/// Parses a decimal literal, with optional sign, fraction and exponent, or
+/// one of `inf`, `infinity` and `nan` in any case, rounding its exact value
+/// to the nearest `F`, ties to even.
+pub fn parse_float<F: Float>(string: &str) -> Result<F, FloatError> {
+    let bytes = string.as_bytes();
+    if bytes.is_empty() {
+        return Err(FloatError::Empty);
+    }
+    let (negative, bytes) = match bytes[0] {
+        b'-' => (true, &bytes[1..]),
+        b'+' => (false, &bytes[1..]),
+        _ => (false, bytes),
+    };
+    let sign = (negative as u64) << (F::MANTISSA_BITS + F::EXPONENT_BITS);
+    let infinity = ((1 << F::EXPONENT_BITS) - 1) << F::MANTISSA_BITS;
+    if bytes.eq_ignore_ascii_case(b"inf") || bytes.eq_ignore_ascii_case(b"infinity") {
+        return Ok(F::from_raw(sign | infinity));
+    } else if bytes.eq_ignore_ascii_case(b"nan") {
+        return Ok(F::from_raw(infinity | 1 << (F::MANTISSA_BITS - 1)));
+    }
+
+    let mut digits = Big::from_u64(0);
+    let mut count = 0;
+    let mut chunk = 0;
+    let mut chunk_len = 0;
+    let mut exp10 = 0i64;
+    let mut dropped = false;
+    let mut any = false;
+    let mut point = false;
+    let mut index = 0;
+    while index < bytes.len() {
+        let c = bytes[index];
+        if c == b'.' && !point {
+            point = true;
+        } else if c.is_ascii_digit() {
+            any = true;
+            let digit = (c - b'0') as u32;
+            if count == 0 && digit == 0 {
+                if point {
+                    exp10 -= 1;
+                }
+            } else if count < MAX_PARSE_DIGITS {
+                chunk = 10 * chunk + digit;
+                chunk_len += 1;
+                if chunk_len == 9 {
+                    digits.mul_pow10(9).add_small(chunk);
+                    chunk = 0;
+                    chunk_len = 0;
+                }
+                count += 1;
+                if point {
+                    exp10 -= 1;
+                }
+            } else {
+                dropped |= digit != 0;
+                if !point {
+                    exp10 += 1;
+                }
+            }
+        } else {
+            break;
+        }
+        index += 1;
+    }
+    if !any {
+        return Err(FloatError::Invalid);
+    }
+    if index < bytes.len() && (bytes[index] == b'e' || bytes[index] == b'E') {
+        index += 1;
+        let negative = index < bytes.len() && bytes[index] == b'-';
+        if index < bytes.len() && (bytes[index] == b'-' || bytes[index] == b'+') {
+            index += 1;
+        }
+        if index == bytes.len() {
+            return Err(FloatError::Invalid);
+        }
+        let mut exp = 0i64;
+        while index < bytes.len() && bytes[index].is_ascii_digit() {
+            exp = (10 * exp + (bytes[index] - b'0') as i64).min(1_000_000);
+            index += 1;
+        }
+        exp10 += if negative { -exp } else { exp };
+    }
+    if index < bytes.len() {
+        return Err(FloatError::Invalid);
+    }
+    digits.mul_pow10(chunk_len).add_small(chunk);
+    if dropped {
+        // A trailing one stands in for the dropped digits: it puts the
+        // value strictly between the same two truncations.
+        digits.mul_small(10).add_small(1);
+        count += 1;
+        exp10 -= 1;
+    }
+
+    let top = count as i64 + exp10;
+    if count == 0 || top <= F::MIN_DECIMAL_EXP as i64 {
+        return Ok(F::from_raw(sign));
+    } else if top > F::MAX_DECIMAL_EXP as i64 {
+        return Ok(F::from_raw(sign | infinity));
+    }
+    let mut scale = Big::from_u64(1);
+    if exp10 >= 0 {
+        digits.mul_pow10(exp10 as usize);
+    } else {
+        scale.mul_pow10(-exp10 as usize);
+    }
+    Ok(F::from_raw(sign | round::<F>(digits, scale)))
+}
+
+/// Rounds `num / den` to the nearest `F`, ties to even, returning its
+/// unsigned bit pattern.
+fn round<F: Float>(mut num: Big, mut den: Big) -> u64 {
+    let bias = (1 << (F::EXPONENT_BITS - 1)) - 1;
+    let max_exponent = (1 << F::EXPONENT_BITS) - 1;
+    let min_exp = 1 - bias;
+    let precision = F::MANTISSA_BITS as i32 + 1;
+    let shift = num.bit_len() as i32 - den.bit_len() as i32;
+    if shift > 0 {
+        den.mul_pow2(shift as usize);
+    } else {
+        num.mul_pow2(-shift as usize);
+    }
+    let mut exp = shift;
+    if num.cmp(&den) == Ordering::Less {
+        num.mul_pow2(1);
+        exp -= 1;
+    }
+    // The value is now 2^exp × num / den, with den <= num < 2 × den.
+    if exp + bias >= max_exponent {
+        return (max_exponent as u64) << F::MANTISSA_BITS;
+    }
+    let bits = if exp >= min_exp { precision } else { precision - (min_exp - exp) };
+    if bits < 0 {
+        return 0;
+    }
+    let mut mantissa = 0u64;
+    for _ in 0..bits {
+        mantissa <<= 1;
+        if num.cmp(&den) != Ordering::Less {
+            num.sub(&den);
+            mantissa |= 1;
+        }
+        num.mul_pow2(1);
+    }
+    let half = num.cmp(&den) != Ordering::Less;
+    if half {
+        num.sub(&den);
+    }
+    if half && (!num.is_zero() || mantissa & 1 != 0) {
+        mantissa += 1;
+    }
+    if exp < min_exp {
+        // Subnormal; rounding up may carry into the smallest normal.
+        return mantissa;
+    }
+    if mantissa == 1 << precision {
+        mantissa >>= 1;
+        exp += 1;
+        if exp + bias >= max_exponent {
+            return (max_exponent as u64) << F::MANTISSA_BITS;
+        }
+    }
+    ((exp + bias) as u64) << F::MANTISSA_BITS | mantissa & ((1 << F::MANTISSA_BITS) - 1)
+}
+
+const BIG_LIMBS: usize = 128;
+
+/// Unsigned integer of up to 4096 bits, enough for the scaled values of
+/// both conversions.
+#[derive(Clone)]
+struct Big {
+    len: usize,
+    limbs: [u32; BIG_LIMBS],
+}
+
+impl Big {
+    fn from_u64(value: u64) -> Big {
+        let mut big = Big { len: 0, limbs: [0; BIG_LIMBS] };
+        big.limbs[0] = value as u32;
+        big.limbs[1] = (value >> 32) as u32;
+        big.len = if value >> 32 != 0 { 2 } else if value != 0 { 1 } else { 0 };
+        big
+    }
+
+    fn is_zero(&self) -> bool {
+        self.len == 0
+    }
+
+    fn bit_len(&self) -> usize {
+        if self.len == 0 {
+            0
+        } else {
+            32 * self.len - self.limbs[self.len - 1].leading_zeros() as usize
+        }
+    }
+
+    fn add_small(&mut self, value: u32) -> &mut Big {
+        let mut carry = value as u64;
+        let mut index = 0;
+        while carry != 0 {
+            let sum = self.limbs[index] as u64 + carry;
+            self.limbs[index] = sum as u32;
+            carry = sum >> 32;
+            index += 1;
+        }
+        if index > self.len {
+            self.len = index;
+        }
+        self
+    }
+
+    fn mul_small(&mut self, value: u32) -> &mut Big {
+        let mut carry = 0u64;
+        for limb in &mut self.limbs[..self.len] {
+            let product = *limb as u64 * value as u64 + carry;
+            *limb = product as u32;
+            carry = product >> 32;
+        }
+        if carry != 0 {
+            self.limbs[self.len] = carry as u32;
+            self.len += 1;
+        }
+        self
+    }
+
+    fn mul_pow10(&mut self, mut n: usize) -> &mut Big {
+        const POW10: [u32; 10] = [1, 10, 100, 1000, 10000, 100000, 1000000, 10000000, 100000000, 1000000000];
+        while n >= 9 {
+            self.mul_small(POW10[9]);
+            n -= 9;
+        }
+        self.mul_small(POW10[n])
+    }
+
+    fn mul_pow2(&mut self, n: usize) -> &mut Big {
+        if self.len == 0 {
+            return self;
+        }
+        let limbs = n / 32;
+        let bits = n % 32;
+        if bits != 0 {
+            let carry = self.limbs[self.len - 1] >> (32 - bits);
+            for index in (1..self.len).rev() {
+                self.limbs[index] = self.limbs[index] << bits | self.limbs[index - 1] >> (32 - bits);
+            }
+            self.limbs[0] <<= bits;
+            if carry != 0 {
+                self.limbs[self.len] = carry;
+                self.len += 1;
+            }
+        }
+        if limbs != 0 {
+            for index in (0..self.len).rev() {
+                self.limbs[index + limbs] = self.limbs[index];
+            }
+            for limb in &mut self.limbs[..limbs] {
+                *limb = 0;
+            }
+            self.len += limbs;
+        }
+        self
+    }
+
+    fn add(&mut self, other: &Big) -> &mut Big {
+        let len = if self.len > other.len { self.len } else { other.len };
+        let mut carry = 0u64;
+        for index in 0..len {
+            let sum = self.limbs[index] as u64 + other.limbs[index] as u64 + carry;
+            self.limbs[index] = sum as u32;
+            carry = sum >> 32;
+        }
+        self.len = len;
+        if carry != 0 {
+            self.limbs[len] = carry as u32;
+            self.len += 1;
+        }
+        self
+    }
+
+    /// Subtracts `other`, which must not be greater than `self`.
+    fn sub(&mut self, other: &Big) -> &mut Big {
+        let mut borrow = 0i64;
+        for index in 0..self.len {
+            let difference = self.limbs[index] as i64 - other.limbs[index] as i64 - borrow;
+            self.limbs[index] = difference as u32;
+            borrow = (difference < 0) as i64;
+        }
+        while self.len > 0 && self.limbs[self.len - 1] == 0 {
+            self.len -= 1;
+        }
+        self
+    }
+
+    fn cmp(&self, other: &Big) -> Ordering {
+        if self.len != other.len {
+            return self.len.cmp(&other.len);
+        }
+        for index in (0..self.len).rev() {
+            if self.limbs[index] != other.limbs[index] {
+                return self.limbs[index].cmp(&other.limbs[index]);
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::str;
+    use super::*;
+
+    struct Buffer {
+        bytes: [u8; 1024],
+        len: usize,
+    }
+
+    impl Buffer {
+        fn new() -> Buffer {
+            Buffer { bytes: [0; 1024], len: 0 }
+        }
+
+        fn as_str(&self) -> &str {
+            str::from_utf8(&self.bytes[..self.len]).unwrap()
+        }
+    }
+
+    impl Write for Buffer {
+        fn write_str(&mut self, string: &str) -> fmt::Result {
+            self.bytes[self.len..self.len + string.len()].copy_from_slice(string.as_bytes());
+            self.len += string.len();
+            Ok(())
+        }
+    }
+
+    fn format<F: Float>(value: F) -> Buffer {
+        let mut buffer = Buffer::new();
+        write_float(value, &mut buffer).unwrap();
+        buffer
+    }
+
+    /// xorshift64*, for reproducible pseudo-random bit patterns.
+    struct Bits(u64);
+
+    impl Bits {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            self.0.wrapping_mul(0x2545F4914F6CDD1D)
+        }
+    }
+
+    #[test]
+    fn test_write_float() {
+        assert_eq!(format(0.1f64).as_str(), "0.1");
+        assert_eq!(format(0.1f64 + 0.2f64).as_str(), "0.30000000000000004");
+        assert_eq!(format(1e15f64).as_str(), "1000000000000000.0");
+        assert_eq!(format(1e16f64).as_str(), "1e16");
+        assert_eq!(format(0.0001f64).as_str(), "0.0001");
+        assert_eq!(format(-2.5e-7f64).as_str(), "-2.5e-7");
+        assert_eq!(format(5e-324f64).as_str(), "5e-324");
+        assert_eq!(format(core::f64::MAX).as_str(), "1.7976931348623157e308");
+        assert_eq!(format(-0.0f64).as_str(), "-0.0");
+        assert_eq!(format(core::f64::NEG_INFINITY).as_str(), "-inf");
+        assert_eq!(format(core::f64::NAN).as_str(), "NaN");
+        assert_eq!(format(0.1f32).as_str(), "0.1");
+        assert_eq!(format(16777216f32).as_str(), "16777216.0");
+        assert_eq!(format(f16::from(0.1f32)).as_str(), "0.1");
+        assert_eq!(format(f16::from(1.0f32)).as_str(), "1.0");
+        assert_eq!(format(f16::MAX).as_str(), "65500.0");
+        assert_eq!(format(bf16::from(0.1f32)).as_str(), "0.1");
+        assert_eq!(format(bf16::MAX).as_str(), "3.39e38");
+    }
+
+    #[test]
+    fn test_parse_float() {
+        assert_eq!(parse_float::<f64>("0.1"), Ok(0.1));
+        assert_eq!(parse_float::<f64>("-.5e1"), Ok(-5.0));
+        assert_eq!(parse_float::<f64>("007.250"), Ok(7.25));
+        assert_eq!(parse_float::<f64>("1e400"), Ok(core::f64::INFINITY));
+        assert_eq!(parse_float::<f64>("-1e-400").map(f64::to_bits), Ok((-0.0f64).to_bits()));
+        assert_eq!(parse_float::<f64>("2.4703282292062328e-324"), Ok(5e-324));
+        assert_eq!(parse_float::<f64>("2.4703282292062327e-324"), Ok(0.0));
+        assert_eq!(parse_float::<f64>("-Infinity"), Ok(core::f64::NEG_INFINITY));
+        assert!(parse_float::<f64>("nan").unwrap().is_nan());
+        assert_eq!(parse_float::<f64>(""), Err(FloatError::Empty));
+        assert_eq!(parse_float::<f64>("."), Err(FloatError::Invalid));
+        assert_eq!(parse_float::<f64>("e5"), Err(FloatError::Invalid));
+        assert_eq!(parse_float::<f64>("1e"), Err(FloatError::Invalid));
+        assert_eq!(parse_float::<f64>("1.2.3"), Err(FloatError::Invalid));
+        // Just above half way between 1 and the next f32; rounding through
+        // an f64 would land exactly half way, and then round down.
+        assert_eq!(parse_float::<f32>("1.00000005960464477550"), Ok(f32::from_bits(0x3F800001)));
+        assert_eq!(parse_float::<f16>("65519.99").map(f16::to_bits), Ok(0x7BFF));
+        assert_eq!(parse_float::<f16>("65520").map(f16::to_bits), Ok(0x7C00));
+        assert_eq!(parse_float::<f16>("5.960464477539063e-8").map(f16::to_bits), Ok(0x0001));
+        assert_eq!(parse_float::<bf16>("3.3961e38").map(bf16::to_bits), Ok(0x7F7F));
+        assert_eq!(parse_float::<bf16>("3.3962e38").map(bf16::to_bits), Ok(0x7F80));
+        assert_eq!(parse_float::<bf16>("4.6e-41").map(bf16::to_bits), Ok(0x0001));
+        assert_eq!(parse_float::<bf16>("4.5e-41").map(bf16::to_bits), Ok(0x0000));
+    }
+
+    #[test]
+    fn test_parse_float_long() {
+        // Exactly half way between 1 and the next f64, which rounds to even.
+        let tie = "1.00000000000000011102230246251565404236316680908203125";
+        assert_eq!(parse_float::<f64>(tie), Ok(1.0));
+        let mut literal = Buffer::new();
+        literal.write_str(tie).unwrap();
+        for _ in 0..900 {
+            literal.write_char('0').unwrap();
+        }
+        assert_eq!(parse_float::<f64>(literal.as_str()), Ok(1.0));
+        literal.write_char('1').unwrap();
+        assert_eq!(parse_float::<f64>(literal.as_str()), Ok(f64::from_bits(0x3FF0000000000001)));
+    }
+
+    /// Returns the number of significant digits of a formatted value.
+    fn significant_digits(formatted: &str) -> usize {
+        let mantissa = formatted.split('e').next().unwrap();
+        let mut first = None;
+        let mut last = 0;
+        for (index, c) in mantissa.bytes().filter(|c| c.is_ascii_digit()).enumerate() {
+            if c != b'0' {
+                first = first.or(Some(index));
+                last = index;
+            }
+        }
+        last + 1 - first.unwrap()
+    }
+
+    #[test]
+    fn test_round_trip_f16() {
+        for bits in 0..=0xFFFFu16 {
+            let value = f16::from_bits(bits);
+            if value.is_nan() {
+                continue;
+            }
+            let formatted = format(value);
+            let parsed: f16 = parse_float(formatted.as_str()).unwrap();
+            assert_eq!(parsed.to_bits(), bits, "{}", formatted.as_str());
+            if !value.is_finite() || bits & 0x7FFF == 0 {
+                continue;
+            }
+            // No correctly rounded shorter decimal reads back as the value.
+            let digits = significant_digits(formatted.as_str());
+            for precision in 0..digits - 1 {
+                let mut shorter = Buffer::new();
+                write!(shorter, "{:.*e}", precision, f64::from(value)).unwrap();
+                let parsed: f16 = parse_float(shorter.as_str()).unwrap();
+                assert_ne!(parsed.to_bits(), bits, "{} {}", formatted.as_str(), shorter.as_str());
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip_bf16() {
+        for bits in 0..=0xFFFFu16 {
+            let value = bf16::from_bits(bits);
+            if value.is_nan() {
+                continue;
+            }
+            let formatted = format(value);
+            let parsed: bf16 = parse_float(formatted.as_str()).unwrap();
+            assert_eq!(parsed.to_bits(), bits, "{}", formatted.as_str());
+            if !value.is_finite() || bits & 0x7FFF == 0 {
+                continue;
+            }
+            let digits = significant_digits(formatted.as_str());
+            for precision in 0..digits - 1 {
+                let mut shorter = Buffer::new();
+                write!(shorter, "{:.*e}", precision, f64::from(value)).unwrap();
+                let parsed: bf16 = parse_float(shorter.as_str()).unwrap();
+                assert_ne!(parsed.to_bits(), bits, "{} {}", formatted.as_str(), shorter.as_str());
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip_f32() {
+        let mut random = Bits(0x9E3779B97F4A7C15);
+        for _ in 0..20000 {
+            let value = f32::from_bits(random.next() as u32);
+            let formatted = format(value);
+            let mut expected = Buffer::new();
+            write!(expected, "{:?}", value).unwrap();
+            assert_eq!(formatted.as_str(), expected.as_str());
+            if !value.is_nan() {
+                let parsed: f32 = parse_float(formatted.as_str()).unwrap();
+                assert_eq!(parsed.to_bits(), value.to_bits(), "{}", formatted.as_str());
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip_f64() {
+        let mut random = Bits(0x9E3779B97F4A7C15);
+        for _ in 0..20000 {
+            let value = f64::from_bits(random.next());
+            let formatted = format(value);
+            let mut expected = Buffer::new();
+            write!(expected, "{:?}", value).unwrap();
+            assert_eq!(formatted.as_str(), expected.as_str());
+            if !value.is_nan() {
+                let parsed: f64 = parse_float(formatted.as_str()).unwrap();
+                assert_eq!(parsed.to_bits(), value.to_bits(), "{}", formatted.as_str());
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_float_random() {
+        let mut random = Bits(0x2545F4914F6CDD1D);
+        for _ in 0..20000 {
+            let mut literal = Buffer::new();
+            let mantissa = random.next() % 10_000_000_000_000_000_000;
+            let exp = (random.next() % 660) as i32 - 340;
+            write!(literal, "{}{}e{}", mantissa, random.next() % 1000, exp).unwrap();
+            let parsed: f64 = parse_float(literal.as_str()).unwrap();
+            assert_eq!(parsed.to_bits(), literal.as_str().parse::<f64>().unwrap().to_bits(), "{}", literal.as_str());
+            let parsed: f32 = parse_float(literal.as_str()).unwrap();
+            assert_eq!(parsed.to_bits(), literal.as_str().parse::<f32>().unwrap().to_bits(), "{}", literal.as_str());
+        }
+    }
+}
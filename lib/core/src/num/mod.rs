@@ -1 +1,4 @@
+pub mod bf16;
+pub mod cast;
 pub mod f16;
+pub mod float;
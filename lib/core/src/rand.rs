@@ -0,0 +1,396 @@
+use core::mem;
+use crate::entropy::{self, EntropyError};
+
+/// Source of uniformly distributed pseudo-random numbers.
+pub trait Rng {
+    /// Returns the next random `u32` value.
+    fn next_u32(&mut self) -> u32;
+
+    /// Returns the next random `u64` value.
+    fn next_u64(&mut self) -> u64;
+
+    /// Fills `bytes` with random data.
+    fn fill_bytes(&mut self, bytes: &mut [u8]) {
+        let mut chunks = bytes.chunks_exact_mut(8);
+        for chunk in &mut chunks {
+            chunk.copy_from_slice(&self.next_u64().to_le_bytes());
+        }
+        let tail = chunks.into_remainder();
+        if !tail.is_empty() {
+            let word = self.next_u64().to_le_bytes();
+            tail.copy_from_slice(&word[..tail.len()]);
+        }
+    }
+
+    /// Returns a random `u64` value uniformly distributed in the half-open
+    /// range `[0, bound)`, without modulo bias.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bound` is zero.
+    fn gen_below(&mut self, bound: u64) -> u64 {
+        assert!(bound != 0, "empty range");
+        // Lemire's nearly divisionless method: reject the low products that
+        // would otherwise over-represent some outputs. Only a low product
+        // below `bound` can be rejected, so the division that computes the
+        // rejection threshold is only needed in that rare case.
+        let mut m = (self.next_u64() as u128) * (bound as u128);
+        if (m as u64) < bound {
+            let threshold = bound.wrapping_neg() % bound;
+            while (m as u64) < threshold {
+                m = (self.next_u64() as u128) * (bound as u128);
+            }
+        }
+        (m >> 64) as u64
+    }
+
+    /// Returns a random value uniformly distributed in the half-open range
+    /// `[low, high)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `low` is not less than `high`.
+    #[inline]
+    fn gen_range<T: SampleRange>(&mut self, low: T, high: T) -> T where Self: Sized {
+        T::sample_range(self, low, high)
+    }
+
+    /// Returns a random `f32` value uniformly distributed in `[0, 1)`.
+    #[inline]
+    fn gen_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 * (1.0 / (1u32 << 24) as f32)
+    }
+
+    /// Returns a random `f64` value uniformly distributed in `[0, 1)`.
+    #[inline]
+    fn gen_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// Returns `true` with probability `p`.
+    #[inline]
+    fn gen_bool(&mut self, p: f64) -> bool {
+        self.gen_f64() < p
+    }
+
+    /// Randomly permutes the elements of `slice` in place.
+    fn shuffle<T>(&mut self, slice: &mut [T]) where Self: Sized {
+        let mut i = slice.len();
+        while i > 1 {
+            let j = self.gen_below(i as u64) as usize;
+            i -= 1;
+            slice.swap(i, j);
+        }
+    }
+
+    /// Returns a reference to a random element of `slice`, or `None` if
+    /// `slice` is empty.
+    fn choose<'b, T>(&mut self, slice: &'b [T]) -> Option<&'b T> where Self: Sized {
+        if !slice.is_empty() {
+            let i = self.gen_below(slice.len() as u64) as usize;
+            Some(&slice[i])
+        } else {
+            None
+        }
+    }
+}
+
+/// Type whose values can be sampled uniformly from a half-open range.
+pub trait SampleRange: Sized {
+    /// Returns a random value uniformly distributed in `[low, high)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `low` is not less than `high`.
+    fn sample_range<R: Rng + ?Sized>(rng: &mut R, low: Self, high: Self) -> Self;
+}
+
+macro_rules! impl_sample_range_int {
+    ($($ty: ty => $uty: ty),*) => ($(
+        impl SampleRange for $ty {
+            #[inline]
+            fn sample_range<R: Rng + ?Sized>(rng: &mut R, low: $ty, high: $ty) -> $ty {
+                assert!(low < high, "empty range");
+                let span = (high as $uty).wrapping_sub(low as $uty) as u64;
+                low.wrapping_add(rng.gen_below(span) as $ty)
+            }
+        }
+    )*);
+}
+
+impl_sample_range_int!(u8 => u8, u16 => u16, u32 => u32, u64 => u64, usize => usize,
+                       i8 => u8, i16 => u16, i32 => u32, i64 => u64, isize => usize);
+
+impl SampleRange for f32 {
+    #[inline]
+    fn sample_range<R: Rng + ?Sized>(rng: &mut R, low: f32, high: f32) -> f32 {
+        assert!(low < high, "empty range");
+        let value = low + (high - low) * rng.gen_f32();
+        // Guard against rounding up to the excluded upper bound.
+        if value < high { value } else { low }
+    }
+}
+
+impl SampleRange for f64 {
+    #[inline]
+    fn sample_range<R: Rng + ?Sized>(rng: &mut R, low: f64, high: f64) -> f64 {
+        assert!(low < high, "empty range");
+        let value = low + (high - low) * rng.gen_f64();
+        if value < high { value } else { low }
+    }
+}
+
+/// PCG-XSH-RR 64/32 generator; a small, fast generator with 64 bits of
+/// state, suitable for sampling and jitter, but not for cryptography.
+#[derive(Clone)]
+pub struct Pcg32 {
+    state: u64,
+    inc: u64,
+}
+
+/// Xoshiro256++ generator; a fast, general purpose generator with 256 bits
+/// of state and a period of 2^256 − 1, but not for cryptography.
+#[derive(Clone)]
+pub struct Xoshiro256PlusPlus {
+    s: [u64; 4],
+}
+
+const PCG32_MULTIPLIER: u64 = 6364136223846793005;
+
+/// Returns the next output of the SplitMix64 generator with `state`; used to
+/// expand small seeds into well-mixed generator states.
+#[inline]
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// Fills `words` with random data from the runtime entropy source.
+fn entropy_words(words: &mut [u64]) -> Result<(), EntropyError> {
+    let mut bytes = [0u8; 32];
+    let bytes = &mut bytes[..mem::size_of_val(words)];
+    entropy::fill(bytes)?;
+    for (word, chunk) in words.iter_mut().zip(bytes.chunks_exact(8)) {
+        let mut data = [0u8; 8];
+        data.copy_from_slice(chunk);
+        *word = u64::from_le_bytes(data);
+    }
+    Ok(())
+}
+
+impl Pcg32 {
+    /// Returns a new generator seeded from the runtime entropy source.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime has no entropy source.
+    pub fn new() -> Pcg32 {
+        match Pcg32::try_new() {
+            Ok(rng) => rng,
+            Err(error) => panic!("{:?}", error),
+        }
+    }
+
+    /// Returns a new generator seeded from the runtime entropy source, or an
+    /// `EntropyError` if the runtime has no entropy source.
+    pub fn try_new() -> Result<Pcg32, EntropyError> {
+        let mut words = [0u64; 2];
+        entropy_words(&mut words)?;
+        Ok(Pcg32::with_seed(words[0], words[1]))
+    }
+
+    /// Returns a new generator with initial state `seed`, producing the
+    /// sequence selected by `stream`; generators with different streams
+    /// produce independent sequences from the same seed.
+    pub fn with_seed(seed: u64, stream: u64) -> Pcg32 {
+        let mut rng = Pcg32 { state: 0, inc: (stream << 1) | 1 };
+        rng.step();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.step();
+        rng
+    }
+
+    #[inline]
+    fn step(&mut self) {
+        self.state = self.state.wrapping_mul(PCG32_MULTIPLIER).wrapping_add(self.inc);
+    }
+}
+
+impl Rng for Pcg32 {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        let state = self.state;
+        self.step();
+        let xorshifted = (((state >> 18) ^ state) >> 27) as u32;
+        xorshifted.rotate_right((state >> 59) as u32)
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        let lo = self.next_u32() as u64;
+        let hi = self.next_u32() as u64;
+        (hi << 32) | lo
+    }
+}
+
+impl Xoshiro256PlusPlus {
+    /// Returns a new generator seeded from the runtime entropy source.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime has no entropy source.
+    pub fn new() -> Xoshiro256PlusPlus {
+        match Xoshiro256PlusPlus::try_new() {
+            Ok(rng) => rng,
+            Err(error) => panic!("{:?}", error),
+        }
+    }
+
+    /// Returns a new generator seeded from the runtime entropy source, or an
+    /// `EntropyError` if the runtime has no entropy source.
+    pub fn try_new() -> Result<Xoshiro256PlusPlus, EntropyError> {
+        let mut seed = [0u64; 4];
+        entropy_words(&mut seed)?;
+        Ok(Xoshiro256PlusPlus::with_seed(seed))
+    }
+
+    /// Returns a new generator with the 256-bit initial state `seed`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `seed` is all zeros, from which the generator never leaves.
+    pub fn with_seed(seed: [u64; 4]) -> Xoshiro256PlusPlus {
+        assert!(seed != [0; 4], "all-zero seed");
+        Xoshiro256PlusPlus { s: seed }
+    }
+
+    /// Returns a new generator whose state is expanded from the 64-bit
+    /// `seed` with SplitMix64.
+    pub fn from_u64(mut seed: u64) -> Xoshiro256PlusPlus {
+        let s0 = splitmix64(&mut seed);
+        let s1 = splitmix64(&mut seed);
+        let s2 = splitmix64(&mut seed);
+        let s3 = splitmix64(&mut seed);
+        Xoshiro256PlusPlus { s: [s0, s1, s2, s3] }
+    }
+}
+
+impl Rng for Xoshiro256PlusPlus {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        // The high bits have the best statistical quality.
+        (self.next_u64() >> 32) as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        let s = &mut self.s;
+        let result = s[0].wrapping_add(s[3]).rotate_left(23).wrapping_add(s[0]);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{i64, u64};
+    use super::*;
+
+    #[test]
+    fn test_pcg32_vectors() {
+        let mut rng = Pcg32::with_seed(42, 54);
+        assert_eq!(rng.next_u32(), 0xa15c02b7);
+        assert_eq!(rng.next_u32(), 0x7b47f409);
+        assert_eq!(rng.next_u32(), 0xba1d3330);
+        assert_eq!(rng.next_u32(), 0x83d2f293);
+        assert_eq!(rng.next_u32(), 0xbfa4784b);
+        assert_eq!(rng.next_u32(), 0xcbed606e);
+    }
+
+    #[test]
+    fn test_xoshiro256plusplus_vectors() {
+        let mut rng = Xoshiro256PlusPlus::with_seed([1, 2, 3, 4]);
+        assert_eq!(rng.next_u64(), 41943041);
+        assert_eq!(rng.next_u64(), 58720359);
+        assert_eq!(rng.next_u64(), 3588806011781223);
+        assert_eq!(rng.next_u64(), 3591011842654386);
+        assert_eq!(rng.next_u64(), 9228616714210784205);
+        assert_eq!(rng.next_u64(), 9973669472204895162);
+    }
+
+    #[test]
+    fn test_splitmix64_seed() {
+        let rng = Xoshiro256PlusPlus::from_u64(0);
+        assert_eq!(rng.s[0], 0xe220a8397b1dcdaf);
+        assert_eq!(rng.s[1], 0x6e789e6aa1b965f4);
+        assert_eq!(rng.s[2], 0x06c45d188009454f);
+    }
+
+    #[test]
+    fn test_fill_bytes() {
+        let mut a = Xoshiro256PlusPlus::with_seed([1, 2, 3, 4]);
+        let mut b = Xoshiro256PlusPlus::with_seed([1, 2, 3, 4]);
+        let mut bytes = [0u8; 13];
+        a.fill_bytes(&mut bytes);
+        assert_eq!(bytes[0..8], b.next_u64().to_le_bytes());
+        assert_eq!(bytes[8..13], b.next_u64().to_le_bytes()[0..5]);
+    }
+
+    #[test]
+    fn test_gen_range() {
+        let mut rng = Pcg32::with_seed(1, 1);
+        let mut seen = [false; 7];
+        for _ in 0..1000 {
+            let i = rng.gen_range(-3i8, 4i8);
+            assert!(-3 <= i && i < 4);
+            seen[(i + 3) as usize] = true;
+            let u = rng.gen_range(u64::MAX - 2, u64::MAX);
+            assert!(u64::MAX - 2 <= u && u < u64::MAX);
+            let x = rng.gen_range(-1.5f64, 2.5f64);
+            assert!(-1.5 <= x && x < 2.5);
+            let f = rng.gen_f32();
+            assert!(0.0 <= f && f < 1.0);
+        }
+        assert!(seen.iter().all(|&seen| seen));
+        assert_eq!(rng.gen_range(i64::MIN, i64::MIN + 1), i64::MIN);
+        assert!(rng.gen_range(i64::MIN, i64::MAX) < i64::MAX);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_empty_range() {
+        Pcg32::with_seed(1, 1).gen_range(5u32, 5u32);
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let mut rng = Xoshiro256PlusPlus::from_u64(7);
+        let mut items = [0usize, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        rng.shuffle(&mut items);
+        assert_ne!(items, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let mut sorted = items;
+        sorted.sort();
+        assert_eq!(sorted, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_choose() {
+        let mut rng = Xoshiro256PlusPlus::from_u64(7);
+        let empty: [u8; 0] = [];
+        assert_eq!(rng.choose(&empty), None);
+        assert_eq!(rng.choose(&[3]), Some(&3));
+        let items = [1, 2, 3];
+        for _ in 0..100 {
+            assert!(items.contains(rng.choose(&items).unwrap()));
+        }
+    }
+}
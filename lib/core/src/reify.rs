@@ -27,6 +27,22 @@ impl<T: ?Sized> Reified<T> {
         }
     }
 
+    /// Returns a new `Reified` structure with the vtable of the passed-in
+    /// trait `object` pointer. The data address of `object` is ignored, so a
+    /// null pointer to the concrete type, unsized to `T`, suffices; this lets
+    /// statics initialize their vtables at compile time.
+    ///
+    /// # Safety
+    ///
+    /// Assumes that `T` is a trait object type.
+    #[inline]
+    pub const unsafe fn from_object(object: *const T) -> Reified<T> {
+        Reified {
+            vtable: NonNull::new_unchecked(TraitRepr { ptr: object }.raw.vtable),
+            marker: PhantomData,
+        }
+    }
+
     /// Initializes the vtable of the `Reified` structure, which resides at the
     /// base address of the referenced `object`, to point to the vtable of the
     /// passed-in trait object.
@@ -58,6 +74,31 @@ impl<T: ?Sized> Reified<T> {
     }
 }
 
+/// Reinterprets a trait object pointer as its raw parts in const contexts.
+union TraitRepr<T: ?Sized> {
+    ptr: *const T,
+    raw: TraitObject,
+}
+
+/// A type whose base address holds a `Reified` structure: either `Reified`
+/// itself, or a `#[repr(C)]` struct whose first field is a `ReifyBase`.
+///
+/// # Safety
+///
+/// Must only be implemented by types that begin with a `Reified` structure.
+/// Implemented automatically by the `reify!` macro.
+pub unsafe trait ReifyBase {
+}
+
+unsafe impl<T: ?Sized> ReifyBase for Reified<T> {
+}
+
+/// Statically asserts that `B` begins with a `Reified` structure.
+#[doc(hidden)]
+#[inline(always)]
+pub const fn assert_reify_base<B: ReifyBase + ?Sized>() {
+}
+
 /// A type with a `Reified` field as its first struct member, from which a
 /// polymorphic trait object can be constructed from a thin pointer to the
 /// base address of the object.
@@ -75,3 +116,147 @@ pub trait Reify<'a, T: ?Sized = Self> {
     /// reference.
     unsafe fn reify(base: &'a Reified<T>) -> &'a T;
 }
+
+/// Declares a `#[repr(C)]` struct whose first field is a `Reified` base, and
+/// implements `Reify` for the struct's trait object type. The first field must
+/// either be a `Reified` structure, or another struct declared by `reify!`;
+/// anything else fails to compile. Also generates a `const fn reified()` that
+/// returns a `Reified` base with the struct's vtable, for initializing statics
+/// and new instances without calling `Reify::deify`. Omitting the trait object
+/// type declares a base struct that other `reify!` structs can embed.
+///
+/// ```
+/// # #![feature(const_fn)]
+/// # #[macro_use] extern crate swim_core;
+/// use swim_core::reify::{Reified, Reify};
+///
+/// trait Shape<'a> {
+///     fn sides(&self) -> usize;
+/// }
+///
+/// reify! {
+///     /// A shape with four equal sides.
+///     struct Square<'a>: dyn Shape<'a> + 'a {
+///         /// Polymorphic shape type.
+///         base: Reified<dyn Shape<'a> + 'a>,
+///     }
+/// }
+///
+/// impl<'a> Shape<'a> for Square<'a> {
+///     fn sides(&self) -> usize {
+///         4
+///     }
+/// }
+///
+/// unsafe impl<'a> Sync for Square<'a> {
+/// }
+///
+/// static SQUARE: Square<'static> = Square { base: Square::reified() };
+///
+/// # fn main() {
+/// let shape = unsafe { Square::reify(&SQUARE.base) };
+/// assert_eq!(shape.sides(), 4);
+/// # }
+/// ```
+///
+/// A struct whose first field isn't a `Reified` base doesn't compile:
+///
+/// ```compile_fail
+/// # #![feature(const_fn)]
+/// # #[macro_use] extern crate swim_core;
+/// # use swim_core::reify::Reified;
+/// # trait Shape<'a> {}
+/// reify! {
+///     struct Square<'a>: dyn Shape<'a> + 'a {
+///         side: usize,
+///         base: Reified<dyn Shape<'a> + 'a>,
+///     }
+/// }
+/// # impl<'a> Shape<'a> for Square<'a> {}
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! reify {
+    // struct $name<'a> { $base: $base_type, $($field: $field_type),* }
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident<$lt:lifetime> {
+            $(#[$base_attr:meta])*
+            $base_vis:vis $base:ident: $base_type:ty
+            $(,
+                $(#[$field_attr:meta])*
+                $field_vis:vis $field:ident: $field_type:ty
+            )*
+            $(,)?
+        }
+    ) => (
+        $(#[$attr])*
+        #[repr(C)]
+        $vis struct $name<$lt> {
+            $(#[$base_attr])*
+            $base_vis $base: $base_type,
+            $(
+                $(#[$field_attr])*
+                $field_vis $field: $field_type,
+            )*
+        }
+
+        impl<$lt> $name<$lt> {
+            /// Statically asserts that the first field is a `Reified` base.
+            #[allow(dead_code)]
+            const REIFY_BASE: () = $crate::reify::assert_reify_base::<$base_type>();
+        }
+
+        unsafe impl<$lt> $crate::reify::ReifyBase for $name<$lt> {
+        }
+    );
+    // struct $name<'a>: $object { $base: $base_type, $($field: $field_type),* }
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident<$lt:lifetime>: $object:ty {
+            $(#[$base_attr:meta])*
+            $base_vis:vis $base:ident: $base_type:ty
+            $(,
+                $(#[$field_attr:meta])*
+                $field_vis:vis $field:ident: $field_type:ty
+            )*
+            $(,)?
+        }
+    ) => (
+        impl<$lt> $name<$lt> {
+            /// Returns a `Reified` base that points to the vtable of this type.
+            #[allow(dead_code)]
+            #[inline]
+            $vis const fn reified() -> $crate::reify::Reified<$object> {
+                unsafe {
+                    $crate::reify::Reified::from_object(
+                        ::core::ptr::null::<$name<$lt>>() as *const $object)
+                }
+            }
+        }
+
+        $crate::reify! {
+            $(#[$attr])*
+            $vis struct $name<$lt> {
+                $(#[$base_attr])*
+                $base_vis $base: $base_type,
+                $(
+                    $(#[$field_attr])*
+                    $field_vis $field: $field_type,
+                )*
+            }
+        }
+
+        impl<$lt> $crate::reify::Reify<$lt, $object> for $name<$lt> {
+            #[inline]
+            unsafe fn deify(object: &mut $object) {
+                $crate::reify::Reified::<$object>::deify(::core::mem::transmute(object));
+            }
+
+            #[inline]
+            unsafe fn reify(base: &$lt $crate::reify::Reified<$object>) -> &$lt $object {
+                ::core::mem::transmute(base.reify())
+            }
+        }
+    );
+}
@@ -0,0 +1,385 @@
+use core::cmp;
+use core::fmt;
+use core::hash::{BuildHasher, Hasher};
+use core::mem;
+use core::ptr;
+use core::sync::atomic::{AtomicU64, AtomicUsize};
+use core::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+use crate::entropy::{self, EntropyError};
+
+/// SipHash-1-3 hasher, keyed with a secret 128-bit key; a fast keyed hash
+/// suitable for hash tables with attacker controlled keys.
+#[derive(Clone)]
+pub struct SipHasher13 {
+    state: SipState,
+    tail: u64,
+    ntail: usize,
+    length: usize,
+}
+
+/// SipHash-2-4 hasher, keyed with a secret 128-bit key; the conservative
+/// SipHash variant recommended by its authors.
+#[derive(Clone)]
+pub struct SipHasher24 {
+    state: SipState,
+    tail: u64,
+    ntail: usize,
+    length: usize,
+}
+
+/// `BuildHasher` of keyed `SipHasher13`s; hash tries built with a secret
+/// random key resist hash flooding by untrusted input.
+#[derive(Clone)]
+pub struct SipHash13 {
+    k0: u64,
+    k1: u64,
+}
+
+/// `BuildHasher` of keyed `SipHasher24`s.
+#[derive(Clone)]
+pub struct SipHash24 {
+    k0: u64,
+    k1: u64,
+}
+
+#[derive(Clone, Copy)]
+struct SipState {
+    v0: u64,
+    v1: u64,
+    v2: u64,
+    v3: u64,
+}
+
+/// Per-process secret key from which keys of new `BuildHasher`s are derived.
+static RANDOM_K0: AtomicU64 = AtomicU64::new(0);
+static RANDOM_K1: AtomicU64 = AtomicU64::new(0);
+/// Seeding state of the secret key; one of `UNSEEDED`, `SEEDING`, or `SEEDED`.
+static RANDOM_STATE: AtomicUsize = AtomicUsize::new(UNSEEDED);
+/// Number of keys derived from the secret key.
+static RANDOM_COUNT: AtomicU64 = AtomicU64::new(0);
+
+const UNSEEDED: usize = 0;
+const SEEDING: usize = 1;
+const SEEDED: usize = 2;
+
+macro_rules! load_int_le {
+    ($buf: expr, $i: expr, $int_ty: ident) => ({
+        debug_assert!($i + mem::size_of::<$int_ty>() <= $buf.len());
+        let mut data = 0 as $int_ty;
+        ptr::copy_nonoverlapping($buf.get_unchecked($i),
+                                 &mut data as *mut _ as *mut u8,
+                                 mem::size_of::<$int_ty>());
+        data.to_le()
+    });
+}
+
+#[inline]
+unsafe fn u8to64_le(buf: &[u8], start: usize, len: usize) -> u64 {
+    debug_assert!(len < 8);
+    let mut i = 0; // current byte index (from LSB) in the output u64
+    let mut out = 0;
+    if i + 3 < len {
+        out = load_int_le!(buf, start + i, u32) as u64;
+        i += 4;
+    }
+    if i + 1 < len {
+        out |= (load_int_le!(buf, start + i, u16) as u64) << (i * 8);
+        i += 2
+    }
+    if i < len {
+        out |= (*buf.get_unchecked(start + i) as u64) << (i * 8);
+        i += 1;
+    }
+    debug_assert_eq!(i, len);
+    out
+}
+
+impl SipState {
+    #[inline]
+    const fn new(k0: u64, k1: u64) -> SipState {
+        SipState {
+            v0: k0 ^ 0x736f6d6570736575,
+            v1: k1 ^ 0x646f72616e646f6d,
+            v2: k0 ^ 0x6c7967656e657261,
+            v3: k1 ^ 0x7465646279746573,
+        }
+    }
+
+    #[inline]
+    fn round(&mut self) {
+        self.v0 = self.v0.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(13);
+        self.v1 ^= self.v0;
+        self.v0 = self.v0.rotate_left(32);
+        self.v2 = self.v2.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(16);
+        self.v3 ^= self.v2;
+        self.v0 = self.v0.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(21);
+        self.v3 ^= self.v0;
+        self.v2 = self.v2.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(17);
+        self.v1 ^= self.v2;
+        self.v2 = self.v2.rotate_left(32);
+    }
+
+    #[inline]
+    fn compress(&mut self, m: u64, c_rounds: usize) {
+        self.v3 ^= m;
+        for _ in 0..c_rounds {
+            self.round();
+        }
+        self.v0 ^= m;
+    }
+
+    #[inline]
+    fn finalize(mut self, d_rounds: usize) -> u64 {
+        self.v2 ^= 0xff;
+        for _ in 0..d_rounds {
+            self.round();
+        }
+        self.v0 ^ self.v1 ^ self.v2 ^ self.v3
+    }
+}
+
+macro_rules! impl_sip_hasher {
+    ($hasher: ident, $c_rounds: expr, $d_rounds: expr) => (
+        impl $hasher {
+            /// Returns a new hasher keyed with the 128-bit key `(k0, k1)`.
+            pub const fn new(k0: u64, k1: u64) -> Self {
+                Self {
+                    state: SipState::new(k0, k1),
+                    tail: 0,
+                    ntail: 0,
+                    length: 0,
+                }
+            }
+        }
+
+        impl Hasher for $hasher {
+            fn write(&mut self, msg: &[u8]) {
+                let length = msg.len();
+                self.length += length;
+
+                // Fill up the buffered tail, if partially filled.
+                let mut needed = 0;
+                if self.ntail != 0 {
+                    needed = 8 - self.ntail;
+                    let take = cmp::min(length, needed);
+                    self.tail |= unsafe { u8to64_le(msg, 0, take) } << (8 * self.ntail);
+                    if length < needed {
+                        self.ntail += length;
+                        return;
+                    }
+                    self.state.compress(self.tail, $c_rounds);
+                    self.ntail = 0;
+                }
+
+                // Compress all whole words of the remaining input.
+                let left = (length - needed) & 0x7;
+                let end = length - left;
+                let mut offset = needed;
+                while offset < end {
+                    let m = unsafe { load_int_le!(msg, offset, u64) };
+                    self.state.compress(m, $c_rounds);
+                    offset += 8;
+                }
+
+                // Buffer the trailing bytes.
+                self.tail = unsafe { u8to64_le(msg, offset, left) };
+                self.ntail = left;
+            }
+
+            fn finish(&self) -> u64 {
+                let mut state = self.state;
+                let b = ((self.length as u64 & 0xff) << 56) | self.tail;
+                state.compress(b, $c_rounds);
+                state.finalize($d_rounds)
+            }
+        }
+    );
+}
+
+impl_sip_hasher!(SipHasher13, 1, 3);
+impl_sip_hasher!(SipHasher24, 2, 4);
+
+/// Returns the per-process secret key, seeding it from the runtime entropy
+/// source on first use; concurrent first callers wait for a single seeding.
+fn random_secret() -> Result<(u64, u64), EntropyError> {
+    loop {
+        match RANDOM_STATE.compare_exchange_weak(UNSEEDED, SEEDING, Acquire, Acquire) {
+            Ok(_) => {
+                let mut bytes = [0u8; 16];
+                if let Err(error) = entropy::fill(&mut bytes) {
+                    // Let a later caller retry the seeding.
+                    RANDOM_STATE.store(UNSEEDED, Release);
+                    return Err(error);
+                }
+                RANDOM_K0.store(unsafe { load_int_le!(bytes, 0, u64) }, Relaxed);
+                RANDOM_K1.store(unsafe { load_int_le!(bytes, 8, u64) }, Relaxed);
+                RANDOM_STATE.store(SEEDED, Release);
+                break;
+            },
+            Err(SEEDED) => break,
+            // Another thread is seeding, or the weak exchange failed spuriously.
+            Err(_) => continue,
+        }
+    }
+    Ok((RANDOM_K0.load(Relaxed), RANDOM_K1.load(Relaxed)))
+}
+
+/// Returns a fresh pair of random keys, derived by hashing a per-process
+/// counter under the secret key, so that no two `BuildHasher`s share a key,
+/// and no key reveals the secret or any other key.
+fn random_keys() -> Result<(u64, u64), EntropyError> {
+    let (s0, s1) = random_secret()?;
+    let count = RANDOM_COUNT.fetch_add(1, Relaxed);
+    let derive = |lane: u8| {
+        let mut hasher = SipHasher24::new(s0, s1);
+        hasher.write_u64(count);
+        hasher.write_u8(lane);
+        hasher.finish()
+    };
+    Ok((derive(0), derive(1)))
+}
+
+macro_rules! impl_sip_hash {
+    ($build: ident, $hasher: ident) => (
+        impl $build {
+            /// Returns a `BuildHasher` keyed with a secret random key.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the runtime has no entropy source.
+            pub fn new() -> Self {
+                match Self::try_new() {
+                    Ok(build) => build,
+                    Err(error) => panic!("{:?}", error),
+                }
+            }
+
+            /// Returns a `BuildHasher` keyed with a secret random key, or an
+            /// `EntropyError` if the runtime has no entropy source.
+            pub fn try_new() -> Result<Self, EntropyError> {
+                let (k0, k1) = random_keys()?;
+                Ok(Self::with_keys(k0, k1))
+            }
+
+            /// Returns a `BuildHasher` keyed with the 128-bit key `(k0, k1)`.
+            pub const fn with_keys(k0: u64, k1: u64) -> Self {
+                Self { k0: k0, k1: k1 }
+            }
+        }
+
+        impl BuildHasher for $build {
+            type Hasher = $hasher;
+
+            #[inline]
+            fn build_hasher(&self) -> $hasher {
+                $hasher::new(self.k0, self.k1)
+            }
+        }
+
+        impl Default for $build {
+            #[inline]
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl fmt::Debug for $build {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                // Never reveal the secret key.
+                f.debug_struct(stringify!($build)).finish()
+            }
+        }
+    );
+}
+
+impl_sip_hash!(SipHash13, SipHasher13);
+impl_sip_hash!(SipHash24, SipHasher24);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const K0: u64 = 0x0706050403020100;
+    const K1: u64 = 0x0f0e0d0c0b0a0908;
+
+    fn assert_hashes13(n: usize, hash: u64) {
+        let mut msg = [0u8; 64];
+        for i in 0..n {
+            msg[i] = i as u8;
+        }
+        let data = &msg[0..n];
+        for i in 0..(n+1) {
+            for j in i..(n+1) {
+                let mut hasher = SipHasher13::new(K0, K1);
+                hasher.write(&data[0..i]);
+                hasher.write(&data[i..j]);
+                hasher.write(&data[j..n]);
+                assert_eq!(hasher.finish(), hash);
+            }
+        }
+    }
+
+    fn assert_hashes24(n: usize, hash: u64) {
+        let mut msg = [0u8; 64];
+        for i in 0..n {
+            msg[i] = i as u8;
+        }
+        let data = &msg[0..n];
+        for i in 0..(n+1) {
+            for j in i..(n+1) {
+                let mut hasher = SipHasher24::new(K0, K1);
+                hasher.write(&data[0..i]);
+                hasher.write(&data[i..j]);
+                hasher.write(&data[j..n]);
+                assert_eq!(hasher.finish(), hash);
+            }
+        }
+    }
+
+    #[test]
+    fn test_siphash13_vectors() {
+        assert_hashes13(0, 0xabac0158050fc4dc);
+        assert_hashes13(1, 0xc9f49bf37d57ca93);
+        assert_hashes13(2, 0x82cb9b024dc7d44d);
+        assert_hashes13(7, 0xd3927d989bb11140);
+        assert_hashes13(8, 0x369095118d299a8e);
+        assert_hashes13(9, 0x25a48eb36c063de4);
+        assert_hashes13(15, 0xd320d86d2a519956);
+        assert_hashes13(16, 0xcc4fdd1a7d908b66);
+        assert_hashes13(17, 0x9cf2689063dbd80c);
+        assert_hashes13(31, 0x2370dd1f8c21d1bc);
+        assert_hashes13(63, 0x9d199062b7bbb3a8);
+    }
+
+    #[test]
+    fn test_siphash24_vectors() {
+        assert_hashes24(0, 0x726fdb47dd0e0e31);
+        assert_hashes24(1, 0x74f839c593dc67fd);
+        assert_hashes24(2, 0x0d6c8009d9a94f5a);
+        assert_hashes24(7, 0xab0200f58b01d137);
+        assert_hashes24(8, 0x93f5f5799a932462);
+        assert_hashes24(9, 0x9e0082df0ba9e4b0);
+        assert_hashes24(15, 0xa129ca6149be45e5);
+        assert_hashes24(16, 0x3f2acc7f57c29bdb);
+        assert_hashes24(17, 0x699ae9f52cbe4794);
+        assert_hashes24(31, 0x32d892fad841c342);
+        assert_hashes24(63, 0x958a324ceb064572);
+    }
+
+    #[test]
+    fn test_keyed_build_hasher() {
+        let build = SipHash13::with_keys(K0, K1);
+        let mut hasher = build.build_hasher();
+        hasher.write(&[0]);
+        assert_eq!(hasher.finish(), 0xc9f49bf37d57ca93);
+
+        let build = SipHash24::with_keys(K0, K1);
+        let mut hasher = build.build_hasher();
+        hasher.write(&[0]);
+        assert_eq!(hasher.finish(), 0x74f839c593dc67fd);
+    }
+}
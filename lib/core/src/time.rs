@@ -0,0 +1,355 @@
+use core::i64;
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+
+pub use core::time::Duration;
+
+#[allow(improper_ctypes)]
+extern "Rust" {
+    fn _swim_global_clock<'a>() -> &'a dyn Clock;
+}
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+const NANOS_PER_MILLI: u32 = 1_000_000;
+const MILLIS_PER_SEC: i64 = 1_000;
+
+/// Source of monotonic and wall-clock time.
+pub trait Clock {
+    /// Returns the time elapsed since some fixed point in the past. Successive
+    /// readings never decrease, and are unaffected by changes to the system
+    /// time.
+    fn monotonic(&self) -> Duration;
+
+    /// Returns the current wall-clock time. Successive readings can jump
+    /// forwards or backwards when the system time is adjusted.
+    fn realtime(&self) -> Timestamp;
+}
+
+impl dyn Clock {
+    /// Returns a handle to the global `Clock`.
+    #[inline]
+    pub fn global() -> &'static dyn Clock {
+        unsafe { _swim_global_clock() }
+    }
+}
+
+/// Opaque reading of a monotonic `Clock`, useful for measuring elapsed time.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Instant {
+    /// Time elapsed since the clock's fixed starting point.
+    t: Duration,
+}
+
+/// Point in wall-clock time, measured relative to the Unix epoch.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct Timestamp {
+    /// Whole seconds since the Unix epoch; negative before the epoch.
+    secs: i64,
+    /// Nanoseconds past `secs`, always less than one second.
+    nanos: u32,
+}
+
+impl Instant {
+    /// Returns the current reading of the global monotonic `Clock`.
+    #[inline]
+    pub fn now() -> Instant {
+        Instant::now_on(<dyn Clock>::global())
+    }
+
+    /// Returns the current reading of the monotonic `clock`.
+    #[inline]
+    pub fn now_on(clock: &dyn Clock) -> Instant {
+        Instant { t: clock.monotonic() }
+    }
+
+    /// Returns the time elapsed from `earlier` to this `Instant`, or zero if
+    /// `earlier` is later than this `Instant`.
+    #[inline]
+    pub fn duration_since(&self, earlier: Instant) -> Duration {
+        self.checked_duration_since(earlier).unwrap_or(Duration::from_secs(0))
+    }
+
+    /// Returns the time elapsed from `earlier` to this `Instant`, or `None`
+    /// if `earlier` is later than this `Instant`.
+    #[inline]
+    pub fn checked_duration_since(&self, earlier: Instant) -> Option<Duration> {
+        self.t.checked_sub(earlier.t)
+    }
+
+    /// Returns the time elapsed since this `Instant`, according to the global
+    /// monotonic `Clock`.
+    #[inline]
+    pub fn elapsed(&self) -> Duration {
+        Instant::now().duration_since(*self)
+    }
+
+    /// Returns the `Instant` that is `duration` after this `Instant`, or
+    /// `None` on overflow.
+    #[inline]
+    pub fn checked_add(&self, duration: Duration) -> Option<Instant> {
+        self.t.checked_add(duration).map(|t| Instant { t: t })
+    }
+
+    /// Returns the `Instant` that is `duration` before this `Instant`, or
+    /// `None` if the result would precede the clock's starting point.
+    #[inline]
+    pub fn checked_sub(&self, duration: Duration) -> Option<Instant> {
+        self.t.checked_sub(duration).map(|t| Instant { t: t })
+    }
+}
+
+impl Add<Duration> for Instant {
+    type Output = Instant;
+
+    fn add(self, duration: Duration) -> Instant {
+        self.checked_add(duration).expect("overflow when adding duration to instant")
+    }
+}
+
+impl AddAssign<Duration> for Instant {
+    fn add_assign(&mut self, duration: Duration) {
+        *self = *self + duration;
+    }
+}
+
+impl Sub<Duration> for Instant {
+    type Output = Instant;
+
+    fn sub(self, duration: Duration) -> Instant {
+        self.checked_sub(duration).expect("overflow when subtracting duration from instant")
+    }
+}
+
+impl SubAssign<Duration> for Instant {
+    fn sub_assign(&mut self, duration: Duration) {
+        *self = *self - duration;
+    }
+}
+
+impl Sub<Instant> for Instant {
+    type Output = Duration;
+
+    fn sub(self, earlier: Instant) -> Duration {
+        self.duration_since(earlier)
+    }
+}
+
+impl Timestamp {
+    /// The Unix epoch: 1970-01-01T00:00:00Z.
+    pub const UNIX_EPOCH: Timestamp = Timestamp { secs: 0, nanos: 0 };
+
+    /// Returns the current wall-clock time of the global `Clock`.
+    #[inline]
+    pub fn now() -> Timestamp {
+        Timestamp::now_on(<dyn Clock>::global())
+    }
+
+    /// Returns the current wall-clock time of the `clock`.
+    #[inline]
+    pub fn now_on(clock: &dyn Clock) -> Timestamp {
+        clock.realtime()
+    }
+
+    /// Returns the `Timestamp` that is `secs` seconds plus `nanos`
+    /// nanoseconds after the Unix epoch. Carries excess nanoseconds into
+    /// the seconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if carrying the nanoseconds overflows the seconds.
+    pub fn new(secs: i64, nanos: u32) -> Timestamp {
+        let secs = secs.checked_add((nanos / NANOS_PER_SEC) as i64)
+                       .expect("overflow in Timestamp::new");
+        Timestamp {
+            secs: secs,
+            nanos: nanos % NANOS_PER_SEC,
+        }
+    }
+
+    /// Returns the `Timestamp` that is `secs` seconds after the Unix epoch.
+    #[inline]
+    pub const fn from_secs(secs: i64) -> Timestamp {
+        Timestamp { secs: secs, nanos: 0 }
+    }
+
+    /// Returns the `Timestamp` that is `millis` milliseconds after the Unix
+    /// epoch.
+    #[inline]
+    pub fn from_millis(millis: i64) -> Timestamp {
+        Timestamp {
+            secs: millis.div_euclid(MILLIS_PER_SEC),
+            nanos: millis.rem_euclid(MILLIS_PER_SEC) as u32 * NANOS_PER_MILLI,
+        }
+    }
+
+    /// Returns the number of whole seconds since the Unix epoch, rounded
+    /// towards negative infinity.
+    #[inline]
+    pub const fn secs(&self) -> i64 {
+        self.secs
+    }
+
+    /// Returns the nanoseconds past the whole seconds of this `Timestamp`.
+    #[inline]
+    pub const fn subsec_nanos(&self) -> u32 {
+        self.nanos
+    }
+
+    /// Returns the number of whole milliseconds since the Unix epoch, rounded
+    /// towards negative infinity, or `None` on overflow.
+    #[inline]
+    pub fn to_millis(&self) -> Option<i64> {
+        self.secs.checked_mul(MILLIS_PER_SEC)?
+                 .checked_add((self.nanos / NANOS_PER_MILLI) as i64)
+    }
+
+    /// Returns the time elapsed from `earlier` to this `Timestamp`, or `None`
+    /// if `earlier` is later than this `Timestamp`.
+    pub fn duration_since(&self, earlier: Timestamp) -> Option<Duration> {
+        if *self < earlier {
+            return None;
+        }
+        let (secs, nanos) = if self.nanos >= earlier.nanos {
+            (self.secs.wrapping_sub(earlier.secs) as u64, self.nanos - earlier.nanos)
+        } else {
+            (self.secs.wrapping_sub(earlier.secs).wrapping_sub(1) as u64,
+             self.nanos + NANOS_PER_SEC - earlier.nanos)
+        };
+        Some(Duration::new(secs, nanos))
+    }
+
+    /// Returns the `Timestamp` that is `duration` after this `Timestamp`, or
+    /// `None` on overflow.
+    pub fn checked_add(&self, duration: Duration) -> Option<Timestamp> {
+        if duration.as_secs() > i64::MAX as u64 {
+            return None;
+        }
+        let mut secs = self.secs.checked_add(duration.as_secs() as i64)?;
+        let mut nanos = self.nanos + duration.subsec_nanos();
+        if nanos >= NANOS_PER_SEC {
+            nanos -= NANOS_PER_SEC;
+            secs = secs.checked_add(1)?;
+        }
+        Some(Timestamp { secs: secs, nanos: nanos })
+    }
+
+    /// Returns the `Timestamp` that is `duration` before this `Timestamp`, or
+    /// `None` on overflow.
+    pub fn checked_sub(&self, duration: Duration) -> Option<Timestamp> {
+        if duration.as_secs() > i64::MAX as u64 {
+            return None;
+        }
+        let mut secs = self.secs.checked_sub(duration.as_secs() as i64)?;
+        let nanos = if self.nanos >= duration.subsec_nanos() {
+            self.nanos - duration.subsec_nanos()
+        } else {
+            secs = secs.checked_sub(1)?;
+            self.nanos + NANOS_PER_SEC - duration.subsec_nanos()
+        };
+        Some(Timestamp { secs: secs, nanos: nanos })
+    }
+}
+
+impl Add<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    fn add(self, duration: Duration) -> Timestamp {
+        self.checked_add(duration).expect("overflow when adding duration to timestamp")
+    }
+}
+
+impl AddAssign<Duration> for Timestamp {
+    fn add_assign(&mut self, duration: Duration) {
+        *self = *self + duration;
+    }
+}
+
+impl Sub<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    fn sub(self, duration: Duration) -> Timestamp {
+        self.checked_sub(duration).expect("overflow when subtracting duration from timestamp")
+    }
+}
+
+impl SubAssign<Duration> for Timestamp {
+    fn sub_assign(&mut self, duration: Duration) {
+        *self = *self - duration;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+    use core::u64;
+    use super::*;
+
+    struct TestClock {
+        monotonic: Cell<Duration>,
+        realtime: Cell<Timestamp>,
+    }
+
+    impl Clock for TestClock {
+        fn monotonic(&self) -> Duration {
+            self.monotonic.get()
+        }
+
+        fn realtime(&self) -> Timestamp {
+            self.realtime.get()
+        }
+    }
+
+    #[test]
+    fn test_instant_arithmetic() {
+        let clock = TestClock {
+            monotonic: Cell::new(Duration::new(5, 900_000_000)),
+            realtime: Cell::new(Timestamp::UNIX_EPOCH),
+        };
+        let t0 = Instant::now_on(&clock);
+        clock.monotonic.set(Duration::new(7, 100_000_000));
+        let t1 = Instant::now_on(&clock);
+        assert_eq!(t1 - t0, Duration::from_millis(1_200));
+        assert_eq!(t0 - t1, Duration::from_secs(0));
+        assert_eq!(t0.checked_duration_since(t1), None);
+        assert_eq!(t0 + Duration::from_millis(1_200), t1);
+        assert_eq!(t1 - Duration::from_millis(1_200), t0);
+        assert!(t0 < t1);
+        assert_eq!(t0.checked_sub(Duration::from_secs(6)), None);
+    }
+
+    #[test]
+    fn test_timestamp_millis() {
+        assert_eq!(Timestamp::from_millis(0), Timestamp::UNIX_EPOCH);
+        assert_eq!(Timestamp::from_millis(1_500), Timestamp::new(1, 500_000_000));
+        assert_eq!(Timestamp::from_millis(-1_500), Timestamp::new(-2, 500_000_000));
+        assert_eq!(Timestamp::from_millis(-1_500).to_millis(), Some(-1_500));
+        assert_eq!(Timestamp::new(-1, 999_999_999).to_millis(), Some(-1));
+        assert_eq!(Timestamp::from_millis(1_546_300_800_123).secs(), 1_546_300_800);
+        assert_eq!(Timestamp::from_millis(1_546_300_800_123).subsec_nanos(), 123_000_000);
+        assert_eq!(Timestamp::from_secs(i64::MAX).to_millis(), None);
+    }
+
+    #[test]
+    fn test_timestamp_arithmetic() {
+        let t0 = Timestamp::new(-1, 700_000_000);
+        let t1 = t0 + Duration::from_millis(600);
+        assert_eq!(t1, Timestamp::new(0, 300_000_000));
+        assert_eq!(t1 - Duration::from_millis(600), t0);
+        assert_eq!(t1.duration_since(t0), Some(Duration::from_millis(600)));
+        assert_eq!(t0.duration_since(t1), None);
+        assert_eq!(Timestamp::new(3, 2_500_000_000), Timestamp::new(5, 500_000_000));
+        assert_eq!(Timestamp::from_secs(i64::MAX).checked_add(Duration::from_secs(1)), None);
+        assert_eq!(Timestamp::from_secs(i64::MIN).checked_sub(Duration::from_nanos(1)), None);
+        assert_eq!(Timestamp::from_secs(i64::MIN).duration_since(Timestamp::from_secs(i64::MAX)), None);
+        assert_eq!(Timestamp::from_secs(i64::MAX).duration_since(Timestamp::from_secs(i64::MIN)),
+                   Some(Duration::from_secs(u64::MAX)));
+    }
+
+    #[test]
+    fn test_timestamp_now_on() {
+        let clock = TestClock {
+            monotonic: Cell::new(Duration::from_secs(0)),
+            realtime: Cell::new(Timestamp::from_millis(1_546_300_800_000)),
+        };
+        assert_eq!(Timestamp::now_on(&clock).secs(), 1_546_300_800);
+    }
+}
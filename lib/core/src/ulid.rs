@@ -0,0 +1,162 @@
+use core::fmt;
+use crate::entropy::{self, EntropyError};
+use crate::time::Timestamp;
+use crate::uuid::{self, Uuid};
+
+const RANDOM_BITS: u32 = 80;
+const RANDOM_MASK: u128 = (1 << RANDOM_BITS) - 1;
+
+/// Universally unique lexicographically sortable identifier: a 48-bit
+/// millisecond timestamp followed by 80 random bits. Ordering compares the
+/// timestamp first, so identifiers sort by creation time.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Ulid(u128);
+
+impl Ulid {
+    /// The nil ULID, with all bits set to zero.
+    pub const NIL: Ulid = Ulid(0);
+
+    /// Returns a new `Ulid` for the current time of the global `Clock`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime has no entropy source.
+    pub fn new() -> Ulid {
+        match Ulid::try_new() {
+            Ok(ulid) => ulid,
+            Err(error) => panic!("{:?}", error),
+        }
+    }
+
+    /// Returns a new `Ulid` for the current time of the global `Clock`, or an
+    /// `EntropyError` if the runtime has no entropy source.
+    pub fn try_new() -> Result<Ulid, EntropyError> {
+        let mut bytes = [0u8; 16];
+        entropy::fill(&mut bytes[6..])?;
+        Ok(Ulid::from_parts(Timestamp::now(), u128::from_be_bytes(bytes)))
+    }
+
+    /// Returns the `Ulid` for the millisecond of `timestamp`, with the low 80
+    /// bits of `random`. Timestamps outside the 48-bit range of milliseconds
+    /// since the Unix epoch wrap around.
+    pub fn from_parts(timestamp: Timestamp, random: u128) -> Ulid {
+        let millis = uuid::wrapping_millis(timestamp);
+        Ulid(((millis as u128) << RANDOM_BITS) | (random & RANDOM_MASK))
+    }
+
+    /// Returns the `Ulid` with the value `value`.
+    #[inline]
+    pub const fn from_u128(value: u128) -> Ulid {
+        Ulid(value)
+    }
+
+    /// Returns the `Ulid` with the given big-endian `bytes`.
+    #[inline]
+    pub fn from_bytes(bytes: [u8; 16]) -> Ulid {
+        Ulid(u128::from_be_bytes(bytes))
+    }
+
+    /// Returns the value of this `Ulid`.
+    #[inline]
+    pub const fn to_u128(&self) -> u128 {
+        self.0
+    }
+
+    /// Returns the big-endian bytes of this `Ulid`.
+    #[inline]
+    pub fn to_bytes(&self) -> [u8; 16] {
+        self.0.to_be_bytes()
+    }
+
+    /// Returns `true` if this is the nil `Ulid`.
+    #[inline]
+    pub fn is_nil(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the creation time of this `Ulid`, with millisecond precision.
+    #[inline]
+    pub fn timestamp(&self) -> Timestamp {
+        Timestamp::from_millis((self.0 >> RANDOM_BITS) as i64)
+    }
+
+    /// Returns the 80 random bits of this `Ulid`.
+    #[inline]
+    pub fn random(&self) -> u128 {
+        self.0 & RANDOM_MASK
+    }
+}
+
+impl From<u128> for Ulid {
+    #[inline]
+    fn from(value: u128) -> Ulid {
+        Ulid(value)
+    }
+}
+
+impl From<Ulid> for u128 {
+    #[inline]
+    fn from(ulid: Ulid) -> u128 {
+        ulid.0
+    }
+}
+
+impl From<Uuid> for Ulid {
+    #[inline]
+    fn from(uuid: Uuid) -> Ulid {
+        Ulid(uuid.to_u128())
+    }
+}
+
+impl From<Ulid> for Uuid {
+    #[inline]
+    fn from(ulid: Ulid) -> Uuid {
+        Uuid::from_u128(ulid.0)
+    }
+}
+
+impl fmt::Debug for Ulid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Ulid({:032x})", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::u128;
+    use super::*;
+
+    #[test]
+    fn test_ulid_parts() {
+        let timestamp = Timestamp::from_millis(1_469_918_176_385);
+        let ulid = Ulid::from_parts(timestamp, u128::MAX);
+        assert_eq!(ulid.timestamp(), timestamp);
+        assert_eq!(ulid.random(), RANDOM_MASK);
+        assert_eq!(ulid.to_u128() >> RANDOM_BITS, 1_469_918_176_385);
+        assert_eq!(Ulid::from_bytes(ulid.to_bytes()), ulid);
+    }
+
+    #[test]
+    fn test_ulid_wraps_timestamp() {
+        let ulid = Ulid::from_parts(Timestamp::from_millis((1 << 48) + 5), 0);
+        assert_eq!(ulid.to_u128() >> RANDOM_BITS, 5);
+        let ulid = Ulid::from_parts(Timestamp::from_millis(-1), 0);
+        assert_eq!(ulid.to_u128() >> RANDOM_BITS, 0xFFFF_FFFF_FFFF);
+    }
+
+    #[test]
+    fn test_ulid_ordering() {
+        let a = Ulid::from_parts(Timestamp::from_millis(1_000), RANDOM_MASK);
+        let b = Ulid::from_parts(Timestamp::from_millis(1_001), 0);
+        assert!(a < b);
+        assert!(Ulid::NIL < a);
+    }
+
+    #[test]
+    fn test_ulid_uuid_roundtrip() {
+        let ulid = Ulid::from_u128(0x0123456789abcdef_fedcba9876543210);
+        let uuid = Uuid::from(ulid);
+        assert_eq!(uuid.as_bytes()[0], 0x01);
+        assert_eq!(Ulid::from(uuid), ulid);
+    }
+}
@@ -0,0 +1,243 @@
+use core::fmt;
+use crate::entropy::{self, EntropyError};
+use crate::time::Timestamp;
+
+/// 128-bit universally unique identifier, laid out in network byte order
+/// as specified by RFC 4122; ordering compares the bytes lexicographically,
+/// so version 7 identifiers sort by creation time.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Uuid([u8; 16]);
+
+impl Uuid {
+    /// The nil UUID, with all bits set to zero.
+    pub const NIL: Uuid = Uuid([0; 16]);
+
+    /// Name space for fully-qualified domain names.
+    pub const NAMESPACE_DNS: Uuid = Uuid([0x6b, 0xa7, 0xb8, 0x10, 0x9d, 0xad, 0x11, 0xd1,
+                                          0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8]);
+
+    /// Name space for URLs.
+    pub const NAMESPACE_URL: Uuid = Uuid([0x6b, 0xa7, 0xb8, 0x11, 0x9d, 0xad, 0x11, 0xd1,
+                                          0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8]);
+
+    /// Name space for ISO OIDs.
+    pub const NAMESPACE_OID: Uuid = Uuid([0x6b, 0xa7, 0xb8, 0x12, 0x9d, 0xad, 0x11, 0xd1,
+                                          0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8]);
+
+    /// Name space for X.500 distinguished names.
+    pub const NAMESPACE_X500: Uuid = Uuid([0x6b, 0xa7, 0xb8, 0x14, 0x9d, 0xad, 0x11, 0xd1,
+                                           0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8]);
+
+    /// Returns the `Uuid` with the given big-endian `bytes`.
+    #[inline]
+    pub const fn from_bytes(bytes: [u8; 16]) -> Uuid {
+        Uuid(bytes)
+    }
+
+    /// Returns the `Uuid` whose big-endian bytes encode `value`.
+    #[inline]
+    pub fn from_u128(value: u128) -> Uuid {
+        Uuid(value.to_be_bytes())
+    }
+
+    /// Returns a new random (version 4) `Uuid`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime has no entropy source.
+    pub fn new_v4() -> Uuid {
+        match Uuid::try_new_v4() {
+            Ok(uuid) => uuid,
+            Err(error) => panic!("{:?}", error),
+        }
+    }
+
+    /// Returns a new random (version 4) `Uuid`, or an `EntropyError` if the
+    /// runtime has no entropy source.
+    pub fn try_new_v4() -> Result<Uuid, EntropyError> {
+        let mut bytes = [0u8; 16];
+        entropy::fill(&mut bytes)?;
+        Ok(Uuid::from_random_bytes(bytes))
+    }
+
+    /// Returns the random (version 4) `Uuid` made from `bytes`, which should
+    /// be drawn from a random source; overwrites the version and variant
+    /// bits.
+    pub fn from_random_bytes(bytes: [u8; 16]) -> Uuid {
+        Uuid(bytes).with_version(4)
+    }
+
+    /// Returns the name-based (version 5) `Uuid` made from the leading 16
+    /// bytes of `digest`, the SHA-1 hash of a name space `Uuid` followed by
+    /// a name; overwrites the version and variant bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `digest` is shorter than 16 bytes.
+    pub fn from_sha1_digest(digest: &[u8]) -> Uuid {
+        let mut bytes = [0u8; 16];
+        bytes.copy_from_slice(&digest[..16]);
+        Uuid(bytes).with_version(5)
+    }
+
+    /// Returns a new time-ordered (version 7) `Uuid` for the current time of
+    /// the global `Clock`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime has no entropy source.
+    pub fn new_v7() -> Uuid {
+        match Uuid::try_new_v7() {
+            Ok(uuid) => uuid,
+            Err(error) => panic!("{:?}", error),
+        }
+    }
+
+    /// Returns a new time-ordered (version 7) `Uuid` for the current time of
+    /// the global `Clock`, or an `EntropyError` if the runtime has no entropy
+    /// source.
+    pub fn try_new_v7() -> Result<Uuid, EntropyError> {
+        let mut random = [0u8; 10];
+        entropy::fill(&mut random)?;
+        Ok(Uuid::from_timestamp_v7(Timestamp::now(), random))
+    }
+
+    /// Returns the time-ordered (version 7) `Uuid` for the millisecond of
+    /// `timestamp`, with the trailing bits taken from `random`; overwrites
+    /// the version and variant bits. Timestamps outside the 48-bit range of
+    /// milliseconds since the Unix epoch wrap around.
+    pub fn from_timestamp_v7(timestamp: Timestamp, random: [u8; 10]) -> Uuid {
+        let millis = wrapping_millis(timestamp);
+        let mut bytes = [0u8; 16];
+        bytes[..6].copy_from_slice(&millis.to_be_bytes()[2..]);
+        bytes[6..].copy_from_slice(&random);
+        Uuid(bytes).with_version(7)
+    }
+
+    /// Returns the big-endian bytes of this `Uuid`.
+    #[inline]
+    pub const fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+
+    /// Returns the value of this `Uuid` as a `u128`.
+    #[inline]
+    pub fn to_u128(&self) -> u128 {
+        u128::from_be_bytes(self.0)
+    }
+
+    /// Returns `true` if this is the nil `Uuid`.
+    #[inline]
+    pub fn is_nil(&self) -> bool {
+        self.0 == [0; 16]
+    }
+
+    /// Returns the version number of this `Uuid`, if it has the RFC 4122
+    /// variant.
+    pub fn version(&self) -> Option<u8> {
+        if self.0[8] & 0xC0 == 0x80 {
+            Some(self.0[6] >> 4)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the creation time of this `Uuid`, with millisecond precision,
+    /// if it's a time-ordered (version 7) `Uuid`.
+    pub fn timestamp(&self) -> Option<Timestamp> {
+        if self.version() == Some(7) {
+            let mut millis = [0u8; 8];
+            millis[2..].copy_from_slice(&self.0[..6]);
+            Some(Timestamp::from_millis(u64::from_be_bytes(millis) as i64))
+        } else {
+            None
+        }
+    }
+
+    /// Returns this `Uuid` with the RFC 4122 variant and the given `version`.
+    fn with_version(mut self, version: u8) -> Uuid {
+        self.0[6] = (self.0[6] & 0x0F) | (version << 4);
+        self.0[8] = (self.0[8] & 0x3F) | 0x80;
+        self
+    }
+}
+
+impl From<u128> for Uuid {
+    #[inline]
+    fn from(value: u128) -> Uuid {
+        Uuid::from_u128(value)
+    }
+}
+
+impl From<Uuid> for u128 {
+    #[inline]
+    fn from(uuid: Uuid) -> u128 {
+        uuid.to_u128()
+    }
+}
+
+impl fmt::Debug for Uuid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Uuid({:032x})", self.to_u128())
+    }
+}
+
+/// Returns the milliseconds since the Unix epoch of `timestamp`, wrapped
+/// around to 48 bits.
+pub(crate) fn wrapping_millis(timestamp: Timestamp) -> u64 {
+    let millis = timestamp.secs().wrapping_mul(1_000)
+                          .wrapping_add((timestamp.subsec_nanos() / 1_000_000) as i64);
+    millis as u64 & 0xFFFF_FFFF_FFFF
+}
+
+#[cfg(test)]
+mod tests {
+    use core::i64;
+    use super::*;
+
+    #[test]
+    fn test_uuid_fields() {
+        let uuid = Uuid::from_u128(0x6ba7b810_9dad_11d1_80b4_00c04fd430c8);
+        assert_eq!(uuid, Uuid::NAMESPACE_DNS);
+        assert_eq!(uuid.to_u128(), 0x6ba7b810_9dad_11d1_80b4_00c04fd430c8);
+        assert_eq!(uuid.version(), Some(1));
+        assert_eq!(uuid.timestamp(), None);
+        assert!(Uuid::NIL.is_nil());
+        assert_eq!(Uuid::NIL.version(), None);
+    }
+
+    #[test]
+    fn test_uuid_random_bytes() {
+        let uuid = Uuid::from_random_bytes([0xFF; 16]);
+        assert_eq!(uuid.to_u128(), 0xffffffff_ffff_4fff_bfff_ffffffffffff);
+        assert_eq!(uuid.version(), Some(4));
+    }
+
+    #[test]
+    fn test_uuid_v7_timestamp() {
+        // RFC 9562, Appendix A.6
+        let timestamp = Timestamp::from_millis(0x017F22E279B0);
+        let uuid = Uuid::from_timestamp_v7(timestamp, [0xCC, 0xC3, 0x18, 0xC4, 0xDC, 0x0C, 0x0C, 0x07, 0x39, 0x8F]);
+        assert_eq!(uuid.to_u128(), 0x017f22e2_79b0_7cc3_98c4_dc0c0c07398f);
+        assert_eq!(uuid.version(), Some(7));
+        assert_eq!(uuid.timestamp(), Some(timestamp));
+    }
+
+    #[test]
+    fn test_uuid_v7_ordering() {
+        let a = Uuid::from_timestamp_v7(Timestamp::from_millis(1_000), [0xFF; 10]);
+        let b = Uuid::from_timestamp_v7(Timestamp::from_millis(1_001), [0x00; 10]);
+        assert!(a < b);
+    }
+
+    #[test]
+    fn test_uuid_v7_wraps_timestamp() {
+        let uuid = Uuid::from_timestamp_v7(Timestamp::from_millis((1 << 48) + 5), [0; 10]);
+        assert_eq!(uuid.to_u128() >> 80, 5);
+        let uuid = Uuid::from_timestamp_v7(Timestamp::from_millis(-1), [0; 10]);
+        assert_eq!(uuid.to_u128() >> 80, 0xFFFF_FFFF_FFFF);
+        let uuid = Uuid::from_timestamp_v7(Timestamp::new(i64::MAX, 999_999_999), [0; 10]);
+        let millis = i64::MAX.wrapping_mul(1_000).wrapping_add(999) as u64 & 0xFFFF_FFFF_FFFF;
+        assert_eq!(uuid.to_u128() >> 80, millis as u128);
+    }
+}
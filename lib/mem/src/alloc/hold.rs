@@ -1,7 +1,7 @@
 use core::cell::UnsafeCell;
 use core::mem;
 use core::ptr;
-use swim_core::reify::{Reified, Reify};
+use swim_core::reify::Reified;
 use crate::block::{Block, Layout, LayoutError};
 use crate::alloc::{AllocTag, HeapError};
 
@@ -47,6 +47,8 @@ pub unsafe trait Hold<'a> {
             ok @ Ok(_) => ok,
             Err(_) => match self.alloc(layout) {
                 Ok(new_block) => {
+                    let size = block.size().min(new_block.size());
+                    ptr::copy_nonoverlapping(block.as_ptr(), new_block.as_ptr(), size);
                     self.dealloc(block);
                     Ok(new_block)
                 },
@@ -83,20 +85,14 @@ impl<'a> Hold<'a> {
 
     /// Returns a reference to a `Hold` that can only allocate zero-sized values.
     pub fn empty() -> &'a impl Hold<'a> {
-        // Declare the empty hold singleton with an uninitialized vtable.
-        // Rust apparently has no way to get a static pointer to a vtable.
-        static mut EMPTY: EmptyHold<'static> = EmptyHold {
-            base: unsafe { Reified::uninitialized() },
-            zero: unsafe { AllocTag::null() },
+        // Declare the empty hold singleton with a statically initialized vtable,
+        // and a shared zero tag that points back to the empty hold.
+        static EMPTY: EmptyHold<'static> = EmptyHold {
+            base: EmptyHold::reified(),
+            zero: AllocTag::new(&EMPTY.base),
         };
-        unsafe {
-            // Initialize the empty hold's vtable (idempotent).
-            EmptyHold::deify(&mut EMPTY);
-            // Initialize the empty hold's shared zero tag (idempotent).
-            EMPTY.zero.init(&EMPTY.base);
-            // Return a reference to the empty hold.
-            mem::transmute::<_, &EmptyHold<'a>>(&EMPTY)
-        }
+        // Return a reference to the empty hold.
+        unsafe { mem::transmute::<_, &EmptyHold<'a>>(&EMPTY) }
     }
 }
 
@@ -203,13 +199,14 @@ impl<'a> Drop for LocalHold<'a> {
     }
 }
 
-/// Degenerate `Hold` that can only `alloc` and `dealloc` zero-sized blocks.
-#[repr(C)]
-struct EmptyHold<'a> {
-    /// Polymorphic hold type
-    base: Reified<Hold<'a>>,
-    /// Tag shared by all zero-sized allocations in the empty hold
-    zero: AllocTag<'a>,
+reify! {
+    /// Degenerate `Hold` that can only `alloc` and `dealloc` zero-sized blocks.
+    struct EmptyHold<'a>: dyn Hold<'a> + 'a {
+        /// Polymorphic hold type
+        base: Reified<dyn Hold<'a> + 'a>,
+        /// Tag shared by all zero-sized allocations in the empty hold
+        zero: AllocTag<'a>,
+    }
 }
 
 impl<'a> EmptyHold<'a> {
@@ -264,18 +261,6 @@ unsafe impl<'a> Hold<'a> for EmptyHold<'a> {
     }
 }
 
-impl<'a> Reify<'a, Hold<'a> + 'a> for EmptyHold<'a> {
-    #[inline]
-    unsafe fn deify(object: &mut (Hold<'a> + 'a)) {
-        Reified::<Hold<'a> + 'a>::deify(mem::transmute(object));
-    }
-
-    #[inline]
-    unsafe fn reify(base: &'a Reified<Hold<'a> + 'a>) -> &'a (Hold<'a> + 'a) {
-        mem::transmute(base.reify())
-    }
-}
-
 /// An object allocated by a `Hold`.
 pub trait Holder<'a> {
     /// Returns the `Hold` that allocated this object.
@@ -5,34 +5,38 @@ use core::ptr;
 use core::sync::atomic::AtomicU32;
 use core::sync::atomic::Ordering::{Acquire, Relaxed, Release, SeqCst};
 use core::u32;
-use swim_core::reify::{Reified, Reify};
+use swim_core::reify::Reified;
 use crate::block::{Block, Layout};
 use crate::alloc::{AllocTag, Hold, HoldError};
 
-/// Base linear allocator for a fixed-size memory block.
-///
-/// A pack allocates space by advancing a pointer into its memory block,
-/// similar to stack allocation. Packs only reclaim space when the most
-/// recent allocation drops, and when the whole pack drops.
-pub(crate) struct PackBase<'a> {
-    /// Polymorphic hold type.
-    base: Reified<Hold<'a>>,
-    /// Total number of bytes in the memory block, including pack header.
-    size: u32,
-    /// Offset from the base pack address of the next free byte in the memory block.
-    mark: AtomicU32,
-    /// Pin to the base address of the memory block.
-    #[allow(dead_code)]
-    pinned: PhantomPinned,
+reify! {
+    /// Base linear allocator for a fixed-size memory block.
+    ///
+    /// A pack allocates space by advancing a pointer into its memory block,
+    /// similar to stack allocation. Packs only reclaim space when the most
+    /// recent allocation drops, and when the whole pack drops.
+    pub(crate) struct PackBase<'a> {
+        /// Polymorphic hold type.
+        base: Reified<dyn Hold<'a> + 'a>,
+        /// Total number of bytes in the memory block, including pack header.
+        size: u32,
+        /// Offset from the base pack address of the next free byte in the memory block.
+        mark: AtomicU32,
+        /// Pin to the base address of the memory block.
+        #[allow(dead_code)]
+        pinned: PhantomPinned,
+    }
 }
 
 impl<'a> PackBase<'a> {
-    /// Constructs a `PackBase` in the memory `block`, with a reserved header.
+    /// Constructs a `PackBase` in the memory `block`, with a reserved header,
+    /// and with the `Reified` `base` of the concrete pack type.
     ///
     /// # Safety
     ///
     /// Assumes `header_size` is large enough to hold the `PackBase` header.
-    pub(crate) fn from_block(block: Block<'a>, header_size: usize) -> *mut PackBase<'a> {
+    pub(crate) fn from_block(block: Block<'a>, header_size: usize,
+                             base: Reified<dyn Hold<'a> + 'a>) -> *mut PackBase<'a> {
         // Get the alignment of the allocation tag.
         let tag_align = mem::align_of::<AllocTag>();
         // Round the header size up to the alignment of the first allocation tag.
@@ -50,7 +54,7 @@ impl<'a> PackBase<'a> {
         unsafe {
             // Initialize the base pack header to the beginning of the memory block.
             ptr::write(pack_ptr, PackBase {
-                base: Reified::uninitialized(),
+                base: base,
                 size: block_size as u32,
                 mark: AtomicU32::new(header_size as u32),
                 pinned: PhantomPinned,
@@ -233,34 +237,24 @@ impl<'a> PackBase<'a> {
     }
 }
 
-impl<'a> Reify<'a, Hold<'a> + 'a> for PackBase<'a> {
-    #[inline]
-    unsafe fn deify(object: &mut (Hold<'a> + 'a)) {
-        Reified::<Hold<'a>>::deify(mem::transmute(object));
-    }
-
-    #[inline]
-    unsafe fn reify(base: &'a Reified<Hold<'a> + 'a>) -> &'a (Hold<'a> + 'a) {
-        mem::transmute(base.reify())
+reify! {
+    /// Linear allocator for a fixed-size memory block.
+    ///
+    /// A pack allocates space by advancing a pointer into its memory block,
+    /// similar to stack allocation. Packs only reclaim space when the most
+    /// recent allocation drops, and when the whole pack drops.
+    pub struct Pack<'a>: dyn Hold<'a> + 'a {
+        /// Inner pack allocator.
+        base: PackBase<'a>,
+        /// Number of live allocations in this pack.
+        live: AtomicU32,
+        /// Number of currently allocated bytes in this pack.
+        used: AtomicU32,
+        /// Tag shared by all zero-sized allocations in this pack.
+        zero: AllocTag<'a>,
     }
 }
 
-/// Linear allocator for a fixed-size memory block.
-///
-/// A pack allocates space by advancing a pointer into its memory block,
-/// similar to stack allocation. Packs only reclaim space when the most
-/// recent allocation drops, and when the whole pack drops.
-pub struct Pack<'a> {
-    /// Inner pack allocator.
-    base: PackBase<'a>,
-    /// Number of live allocations in this pack.
-    live: AtomicU32,
-    /// Number of currently allocated bytes in this pack.
-    used: AtomicU32,
-    /// Tag shared by all zero-sized allocations in this pack.
-    zero: AllocTag<'a>,
-}
-
 impl<'a> Pack<'a> {
     /// Constructs a `Pack` in a memory `block`.
     pub fn new(block: Block<'a>) -> &'a Pack<'a> {
@@ -274,7 +268,7 @@ impl<'a> Pack<'a> {
     /// Assumes `header_size` is large enough to hold the `Pack` header.
     pub fn from_block(block: Block<'a>, header_size: usize) -> *mut Pack<'a> {
         // Construct a base pack in the memory block with a pack header.
-        let pack = PackBase::from_block(block, header_size) as *mut Pack<'a>;
+        let pack = PackBase::from_block(block, header_size, Pack::reified()) as *mut Pack<'a>;
         unsafe {
             // Initialize the live allocation count.
             ptr::write(&mut (*pack).live, AtomicU32::new(0));
@@ -282,8 +276,6 @@ impl<'a> Pack<'a> {
             ptr::write(&mut (*pack).used, AtomicU32::new(0));
             // Initialize the zero-sized allocation tag.
             ptr::write(&mut (*pack).zero, AllocTag::new(&(*pack).base.base));
-            // Initialize the hold base with the concrete type of the pack.
-            Pack::deify(&mut *pack);
         }
         // Return a pointer to the pack header.
         pack
@@ -384,15 +376,3 @@ unsafe impl<'a> Hold<'a> for Pack<'a> {
         }
     }
 }
-
-impl<'a> Reify<'a, Hold<'a> + 'a> for Pack<'a> {
-    #[inline]
-    unsafe fn deify(object: &mut (Hold<'a> + 'a)) {
-        Reified::<Hold<'a>>::deify(mem::transmute(object));
-    }
-
-    #[inline]
-    unsafe fn reify(base: &'a Reified<Hold<'a> + 'a>) -> &'a (Hold<'a> + 'a) {
-        mem::transmute(base.reify())
-    }
-}
@@ -2,7 +2,6 @@ use core::mem;
 use core::ptr;
 use core::sync::atomic::{AtomicPtr, AtomicUsize};
 use core::sync::atomic::Ordering::{Acquire, Relaxed, Release};
-use swim_core::reify::{Reified, Reify};
 use crate::block::{Block, Layout};
 use crate::alloc::{Heap, Hold, HoldError};
 use crate::alloc::pack::PackBase;
@@ -248,26 +247,27 @@ impl<'a> Drop for Pool<'a> {
     }
 }
 
-struct PackList<'a> {
-    /// Inner pack allocator.
-    base: PackBase<'a>,
-    /// Pointer to the next pack in the used pack list.
-    next: AtomicPtr<PackList<'a>>,
-    /// Non-zero pointer to the pool that owns this pack.
-    pool: *mut Pool<'a>,
+reify! {
+    struct PackList<'a>: dyn Hold<'a> + 'a {
+        /// Inner pack allocator.
+        base: PackBase<'a>,
+        /// Pointer to the next pack in the used pack list.
+        next: AtomicPtr<PackList<'a>>,
+        /// Non-zero pointer to the pool that owns this pack.
+        pool: *mut Pool<'a>,
+    }
 }
 
 impl<'a> PackList<'a> {
     // Constructs a `PackList` in a memory `block`.
     unsafe fn from_block(block: Block<'a>, pool: *mut Pool<'a>) -> *mut PackList<'a> {
         // Construct a base pack in the memory block with a pack list header.
-        let pack = PackBase::from_block(block, mem::size_of::<PackList<'a>>()) as *mut PackList<'a>;
+        let pack = PackBase::from_block(block, mem::size_of::<PackList<'a>>(),
+                                        PackList::reified()) as *mut PackList<'a>;
         // Initialize the next pointer.
         ptr::write(&mut (*pack).next, AtomicPtr::new(ptr::null_mut()));
         // Initialize the pool pointer.
         ptr::write(&mut (*pack).pool, pool);
-        // Initialize the hold base with the concrete type of the pack list.
-        PackList::deify(&mut *pack);
         // Return a pointer to the pack list header.
         pack
     }
@@ -314,15 +314,3 @@ unsafe impl<'a> Hold<'a> for PackList<'a> {
         }
     }
 }
-
-impl<'a> Reify<'a, Hold<'a> + 'a> for PackList<'a> {
-    #[inline]
-    unsafe fn deify(object: &mut (Hold<'a> + 'a)) {
-        Reified::<Hold<'a>>::deify(mem::transmute(object));
-    }
-
-    #[inline]
-    unsafe fn reify(base: &'a Reified<Hold<'a> + 'a>) -> &'a (Hold<'a> + 'a) {
-        mem::transmute(base.reify())
-    }
-}
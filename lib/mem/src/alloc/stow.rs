@@ -2,6 +2,7 @@ use core::mem;
 use core::ptr;
 use swim_core::f16;
 use swim_core::murmur3::Murmur3;
+use swim_core::siphash::{SipHash13, SipHash24};
 use crate::alloc::{Hold, HoldError};
 
 /// Conversion from a value to `Self`, allocating in a `Hold` as needed.
@@ -100,6 +101,8 @@ stow_from_value!(f64);
 stow_from_value!(char);
 stow_from_value!(bool);
 stow_from_value!(Murmur3);
+stow_from_value!(SipHash13);
+stow_from_value!(SipHash24);
 
 macro_rules! stow_value {
     ($type:ty) => (
@@ -135,6 +138,8 @@ stow_value!(f64);
 stow_value!(char);
 stow_value!(bool);
 stow_value!(Murmur3);
+stow_value!(SipHash13);
+stow_value!(SipHash24);
 
 impl<'b, T: Stow<'b>> Stow<'b> for [T] {
     default unsafe fn stow(src: *mut [T], dst: *mut [T], hold: &dyn Hold<'b>) -> Result<(), HoldError> {
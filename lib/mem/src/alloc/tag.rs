@@ -13,7 +13,7 @@ use crate::alloc::{Hold, HoldError};
 pub struct AllocTag<'a> {
     /// Atomic thin pointer to the `Hold` that allocated this tag. The pointed-to
     /// `HoldBase` contains the vtable of the reified `Hold` trait object.
-    pub(crate) base: AtomicPtr<Reified<Hold<'a>>>,
+    pub(crate) base: AtomicPtr<Reified<dyn Hold<'a> + 'a>>,
     /// Pin to the preceding aligned address of the tagged memory block.
     pinned: PhantomPinned,
 }
@@ -39,16 +39,16 @@ impl<'a> AllocTag<'a> {
     #[inline]
     pub fn empty() -> AllocTag<'a> {
         AllocTag {
-            base: AtomicPtr::new(&*Hold::empty() as *const Hold<'a> as *mut Reified<Hold<'a>>),
+            base: AtomicPtr::new(&*Hold::empty() as *const dyn Hold<'a> as *mut Reified<dyn Hold<'a> + 'a>),
             pinned: PhantomPinned,
         }
     }
 
     /// Returns a new `AllocTag` that points back to the `Hold` that allocated this tag.
     #[inline]
-    pub fn new(base: &Reified<Hold<'a>>) -> AllocTag<'a> {
+    pub const fn new(base: &Reified<dyn Hold<'a> + 'a>) -> AllocTag<'a> {
         AllocTag {
-            base: AtomicPtr::new(base as *const Reified<Hold<'a>> as *mut Reified<Hold<'a>>),
+            base: AtomicPtr::new(base as *const Reified<dyn Hold<'a> + 'a> as *mut Reified<dyn Hold<'a> + 'a>),
             pinned: PhantomPinned,
         }
     }
@@ -56,8 +56,8 @@ impl<'a> AllocTag<'a> {
     /// Initializes this `AllocTag` to point to the `Hold` at `base`. This
     /// operation is idempotent when called repeatedly with the same `base` address.
     #[inline(always)]
-    pub fn init(&mut self, base: &Reified<Hold<'a>>) {
-        self.base.store(base as *const Reified<Hold<'a>> as *mut Reified<Hold<'a>>, Relaxed);
+    pub fn init(&mut self, base: &Reified<dyn Hold<'a> + 'a>) {
+        self.base.store(base as *const Reified<dyn Hold<'a> + 'a> as *mut Reified<dyn Hold<'a> + 'a>, Relaxed);
     }
 
     /// Returns a pointer to the `AllocTag` preceding a `data` pointer allocated by a `Hold`.
@@ -162,6 +162,7 @@
 #![feature(thread_local)]
 #![feature(trusted_len)]
 
+#[macro_use]
 extern crate swim_core;
 
 pub mod block;
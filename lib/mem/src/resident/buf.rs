@@ -699,7 +699,8 @@ impl<'a, L: DynamicLease<'a, Data=T, Meta=BufHeader<M>>, T, M> BufLease<L, T, M>
             let new_layout = Layout::for_array::<T>(new_cap)?;
             match self.lease.realloc(new_layout) {
                 ok @ Ok(_) => {
-                    (*header).cap = new_cap;
+                    // Reallocation may have moved a header stored in the leased block.
+                    (*self.lease.meta()).cap = new_cap;
                     ok
                 },
                 err @ Err(_) => err,
@@ -726,7 +727,8 @@ impl<'a, L: DynamicLease<'a, Data=T, Meta=BufHeader<M>>, T, M> BufLease<L, T, M>
             let new_layout = Layout::for_array::<T>(new_cap)?;
             match self.lease.realloc(new_layout) {
                 ok @ Ok(_) => {
-                    (*header).cap = new_cap;
+                    // Reallocation may have moved a header stored in the leased block.
+                    (*self.lease.meta()).cap = new_cap;
                     ok
                 },
                 err @ Err(_) => err,
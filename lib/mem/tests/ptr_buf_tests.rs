@@ -0,0 +1,30 @@
+extern crate swim_mem;
+
+use swim_mem::block::Block;
+use swim_mem::alloc::Pack;
+use swim_mem::lease::{PtrBuf, RawBox};
+
+#[test]
+fn test_ptr_buf_reserve_moved_header() {
+    static mut TEST_AREA: [u8; 4096] = [0; 4096];
+    let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+
+    let mut xs = PtrBuf::<usize>::hold_cap(pack, 2);
+    xs.push(5);
+    xs.push(9);
+    // Allocate past the end of xs so that its header has to move.
+    let y = RawBox::hold_new(pack, 7usize);
+    let old_addr = xs.as_ptr();
+    xs.push(11);
+    assert_ne!(xs.as_ptr(), old_addr);
+    assert_eq!(pack.live(), 2);
+    assert_eq!(xs.len(), 3);
+    assert_eq!(xs.cap(), 4);
+    assert_eq!(xs[0], 5);
+    assert_eq!(xs[1], 9);
+    assert_eq!(xs[2], 11);
+    xs.push(13);
+    assert_eq!(xs.len(), 4);
+    assert_eq!(xs[3], 13);
+    assert_eq!(*y, 7);
+}
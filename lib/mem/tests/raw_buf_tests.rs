@@ -41,3 +41,24 @@ fn test_raw_buf_hold_cap() {
     assert_eq!(pack.used(), 0);
     assert_eq!(pack.free(), 4064);
 }
+
+#[test]
+fn test_raw_buf_reserve_moved() {
+    static mut TEST_AREA: [u8; 4096] = [0; 4096];
+    let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+
+    let mut xs = RawBuf::<usize>::hold_cap(pack, 2);
+    xs.push(5);
+    xs.push(9);
+    // Allocate past the end of xs so that it can't grow in place.
+    let ys = RawBuf::<usize>::hold_cap(pack, 1);
+    let old_addr = xs.as_ptr();
+    xs.push(11);
+    assert_ne!(xs.as_ptr(), old_addr);
+    assert_eq!(pack.live(), 2);
+    assert_eq!(xs.len(), 3);
+    assert_eq!(xs[0], 5);
+    assert_eq!(xs[1], 9);
+    assert_eq!(xs[2], 11);
+    assert_eq!(ys.len(), 0);
+}
@@ -7,6 +7,8 @@ use core::num::NonZeroU64;
 use core::ops::{Deref, DerefMut, Index, IndexMut};
 use core::ptr;
 use core::slice::{self, SliceIndex};
+use swim_core::ulid::Ulid;
+use swim_core::uuid::Uuid;
 use swim_mem::block::{Block, Layout};
 use swim_mem::alloc::{Hold, Holder, HoldError, AllocTag, Stow, TryClone, CloneIntoHold};
 use swim_mem::lease::PtrBuf;
@@ -577,6 +579,24 @@ impl<'a, Idx: SliceIndex<[u8]>> IndexMut<Idx> for Data<'a> {
     }
 }
 
+impl<'a> From<Uuid> for Data<'a> {
+    /// Returns the 16 big-endian bytes of `uuid` as a `Data` value allocated
+    /// in the global `Hold`.
+    #[inline]
+    fn from(uuid: Uuid) -> Data<'a> {
+        Data::from_slice(uuid.as_bytes())
+    }
+}
+
+impl<'a> From<Ulid> for Data<'a> {
+    /// Returns the 16 big-endian bytes of `ulid` as a `Data` value allocated
+    /// in the global `Hold`.
+    #[inline]
+    fn from(ulid: Ulid) -> Data<'a> {
+        Data::from_slice(&ulid.to_bytes())
+    }
+}
+
 impl<'a> PartialEq for Data<'a> {
     #[inline]
     fn eq(&self, that: &Data<'a>) -> bool {
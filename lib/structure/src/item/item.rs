@@ -3,7 +3,7 @@ use core::fmt;
 use core::hash;
 use core::mem;
 use core::ptr;
-use swim_core::f16;
+use swim_core::{f16, bf16};
 use swim_mem::alloc::{Hold, HoldError, Stow, TryClone, CloneIntoHold};
 use crate::item::{Field, Attr, Slot, Value, Record, Data, Text, Num, Bool, Extant, Absent};
 
@@ -96,6 +96,14 @@ impl<'a> Item<'a> {
         }
     }
 
+    /// Constructs a new `Item` from a `bf16` value.
+    pub fn from_bf16(value: bf16) -> Item<'a> {
+        Item {
+            val: Value::from_bf16(value),
+            key: None,
+        }
+    }
+
     /// Constructs a new `Item` from an `f32` value.
     pub fn from_f32(value: f32) -> Item<'a> {
         Item {
@@ -279,7 +287,7 @@ impl<'a> Item<'a> {
     /// Returns `true` if this `Item` is a `Slot`.
     pub fn is_slot(&self) -> bool {
         let key_tag = self.key_tag();
-        key_tag ^ Value::ATTR_FLAG == key_tag & Value::TYPE_MASK
+        key_tag != 0 && key_tag & Value::ATTR_FLAG == 0
     }
 
     /// Returns `true` if this `Item` is a `Value`.
@@ -1591,6 +1599,12 @@ impl<'a> From<f16> for Item<'a> {
     }
 }
 
+impl<'a> From<bf16> for Item<'a> {
+    fn from(value: bf16) -> Item<'a> {
+        Item::from_bf16(value)
+    }
+}
+
 impl<'a> From<f32> for Item<'a> {
     fn from(value: f32) -> Item<'a> {
         Item::from_f32(value)
@@ -13,7 +13,9 @@ use core::u8;
 use core::u16;
 use core::u32;
 use core::u64;
-use swim_core::f16;
+use swim_core::{f16, bf16};
+use swim_core::num::cast::TryFromNum;
+use swim_core::time::{Duration, Timestamp};
 use swim_mem::alloc::{Hold, HoldError, Stow, TryClone, CloneIntoHold};
 use crate::item::{Item, Value};
 
@@ -118,6 +120,15 @@ impl<'a> Num<'a> {
         }
     }
 
+    /// Constructs a new `Num` from a `bf16` value.
+    pub fn from_bf16(value: bf16) -> Num<'a> {
+        Num {
+            _0: unsafe { NonZeroU64::new_unchecked(Value::discriminant(Value::BF16_TYPE)) },
+            _1: unsafe { mem::transmute::<bf16, u16>(value) as u64 },
+            lifetime: PhantomData,
+        }
+    }
+
     /// Constructs a new `Num` from an `f32` value.
     pub fn from_f32(value: f32) -> Num<'a> {
         Num {
@@ -158,6 +169,25 @@ impl<'a> Num<'a> {
         Num::from_i64(value as i64)
     }
 
+    /// Constructs a new `Num` from the number of milliseconds in a `Duration`;
+    /// truncates sub-millisecond precision, and saturates at `u64::MAX`.
+    pub fn from_duration(value: Duration) -> Num<'a> {
+        let millis = value.as_millis();
+        Num::from_u64(if millis <= u64::MAX as u128 { millis as u64 } else { u64::MAX })
+    }
+
+    /// Constructs a new `Num` from the number of milliseconds since the Unix
+    /// epoch of a `Timestamp`; rounds towards negative infinity, and saturates
+    /// at the bounds of `i64`.
+    pub fn from_timestamp(value: Timestamp) -> Num<'a> {
+        let millis = match value.to_millis() {
+            Some(millis) => millis,
+            None if value.secs() < 0 => i64::MIN,
+            None => i64::MAX,
+        };
+        Num::from_i64(millis)
+    }
+
     /// Returns a pointer to the tag in the first byte of this `Num`.
     #[inline(always)]
     pub(crate) unsafe fn tag_ptr(&self) -> *mut u8 {
@@ -245,6 +275,11 @@ impl<'a> Num<'a> {
         self.type_tag() == Value::F16_TYPE
     }
 
+    /// Returns `true` if this `Num` was created from a `bf16` value.
+    pub fn is_bf16(&self) -> bool {
+        self.type_tag() == Value::BF16_TYPE
+    }
+
     /// Returns `true` if this `Num` was created from an `f32` value.
     pub fn is_f32(&self) -> bool {
         self.type_tag() == Value::F32_TYPE
@@ -285,291 +320,97 @@ impl<'a> Num<'a> {
         is
     }
 
-    /// Returns `true` if this `Num` can losslessly convert to a `u8` value.
-    pub fn is_valid_u8(&self) -> bool {
+    /// Losslessly converts this `Num` to a `T` value, if possible.
+    pub fn try_to<T: TryFromNum>(&self) -> Option<T> {
         match self.type_tag() {
-            Value::U8_TYPE => true,
-            Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE => {
-                self._1 <= u8::MAX as u64
-            },
-            Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
-                u8::MIN as i64 <= self._1 as i64 && self._1 as i64 <= u8::MAX as i64
-            },
-            Value::F16_TYPE => {
-                let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                value == f16::from(f32::from(value) as u8 as f32)
-            },
-            Value::F32_TYPE => {
-                let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                value == value as u8 as f32
-            },
-            Value::F64_TYPE => {
-                let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                value == value as u8 as f64
-            },
+            Value::U8_TYPE => T::try_from_num(self._1 as u8),
+            Value::U16_TYPE => T::try_from_num(self._1 as u16),
+            Value::U32_TYPE => T::try_from_num(self._1 as u32),
+            Value::U64_TYPE => T::try_from_num(self._1),
+            Value::I8_TYPE => T::try_from_num(self._1 as i8),
+            Value::I16_TYPE => T::try_from_num(self._1 as i16),
+            Value::I32_TYPE => T::try_from_num(self._1 as i32),
+            Value::I64_TYPE => T::try_from_num(self._1 as i64),
+            Value::F16_TYPE => T::try_from_num(f16::from_bits(self._1 as u16)),
+            Value::BF16_TYPE => T::try_from_num(bf16::from_bits(self._1 as u16)),
+            Value::F32_TYPE => T::try_from_num(f32::from_bits(self._1 as u32)),
+            Value::F64_TYPE => T::try_from_num(f64::from_bits(self._1)),
             Value::BIG_INT_TYPE => unimplemented!(),
             Value::BIG_DEC_TYPE => unimplemented!(),
             _ => unreachable!(),
         }
     }
 
+    /// Returns `true` if this `Num` can losslessly convert to a `u8` value.
+    #[inline]
+    pub fn is_valid_u8(&self) -> bool {
+        self.try_to::<u8>().is_some()
+    }
+
     /// Returns `true` if this `Num` can losslessly convert to an `i8` value.
+    #[inline]
     pub fn is_valid_i8(&self) -> bool {
-        match self.type_tag() {
-            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE => {
-                self._1 <= i8::MAX as u64
-            },
-            Value::I8_TYPE => true,
-            Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
-                i8::MIN as i64 <= self._1 as i64 && self._1 as i64 <= i8::MAX as i64
-            },
-            Value::F16_TYPE => {
-                let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                value == f16::from(f32::from(value) as i8 as f32)
-            },
-            Value::F32_TYPE => {
-                let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                value == value as i8 as f32
-            },
-            Value::F64_TYPE => {
-                let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                value == value as i8 as f64
-            },
-            Value::BIG_INT_TYPE => unimplemented!(),
-            Value::BIG_DEC_TYPE => unimplemented!(),
-            _ => unreachable!(),
-        }
+        self.try_to::<i8>().is_some()
     }
 
     /// Returns `true` if this `Num` can losslessly convert to a `u16` value.
+    #[inline]
     pub fn is_valid_u16(&self) -> bool {
-        match self.type_tag() {
-            Value::U8_TYPE | Value::U16_TYPE => true,
-            Value::U32_TYPE | Value::U64_TYPE => {
-                self._1 <= u16::MAX as u64
-            },
-            Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
-                u16::MIN as i64 <= self._1 as i64 && self._1 as i64 <= u16::MAX as i64
-            },
-            Value::F16_TYPE => {
-                let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                value == f16::from(f32::from(value) as u16 as f32)
-            },
-            Value::F32_TYPE => {
-                let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                value == value as u16 as f32
-            },
-            Value::F64_TYPE => {
-                let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                value == value as u16 as f64
-            },
-            Value::BIG_INT_TYPE => unimplemented!(),
-            Value::BIG_DEC_TYPE => unimplemented!(),
-            _ => unreachable!(),
-        }
+        self.try_to::<u16>().is_some()
     }
 
     /// Returns `true` if this `Num` can losslessly convert to an `i16` value.
+    #[inline]
     pub fn is_valid_i16(&self) -> bool {
-        match self.type_tag() {
-            Value::U8_TYPE => true,
-            Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE => {
-                self._1 <= i16::MAX as u64
-            },
-            Value::I8_TYPE | Value::I16_TYPE => true,
-            Value::I32_TYPE | Value::I64_TYPE => {
-                i16::MIN as i64 <= self._1 as i64 && self._1 as i64 <= i16::MAX as i64
-            },
-            Value::F16_TYPE => {
-                let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                value == f16::from(f32::from(value) as i16 as f32)
-            },
-            Value::F32_TYPE => {
-                let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                value == value as i16 as f32
-            },
-            Value::F64_TYPE => {
-                let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                value == value as i16 as f64
-            },
-            Value::BIG_INT_TYPE => unimplemented!(),
-            Value::BIG_DEC_TYPE => unimplemented!(),
-            _ => unreachable!(),
-        }
+        self.try_to::<i16>().is_some()
     }
 
     /// Returns `true` if this `Num` can losslessly convert to a `u32` value.
+    #[inline]
     pub fn is_valid_u32(&self) -> bool {
-        match self.type_tag() {
-            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE => true,
-            Value::U64_TYPE => {
-                self._1 <= u32::MAX as u64
-            },
-            Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
-                u32::MIN as i64 <= self._1 as i64 && self._1 as i64 <= u32::MAX as i64
-            },
-            Value::F16_TYPE => {
-                let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                value == f16::from(f32::from(value) as u32 as f32)
-            },
-            Value::F32_TYPE => {
-                let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                value == value as u32 as f32
-            },
-            Value::F64_TYPE => {
-                let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                value == value as u32 as f64
-            },
-            Value::BIG_INT_TYPE => unimplemented!(),
-            Value::BIG_DEC_TYPE => unimplemented!(),
-            _ => unreachable!(),
-        }
+        self.try_to::<u32>().is_some()
     }
 
     /// Returns `true` if this `Num` can losslessly convert to an `i32` value.
+    #[inline]
     pub fn is_valid_i32(&self) -> bool {
-        match self.type_tag() {
-            Value::U8_TYPE | Value::U16_TYPE => true,
-            Value::U32_TYPE | Value::U64_TYPE => {
-                self._1 <= i32::MAX as u64
-            },
-            Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE => true,
-            Value::I64_TYPE => {
-                i32::MIN as i64 <= self._1 as i64 && self._1 as i64 <= i32::MAX as i64
-            },
-            Value::F16_TYPE => {
-                let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                value == f16::from(f32::from(value) as i32 as f32)
-            },
-            Value::F32_TYPE => {
-                let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                value == value as i32 as f32
-            },
-            Value::F64_TYPE => {
-                let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                value == value as i32 as f64
-            },
-            Value::BIG_INT_TYPE => unimplemented!(),
-            Value::BIG_DEC_TYPE => unimplemented!(),
-            _ => unreachable!(),
-        }
+        self.try_to::<i32>().is_some()
     }
 
     /// Returns `true` if this `Num` can losslessly convert to a `u64` value.
+    #[inline]
     pub fn is_valid_u64(&self) -> bool {
-        match self.type_tag() {
-            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE => true,
-            Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
-                u64::MIN as i64 <= self._1 as i64
-            },
-            Value::F16_TYPE => {
-                let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                value == f16::from(f32::from(value) as u64 as f32)
-            },
-            Value::F32_TYPE => {
-                let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                value == value as u64 as f32
-            },
-            Value::F64_TYPE => {
-                let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                value == value as u64 as f64
-            },
-            Value::BIG_INT_TYPE => unimplemented!(),
-            Value::BIG_DEC_TYPE => unimplemented!(),
-            _ => unreachable!(),
-        }
+        self.try_to::<u64>().is_some()
     }
 
     /// Returns `true` if this `Num` can losslessly convert to an `i64` value.
+    #[inline]
     pub fn is_valid_i64(&self) -> bool {
-        match self.type_tag() {
-            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE => true,
-            Value::U64_TYPE => {
-                self._1 <= i64::MAX as u64
-            },
-            Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => true,
-            Value::F16_TYPE => {
-                let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                value == f16::from(f32::from(value) as i64 as f32)
-            },
-            Value::F32_TYPE => {
-                let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                value == value as i64 as f32
-            },
-            Value::F64_TYPE => {
-                let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                value == value as i64 as f64
-            },
-            Value::BIG_INT_TYPE => unimplemented!(),
-            Value::BIG_DEC_TYPE => unimplemented!(),
-            _ => unreachable!(),
-        }
+        self.try_to::<i64>().is_some()
     }
 
     /// Returns `true` if this `Num` can losslessly convert to an `f16` value.
+    #[inline]
     pub fn is_valid_f16(&self) -> bool {
-        match self.type_tag() {
-            Value::U8_TYPE => true,
-            Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE => {
-                self._1 == f32::from(f16::from(self._1 as f32)) as u64
-            },
-            Value::I8_TYPE => true,
-            Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
-                self._1 as i64 == f32::from(f16::from(self._1 as i64 as f32)) as i64
-            },
-            Value::F16_TYPE => true,
-            Value::F32_TYPE => {
-                let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                value == f32::from(f16::from(value))
-            },
-            Value::F64_TYPE => {
-                let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                value == f64::from(f16::from(value))
-            },
-            Value::BIG_INT_TYPE => unimplemented!(),
-            Value::BIG_DEC_TYPE => unimplemented!(),
-            _ => unreachable!(),
-        }
+        self.try_to::<f16>().is_some()
+    }
+
+    /// Returns `true` if this `Num` can losslessly convert to a `bf16` value.
+    #[inline]
+    pub fn is_valid_bf16(&self) -> bool {
+        self.try_to::<bf16>().is_some()
     }
 
     /// Returns `true` if this `Num` can losslessly convert to an `f32` value.
+    #[inline]
     pub fn is_valid_f32(&self) -> bool {
-        match self.type_tag() {
-            Value::U8_TYPE | Value::U16_TYPE => true,
-            Value::U32_TYPE | Value::U64_TYPE => {
-                self._1 == self._1 as f32 as u64
-            },
-            Value::I8_TYPE | Value::I16_TYPE => true,
-            Value::I32_TYPE | Value::I64_TYPE => {
-                self._1 as i64 == self._1 as i64 as f32 as i64
-            },
-            Value::F16_TYPE | Value::F32_TYPE => true,
-            Value::F64_TYPE => {
-                let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                value == value as f32 as f64
-            },
-            Value::BIG_INT_TYPE => unimplemented!(),
-            Value::BIG_DEC_TYPE => unimplemented!(),
-            _ => unreachable!(),
-        }
+        self.try_to::<f32>().is_some()
     }
 
     /// Returns `true` if this `Num` can losslessly convert to an `f64` value.
+    #[inline]
     pub fn is_valid_f64(&self) -> bool {
-        match self.type_tag() {
-            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE => true,
-            Value::U64_TYPE => {
-                self._1 == self._1 as f64 as u64
-            },
-            Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE => true,
-            Value::I64_TYPE => {
-                self._1 as i64 == self._1 as i64 as f64 as i64
-            },
-            Value::F16_TYPE | Value::F32_TYPE | Value::F64_TYPE => true,
-            Value::BIG_INT_TYPE => unimplemented!(),
-            Value::BIG_DEC_TYPE => unimplemented!(),
-            _ => unreachable!(),
-        }
+        self.try_to::<f64>().is_some()
     }
 
     /// Returns `true` if this `Num` can losslessly convert to a big integer.
@@ -577,7 +418,7 @@ impl<'a> Num<'a> {
         match self.type_tag() {
             Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE => true,
             Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => true,
-            Value::F16_TYPE | Value::F32_TYPE | Value::F64_TYPE => unimplemented!(),
+            Value::F16_TYPE | Value::BF16_TYPE | Value::F32_TYPE | Value::F64_TYPE => unimplemented!(),
             Value::BIG_INT_TYPE => true,
             Value::BIG_DEC_TYPE => unimplemented!(),
             _ => unreachable!(),
@@ -592,21 +433,13 @@ impl<'a> Num<'a> {
     /// Returns `true` if this `Num` can losslessly convert to a `usize` value.
     #[inline]
     pub fn is_valid_usize(&self) -> bool {
-        #[cfg(target_pointer_width = "32")]
-        let is = self.is_valid_u32();
-        #[cfg(target_pointer_width = "64")]
-        let is = self.is_valid_u64();
-        is
+        self.try_to::<usize>().is_some()
     }
 
     /// Returns `true` if this `Num` can losslessly convert to an `isize` value.
     #[inline]
     pub fn is_valid_isize(&self) -> bool {
-        #[cfg(target_pointer_width = "32")]
-        let is = self.is_valid_i32();
-        #[cfg(target_pointer_width = "64")]
-        let is = self.is_valid_i64();
-        is
+        self.try_to::<isize>().is_some()
     }
 
     /// Coerces this `Num` to a `u8` value.
@@ -620,6 +453,10 @@ impl<'a> Num<'a> {
                 let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
                 f32::from(value) as u8
             },
+            Value::BF16_TYPE => {
+                let value = unsafe { mem::transmute::<u16, bf16>(self._1 as u16) };
+                f32::from(value) as u8
+            },
             Value::F32_TYPE => {
                 let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
                 value as u8
@@ -645,6 +482,10 @@ impl<'a> Num<'a> {
                 let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
                 f32::from(value) as i8
             },
+            Value::BF16_TYPE => {
+                let value = unsafe { mem::transmute::<u16, bf16>(self._1 as u16) };
+                f32::from(value) as i8
+            },
             Value::F32_TYPE => {
                 let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
                 value as i8
@@ -670,6 +511,10 @@ impl<'a> Num<'a> {
                 let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
                 f32::from(value) as u16
             },
+            Value::BF16_TYPE => {
+                let value = unsafe { mem::transmute::<u16, bf16>(self._1 as u16) };
+                f32::from(value) as u16
+            },
             Value::F32_TYPE => {
                 let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
                 value as u16
@@ -695,6 +540,10 @@ impl<'a> Num<'a> {
                 let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
                 f32::from(value) as i16
             },
+            Value::BF16_TYPE => {
+                let value = unsafe { mem::transmute::<u16, bf16>(self._1 as u16) };
+                f32::from(value) as i16
+            },
             Value::F32_TYPE => {
                 let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
                 value as i16
@@ -720,392 +569,17 @@ impl<'a> Num<'a> {
                 let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
                 f32::from(value) as u32
             },
-            Value::F32_TYPE => {
-                let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                value as u32
-            },
-            Value::F64_TYPE => {
-                let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                value as u32
-            },
-            Value::BIG_INT_TYPE => unimplemented!(),
-            Value::BIG_DEC_TYPE => unimplemented!(),
-            _ => unreachable!(),
-        }
-    }
-
-    /// Coerces this `Num` to an `i32` value.
-    pub fn as_i32(&self) -> i32 {
-        match self.type_tag() {
-            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE |
-            Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
-                self._1 as i32
-            },
-            Value::F16_TYPE => {
-                let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                f32::from(value) as i32
-            },
-            Value::F32_TYPE => {
-                let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                value as i32
-            },
-            Value::F64_TYPE => {
-                let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                value as i32
-            },
-            Value::BIG_INT_TYPE => unimplemented!(),
-            Value::BIG_DEC_TYPE => unimplemented!(),
-            _ => unreachable!(),
-        }
-    }
-
-    /// Coerces this `Num` to a `u64` value.
-    pub fn as_u64(&self) -> u64 {
-        match self.type_tag() {
-            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE |
-            Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
-                self._1
-            },
-            Value::F16_TYPE => {
-                let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                f32::from(value) as u64
-            },
-            Value::F32_TYPE => {
-                let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                value as u64
-            },
-            Value::F64_TYPE => {
-                let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                value as u64
-            },
-            Value::BIG_INT_TYPE => unimplemented!(),
-            Value::BIG_DEC_TYPE => unimplemented!(),
-            _ => unreachable!(),
-        }
-    }
-
-    /// Coerces this `Num` to an `i64` value.
-    pub fn as_i64(&self) -> i64 {
-        match self.type_tag() {
-            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE |
-            Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
-                self._1 as i64
-            },
-            Value::F16_TYPE => {
-                let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                f32::from(value) as i64
-            },
-            Value::F32_TYPE => {
-                let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                value as i64
-            },
-            Value::F64_TYPE => {
-                let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                value as i64
-            },
-            Value::BIG_INT_TYPE => unimplemented!(),
-            Value::BIG_DEC_TYPE => unimplemented!(),
-            _ => unreachable!(),
-        }
-    }
-
-    /// Coerces this `Num` to an `f16` value.
-    pub fn as_f16(&self) -> f16 {
-        match self.type_tag() {
-            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE => {
-                f16::from(self._1 as f32)
-            },
-            Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
-                f16::from(self._1 as i64 as f32)
-            },
-            Value::F16_TYPE => {
-                let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                value
-            },
-            Value::F32_TYPE => {
-                let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                f16::from(value)
-            },
-            Value::F64_TYPE => {
-                let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                f16::from(value)
-            },
-            Value::BIG_INT_TYPE => unimplemented!(),
-            Value::BIG_DEC_TYPE => unimplemented!(),
-            _ => unreachable!(),
-        }
-    }
-
-    /// Coerces this `Num` to an `f32` value.
-    pub fn as_f32(&self) -> f32 {
-        match self.type_tag() {
-            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE => {
-                self._1 as f32
-            },
-            Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
-                self._1 as i64 as f32
-            },
-            Value::F16_TYPE => {
-                let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                f32::from(value)
-            },
-            Value::F32_TYPE => {
-                let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                value
-            },
-            Value::F64_TYPE => {
-                let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                value as f32
-            },
-            Value::BIG_INT_TYPE => unimplemented!(),
-            Value::BIG_DEC_TYPE => unimplemented!(),
-            _ => unreachable!(),
-        }
-    }
-
-    /// Coerces this `Num` to an `f64` value.
-    pub fn as_f64(&self) -> f64 {
-        match self.type_tag() {
-            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE => {
-                self._1 as f64
-            },
-            Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
-                self._1 as i64 as f64
-            },
-            Value::F16_TYPE => {
-                let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                f64::from(value)
-            },
-            Value::F32_TYPE => {
-                let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                value as f64
-            },
-            Value::F64_TYPE => {
-                let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                value
-            },
-            Value::BIG_INT_TYPE => unimplemented!(),
-            Value::BIG_DEC_TYPE => unimplemented!(),
-            _ => unreachable!(),
-        }
-    }
-
-    /// Coerces this `Num` to a `usize` value.
-    #[inline]
-    pub fn as_usize(&self) -> usize {
-        #[cfg(target_pointer_width = "32")]
-        let value = self.as_u32();
-        #[cfg(target_pointer_width = "64")]
-        let value = self.as_u64();
-        value as usize
-    }
-
-    /// Coerces this `Num` to an `isize` value.
-    #[inline]
-    pub fn as_isize(&self) -> isize {
-        #[cfg(target_pointer_width = "32")]
-        let value = self.as_i32();
-        #[cfg(target_pointer_width = "64")]
-        let value = self.as_i64();
-        value as isize
-    }
-
-    /// Losslessly converts this `Num` to a `u8` value, if possible.
-    pub fn to_u8(&self) -> Option<u8> {
-        match self.type_tag() {
-            Value::U8_TYPE => {
-                Some(self._1 as u8)
-            },
-            Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE => {
-                if self._1 <= u8::MAX as u64 {
-                    Some(self._1 as u8)
-                } else {
-                    None
-                }
-            },
-            Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
-                if u8::MIN as i64 <= self._1 as i64 && self._1 as i64 <= u8::MAX as i64 {
-                    Some(self._1 as u8)
-                } else {
-                    None
-                }
-            },
-            Value::F16_TYPE => {
-                let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                if value == f16::from(f32::from(value) as u8 as f32) {
-                    Some(f32::from(value) as u8)
-                } else {
-                    None
-                }
-            },
-            Value::F32_TYPE => {
-                let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                if value == value as u8 as f32 {
-                    Some(value as u8)
-                } else {
-                    None
-                }
-            },
-            Value::F64_TYPE => {
-                let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                if value == value as u8 as f64 {
-                    Some(value as u8)
-                } else {
-                    None
-                }
-            },
-            Value::BIG_INT_TYPE => unimplemented!(),
-            Value::BIG_DEC_TYPE => unimplemented!(),
-            _ => unreachable!(),
-        }
-    }
-
-    /// Losslessly converts this `Num` to an `i8` value, if possible.
-    pub fn to_i8(&self) -> Option<i8> {
-        match self.type_tag() {
-            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE => {
-                if self._1 <= i8::MAX as u64 {
-                    Some(self._1 as i8)
-                } else {
-                    None
-                }
-            },
-            Value::I8_TYPE => {
-                Some(self._1 as i8)
-            },
-            Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
-                if i8::MIN as i64 <= self._1 as i64 && self._1 as i64 <= i8::MAX as i64 {
-                    Some(self._1 as i8)
-                } else {
-                    None
-                }
-            },
-            Value::F16_TYPE => {
-                let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                if value == f16::from(f32::from(value) as i8 as f32) {
-                    Some(f32::from(value) as i8)
-                } else {
-                    None
-                }
-            },
-            Value::F32_TYPE => {
-                let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                if value == value as i8 as f32 {
-                    Some(value as i8)
-                } else {
-                    None
-                }
-            },
-            Value::F64_TYPE => {
-                let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                if value == value as i8 as f64 {
-                    Some(value as i8)
-                } else {
-                    None
-                }
-            },
-            Value::BIG_INT_TYPE => unimplemented!(),
-            Value::BIG_DEC_TYPE => unimplemented!(),
-            _ => unreachable!(),
-        }
-    }
-
-    /// Losslessly converts this `Num` to a `u16` value, if possible.
-    pub fn to_u16(&self) -> Option<u16> {
-        match self.type_tag() {
-            Value::U8_TYPE | Value::U16_TYPE => {
-                Some(self._1 as u16)
-            },
-            Value::U32_TYPE | Value::U64_TYPE => {
-                if self._1 <= u16::MAX as u64 {
-                    Some(self._1 as u16)
-                } else {
-                    None
-                }
-            },
-            Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
-                if u16::MIN as i64 <= self._1 as i64 && self._1 as i64 <= u16::MAX as i64 {
-                    Some(self._1 as u16)
-                } else {
-                    None
-                }
-            },
-            Value::F16_TYPE => {
-                let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                if value == f16::from(f32::from(value) as u16 as f32) {
-                    Some(f32::from(value) as u16)
-                } else {
-                    None
-                }
-            },
-            Value::F32_TYPE => {
-                let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                if value == value as u16 as f32 {
-                    Some(value as u16)
-                } else {
-                    None
-                }
-            },
-            Value::F64_TYPE => {
-                let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                if value == value as u16 as f64 {
-                    Some(value as u16)
-                } else {
-                    None
-                }
-            },
-            Value::BIG_INT_TYPE => unimplemented!(),
-            Value::BIG_DEC_TYPE => unimplemented!(),
-            _ => unreachable!(),
-        }
-    }
-
-    /// Losslessly converts this `Num` to an `i16` value, if possible.
-    pub fn to_i16(&self) -> Option<i16> {
-        match self.type_tag() {
-            Value::U8_TYPE => {
-                Some(self._1 as i16)
-            },
-            Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE => {
-                if self._1 <= i16::MAX as u64 {
-                    Some(self._1 as i16)
-                } else {
-                    None
-                }
-            },
-            Value::I8_TYPE | Value::I16_TYPE => {
-                Some(self._1 as i16)
-            },
-            Value::I32_TYPE | Value::I64_TYPE => {
-                if i16::MIN as i64 <= self._1 as i64 && self._1 as i64 <= i16::MAX as i64 {
-                    Some(self._1 as i16)
-                } else {
-                    None
-                }
-            },
-            Value::F16_TYPE => {
-                let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                if value == f16::from(f32::from(value) as i16 as f32) {
-                    Some(f32::from(value) as i16)
-                } else {
-                    None
-                }
+            Value::BF16_TYPE => {
+                let value = unsafe { mem::transmute::<u16, bf16>(self._1 as u16) };
+                f32::from(value) as u32
             },
             Value::F32_TYPE => {
                 let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                if value == value as i16 as f32 {
-                    Some(value as i16)
-                } else {
-                    None
-                }
+                value as u32
             },
             Value::F64_TYPE => {
-                let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                if value == value as i16 as f64 {
-                    Some(value as i16)
-                } else {
-                    None
-                }
+                let value = unsafe { mem::transmute::<u64, f64>(self._1) };
+                value as u32
             },
             Value::BIG_INT_TYPE => unimplemented!(),
             Value::BIG_DEC_TYPE => unimplemented!(),
@@ -1113,49 +587,28 @@ impl<'a> Num<'a> {
         }
     }
 
-    /// Losslessly converts this `Num` to a `u32` value, if possible.
-    pub fn to_u32(&self) -> Option<u32> {
+    /// Coerces this `Num` to an `i32` value.
+    pub fn as_i32(&self) -> i32 {
         match self.type_tag() {
-            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE => {
-                Some(self._1 as u32)
-            },
-            Value::U64_TYPE => {
-                if self._1 <= u32::MAX as u64 {
-                    Some(self._1 as u32)
-                } else {
-                    None
-                }
-            },
+            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE |
             Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
-                if u32::MIN as i64 <= self._1 as i64 && self._1 as i64 <= u32::MAX as i64 {
-                    Some(self._1 as u32)
-                } else {
-                    None
-                }
+                self._1 as i32
             },
             Value::F16_TYPE => {
                 let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                if value == f16::from(f32::from(value) as u32 as f32) {
-                    Some(f32::from(value) as u32)
-                } else {
-                    None
-                }
+                f32::from(value) as i32
+            },
+            Value::BF16_TYPE => {
+                let value = unsafe { mem::transmute::<u16, bf16>(self._1 as u16) };
+                f32::from(value) as i32
             },
             Value::F32_TYPE => {
                 let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                if value == value as u32 as f32 {
-                    Some(value as u32)
-                } else {
-                    None
-                }
+                value as i32
             },
             Value::F64_TYPE => {
                 let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                if value == value as u32 as f64 {
-                    Some(value as u32)
-                } else {
-                    None
-                }
+                value as i32
             },
             Value::BIG_INT_TYPE => unimplemented!(),
             Value::BIG_DEC_TYPE => unimplemented!(),
@@ -1163,52 +616,28 @@ impl<'a> Num<'a> {
         }
     }
 
-    /// Losslessly converts this `Num` to an `i32` value, if possible.
-    pub fn to_i32(&self) -> Option<i32> {
+    /// Coerces this `Num` to a `u64` value.
+    pub fn as_u64(&self) -> u64 {
         match self.type_tag() {
-            Value::U8_TYPE | Value::U16_TYPE => {
-                Some(self._1 as i32)
-            },
-            Value::U32_TYPE | Value::U64_TYPE => {
-                if self._1 <= i32::MAX as u64 {
-                    Some(self._1 as i32)
-                } else {
-                    None
-                }
-            },
-            Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE => {
-                Some(self._1 as i32)
-            },
-            Value::I64_TYPE => {
-                if i32::MIN as i64 <= self._1 as i64 && self._1 as i64 <= i32::MAX as i64 {
-                    Some(self._1 as i32)
-                } else {
-                    None
-                }
+            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE |
+            Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
+                self._1
             },
             Value::F16_TYPE => {
                 let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                if value == f16::from(f32::from(value) as i32 as f32) {
-                    Some(f32::from(value) as i32)
-                } else {
-                    None
-                }
+                f32::from(value) as u64
+            },
+            Value::BF16_TYPE => {
+                let value = unsafe { mem::transmute::<u16, bf16>(self._1 as u16) };
+                f32::from(value) as u64
             },
             Value::F32_TYPE => {
                 let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                if value == value as i32 as f32 {
-                    Some(value as i32)
-                } else {
-                    None
-                }
+                value as u64
             },
             Value::F64_TYPE => {
                 let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                if value == value as i32 as f64 {
-                    Some(value as i32)
-                } else {
-                    None
-                }
+                value as u64
             },
             Value::BIG_INT_TYPE => unimplemented!(),
             Value::BIG_DEC_TYPE => unimplemented!(),
@@ -1216,42 +645,28 @@ impl<'a> Num<'a> {
         }
     }
 
-    /// Losslessly converts this `Num` to a `u64` value, if possible.
-    pub fn to_u64(&self) -> Option<u64> {
+    /// Coerces this `Num` to an `i64` value.
+    pub fn as_i64(&self) -> i64 {
         match self.type_tag() {
-            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE => {
-                Some(self._1)
-            },
+            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE |
             Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
-                if u64::MIN as i64 <= self._1 as i64 {
-                    Some(self._1)
-                } else {
-                    None
-                }
+                self._1 as i64
             },
             Value::F16_TYPE => {
                 let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                if value == f16::from(f32::from(value) as u64 as f32) {
-                    Some(f32::from(value) as u64)
-                } else {
-                    None
-                }
+                f32::from(value) as i64
+            },
+            Value::BF16_TYPE => {
+                let value = unsafe { mem::transmute::<u16, bf16>(self._1 as u16) };
+                f32::from(value) as i64
             },
             Value::F32_TYPE => {
                 let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                if value == value as u64 as f32 {
-                    Some(self._1 as u64)
-                } else {
-                    None
-                }
+                value as i64
             },
             Value::F64_TYPE => {
                 let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                if value == value as u64 as f64 {
-                    Some(self._1 as u64)
-                } else {
-                    None
-                }
+                value as i64
             },
             Value::BIG_INT_TYPE => unimplemented!(),
             Value::BIG_DEC_TYPE => unimplemented!(),
@@ -1259,45 +674,30 @@ impl<'a> Num<'a> {
         }
     }
 
-    /// Losslessly converts this `Num` to an `i64` value, if possible.
-    pub fn to_i64(&self) -> Option<i64> {
+    /// Coerces this `Num` to an `f16` value.
+    pub fn as_f16(&self) -> f16 {
         match self.type_tag() {
-            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE => {
-                Some(self._1 as i64)
-            },
-            Value::U64_TYPE => {
-                if self._1 <= i64::MAX as u64 {
-                    Some(self._1 as i64)
-                } else {
-                    None
-                }
+            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE => {
+                f16::from(self._1 as f32)
             },
             Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
-                Some(self._1 as i64)
+                f16::from(self._1 as i64 as f32)
             },
             Value::F16_TYPE => {
                 let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                if value == f16::from(f32::from(value) as i64 as f32) {
-                    Some(f32::from(value) as i64)
-                } else {
-                    None
-                }
+                value
+            },
+            Value::BF16_TYPE => {
+                let value = unsafe { mem::transmute::<u16, bf16>(self._1 as u16) };
+                f16::from(f32::from(value))
             },
             Value::F32_TYPE => {
                 let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                if value == value as i64 as f32 {
-                    Some(value as i64)
-                } else {
-                    None
-                }
+                f16::from(value)
             },
             Value::F64_TYPE => {
                 let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                if value == value as i64 as f64 {
-                    Some(value as i64)
-                } else {
-                    None
-                }
+                f16::from(value)
             },
             Value::BIG_INT_TYPE => unimplemented!(),
             Value::BIG_DEC_TYPE => unimplemented!(),
@@ -1305,48 +705,30 @@ impl<'a> Num<'a> {
         }
     }
 
-    /// Losslessly converts this `Num` to an `f16` value, if possible.
-    pub fn to_f16(&self) -> Option<f16> {
+    /// Coerces this `Num` to a `bf16` value.
+    pub fn as_bf16(&self) -> bf16 {
         match self.type_tag() {
-            Value::U8_TYPE => {
-                Some(f16::from(self._1 as u8))
-            },
-            Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE => {
-                if self._1 == f32::from(f16::from(self._1 as f32)) as u64 {
-                    Some(f16::from(self._1 as f32))
-                } else {
-                    None
-                }
-            },
-            Value::I8_TYPE => {
-                Some(f16::from(self._1 as i8))
+            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE => {
+                bf16::from(self._1 as f32)
             },
-            Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
-                if self._1 as i64 == f32::from(f16::from(self._1 as i64 as f32)) as i64 {
-                    Some(f16::from(self._1 as i64 as f32))
-                } else {
-                    None
-                }
+            Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
+                bf16::from(self._1 as i64 as f32)
             },
             Value::F16_TYPE => {
                 let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                Some(value)
+                bf16::from(f32::from(value))
+            },
+            Value::BF16_TYPE => {
+                let value = unsafe { mem::transmute::<u16, bf16>(self._1 as u16) };
+                value
             },
             Value::F32_TYPE => {
                 let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                if value == f32::from(f16::from(value)) {
-                    Some(f16::from(value))
-                } else {
-                    None
-                }
+                bf16::from(value)
             },
             Value::F64_TYPE => {
                 let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                if value == f64::from(f16::from(value)) {
-                    Some(f16::from(value))
-                } else {
-                    None
-                }
+                bf16::from(value)
             },
             Value::BIG_INT_TYPE => unimplemented!(),
             Value::BIG_DEC_TYPE => unimplemented!(),
@@ -1354,44 +736,30 @@ impl<'a> Num<'a> {
         }
     }
 
-    /// Losslessly converts this `Num` to an `f32` value, if possible.
-    pub fn to_f32(&self) -> Option<f32> {
+    /// Coerces this `Num` to an `f32` value.
+    pub fn as_f32(&self) -> f32 {
         match self.type_tag() {
-            Value::U8_TYPE | Value::U16_TYPE => {
-                Some(self._1 as f32)
-            },
-            Value::U32_TYPE | Value::U64_TYPE => {
-                if self._1 == self._1 as f32 as u64 {
-                    Some(self._1 as f32)
-                } else {
-                    None
-                }
-            },
-            Value::I8_TYPE | Value::I16_TYPE => {
-                Some(self._1 as i64 as f32)
+            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE => {
+                self._1 as f32
             },
-            Value::I32_TYPE | Value::I64_TYPE => {
-                if self._1 as i64 == self._1 as i64 as f32 as i64 {
-                    Some(self._1 as i64 as f32)
-                } else {
-                    None
-                }
+            Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
+                self._1 as i64 as f32
             },
             Value::F16_TYPE => {
                 let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                Some(f32::from(value))
+                f32::from(value)
+            },
+            Value::BF16_TYPE => {
+                let value = unsafe { mem::transmute::<u16, bf16>(self._1 as u16) };
+                f32::from(value)
             },
             Value::F32_TYPE => {
                 let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                Some(value)
+                value
             },
             Value::F64_TYPE => {
                 let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                if value == value as f32 as f64 {
-                    Some(value as f32)
-                } else {
-                    None
-                }
+                value as f32
             },
             Value::BIG_INT_TYPE => unimplemented!(),
             Value::BIG_DEC_TYPE => unimplemented!(),
@@ -1399,40 +767,30 @@ impl<'a> Num<'a> {
         }
     }
 
-    /// Losslessly converts this `Num` to an `f64` value, if possible.
-    pub fn to_f64(&self) -> Option<f64> {
+    /// Coerces this `Num` to an `f64` value.
+    pub fn as_f64(&self) -> f64 {
         match self.type_tag() {
-            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE => {
-                Some(self._1 as f64)
-            },
-            Value::U64_TYPE => {
-                if self._1 == self._1 as f64 as u64 {
-                    Some(self._1 as f64)
-                } else {
-                    None
-                }
-            },
-            Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE => {
-                Some(self._1 as i64 as f64)
+            Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE => {
+                self._1 as f64
             },
-            Value::I64_TYPE => {
-                if self._1 as i64 == self._1 as i64 as f64 as i64 {
-                    Some(self._1 as i64 as f64)
-                } else {
-                    None
-                }
+            Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => {
+                self._1 as i64 as f64
             },
             Value::F16_TYPE => {
                 let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
-                Some(f64::from(value))
+                f64::from(value)
+            },
+            Value::BF16_TYPE => {
+                let value = unsafe { mem::transmute::<u16, bf16>(self._1 as u16) };
+                f64::from(value)
             },
             Value::F32_TYPE => {
                 let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
-                Some(value as f64)
+                value as f64
             },
             Value::F64_TYPE => {
                 let value = unsafe { mem::transmute::<u64, f64>(self._1) };
-                Some(value)
+                value
             },
             Value::BIG_INT_TYPE => unimplemented!(),
             Value::BIG_DEC_TYPE => unimplemented!(),
@@ -1440,31 +798,129 @@ impl<'a> Num<'a> {
         }
     }
 
-    /// Losslessly converts this `Num` to a `usize` value, if possible.
+    /// Coerces this `Num` to a `usize` value.
     #[inline]
-    pub fn to_usize(&self) -> Option<usize> {
+    pub fn as_usize(&self) -> usize {
         #[cfg(target_pointer_width = "32")]
-        let value = self.to_u32();
+        let value = self.as_u32();
         #[cfg(target_pointer_width = "64")]
-        let value = self.to_u64();
-        unsafe { mem::transmute::<_, Option<usize>>(value) }
+        let value = self.as_u64();
+        value as usize
     }
 
-    /// Losslessly converts this `Num` to an `isize` value, if possible.
+    /// Coerces this `Num` to an `isize` value.
     #[inline]
-    pub fn to_isize(&self) -> Option<isize> {
+    pub fn as_isize(&self) -> isize {
         #[cfg(target_pointer_width = "32")]
-        let value = self.to_i32();
+        let value = self.as_i32();
         #[cfg(target_pointer_width = "64")]
-        let value = self.to_i64();
-        unsafe { mem::transmute::<_, Option<isize>>(value) }
+        let value = self.as_i64();
+        value as isize
+    }
+
+    /// Losslessly converts this `Num` to a `u8` value, if possible.
+    #[inline]
+    pub fn to_u8(&self) -> Option<u8> {
+        self.try_to()
+    }
+
+    /// Losslessly converts this `Num` to an `i8` value, if possible.
+    #[inline]
+    pub fn to_i8(&self) -> Option<i8> {
+        self.try_to()
+    }
+
+    /// Losslessly converts this `Num` to a `u16` value, if possible.
+    #[inline]
+    pub fn to_u16(&self) -> Option<u16> {
+        self.try_to()
+    }
+
+    /// Losslessly converts this `Num` to an `i16` value, if possible.
+    #[inline]
+    pub fn to_i16(&self) -> Option<i16> {
+        self.try_to()
+    }
+
+    /// Losslessly converts this `Num` to a `u32` value, if possible.
+    #[inline]
+    pub fn to_u32(&self) -> Option<u32> {
+        self.try_to()
+    }
+
+    /// Losslessly converts this `Num` to an `i32` value, if possible.
+    #[inline]
+    pub fn to_i32(&self) -> Option<i32> {
+        self.try_to()
+    }
+
+    /// Losslessly converts this `Num` to a `u64` value, if possible.
+    #[inline]
+    pub fn to_u64(&self) -> Option<u64> {
+        self.try_to()
+    }
+
+    /// Losslessly converts this `Num` to an `i64` value, if possible.
+    #[inline]
+    pub fn to_i64(&self) -> Option<i64> {
+        self.try_to()
+    }
+
+    /// Losslessly converts this `Num` to an `f16` value, if possible.
+    #[inline]
+    pub fn to_f16(&self) -> Option<f16> {
+        self.try_to()
+    }
+
+    /// Losslessly converts this `Num` to a `bf16` value, if possible.
+    #[inline]
+    pub fn to_bf16(&self) -> Option<bf16> {
+        self.try_to()
+    }
+
+    /// Losslessly converts this `Num` to an `f32` value, if possible.
+    #[inline]
+    pub fn to_f32(&self) -> Option<f32> {
+        self.try_to()
+    }
+
+    /// Losslessly converts this `Num` to an `f64` value, if possible.
+    #[inline]
+    pub fn to_f64(&self) -> Option<f64> {
+        self.try_to()
+    }
+
+    /// Losslessly converts this `Num` to a `usize` value, if possible.
+    #[inline]
+    pub fn to_usize(&self) -> Option<usize> {
+        self.try_to()
+    }
+
+    /// Losslessly converts this `Num` to an `isize` value, if possible.
+    #[inline]
+    pub fn to_isize(&self) -> Option<isize> {
+        self.try_to()
+    }
+
+    /// Converts this `Num`, interpreted as a number of milliseconds, to a
+    /// `Duration`, if it's a non-negative integral value.
+    #[inline]
+    pub fn to_duration(&self) -> Option<Duration> {
+        self.to_u64().map(Duration::from_millis)
+    }
+
+    /// Converts this `Num`, interpreted as a number of milliseconds since the
+    /// Unix epoch, to a `Timestamp`, if it's an integral value.
+    #[inline]
+    pub fn to_timestamp(&self) -> Option<Timestamp> {
+        self.to_i64().map(Timestamp::from_millis)
     }
 
     pub(crate) unsafe fn dealloc(&mut self) {
         match self.type_tag() {
             Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE => (),
             Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE => (),
-            Value::F16_TYPE | Value::F32_TYPE | Value::F64_TYPE => (),
+            Value::F16_TYPE | Value::BF16_TYPE | Value::F32_TYPE | Value::F64_TYPE => (),
             Value::BIG_INT_TYPE => unimplemented!(),
             Value::BIG_DEC_TYPE => unimplemented!(),
             _ => unreachable!(),
@@ -1515,14 +971,18 @@ impl<'a> PartialEq for Num<'a> {
 
         // Try floating point comparisons.
         let self_is_f16 = self_tag == Value::F16_TYPE;
+        let self_is_bf16 = self_tag == Value::BF16_TYPE;
         let that_is_f16 = that_tag == Value::F16_TYPE;
+        let that_is_bf16 = that_tag == Value::BF16_TYPE;
         let self_is_f32 = self_tag == Value::F32_TYPE;
         let that_is_f32 = that_tag == Value::F32_TYPE;
         let self_is_f64 = self_tag == Value::F64_TYPE;
         let that_is_f64 = that_tag == Value::F64_TYPE;
-        if (self_is_f32 || self_is_f64) && (that_is_f32 || that_is_f64) {
+        if (self_is_f16 || self_is_bf16 || self_is_f32 || self_is_f64) && (that_is_f16 || that_is_bf16 || that_is_f32 || that_is_f64) {
             let self_float = if self_is_f16 {
                 f64::from(unsafe { mem::transmute::<u16, f16>(self._1 as u16) })
+            } else if self_is_bf16 {
+                f64::from(unsafe { mem::transmute::<u16, bf16>(self._1 as u16) })
             } else if self_is_f32 {
                 unsafe { mem::transmute::<u32, f32>(self._1 as u32) as f64 }
             } else {
@@ -1530,15 +990,19 @@ impl<'a> PartialEq for Num<'a> {
             };
             let that_float = if that_is_f16 {
                 f64::from(unsafe { mem::transmute::<u16, f16>(that._1 as u16) })
+            } else if that_is_bf16 {
+                f64::from(unsafe { mem::transmute::<u16, bf16>(that._1 as u16) })
             } else if that_is_f32 {
                 unsafe { mem::transmute::<u32, f32>(that._1 as u32) as f64 }
             } else {
                 unsafe { mem::transmute::<u64, f64>(that._1) }
             };
             return self_float == that_float || self_float.is_nan() && that_float.is_nan();
-        } else if (self_is_f32 || self_is_f64) && (that_is_uint || that_is_sint) {
+        } else if (self_is_f16 || self_is_bf16 || self_is_f32 || self_is_f64) && (that_is_uint || that_is_sint) {
             let self_float = if self_is_f16 {
                 f64::from(unsafe { mem::transmute::<u16, f16>(self._1 as u16) })
+            } else if self_is_bf16 {
+                f64::from(unsafe { mem::transmute::<u16, bf16>(self._1 as u16) })
             } else if self_is_f32 {
                 unsafe { mem::transmute::<u32, f32>(self._1 as u32) as f64 }
             } else {
@@ -1551,9 +1015,11 @@ impl<'a> PartialEq for Num<'a> {
             } else {
                 unreachable!();
             }
-        } else if (self_is_uint || self_is_sint) && (that_is_f32 || that_is_f64) {
+        } else if (self_is_uint || self_is_sint) && (that_is_f16 || that_is_bf16 || that_is_f32 || that_is_f64) {
             let that_float = if that_is_f16 {
                 f64::from(unsafe { mem::transmute::<u16, f16>(that._1 as u16) })
+            } else if that_is_bf16 {
+                f64::from(unsafe { mem::transmute::<u16, bf16>(that._1 as u16) })
             } else if that_is_f32 {
                 unsafe { mem::transmute::<u32, f32>(that._1 as u32) as f64 }
             } else {
@@ -1637,14 +1103,18 @@ impl<'a> Ord for Num<'a> {
 
         // Try floating point comparisons.
         let self_is_f16 = self_tag == Value::F16_TYPE;
+        let self_is_bf16 = self_tag == Value::BF16_TYPE;
         let that_is_f16 = that_tag == Value::F16_TYPE;
+        let that_is_bf16 = that_tag == Value::BF16_TYPE;
         let self_is_f32 = self_tag == Value::F32_TYPE;
         let that_is_f32 = that_tag == Value::F32_TYPE;
         let self_is_f64 = self_tag == Value::F64_TYPE;
         let that_is_f64 = that_tag == Value::F64_TYPE;
-        if (self_is_f16 || self_is_f32 || self_is_f64) && (that_is_f16 || that_is_f32 || that_is_f64) {
+        if (self_is_f16 || self_is_bf16 || self_is_f32 || self_is_f64) && (that_is_f16 || that_is_bf16 || that_is_f32 || that_is_f64) {
             let self_float = if self_is_f16 {
                 f64::from(unsafe { mem::transmute::<u16, f16>(self._1 as u16) })
+            } else if self_is_bf16 {
+                f64::from(unsafe { mem::transmute::<u16, bf16>(self._1 as u16) })
             } else if self_is_f32 {
                 unsafe { mem::transmute::<u32, f32>(self._1 as u32) as f64 }
             } else {
@@ -1652,6 +1122,8 @@ impl<'a> Ord for Num<'a> {
             };
             let that_float = if that_is_f16 {
                 f64::from(unsafe { mem::transmute::<u16, f16>(that._1 as u16) })
+            } else if that_is_bf16 {
+                f64::from(unsafe { mem::transmute::<u16, bf16>(that._1 as u16) })
             } else if that_is_f32 {
                 unsafe { mem::transmute::<u32, f32>(that._1 as u32) as f64 }
             } else {
@@ -1666,9 +1138,11 @@ impl<'a> Ord for Num<'a> {
             } else {
                 return self_float.partial_cmp(&that_float).unwrap();
             }
-        } else if (self_is_f16 || self_is_f32 || self_is_f64) && (that_is_uint || that_is_sint) {
+        } else if (self_is_f16 || self_is_bf16 || self_is_f32 || self_is_f64) && (that_is_uint || that_is_sint) {
             let self_float = if self_is_f16 {
                 f64::from(unsafe { mem::transmute::<u16, f16>(self._1 as u16) })
+            } else if self_is_bf16 {
+                f64::from(unsafe { mem::transmute::<u16, bf16>(self._1 as u16) })
             } else if self_is_f32 {
                 unsafe { mem::transmute::<u32, f32>(self._1 as u32) as f64 }
             } else {
@@ -1683,9 +1157,11 @@ impl<'a> Ord for Num<'a> {
             } else {
                 unreachable!();
             }
-        } else if (self_is_uint || self_is_sint) && (that_is_f16 || that_is_f32 || that_is_f64) {
+        } else if (self_is_uint || self_is_sint) && (that_is_f16 || that_is_bf16 || that_is_f32 || that_is_f64) {
             let that_float = if that_is_f16 {
                 f64::from(unsafe { mem::transmute::<u16, f16>(that._1 as u16) })
+            } else if that_is_bf16 {
+                f64::from(unsafe { mem::transmute::<u16, bf16>(that._1 as u16) })
             } else if that_is_f32 {
                 unsafe { mem::transmute::<u32, f32>(that._1 as u32) as f64 }
             } else {
@@ -1749,6 +1225,18 @@ impl<'a> hash::Hash for Num<'a> {
                     (self._1 as u16).hash(hasher);
                 }
             },
+            Value::BF16_TYPE => {
+                let value = f64::from(unsafe { mem::transmute::<u16, bf16>(self._1 as u16) });
+                if value == value as u64 as f64 {
+                    // Hash as u64, if equivalent.
+                    (value as u64).hash(hasher);
+                } else if value == value as i64 as f64 {
+                    // Hash as i64, if equivalent.
+                    (value as i64).hash(hasher);
+                } else {
+                    ((self._1 as u32) << 16).hash(hasher);
+                }
+            },
             Value::F32_TYPE => {
                 let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) as f64 };
                 if value == value as u64 as f64 {
@@ -1793,6 +1281,10 @@ impl<'a> fmt::Debug for Num<'a> {
                 let value = unsafe { mem::transmute::<u16, f16>(self._1 as u16) };
                 value.fmt(f)
             },
+            Value::BF16_TYPE => {
+                let value = unsafe { mem::transmute::<u16, bf16>(self._1 as u16) };
+                value.fmt(f)
+            },
             Value::F32_TYPE => {
                 let value = unsafe { mem::transmute::<u32, f32>(self._1 as u32) };
                 value.fmt(f)
@@ -1814,7 +1306,7 @@ impl<'a> Clone for Num<'a> {
         match type_tag {
             Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE |
             Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE |
-            Value::F16_TYPE | Value::F32_TYPE | Value::F64_TYPE => {
+            Value::F16_TYPE | Value::BF16_TYPE | Value::F32_TYPE | Value::F64_TYPE => {
                 Num {
                     _0: unsafe { NonZeroU64::new_unchecked(Value::discriminant(type_tag)) },
                     _1: self._1,
@@ -1834,7 +1326,7 @@ impl<'a> TryClone for Num<'a> {
         match type_tag {
             Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE |
             Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE |
-            Value::F16_TYPE | Value::F32_TYPE | Value::F64_TYPE => {
+            Value::F16_TYPE | Value::BF16_TYPE | Value::F32_TYPE | Value::F64_TYPE => {
                 Ok(Num {
                     _0: unsafe { NonZeroU64::new_unchecked(Value::discriminant(type_tag)) },
                     _1: self._1,
@@ -1854,7 +1346,7 @@ impl<'a, 'b> CloneIntoHold<'a, Num<'a>> for Num<'b> {
         match type_tag {
             Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE |
             Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE |
-            Value::F16_TYPE | Value::F32_TYPE | Value::F64_TYPE => {
+            Value::F16_TYPE | Value::BF16_TYPE | Value::F32_TYPE | Value::F64_TYPE => {
                 Ok(Num {
                     _0: unsafe { NonZeroU64::new_unchecked(Value::discriminant(type_tag)) },
                     _1: self._1,
@@ -1874,7 +1366,7 @@ impl<'a, 'b> Stow<'b, Num<'b>> for Num<'a> {
         match type_tag {
             Value::U8_TYPE | Value::U16_TYPE | Value::U32_TYPE | Value::U64_TYPE |
             Value::I8_TYPE | Value::I16_TYPE | Value::I32_TYPE | Value::I64_TYPE |
-            Value::F16_TYPE | Value::F32_TYPE | Value::F64_TYPE => {
+            Value::F16_TYPE | Value::BF16_TYPE | Value::F32_TYPE | Value::F64_TYPE => {
                 ptr::write(&mut (*dst)._0, NonZeroU64::new_unchecked(Value::discriminant(type_tag)));
                 ptr::write(&mut (*dst)._1, (*src)._1);
             },
@@ -1956,6 +1448,12 @@ impl<'a> From<f16> for Num<'a> {
     }
 }
 
+impl<'a> From<bf16> for Num<'a> {
+    fn from(value: bf16) -> Num<'a> {
+        Num::from_bf16(value)
+    }
+}
+
 impl<'a> From<f32> for Num<'a> {
     fn from(value: f32) -> Num<'a> {
         Num::from_f32(value)
@@ -1979,3 +1477,15 @@ impl<'a> From<isize> for Num<'a> {
         Num::from_isize(value)
     }
 }
+
+impl<'a> From<Duration> for Num<'a> {
+    fn from(value: Duration) -> Num<'a> {
+        Num::from_duration(value)
+    }
+}
+
+impl<'a> From<Timestamp> for Num<'a> {
+    fn from(value: Timestamp) -> Num<'a> {
+        Num::from_timestamp(value)
+    }
+}
@@ -315,7 +315,7 @@ impl<'a> Record<'a> {
             assert!(index <= len);
             self.try_reserve(1)?;
             let buf = mem::transmute::<_, &mut PtrBuf<'a, Item<'a>>>(&mut self._1);
-            let data = buf.as_mut_ptr().wrapping_add(len);
+            let data = buf.as_mut_ptr().wrapping_add(index);
             ptr::copy(data, data.wrapping_add(1), len.wrapping_sub(index));
             ptr::write(data, item);
             buf.set_len(len.wrapping_add(1));
@@ -345,11 +345,11 @@ impl<'a> Record<'a> {
     pub fn remove(&mut self, index: usize) -> Item<'a> {
         unsafe {
             let len = self.len();
-            assert!(index <= len);
+            assert!(index < len);
             let buf = mem::transmute::<_, &mut PtrBuf<'a, Item<'a>>>(&mut self._1);
             let data = buf.as_mut_ptr().wrapping_add(index);
             let item = ptr::read(data);
-            ptr::copy(data.wrapping_add(1), data, len.wrapping_sub(index).wrapping_add(1));
+            ptr::copy(data.wrapping_add(1), data, len.wrapping_sub(index).wrapping_sub(1));
             buf.set_len(len.wrapping_sub(1));
             item
         }
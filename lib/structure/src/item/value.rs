@@ -4,7 +4,7 @@ use core::hash;
 use core::marker::PhantomData;
 use core::mem;
 use core::num::NonZeroU64;
-use swim_core::f16;
+use swim_core::{f16, bf16};
 use swim_mem::alloc::{Hold, HoldError, Stow, TryClone, CloneIntoHold};
 use crate::item::{Item, Record, Data, Text, Num, Bool, Extant, Absent};
 
@@ -38,18 +38,19 @@ impl<'a> Value<'a> {
     pub(crate) const I32_TYPE: u8 = 0x0B;
     pub(crate) const I64_TYPE: u8 = 0x0C;
     pub(crate) const F16_TYPE: u8 = 0x0D;
-    pub(crate) const F32_TYPE: u8 = 0x0E;
-    pub(crate) const F64_TYPE: u8 = 0x0F;
-    pub(crate) const BIG_INT_TYPE: u8 = 0x10;
-    pub(crate) const BIG_DEC_TYPE: u8 = 0x11;
-    pub(crate) const TEXT0_TYPE: u8 = 0x12;
-    pub(crate) const TEXT7_TYPE: u8 = 0x19;
-    pub(crate) const TEXT_TYPE: u8 = 0x1A;
-    pub(crate) const DATA0_TYPE: u8 = 0x1B;
-    pub(crate) const DATA7_TYPE: u8 = 0x22;
-    pub(crate) const DATA_TYPE: u8 = 0x23;
-    pub(crate) const RECORD0_TYPE: u8 = 0x24;
-    pub(crate) const RECORD_TYPE: u8 = 0x25;
+    pub(crate) const BF16_TYPE: u8 = 0x0E;
+    pub(crate) const F32_TYPE: u8 = 0x0F;
+    pub(crate) const F64_TYPE: u8 = 0x10;
+    pub(crate) const BIG_INT_TYPE: u8 = 0x11;
+    pub(crate) const BIG_DEC_TYPE: u8 = 0x12;
+    pub(crate) const TEXT0_TYPE: u8 = 0x13;
+    pub(crate) const TEXT7_TYPE: u8 = 0x1A;
+    pub(crate) const TEXT_TYPE: u8 = 0x1B;
+    pub(crate) const DATA0_TYPE: u8 = 0x1C;
+    pub(crate) const DATA7_TYPE: u8 = 0x23;
+    pub(crate) const DATA_TYPE: u8 = 0x24;
+    pub(crate) const RECORD0_TYPE: u8 = 0x25;
+    pub(crate) const RECORD_TYPE: u8 = 0x26;
 
     pub(crate) const BOOL_TYPE_MIN: u8 = Value::FALSE_TYPE;
     pub(crate) const BOOL_TYPE_MAX: u8 = Value::TRUE_TYPE;
@@ -170,6 +171,15 @@ impl<'a> Value<'a> {
         }
     }
 
+    /// Constructs a new `Value` from a `bf16` value.
+    pub fn from_bf16(value: bf16) -> Value<'a> {
+        Value {
+            _0: unsafe { NonZeroU64::new_unchecked(Value::discriminant(Value::BF16_TYPE)) },
+            _1: unsafe { mem::transmute::<bf16, u16>(value) as u64 },
+            lifetime: PhantomData,
+        }
+    }
+
     /// Constructs a new `Value` from an `f32` value.
     pub fn from_f32(value: f32) -> Value<'a> {
         Value {
@@ -1363,6 +1373,12 @@ impl<'a> From<f16> for Value<'a> {
     }
 }
 
+impl<'a> From<bf16> for Value<'a> {
+    fn from(value: bf16) -> Value<'a> {
+        Value::from_bf16(value)
+    }
+}
+
 impl<'a> From<f32> for Value<'a> {
     fn from(value: f32) -> Value<'a> {
         Value::from_f32(value)
@@ -0,0 +1,25 @@
+extern crate swim_structure;
+extern crate swim_c_rt;
+
+use swim_structure::item::{Item, Text, Value};
+
+#[test]
+fn test_item_kinds() {
+    let attr = Item::attr(Text::from_str("a"), Value::from(1));
+    assert!(attr.is_field());
+    assert!(attr.is_attr());
+    assert!(!attr.is_slot());
+    assert!(!attr.is_value());
+
+    let slot = Item::slot(Value::from_str("b"), Value::from(2));
+    assert!(slot.is_field());
+    assert!(!slot.is_attr());
+    assert!(slot.is_slot());
+    assert!(!slot.is_value());
+
+    let value = Item::from(3);
+    assert!(!value.is_field());
+    assert!(!value.is_attr());
+    assert!(!value.is_slot());
+    assert!(value.is_value());
+}
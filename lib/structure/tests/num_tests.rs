@@ -0,0 +1,167 @@
+extern crate swim_core;
+extern crate swim_structure;
+extern crate swim_c_rt;
+
+use core::{i32, i64, u32, u64};
+use swim_core::bf16;
+use swim_core::time::{Duration, Timestamp};
+use swim_structure::item::{Item, Num};
+
+#[test]
+fn test_num_float_to_u64() {
+    assert_eq!(Num::from_f32(3.0).to_u64(), Some(3));
+    assert_eq!(Num::from_f64(3.0).to_u64(), Some(3));
+    assert_eq!(Num::from_f64(0.0).to_u64(), Some(0));
+    assert_eq!(Num::from_f64(2.5).to_u64(), None);
+    assert_eq!(Num::from_f64(-1.0).to_u64(), None);
+}
+
+#[test]
+fn test_num_bf16_round_trip() {
+    for &bits in [0x0000u16, 0x8000, 0x3FC0, 0xC049, 0x0001, 0x7F7F, 0xFF7F, 0x7F80, 0xFF80].iter() {
+        let value = bf16::from_bits(bits);
+        let num = Num::from_bf16(value);
+        assert!(num.is_bf16());
+        assert_eq!(num.to_bf16().map(bf16::to_bits), Some(bits));
+        assert_eq!(num.as_bf16().to_bits(), bits);
+        assert_eq!(num.to_f32().map(f32::to_bits), Some(f32::from(value).to_bits()));
+        let item = Item::from(value);
+        assert_eq!(item.as_num().to_bf16().map(bf16::to_bits), Some(bits));
+        assert_eq!(item.clone().as_num().to_bf16().map(bf16::to_bits), Some(bits));
+    }
+    let nan = Num::from_bf16(bf16::NAN);
+    assert!(nan.is_bf16());
+    assert!(nan.to_bf16().unwrap().is_nan());
+    assert_eq!(Num::from_bf16(bf16::from(1.5f32)), Num::from_f32(1.5));
+    assert_eq!(Num::from_f32(1.5).to_bf16(), Some(bf16::from(1.5f32)));
+    assert_eq!(Num::from_f32(1.001).to_bf16(), None);
+    assert_eq!(Num::from_u16(257).to_bf16(), None);
+    assert_eq!(Num::from_u16(256).to_bf16(), Some(bf16::from(256.0f32)));
+}
+
+#[test]
+fn test_num_from_duration() {
+    assert_eq!(Num::from_duration(Duration::from_millis(1_500)).to_u64(), Some(1_500));
+    assert_eq!(Num::from_duration(Duration::new(1, 999_999)).to_u64(), Some(1_000));
+    assert_eq!(Num::from_duration(Duration::from_nanos(999_999)).to_u64(), Some(0));
+    assert_eq!(Num::from_duration(Duration::from_millis(u64::MAX)).to_u64(), Some(u64::MAX));
+    assert_eq!(Num::from_duration(Duration::new(u64::MAX, 999_999_999)).to_u64(), Some(u64::MAX));
+    assert_eq!(Num::from(Duration::from_secs(2)).to_u64(), Some(2_000));
+}
+
+#[test]
+fn test_num_from_timestamp() {
+    assert_eq!(Num::from_timestamp(Timestamp::from_millis(1_500)).to_i64(), Some(1_500));
+    assert_eq!(Num::from_timestamp(Timestamp::new(1, 999_999)).to_i64(), Some(1_000));
+    assert_eq!(Num::from_timestamp(Timestamp::from_millis(-1_500)).to_i64(), Some(-1_500));
+    assert_eq!(Num::from_timestamp(Timestamp::new(-1, 999_999)).to_i64(), Some(-1_000));
+    assert_eq!(Num::from_timestamp(Timestamp::new(-1, 1)).to_i64(), Some(-1_000));
+    assert_eq!(Num::from_timestamp(Timestamp::new(-1, 999_000_001)).to_i64(), Some(-1));
+    assert_eq!(Num::from_timestamp(Timestamp::from_millis(i64::MAX)).to_i64(), Some(i64::MAX));
+    assert_eq!(Num::from_timestamp(Timestamp::from_millis(i64::MIN)).to_i64(), Some(i64::MIN));
+    assert_eq!(Num::from_timestamp(Timestamp::from_secs(i64::MAX)).to_i64(), Some(i64::MAX));
+    assert_eq!(Num::from_timestamp(Timestamp::from_secs(i64::MIN)).to_i64(), Some(i64::MIN));
+    assert_eq!(Num::from(Timestamp::from_secs(-2)).to_i64(), Some(-2_000));
+}
+
+#[test]
+fn test_num_to_duration() {
+    assert_eq!(Num::from_u64(1_500).to_duration(), Some(Duration::from_millis(1_500)));
+    assert_eq!(Num::from_u64(u64::MAX).to_duration(), Some(Duration::from_millis(u64::MAX)));
+    assert_eq!(Num::from_f64(2.0).to_duration(), Some(Duration::from_millis(2)));
+    assert_eq!(Num::from_i64(-1).to_duration(), None);
+    assert_eq!(Num::from_f64(-2.0).to_duration(), None);
+    assert_eq!(Num::from_f64(1.5).to_duration(), None);
+    assert_eq!(Num::from_f64(core::f64::NAN).to_duration(), None);
+}
+
+#[test]
+fn test_num_to_timestamp() {
+    assert_eq!(Num::from_i64(1_500).to_timestamp(), Some(Timestamp::from_millis(1_500)));
+    assert_eq!(Num::from_i64(-1).to_timestamp(), Some(Timestamp::new(-1, 999_000_000)));
+    assert_eq!(Num::from_i64(i64::MIN).to_timestamp(), Some(Timestamp::from_millis(i64::MIN)));
+    assert_eq!(Num::from_f64(-2.0).to_timestamp(), Some(Timestamp::from_millis(-2)));
+    assert_eq!(Num::from_f64(1.5).to_timestamp(), None);
+    assert_eq!(Num::from_f64(-1.5).to_timestamp(), None);
+    assert_eq!(Num::from_u64(u64::MAX).to_timestamp(), None);
+}
+
+#[test]
+fn test_num_nan_conversions() {
+    let nan = Num::from_f64(core::f64::NAN);
+    assert!(nan.is_valid_f32());
+    assert!(nan.to_f32().unwrap().is_nan());
+    assert!(nan.try_to::<f32>().unwrap().is_nan());
+    assert!(nan.to_f16().unwrap().is_nan());
+    assert!(nan.to_bf16().unwrap().is_nan());
+    assert!(nan.to_f64().unwrap().is_nan());
+    assert!(Num::from_f32(core::f32::NAN).to_f64().unwrap().is_nan());
+    for nan in [Num::from_f64(core::f64::NAN), Num::from_f32(core::f32::NAN)].iter() {
+        assert!(!nan.is_valid_i32());
+        assert_eq!(nan.to_u8(), None);
+        assert_eq!(nan.to_i8(), None);
+        assert_eq!(nan.to_u16(), None);
+        assert_eq!(nan.to_i16(), None);
+        assert_eq!(nan.to_u32(), None);
+        assert_eq!(nan.to_i32(), None);
+        assert_eq!(nan.to_u64(), None);
+        assert_eq!(nan.to_i64(), None);
+        assert_eq!(nan.try_to::<i32>(), None);
+    }
+}
+
+#[test]
+fn test_num_infinite_conversions() {
+    for &value in [core::f64::INFINITY, core::f64::NEG_INFINITY].iter() {
+        let num = Num::from_f64(value);
+        assert_eq!(num.to_f32(), Some(value as f32));
+        assert_eq!(num.to_f16().map(|x| x.is_infinite()), Some(true));
+        assert_eq!(num.to_bf16().map(|x| x.is_infinite()), Some(true));
+        assert_eq!(Num::from_f32(value as f32).to_f64(), Some(value));
+        assert_eq!(num.to_u8(), None);
+        assert_eq!(num.to_i8(), None);
+        assert_eq!(num.to_u32(), None);
+        assert_eq!(num.to_i32(), None);
+        assert_eq!(num.to_u64(), None);
+        assert_eq!(num.to_i64(), None);
+        assert_eq!(Num::from_f32(value as f32).to_i64(), None);
+    }
+}
+
+#[test]
+fn test_num_integer_range_conversions() {
+    assert_eq!(Num::from_i64(255).to_u8(), Some(255));
+    assert_eq!(Num::from_i64(256).to_u8(), None);
+    assert_eq!(Num::from_i64(-1).to_u8(), None);
+    assert_eq!(Num::from_i64(127).to_i8(), Some(127));
+    assert_eq!(Num::from_i64(128).to_i8(), None);
+    assert_eq!(Num::from_i64(-128).to_i8(), Some(-128));
+    assert_eq!(Num::from_i64(-129).to_i8(), None);
+    assert_eq!(Num::from_i64(65_535).to_u16(), Some(65_535));
+    assert_eq!(Num::from_i64(65_536).to_u16(), None);
+    assert_eq!(Num::from_i64(-1).to_u16(), None);
+    assert_eq!(Num::from_i64(32_768).to_i16(), None);
+    assert_eq!(Num::from_i64(-32_769).to_i16(), None);
+    assert_eq!(Num::from_i64(u32::MAX as i64).to_u32(), Some(u32::MAX));
+    assert_eq!(Num::from_i64(u32::MAX as i64 + 1).to_u32(), None);
+    assert_eq!(Num::from_i64(-1).to_u32(), None);
+    assert_eq!(Num::from_i64(i32::MAX as i64 + 1).to_i32(), None);
+    assert_eq!(Num::from_i64(i32::MIN as i64 - 1).to_i32(), None);
+    assert_eq!(Num::from_i64(-1).to_u64(), None);
+    assert_eq!(Num::from_u64(u64::MAX).to_u64(), Some(u64::MAX));
+    assert_eq!(Num::from_u64(i64::MAX as u64).to_i64(), Some(i64::MAX));
+    assert_eq!(Num::from_u64(i64::MAX as u64 + 1).to_i64(), None);
+    assert_eq!(Num::from_u64(u64::MAX).to_i64(), None);
+
+    assert_eq!(Num::from_f64(255.0).to_u8(), Some(255));
+    assert_eq!(Num::from_f64(256.0).to_u8(), None);
+    assert_eq!(Num::from_f64(-129.0).to_i8(), None);
+    assert_eq!(Num::from_f64(4_294_967_296.0).to_u32(), None);
+    assert_eq!(Num::from_f64(-2_147_483_649.0).to_i32(), None);
+    // 2^64 and 2^63 are the smallest floats past the u64 and i64 ranges.
+    assert_eq!(Num::from_f64(18_446_744_073_709_551_616.0).to_u64(), None);
+    assert_eq!(Num::from_f64(9_223_372_036_854_775_808.0).to_i64(), None);
+    assert_eq!(Num::from_f64(-9_223_372_036_854_775_808.0).to_i64(), Some(i64::MIN));
+    assert_eq!(Num::from_f32(4_294_967_296.0).to_u32(), None);
+    assert_eq!(Num::from_f32(-2_147_483_648.0).to_i32(), Some(i32::MIN));
+}
@@ -0,0 +1,48 @@
+extern crate swim_structure;
+extern crate swim_c_rt;
+
+use swim_structure::item::{Item, Record};
+
+fn record(values: &[i32]) -> Record<'static> {
+    let items = values.iter().map(|&value| Item::from(value)).collect::<Vec<_>>();
+    Record::from_slice(&items)
+}
+
+#[test]
+fn test_record_insert_middle() {
+    let mut xs = record(&[0, 1, 2]);
+    xs.insert(1, Item::from(9));
+    assert_eq!(xs.len(), 4);
+    assert_eq!(xs, record(&[0, 9, 1, 2]));
+}
+
+#[test]
+fn test_record_insert_end() {
+    let mut xs = record(&[0, 1, 2]);
+    xs.insert(3, Item::from(9));
+    assert_eq!(xs.len(), 4);
+    assert_eq!(xs, record(&[0, 1, 2, 9]));
+}
+
+#[test]
+fn test_record_remove_middle() {
+    let mut xs = record(&[0, 1, 2, 3]);
+    assert_eq!(xs.remove(1), Item::from(1));
+    assert_eq!(xs.len(), 3);
+    assert_eq!(xs, record(&[0, 2, 3]));
+}
+
+#[test]
+fn test_record_remove_end() {
+    let mut xs = record(&[0, 1, 2, 3]);
+    assert_eq!(xs.remove(3), Item::from(3));
+    assert_eq!(xs.len(), 3);
+    assert_eq!(xs, record(&[0, 1, 2]));
+}
+
+#[test]
+#[should_panic]
+fn test_record_remove_out_of_bounds() {
+    let mut xs = record(&[0, 1, 2]);
+    xs.remove(3);
+}
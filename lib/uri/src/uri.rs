@@ -0,0 +1,905 @@
+use core::cmp;
+use core::fmt;
+use core::hash;
+use core::marker::PhantomData;
+use core::str;
+use swim_mem::alloc::{Hold, Holder, HoldError};
+use swim_mem::lease::RawBuf;
+use swim_codec::step::{In, Out, Over};
+use swim_codec::then::{Then, Cont, Done, Fail};
+use swim_codec::input::{Input, AsInput};
+use swim_codec::decoder::Decoder;
+use swim_codec::percent::{is_unreserved, is_sub_delim};
+use swim_structure::item::{Text, Value};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UriError {
+    /// Input ended before the URI did, or did not form a complete URI.
+    Unexpected,
+    /// Malformed percent-encoded octet.
+    Escape,
+    Hold(HoldError),
+}
+
+impl From<HoldError> for UriError {
+    fn from(error: HoldError) -> UriError {
+        UriError::Hold(error)
+    }
+}
+
+/// An RFC 3986 URI reference, stored as a single ASCII string allocated in
+/// a `Hold`, with the spans of its components.
+pub struct Uri<'a> {
+    string: RawBuf<'a, u8>,
+    scheme: Option<usize>,
+    authority: Option<(usize, usize)>,
+    path: (usize, usize),
+    query: Option<(usize, usize)>,
+    fragment: Option<(usize, usize)>,
+}
+
+/// Borrowed URI components, used to compose a new `Uri`. Components must
+/// already be percent-encoded.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct UriParts<'b> {
+    pub scheme: Option<&'b str>,
+    pub authority: Option<&'b str>,
+    pub path: &'b str,
+    pub query: Option<&'b str>,
+    pub fragment: Option<&'b str>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct UriAuthority<'b> {
+    string: &'b str,
+}
+
+#[derive(Clone)]
+pub struct UriSegments<'b> {
+    segments: str::Split<'b, char>,
+}
+
+#[derive(Clone)]
+pub struct UriQueryParams<'b> {
+    params: str::Split<'b, char>,
+}
+
+pub struct UriDecoder<'a, I: Input<Token=char>> {
+    string: RawBuf<'a, u8>,
+    scheme: Option<usize>,
+    authority: Option<(usize, usize)>,
+    path: (usize, usize),
+    query: Option<(usize, usize)>,
+    start: usize,
+    escape: u32,
+    state: u32,
+    input: PhantomData<I>,
+}
+
+/// Yields the characters of `UriParts` with their delimiters.
+struct UriPartsInput<'b> {
+    parts: [&'b str; 9],
+    index: usize,
+    offset: usize,
+}
+
+impl<'a> Uri<'a> {
+    pub fn try_parse(hold: &dyn Hold<'a>, string: &str) -> Result<Uri<'a>, UriError> {
+        let mut input = string.as_input();
+        let uri = UriDecoder::try_hold(hold)?.consume(&mut input)?;
+        if input.is_in() {
+            return Err(UriError::Unexpected);
+        }
+        Ok(uri)
+    }
+
+    pub fn parse(hold: &dyn Hold<'a>, string: &str) -> Uri<'a> {
+        Uri::try_parse(hold, string).unwrap()
+    }
+
+    pub fn try_from_text(text: &Text<'a>) -> Result<Uri<'a>, UriError> {
+        Uri::try_parse(text.holder(), text.as_str())
+    }
+
+    pub fn try_from_value(value: &Value<'a>) -> Result<Uri<'a>, UriError> {
+        match value.cast_as_text() {
+            Some(text) => Uri::try_from_text(text),
+            None => Err(UriError::Unexpected),
+        }
+    }
+
+    pub fn try_to_text(&self) -> Result<Text<'a>, HoldError> {
+        Text::try_hold_str(self.string.holder(), self.as_str())
+    }
+
+    pub fn to_text(&self) -> Text<'a> {
+        self.try_to_text().unwrap()
+    }
+
+    pub fn to_value(&self) -> Value<'a> {
+        self.to_text().into_value()
+    }
+
+    pub fn as_str(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(&self.string) }
+    }
+
+    fn span(&self, (start, end): (usize, usize)) -> &str {
+        &self.as_str()[start..end]
+    }
+
+    pub fn scheme(&self) -> Option<&str> {
+        self.scheme.map(|end| &self.as_str()[..end])
+    }
+
+    pub fn authority(&self) -> Option<UriAuthority<'_>> {
+        self.authority.map(|span| UriAuthority { string: self.span(span) })
+    }
+
+    pub fn path(&self) -> &str {
+        self.span(self.path)
+    }
+
+    /// Returns the `/`-separated segments of the path, excluding the empty
+    /// segment that precedes the first `/` of an absolute path.
+    pub fn path_segments(&self) -> UriSegments<'_> {
+        let path = self.path();
+        let path = if path.starts_with('/') { &path[1..] } else { path };
+        let mut segments = path.split('/');
+        if path.is_empty() {
+            segments.next();
+        }
+        UriSegments { segments: segments }
+    }
+
+    pub fn query(&self) -> Option<&str> {
+        self.query.map(|span| self.span(span))
+    }
+
+    /// Returns the `&`-separated `key=value` pairs of the query, still
+    /// percent-encoded.
+    pub fn query_params(&self) -> UriQueryParams<'_> {
+        let mut params = self.query().unwrap_or("").split('&');
+        if self.query().map_or(true, str::is_empty) {
+            params.next();
+        }
+        UriQueryParams { params: params }
+    }
+
+    pub fn query_param(&self, key: &str) -> Option<&str> {
+        self.query_params().find(|&(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    pub fn fragment(&self) -> Option<&str> {
+        self.fragment.map(|span| self.span(span))
+    }
+
+    /// Returns `true` if this URI has no scheme, and so must be resolved
+    /// against a base URI.
+    pub fn is_relative(&self) -> bool {
+        self.scheme.is_none()
+    }
+
+    pub fn parts(&self) -> UriParts<'_> {
+        UriParts {
+            scheme: self.scheme(),
+            authority: self.authority.map(|span| self.span(span)),
+            path: self.path(),
+            query: self.query(),
+            fragment: self.fragment(),
+        }
+    }
+
+    /// Resolves a URI `reference` against this base URI, per RFC 3986
+    /// section 5.2.
+    pub fn try_resolve(&self, reference: &Uri) -> Result<Uri<'a>, UriError> {
+        let hold = self.string.holder();
+        let base = self.parts();
+        let target = reference.parts();
+        let mut merged = RawBuf::<u8>::try_hold_cap(hold, base.path.len() + target.path.len() + 1)?;
+        let mut path = RawBuf::<u8>::try_hold_cap(hold, base.path.len() + target.path.len() + 1)?;
+        let mut parts = UriParts { fragment: target.fragment, ..target };
+        if target.scheme.is_some() || target.authority.is_some() {
+            remove_dot_segments(target.path.as_bytes(), &mut path)?;
+            parts.scheme = target.scheme.or(base.scheme);
+        } else {
+            parts.scheme = base.scheme;
+            parts.authority = base.authority;
+            if target.path.is_empty() {
+                path.try_extend_from_slice(base.path.as_bytes())?;
+                parts.query = target.query.or(base.query);
+            } else if target.path.starts_with('/') {
+                remove_dot_segments(target.path.as_bytes(), &mut path)?;
+            } else {
+                if base.authority.is_some() && base.path.is_empty() {
+                    merged.try_push(b'/')?;
+                } else if let Some(index) = base.path.rfind('/') {
+                    merged.try_extend_from_slice(base.path[..index + 1].as_bytes())?;
+                }
+                merged.try_extend_from_slice(target.path.as_bytes())?;
+                remove_dot_segments(&merged, &mut path)?;
+            }
+        }
+        parts.path = unsafe { str::from_utf8_unchecked(&path) };
+        parts.try_hold(hold)
+    }
+
+    pub fn resolve(&self, reference: &Uri) -> Uri<'a> {
+        self.try_resolve(reference).unwrap()
+    }
+
+    /// Returns a syntax-normalized copy of this URI, per RFC 3986 section
+    /// 6.2.2: lowercases the scheme and host, uppercases percent-encoded
+    /// hex digits, decodes percent-encoded unreserved characters, removes
+    /// dot segments, and gives an empty path under an authority a `/`.
+    pub fn try_normalize(&self) -> Result<Uri<'a>, UriError> {
+        let hold = self.string.holder();
+        let mut string = RawBuf::<u8>::try_hold_cap(hold, self.string.len() + 1)?;
+        if let Some(scheme) = self.scheme() {
+            for &b in scheme.as_bytes() {
+                string.try_push(b.to_ascii_lowercase())?;
+            }
+            string.try_push(b':')?;
+        }
+        if let Some(authority) = self.authority() {
+            string.try_extend_from_slice(b"//")?;
+            if let Some(user_info) = authority.user_info() {
+                normalize_escapes(user_info.as_bytes(), false, &mut string)?;
+                string.try_push(b'@')?;
+            }
+            normalize_escapes(authority.host().as_bytes(), true, &mut string)?;
+            if let Some(port) = authority.port() {
+                if !port.is_empty() {
+                    string.try_push(b':')?;
+                    string.try_extend_from_slice(port.as_bytes())?;
+                }
+            }
+        }
+        let mut path = RawBuf::<u8>::try_hold_cap(hold, self.path().len())?;
+        normalize_escapes(self.path().as_bytes(), false, &mut path)?;
+        if self.scheme.is_some() || path.starts_with(b"/") {
+            remove_dot_segments(&path, &mut string)?;
+        } else {
+            string.try_extend_from_slice(&path)?;
+        }
+        if self.authority.is_some() && path.is_empty() {
+            string.try_push(b'/')?;
+        }
+        if let Some(query) = self.query() {
+            string.try_push(b'?')?;
+            normalize_escapes(query.as_bytes(), false, &mut string)?;
+        }
+        if let Some(fragment) = self.fragment() {
+            string.try_push(b'#')?;
+            normalize_escapes(fragment.as_bytes(), false, &mut string)?;
+        }
+        Uri::try_parse(hold, unsafe { str::from_utf8_unchecked(&string) })
+    }
+
+    pub fn normalize(&self) -> Uri<'a> {
+        self.try_normalize().unwrap()
+    }
+}
+
+impl<'a> Holder<'a> for Uri<'a> {
+    #[inline]
+    fn holder(&self) -> &'a dyn Hold<'a> {
+        self.string.holder()
+    }
+}
+
+impl<'a> AsRef<str> for Uri<'a> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<'a> PartialEq for Uri<'a> {
+    fn eq(&self, that: &Uri<'a>) -> bool {
+        self.as_str() == that.as_str()
+    }
+}
+
+impl<'a> Eq for Uri<'a> {}
+
+impl<'a> cmp::PartialOrd for Uri<'a> {
+    fn partial_cmp(&self, that: &Uri<'a>) -> Option<cmp::Ordering> {
+        self.as_str().partial_cmp(that.as_str())
+    }
+}
+
+impl<'a> cmp::Ord for Uri<'a> {
+    fn cmp(&self, that: &Uri<'a>) -> cmp::Ordering {
+        self.as_str().cmp(that.as_str())
+    }
+}
+
+impl<'a> hash::Hash for Uri<'a> {
+    fn hash<H: hash::Hasher>(&self, hasher: &mut H) {
+        self.as_str().hash(hasher);
+    }
+}
+
+impl<'a> fmt::Display for Uri<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<'a> fmt::Debug for Uri<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<'a> From<Uri<'a>> for Text<'a> {
+    fn from(uri: Uri<'a>) -> Text<'a> {
+        uri.to_text()
+    }
+}
+
+impl<'a> From<Uri<'a>> for Value<'a> {
+    fn from(uri: Uri<'a>) -> Value<'a> {
+        uri.to_value()
+    }
+}
+
+impl<'b> UriParts<'b> {
+    /// Composes these parts into a new `Uri`, validating each component.
+    pub fn try_hold<'a>(self, hold: &dyn Hold<'a>) -> Result<Uri<'a>, UriError> {
+        let mut input = UriPartsInput::new(self);
+        let uri = UriDecoder::try_hold(hold)?.consume(&mut input)?;
+        if input.is_in() || uri.parts() != self {
+            return Err(UriError::Unexpected);
+        }
+        Ok(uri)
+    }
+}
+
+impl<'b> UriAuthority<'b> {
+    pub fn as_str(&self) -> &'b str {
+        self.string
+    }
+
+    pub fn user_info(&self) -> Option<&'b str> {
+        self.string.find('@').map(|index| &self.string[..index])
+    }
+
+    fn host_port(&self) -> &'b str {
+        match self.string.find('@') {
+            Some(index) => &self.string[index + 1..],
+            None => self.string,
+        }
+    }
+
+    /// Returns the host, including the brackets of an IP literal.
+    pub fn host(&self) -> &'b str {
+        let host_port = self.host_port();
+        match self.port_index(host_port) {
+            Some(index) => &host_port[..index],
+            None => host_port,
+        }
+    }
+
+    /// Returns the port, which may be empty, if the authority has a `:`
+    /// delimited port component.
+    pub fn port(&self) -> Option<&'b str> {
+        let host_port = self.host_port();
+        self.port_index(host_port).map(|index| &host_port[index + 1..])
+    }
+
+    pub fn port_number(&self) -> Option<u16> {
+        self.port().and_then(|port| port.parse().ok())
+    }
+
+    fn port_index(&self, host_port: &str) -> Option<usize> {
+        let index = host_port.rfind(':')?;
+        match host_port.rfind(']') {
+            Some(bracket) if bracket > index => None,
+            _ => Some(index),
+        }
+    }
+}
+
+impl<'b> Iterator for UriSegments<'b> {
+    type Item = &'b str;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'b str> {
+        self.segments.next()
+    }
+}
+
+impl<'b> Iterator for UriQueryParams<'b> {
+    type Item = (&'b str, &'b str);
+
+    fn next(&mut self) -> Option<(&'b str, &'b str)> {
+        self.params.next().map(|param| {
+            match param.find('=') {
+                Some(index) => (&param[..index], &param[index + 1..]),
+                None => (param, ""),
+            }
+        })
+    }
+}
+
+impl<'a, I> UriDecoder<'a, I> where I: Input<Token=char> {
+    pub fn try_hold(hold: &dyn Hold<'a>) -> Result<Self, HoldError> {
+        Ok(Self {
+            string: RawBuf::try_hold_empty(hold)?,
+            scheme: None,
+            authority: None,
+            path: (0, 0),
+            query: None,
+            start: 0,
+            escape: 0,
+            state: 1,
+            input: PhantomData,
+        })
+    }
+
+    pub fn hold(hold: &dyn Hold<'a>) -> Self {
+        Self::try_hold(hold).unwrap()
+    }
+
+    pub fn consume(mut self, input: &mut I) -> Result<Uri<'a>, UriError> {
+        loop {
+            match self.decode(input) {
+                Done(uri) => return Ok(uri),
+                Fail(error) => return Err(error),
+                Cont(next) => {
+                    if input.is_out() {
+                        input.over();
+                        self = next;
+                    } else {
+                        return Err(UriError::Unexpected);
+                    }
+                },
+            }
+        }
+    }
+
+    fn push(&mut self, c: char) -> Result<(), HoldError> {
+        if c == '%' {
+            self.escape = 2;
+        }
+        self.string.try_push(c as u8)
+    }
+
+    fn finish(self) -> Then<Self, Uri<'a>, UriError> {
+        if self.escape != 0 {
+            return Fail(UriError::Escape);
+        }
+        let len = self.string.len();
+        let UriDecoder { string, scheme, mut authority, mut path, mut query, start, state, .. } = self;
+        let mut fragment = None;
+        match state {
+            1 | 2 => path = (0, len),
+            3 => path = (len, len),
+            5 => {
+                authority = Some((start, len));
+                path = (len, len);
+            },
+            4 | 6 => path = (path.0, len),
+            7 => query = Some((start, len)),
+            8 => fragment = Some((start, len)),
+            _ => unreachable!(),
+        };
+        Done(Uri {
+            string: string,
+            scheme: scheme,
+            authority: authority,
+            path: path,
+            query: query,
+            fragment: fragment,
+        })
+    }
+}
+
+impl<'a, I> Decoder for UriDecoder<'a, I> where I: Input<Token=char> {
+    type Input = I;
+    type Output = Uri<'a>;
+    type Error = UriError;
+
+    fn decode(mut self, input: &mut I) -> Then<Self, Uri<'a>, UriError> {
+        loop {
+            let c = match input.head() {
+                In(c) => c,
+                Over => return self.finish(),
+                Out => return Cont(self),
+            };
+            if self.escape != 0 {
+                if !c.is_ascii_hexdigit() {
+                    return Fail(UriError::Escape);
+                }
+                self.escape -= 1;
+                if let Err(error) = self.string.try_push(c as u8) {
+                    return Fail(UriError::Hold(error));
+                }
+                input.step();
+                continue;
+            }
+            let len = self.string.len();
+            let accept = match self.state {
+                1 => { // scheme or path
+                    if c.is_ascii_alphabetic() {
+                        self.state = 2;
+                        true
+                    } else if c == '/' {
+                        self.state = 4;
+                        true
+                    } else {
+                        self.state = 6;
+                        continue;
+                    }
+                },
+                2 => { // scheme
+                    if is_scheme_char(c) {
+                        true
+                    } else if c == ':' {
+                        self.scheme = Some(len);
+                        self.path = (len + 1, len + 1);
+                        self.state = 3;
+                        true
+                    } else {
+                        self.state = 6;
+                        continue;
+                    }
+                },
+                3 => { // hier-part
+                    if c == '/' {
+                        self.state = 4;
+                        true
+                    } else {
+                        self.state = 6;
+                        continue;
+                    }
+                },
+                4 => { // authority or absolute path
+                    if c == '/' && len == self.path.0 + 1 {
+                        self.start = len + 1;
+                        self.state = 5;
+                        true
+                    } else {
+                        self.state = 6;
+                        continue;
+                    }
+                },
+                5 => { // authority
+                    if is_authority_char(c) {
+                        true
+                    } else {
+                        self.authority = Some((self.start, len));
+                        self.path = (len, len);
+                        self.state = 6;
+                        continue;
+                    }
+                },
+                6 => { // path
+                    if c == '/' || is_pchar(c) {
+                        true
+                    } else if c == '?' {
+                        self.path.1 = len;
+                        self.start = len + 1;
+                        self.state = 7;
+                        true
+                    } else if c == '#' {
+                        self.path.1 = len;
+                        self.start = len + 1;
+                        self.state = 8;
+                        true
+                    } else {
+                        return self.finish();
+                    }
+                },
+                7 => { // query
+                    if is_query_char(c) {
+                        true
+                    } else if c == '#' {
+                        self.query = Some((self.start, len));
+                        self.start = len + 1;
+                        self.state = 8;
+                        true
+                    } else {
+                        return self.finish();
+                    }
+                },
+                8 => { // fragment
+                    if is_query_char(c) {
+                        true
+                    } else {
+                        return self.finish();
+                    }
+                },
+                _ => unreachable!(),
+            };
+            if accept {
+                if let Err(error) = self.push(c) {
+                    return Fail(UriError::Hold(error));
+                }
+                input.step();
+            }
+        }
+    }
+}
+
+impl<'b> UriPartsInput<'b> {
+    fn new(parts: UriParts<'b>) -> Self {
+        let (scheme, colon) = match parts.scheme {
+            Some(scheme) => (scheme, ":"),
+            None => ("", ""),
+        };
+        let (slashes, authority) = match parts.authority {
+            Some(authority) => ("//", authority),
+            None => ("", ""),
+        };
+        let (question, query) = match parts.query {
+            Some(query) => ("?", query),
+            None => ("", ""),
+        };
+        let (hash, fragment) = match parts.fragment {
+            Some(fragment) => ("#", fragment),
+            None => ("", ""),
+        };
+        Self {
+            parts: [scheme, colon, slashes, authority, parts.path, question, query, hash, fragment],
+            index: 0,
+            offset: 0,
+        }
+    }
+}
+
+impl<'b> Input for UriPartsInput<'b> {
+    type Token = char;
+
+    fn head(&mut self) -> swim_codec::step::Step<char> {
+        while self.index < self.parts.len() {
+            match self.parts[self.index][self.offset..].chars().next() {
+                Some(c) => return In(c),
+                None => {
+                    self.index += 1;
+                    self.offset = 0;
+                },
+            }
+        }
+        Over
+    }
+
+    fn step(&mut self) {
+        if let In(c) = self.head() {
+            self.offset += c.len_utf8();
+        }
+    }
+
+    fn over(&mut self) {
+        self.index = self.parts.len();
+    }
+}
+
+#[inline]
+fn is_scheme_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.'
+}
+
+#[inline]
+fn is_pchar(c: char) -> bool {
+    c.is_ascii() && (is_unreserved(c as u8) || is_sub_delim(c as u8)) || c == ':' || c == '@' || c == '%'
+}
+
+#[inline]
+fn is_authority_char(c: char) -> bool {
+    is_pchar(c) || c == '[' || c == ']'
+}
+
+#[inline]
+fn is_query_char(c: char) -> bool {
+    is_pchar(c) || c == '/' || c == '?'
+}
+
+/// Appends `path` to `output` with `.` and `..` segments removed, per
+/// RFC 3986 section 5.2.4.
+fn remove_dot_segments<'a>(mut path: &[u8], output: &mut RawBuf<'a, u8>) -> Result<(), HoldError> {
+    let start = output.len();
+    while !path.is_empty() {
+        if path.starts_with(b"../") {
+            path = &path[3..];
+        } else if path.starts_with(b"./") || path.starts_with(b"/./") {
+            path = &path[2..];
+        } else if path == b"/." {
+            path = b"/";
+        } else if path.starts_with(b"/../") || path == b"/.." {
+            path = if path.len() == 3 { b"/" } else { &path[3..] };
+            let index = output[start..].iter().rposition(|&b| b == b'/').unwrap_or(0);
+            output.truncate(start + index);
+        } else if path == b"." || path == b".." {
+            path = b"";
+        } else {
+            let end = path[1..].iter().position(|&b| b == b'/').map_or(path.len(), |index| index + 1);
+            output.try_extend_from_slice(&path[..end])?;
+            path = &path[end..];
+        }
+    }
+    Ok(())
+}
+
+fn normalize_escapes<'a>(bytes: &[u8], lowercase: bool, output: &mut RawBuf<'a, u8>) -> Result<(), HoldError> {
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if b == b'%' {
+            let x = (hex_value(bytes[i + 1]) << 4) | hex_value(bytes[i + 2]);
+            if is_unreserved(x) {
+                output.try_push(if lowercase { x.to_ascii_lowercase() } else { x })?;
+            } else {
+                output.try_push(b'%')?;
+                output.try_push(bytes[i + 1].to_ascii_uppercase())?;
+                output.try_push(bytes[i + 2].to_ascii_uppercase())?;
+            }
+            i += 3;
+        } else {
+            output.try_push(if lowercase { b.to_ascii_lowercase() } else { b })?;
+            i += 1;
+        }
+    }
+    Ok(())
+}
+
+#[inline]
+fn hex_value(b: u8) -> u8 {
+    (b as char).to_digit(16).unwrap() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use swim_mem::block::Block;
+    use swim_mem::alloc::Pack;
+    use super::*;
+
+    #[test]
+    fn test_uri_parse_components() {
+        static mut TEST_AREA: [u8; 16384] = [0; 16384];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let uri = Uri::parse(pack, "warp://user@example.com:9001/node/a%20b?lane=x&prio=2#frag");
+        assert_eq!(uri.scheme(), Some("warp"));
+        let authority = uri.authority().unwrap();
+        assert_eq!(authority.as_str(), "user@example.com:9001");
+        assert_eq!(authority.user_info(), Some("user"));
+        assert_eq!(authority.host(), "example.com");
+        assert_eq!(authority.port_number(), Some(9001));
+        assert_eq!(uri.path(), "/node/a%20b");
+        assert!(uri.path_segments().eq(["node", "a%20b"].iter().cloned()));
+        assert_eq!(uri.query(), Some("lane=x&prio=2"));
+        assert_eq!(uri.query_param("lane"), Some("x"));
+        assert_eq!(uri.query_param("prio"), Some("2"));
+        assert_eq!(uri.query_param("none"), None);
+        assert_eq!(uri.fragment(), Some("frag"));
+        assert!(!uri.is_relative());
+
+        let uri = Uri::parse(pack, "http://[::1]:80");
+        assert_eq!(uri.authority().unwrap().host(), "[::1]");
+        assert_eq!(uri.authority().unwrap().port(), Some("80"));
+        assert_eq!(uri.path(), "");
+        let uri = Uri::parse(pack, "http://[::1]");
+        assert_eq!(uri.authority().unwrap().port(), None);
+
+        let uri = Uri::parse(pack, "mailto:swim@example.com");
+        assert_eq!((uri.scheme(), uri.authority(), uri.path()), (Some("mailto"), None, "swim@example.com"));
+        let uri = Uri::parse(pack, "../a/b?q");
+        assert_eq!((uri.scheme(), uri.path(), uri.query()), (None, "../a/b", Some("q")));
+        assert!(uri.is_relative());
+        let uri = Uri::parse(pack, "//host");
+        assert_eq!((uri.authority().map(|a| a.as_str()), uri.path()), (Some("host"), ""));
+        let uri = Uri::parse(pack, "a1+b.c");
+        assert_eq!((uri.scheme(), uri.path()), (None, "a1+b.c"));
+        let uri = Uri::parse(pack, "");
+        assert_eq!((uri.path(), uri.query(), uri.fragment()), ("", None, None));
+        let uri = Uri::parse(pack, "?#");
+        assert_eq!((uri.path(), uri.query(), uri.fragment()), ("", Some(""), Some("")));
+        assert_eq!(uri.path_segments().count(), 0);
+        assert_eq!(uri.query_params().count(), 0);
+    }
+
+    #[test]
+    fn test_uri_decode_incremental() {
+        let encoded = "warp://localhost:9001/unit/1?lane=info#x";
+        for split in 0..=encoded.len() {
+            let mut test_area = [0u8; 2048];
+            let pack = Pack::new(Block::from_slice(&mut test_area));
+            let uri = match UriDecoder::hold(pack).decode(&mut encoded[..split].as_input()) {
+                Cont(decoder) => decoder.consume(&mut encoded[split..].as_input()).unwrap(),
+                Done(uri) => uri,
+                Fail(error) => panic!("{:?}", error),
+            };
+            assert_eq!(uri.as_str(), encoded);
+            assert_eq!(uri.path(), "/unit/1");
+            assert_eq!(uri.query(), Some("lane=info"));
+        }
+    }
+
+    #[test]
+    fn test_uri_decode_terminators() {
+        static mut TEST_AREA: [u8; 4096] = [0; 4096];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let mut input = "http://a/b c".as_input();
+        let uri = UriDecoder::hold(pack).consume(&mut input).unwrap();
+        assert_eq!(uri.as_str(), "http://a/b");
+        assert_eq!(input.head(), In(' '));
+        assert_eq!(Uri::try_parse(pack, "http://a/b c").err(), Some(UriError::Unexpected));
+        assert_eq!(Uri::try_parse(pack, "/a%2").err(), Some(UriError::Escape));
+        assert_eq!(Uri::try_parse(pack, "/a%zz").err(), Some(UriError::Escape));
+    }
+
+    #[test]
+    fn test_uri_resolve_rfc3986_examples() {
+        static mut TEST_AREA: [u8; 65536] = [0; 65536];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let base = Uri::parse(pack, "http://a/b/c/d;p?q");
+        let examples = [
+            ("g:h", "g:h"), ("g", "http://a/b/c/g"), ("./g", "http://a/b/c/g"),
+            ("g/", "http://a/b/c/g/"), ("/g", "http://a/g"), ("//g", "http://g"),
+            ("?y", "http://a/b/c/d;p?y"), ("g?y", "http://a/b/c/g?y"), ("#s", "http://a/b/c/d;p?q#s"),
+            ("g#s", "http://a/b/c/g#s"), ("g?y#s", "http://a/b/c/g?y#s"), (";x", "http://a/b/c/;x"),
+            ("g;x", "http://a/b/c/g;x"), ("g;x?y#s", "http://a/b/c/g;x?y#s"), ("", "http://a/b/c/d;p?q"),
+            (".", "http://a/b/c/"), ("./", "http://a/b/c/"), ("..", "http://a/b/"),
+            ("../", "http://a/b/"), ("../g", "http://a/b/g"), ("../..", "http://a/"),
+            ("../../", "http://a/"), ("../../g", "http://a/g"),
+            ("../../../g", "http://a/g"), ("../../../../g", "http://a/g"), ("/./g", "http://a/g"),
+            ("/../g", "http://a/g"), ("g.", "http://a/b/c/g."), (".g", "http://a/b/c/.g"),
+            ("g..", "http://a/b/c/g.."), ("..g", "http://a/b/c/..g"), ("./../g", "http://a/b/g"),
+            ("./g/.", "http://a/b/c/g/"), ("g/./h", "http://a/b/c/g/h"), ("g/../h", "http://a/b/c/h"),
+            ("g;x=1/./y", "http://a/b/c/g;x=1/y"), ("g;x=1/../y", "http://a/b/c/y"),
+            ("g?y/./x", "http://a/b/c/g?y/./x"), ("g?y/../x", "http://a/b/c/g?y/../x"),
+            ("g#s/./x", "http://a/b/c/g#s/./x"), ("g#s/../x", "http://a/b/c/g#s/../x"),
+            ("http:g", "http:g"),
+        ];
+        for &(reference, target) in examples.iter() {
+            let reference = Uri::parse(pack, reference);
+            assert_eq!(base.resolve(&reference).as_str(), target);
+        }
+        let base = Uri::parse(pack, "warp://host");
+        assert_eq!(base.resolve(&Uri::parse(pack, "node")).as_str(), "warp://host/node");
+    }
+
+    #[test]
+    fn test_uri_normalize() {
+        static mut TEST_AREA: [u8; 16384] = [0; 16384];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let uri = Uri::parse(pack, "HTTP://User@Example.COM:/a/./b/../%7euser/%3f%7a?Q%2f#F%2F");
+        assert_eq!(uri.normalize().as_str(), "http://User@example.com/a/~user/%3Fz?Q%2F#F%2F");
+        assert_eq!(Uri::parse(pack, "http://example.com").normalize().as_str(), "http://example.com/");
+        assert_eq!(Uri::parse(pack, "a/./b").normalize().as_str(), "a/./b");
+    }
+
+    #[test]
+    fn test_uri_compose() {
+        static mut TEST_AREA: [u8; 4096] = [0; 4096];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let parts = UriParts {
+            scheme: Some("warp"),
+            authority: Some("host:80"),
+            path: "/node",
+            query: Some("lane=x"),
+            fragment: None,
+        };
+        let uri = parts.try_hold(pack).unwrap();
+        assert_eq!(uri.as_str(), "warp://host:80/node?lane=x");
+        assert_eq!(uri.parts(), parts);
+        let parts = UriParts { path: "a b", ..parts };
+        assert_eq!(parts.try_hold(pack).err(), Some(UriError::Unexpected));
+    }
+
+    #[test]
+    fn test_uri_text_value_conversion() {
+        static mut TEST_AREA: [u8; 4096] = [0; 4096];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let value = Value::hold_str(pack, "warp://host/node?lane=x");
+        let uri = Uri::try_from_value(&value).unwrap();
+        assert_eq!(uri.query_param("lane"), Some("x"));
+        let text: Text = uri.to_text();
+        assert_eq!(text.as_str(), "warp://host/node?lane=x");
+        let value: Value = Uri::parse(pack, "/a").into();
+        assert_eq!(value.as_text().as_str(), "/a");
+        assert_eq!(Uri::try_from_value(&Value::hold_str(pack, "a b")).err(), Some(UriError::Unexpected));
+    }
+}
@@ -0,0 +1,591 @@
+use core::fmt::{self, Write};
+use swim_mem::alloc::{Hold, HoldError};
+use swim_codec::then::{Then, Cont, Done, Fail};
+use swim_codec::input::{Input, AsInput};
+use swim_codec::decoder::Decoder;
+use swim_structure::item::{Item, Value, Record, Text};
+use crate::recon::{self, ReconError, WriteRecon, ReconDecoder, ReconEncoder};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EnvelopeError {
+    /// Not a record headed by a known envelope tag.
+    Tag,
+    /// Missing or malformed `node`, `lane`, `prio` or `rate` header.
+    Header,
+    Recon(ReconError),
+    Hold(HoldError),
+}
+
+impl From<ReconError> for EnvelopeError {
+    fn from(error: ReconError) -> EnvelopeError {
+        EnvelopeError::Recon(error)
+    }
+}
+
+impl From<HoldError> for EnvelopeError {
+    fn from(error: HoldError) -> EnvelopeError {
+        EnvelopeError::Hold(error)
+    }
+}
+
+/// A WARP protocol message, such as `@link(node:"/unit/1",lane:info)`.
+#[derive(PartialEq, Debug)]
+pub enum Envelope<'a> {
+    Event(LaneAddressed<'a>),
+    Command(LaneAddressed<'a>),
+    Link(LinkAddressed<'a>),
+    Linked(LinkAddressed<'a>),
+    Sync(LinkAddressed<'a>),
+    Synced(LaneAddressed<'a>),
+    Unlink(LaneAddressed<'a>),
+    Unlinked(LaneAddressed<'a>),
+    Auth(HostAddressed<'a>),
+    Authed(HostAddressed<'a>),
+    Deauth(HostAddressed<'a>),
+    Deauthed(HostAddressed<'a>),
+}
+
+/// Headers and body of an envelope addressed to a lane of a node.
+#[derive(PartialEq, Debug)]
+pub struct LaneAddressed<'a> {
+    pub node: Text<'a>,
+    pub lane: Text<'a>,
+    pub body: Value<'a>,
+}
+
+/// Headers and body of an envelope that establishes a link to a lane;
+/// a `prio` or `rate` of zero, or one that is not finite, is left
+/// unspecified.
+#[derive(PartialEq, Debug)]
+pub struct LinkAddressed<'a> {
+    pub node: Text<'a>,
+    pub lane: Text<'a>,
+    pub prio: f64,
+    pub rate: f64,
+    pub body: Value<'a>,
+}
+
+/// Body of an envelope addressed to the remote host as a whole.
+#[derive(PartialEq, Debug)]
+pub struct HostAddressed<'a> {
+    pub body: Value<'a>,
+}
+
+pub struct EnvelopeDecoder<'a, I: Input<Token=char>> {
+    recon: ReconDecoder<'a, I>,
+}
+
+pub type EnvelopeEncoder<'a, 'b, O> = ReconEncoder<'a, 'b, Envelope<'a>, O>;
+
+/// Header fields common to lane and link addressed envelopes.
+struct EnvelopeHeaders<'a> {
+    node: Option<Text<'a>>,
+    lane: Option<Text<'a>>,
+    prio: f64,
+    rate: f64,
+}
+
+impl<'a> Envelope<'a> {
+    pub fn try_parse(hold: &dyn Hold<'a>, string: &str) -> Result<Envelope<'a>, EnvelopeError> {
+        EnvelopeDecoder::try_hold(hold)?.consume(&mut string.as_input())
+    }
+
+    /// Converts a record whose first item is an envelope tag attribute,
+    /// with the remaining items forming the body.
+    pub fn try_from_value(value: Value<'a>) -> Result<Envelope<'a>, EnvelopeError> {
+        let mut record = match value.cast_into_record() {
+            Some(record) => record,
+            None => return Err(EnvelopeError::Tag),
+        };
+        if !record.first().map_or(false, Item::is_attr) {
+            return Err(EnvelopeError::Tag);
+        }
+        let (tag, headers) = record.remove(0).into_attr().into_key_val();
+        let body = recon::block_value(record, Value::absent());
+        let envelope = match tag.as_str() {
+            "event" => Envelope::Event(LaneAddressed::from_headers(headers, body)?),
+            "command" => Envelope::Command(LaneAddressed::from_headers(headers, body)?),
+            "link" => Envelope::Link(LinkAddressed::from_headers(headers, body)?),
+            "linked" => Envelope::Linked(LinkAddressed::from_headers(headers, body)?),
+            "sync" => Envelope::Sync(LinkAddressed::from_headers(headers, body)?),
+            "synced" => Envelope::Synced(LaneAddressed::from_headers(headers, body)?),
+            "unlink" => Envelope::Unlink(LaneAddressed::from_headers(headers, body)?),
+            "unlinked" => Envelope::Unlinked(LaneAddressed::from_headers(headers, body)?),
+            "auth" => Envelope::Auth(HostAddressed { body: body }),
+            "authed" => Envelope::Authed(HostAddressed { body: body }),
+            "deauth" => Envelope::Deauth(HostAddressed { body: body }),
+            "deauthed" => Envelope::Deauthed(HostAddressed { body: body }),
+            _ => return Err(EnvelopeError::Tag),
+        };
+        Ok(envelope)
+    }
+
+    /// Converts this envelope into a record headed by its tag attribute,
+    /// followed by the items of its body.
+    pub fn try_into_value(self, hold: &dyn Hold<'a>) -> Result<Value<'a>, HoldError> {
+        let tag = self.tag();
+        let (headers, body) = match self {
+            Envelope::Event(envelope) | Envelope::Command(envelope) |
+            Envelope::Synced(envelope) | Envelope::Unlink(envelope) |
+            Envelope::Unlinked(envelope) => envelope.into_headers(hold)?,
+            Envelope::Link(envelope) | Envelope::Linked(envelope) |
+            Envelope::Sync(envelope) => envelope.into_headers(hold)?,
+            Envelope::Auth(envelope) | Envelope::Authed(envelope) |
+            Envelope::Deauth(envelope) | Envelope::Deauthed(envelope) => (Value::extant(), envelope.body),
+        };
+        let mut record = Record::try_hold_slice(hold, &[])?;
+        record.try_push(Item::attr(Text::try_hold_str(hold, tag)?, headers))?;
+        if body.cast_as_record().map_or(false, |record| is_spliced(record)) {
+            let mut body = body.into_record();
+            record.try_reserve(body.len())?;
+            while !body.is_empty() {
+                record.try_push(body.remove(0))?;
+            }
+        } else if body.is_defined() {
+            record.try_push(body.into_item())?;
+        }
+        Ok(record.into_value())
+    }
+
+    pub fn tag(&self) -> &'static str {
+        match *self {
+            Envelope::Event(_) => "event",
+            Envelope::Command(_) => "command",
+            Envelope::Link(_) => "link",
+            Envelope::Linked(_) => "linked",
+            Envelope::Sync(_) => "sync",
+            Envelope::Synced(_) => "synced",
+            Envelope::Unlink(_) => "unlink",
+            Envelope::Unlinked(_) => "unlinked",
+            Envelope::Auth(_) => "auth",
+            Envelope::Authed(_) => "authed",
+            Envelope::Deauth(_) => "deauth",
+            Envelope::Deauthed(_) => "deauthed",
+        }
+    }
+
+    pub fn node(&self) -> Option<&str> {
+        self.lane_headers().map(|(node, _)| node.as_str())
+    }
+
+    pub fn lane(&self) -> Option<&str> {
+        self.lane_headers().map(|(_, lane)| lane.as_str())
+    }
+
+    fn lane_headers(&self) -> Option<(&Text<'a>, &Text<'a>)> {
+        match *self {
+            Envelope::Event(ref envelope) | Envelope::Command(ref envelope) |
+            Envelope::Synced(ref envelope) | Envelope::Unlink(ref envelope) |
+            Envelope::Unlinked(ref envelope) => Some((&envelope.node, &envelope.lane)),
+            Envelope::Link(ref envelope) | Envelope::Linked(ref envelope) |
+            Envelope::Sync(ref envelope) => Some((&envelope.node, &envelope.lane)),
+            _ => None,
+        }
+    }
+
+    pub fn body(&self) -> &Value<'a> {
+        match *self {
+            Envelope::Event(ref envelope) | Envelope::Command(ref envelope) |
+            Envelope::Synced(ref envelope) | Envelope::Unlink(ref envelope) |
+            Envelope::Unlinked(ref envelope) => &envelope.body,
+            Envelope::Link(ref envelope) | Envelope::Linked(ref envelope) |
+            Envelope::Sync(ref envelope) => &envelope.body,
+            Envelope::Auth(ref envelope) | Envelope::Authed(ref envelope) |
+            Envelope::Deauth(ref envelope) | Envelope::Deauthed(ref envelope) => &envelope.body,
+        }
+    }
+}
+
+impl<'a> WriteRecon for Envelope<'a> {
+    fn write_recon<W: Write>(&self, output: &mut W) -> fmt::Result {
+        output.write_char('@')?;
+        output.write_str(self.tag())?;
+        let bare = match *self {
+            Envelope::Link(ref envelope) | Envelope::Linked(ref envelope) |
+            Envelope::Sync(ref envelope) => {
+                write_headers(&envelope.node, &envelope.lane, output)?;
+                if is_specified(envelope.prio) {
+                    write!(output, ",prio:{:?}", envelope.prio)?;
+                }
+                if is_specified(envelope.rate) {
+                    write!(output, ",rate:{:?}", envelope.rate)?;
+                }
+                output.write_char(')')?;
+                false
+            },
+            Envelope::Auth(_) | Envelope::Authed(_) |
+            Envelope::Deauth(_) | Envelope::Deauthed(_) => true,
+            _ => {
+                let (node, lane) = self.lane_headers().unwrap();
+                write_headers(node, lane, output)?;
+                output.write_char(')')?;
+                false
+            },
+        };
+        let body = self.body();
+        match body.cast_as_record() {
+            Some(record) if is_spliced(record) => recon::write_body(record, bare, output),
+            Some(_) => {
+                output.write_char('{')?;
+                recon::write_value(body, output)?;
+                output.write_char('}')
+            },
+            None if body.is_defined() => {
+                if bare {
+                    output.write_char(' ')?;
+                }
+                recon::write_value(body, output)
+            },
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> fmt::Display for Envelope<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_recon(f)
+    }
+}
+
+/// Returns `true` if a record body can be written as the items that follow
+/// the tag attribute; an empty body, or one whose sole item is a value,
+/// would parse back as no body or as that value, and so is nested instead.
+fn is_spliced(body: &[Item]) -> bool {
+    body.len() > 1 || body.first().map_or(false, |item| !item.is_value())
+}
+
+/// Returns `true` if a `prio` or `rate` header should be written.
+#[inline]
+fn is_specified(value: f64) -> bool {
+    value != 0.0 && value.is_finite()
+}
+
+fn write_headers<W: Write>(node: &Text, lane: &Text, output: &mut W) -> fmt::Result {
+    output.write_str("(node:")?;
+    recon::write_text(node.as_str(), output)?;
+    output.write_str(",lane:")?;
+    recon::write_text(lane.as_str(), output)
+}
+
+impl<'a> LaneAddressed<'a> {
+    fn from_headers(headers: Value<'a>, body: Value<'a>) -> Result<LaneAddressed<'a>, EnvelopeError> {
+        let headers = EnvelopeHeaders::from_value(headers)?;
+        Ok(LaneAddressed {
+            node: headers.node.ok_or(EnvelopeError::Header)?,
+            lane: headers.lane.ok_or(EnvelopeError::Header)?,
+            body: body,
+        })
+    }
+
+    fn into_headers(self, hold: &dyn Hold<'a>) -> Result<(Value<'a>, Value<'a>), HoldError> {
+        let headers = Record::try_hold_slice(hold, &[
+            Item::slot(Value::try_hold_str(hold, "node")?, self.node.into_value()),
+            Item::slot(Value::try_hold_str(hold, "lane")?, self.lane.into_value()),
+        ])?;
+        Ok((headers.into_value(), self.body))
+    }
+}
+
+impl<'a> LinkAddressed<'a> {
+    fn from_headers(headers: Value<'a>, body: Value<'a>) -> Result<LinkAddressed<'a>, EnvelopeError> {
+        let headers = EnvelopeHeaders::from_value(headers)?;
+        Ok(LinkAddressed {
+            node: headers.node.ok_or(EnvelopeError::Header)?,
+            lane: headers.lane.ok_or(EnvelopeError::Header)?,
+            prio: headers.prio,
+            rate: headers.rate,
+            body: body,
+        })
+    }
+
+    fn into_headers(self, hold: &dyn Hold<'a>) -> Result<(Value<'a>, Value<'a>), HoldError> {
+        let mut headers = Record::try_hold_slice(hold, &[
+            Item::slot(Value::try_hold_str(hold, "node")?, self.node.into_value()),
+            Item::slot(Value::try_hold_str(hold, "lane")?, self.lane.into_value()),
+        ])?;
+        if is_specified(self.prio) {
+            headers.try_push(Item::slot(Value::try_hold_str(hold, "prio")?, Value::from_f64(self.prio)))?;
+        }
+        if is_specified(self.rate) {
+            headers.try_push(Item::slot(Value::try_hold_str(hold, "rate")?, Value::from_f64(self.rate)))?;
+        }
+        Ok((headers.into_value(), self.body))
+    }
+}
+
+impl<'a> EnvelopeHeaders<'a> {
+    /// Reads headers from `node`, `lane`, `prio` and `rate` slots, or from
+    /// leading positional `node` and `lane` values. Unknown slots are
+    /// ignored for forward compatibility.
+    fn from_value(value: Value<'a>) -> Result<EnvelopeHeaders<'a>, EnvelopeError> {
+        let mut headers = EnvelopeHeaders { node: None, lane: None, prio: 0.0, rate: 0.0 };
+        let mut record = match value.cast_into_record() {
+            Some(record) => record,
+            None => return Err(EnvelopeError::Header),
+        };
+        let mut index = 0;
+        while !record.is_empty() {
+            let item = record.remove(0);
+            if item.is_slot() {
+                let (key, value) = item.into_slot().into_key_val();
+                match key.cast_as_text().map(|key| key.as_str()) {
+                    Some("node") => headers.node = Some(header_text(value)?),
+                    Some("lane") => headers.lane = Some(header_text(value)?),
+                    Some("prio") => headers.prio = header_num(&value)?,
+                    Some("rate") => headers.rate = header_num(&value)?,
+                    _ => (),
+                };
+            } else if item.is_value() && index < 2 {
+                let text = header_text(item.into_value())?;
+                if index == 0 {
+                    headers.node = Some(text);
+                } else {
+                    headers.lane = Some(text);
+                }
+                index += 1;
+            } else {
+                return Err(EnvelopeError::Header);
+            }
+        }
+        Ok(headers)
+    }
+}
+
+fn header_text<'a>(value: Value<'a>) -> Result<Text<'a>, EnvelopeError> {
+    value.cast_into_text().ok_or(EnvelopeError::Header)
+}
+
+fn header_num(value: &Value) -> Result<f64, EnvelopeError> {
+    match value.cast_as_num().map(|num| num.as_f64()) {
+        Some(num) if num.is_finite() => Ok(num),
+        _ => Err(EnvelopeError::Header),
+    }
+}
+
+impl<'a, I> EnvelopeDecoder<'a, I> where I: Input<Token=char> {
+    pub fn try_hold(hold: &dyn Hold<'a>) -> Result<Self, HoldError> {
+        Ok(Self {
+            recon: ReconDecoder::try_hold(hold)?,
+        })
+    }
+
+    pub fn hold(hold: &dyn Hold<'a>) -> Self {
+        Self::try_hold(hold).unwrap()
+    }
+
+    pub fn consume(mut self, input: &mut I) -> Result<Envelope<'a>, EnvelopeError> {
+        loop {
+            match self.decode(input) {
+                Done(envelope) => return Ok(envelope),
+                Fail(error) => return Err(error),
+                Cont(next) => {
+                    if input.is_out() {
+                        input.over();
+                        self = next;
+                    } else {
+                        return Err(EnvelopeError::Recon(ReconError::Unexpected));
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl<'a, I> Decoder for EnvelopeDecoder<'a, I> where I: Input<Token=char> {
+    type Input = I;
+    type Output = Envelope<'a>;
+    type Error = EnvelopeError;
+
+    fn decode(self, input: &mut I) -> Then<Self, Envelope<'a>, EnvelopeError> {
+        match self.recon.decode(input) {
+            Cont(recon) => Cont(Self { recon: recon }),
+            Done(value) => {
+                match Envelope::try_from_value(value) {
+                    Ok(envelope) => Done(envelope),
+                    Err(error) => Fail(error),
+                }
+            },
+            Fail(error) => Fail(EnvelopeError::Recon(error)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use swim_mem::block::Block;
+    use swim_mem::alloc::Pack;
+    use swim_codec::encoder::Encoder;
+    use swim_codec::output::{Output, StrOutput};
+    use super::*;
+
+    fn assert_transcodes(encoded: &str) {
+        let mut test_area = [0u8; 8192];
+        let pack = Pack::new(Block::from_slice(&mut test_area));
+        let envelope = Envelope::try_parse(pack, encoded).unwrap();
+        let mut buffer = [0u8; 256];
+        let encoder = EnvelopeEncoder::hold(pack, &envelope);
+        assert_eq!(encoder.produce(StrOutput::new(&mut buffer)).unwrap(), encoded);
+        let value = envelope.try_into_value(pack).unwrap();
+        let mut buffer = [0u8; 256];
+        let encoder = ReconEncoder::hold(pack, &value);
+        assert_eq!(encoder.produce(StrOutput::new(&mut buffer)).unwrap(), encoded);
+        let envelope = Envelope::try_from_value(value).unwrap();
+        let mut buffer = [0u8; 256];
+        let encoder = EnvelopeEncoder::hold(pack, &envelope);
+        assert_eq!(encoder.produce(StrOutput::new(&mut buffer)).unwrap(), encoded);
+    }
+
+    #[test]
+    fn test_envelope_transcode_captured() {
+        assert_transcodes("@link(node:\"/unit/foo\",lane:info)");
+        assert_transcodes("@link(node:\"/unit/foo\",lane:info,prio:0.5,rate:2.0)");
+        assert_transcodes("@linked(node:\"/unit/foo\",lane:info)");
+        assert_transcodes("@sync(node:\"/unit/foo\",lane:info)");
+        assert_transcodes("@synced(node:\"/unit/foo\",lane:info)");
+        assert_transcodes("@unlink(node:\"/unit/foo\",lane:info)");
+        assert_transcodes("@unlinked(node:\"/unit/foo\",lane:info)@laneNotFound");
+        assert_transcodes("@command(node:\"/unit/foo\",lane:publish)\"Hello, world!\"");
+        assert_transcodes("@command(node:\"/unit/foo\",lane:count)42");
+        assert_transcodes("@event(node:\"/unit/foo\",lane:info)\"Hello from Swim!\"");
+        assert_transcodes("@event(node:\"/unit/foo\",lane:shoppingCart)@update(key:apple)3");
+        assert_transcodes("@event(node:\"/unit/foo\",lane:latest)@remove(key:\"a b\")");
+        assert_transcodes("@event(node:\"/unit/foo\",lane:status){temp:21.5,online:true}");
+        assert_transcodes("@event(node:\"/unit/foo\",lane:status){{}}");
+        assert_transcodes("@event(node:\"/unit/foo\",lane:status){{1}}");
+        assert_transcodes("@auth");
+        assert_transcodes("@auth@googleId(\"abc.def\")");
+        assert_transcodes("@authed@session{id:7}");
+        assert_transcodes("@deauth");
+        assert_transcodes("@deauthed \"session expired\"");
+    }
+
+    #[test]
+    fn test_envelope_headers() {
+        static mut TEST_AREA: [u8; 8192] = [0; 8192];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let envelope = Envelope::try_parse(pack, "@sync(node:\"/unit/1\",lane:info,prio:1,rate:0.25)").unwrap();
+        assert_eq!(envelope.tag(), "sync");
+        assert_eq!(envelope.node(), Some("/unit/1"));
+        assert_eq!(envelope.lane(), Some("info"));
+        match envelope {
+            Envelope::Sync(ref link) => assert_eq!((link.prio, link.rate), (1.0, 0.25)),
+            _ => panic!(),
+        };
+        assert!(envelope.body().is_absent());
+
+        let envelope = Envelope::try_parse(pack, "@command(\"/unit/1\", info, extra: 1) {x: 1}").unwrap();
+        assert_eq!((envelope.node(), envelope.lane()), (Some("/unit/1"), Some("info")));
+        assert!(envelope.body().is_record());
+
+        let envelope = Envelope::try_parse(pack, "@auth 1").unwrap();
+        assert_eq!(envelope.node(), None);
+        assert_eq!(envelope.body(), &Value::from_i32(1));
+
+        let envelope = Envelope::try_parse(pack, "@event(node:\"/unit/1\",lane:info){{}}").unwrap();
+        assert_eq!(envelope.body(), &Record::hold_slice(pack, &[]).into_value());
+    }
+
+    #[test]
+    fn test_envelope_non_finite_headers() {
+        static mut TEST_AREA: [u8; 8192] = [0; 8192];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let envelope = Envelope::Link(LinkAddressed {
+            node: Text::hold_str(pack, "/unit/1"),
+            lane: Text::hold_str(pack, "info"),
+            prio: core::f64::NAN,
+            rate: core::f64::INFINITY,
+            body: Value::absent(),
+        });
+        let mut buffer = [0u8; 256];
+        let encoder = EnvelopeEncoder::hold(pack, &envelope);
+        assert_eq!(encoder.produce(StrOutput::new(&mut buffer)).unwrap(), "@link(node:\"/unit/1\",lane:info)");
+        let mut buffer = [0u8; 256];
+        let value = envelope.try_into_value(pack).unwrap();
+        let encoder = ReconEncoder::hold(pack, &value);
+        assert_eq!(encoder.produce(StrOutput::new(&mut buffer)).unwrap(), "@link(node:\"/unit/1\",lane:info)");
+        let mut headers = Record::hold_slice(pack, &[
+            Item::slot(Value::hold_str(pack, "node"), Value::hold_str(pack, "/unit/1")),
+            Item::slot(Value::hold_str(pack, "lane"), Value::hold_str(pack, "info")),
+        ]);
+        headers.push(Item::slot(Value::hold_str(pack, "prio"), Value::from_f64(core::f64::NAN)));
+        let value = Record::hold_slice(pack, &[
+            Item::attr(Text::hold_str(pack, "link"), headers.into_value()),
+        ]).into_value();
+        assert_eq!(Envelope::try_from_value(value).unwrap_err(), EnvelopeError::Header);
+    }
+
+    #[test]
+    fn test_envelope_decode_incremental() {
+        let encoded = "@event(node:\"/unit/foo\",lane:info)@update(key:apple)3";
+        for split in 0..=encoded.len() {
+            let mut test_area = [0u8; 8192];
+            let pack = Pack::new(Block::from_slice(&mut test_area));
+            let envelope = match EnvelopeDecoder::hold(pack).decode(&mut encoded[..split].as_input()) {
+                Cont(decoder) => decoder.consume(&mut encoded[split..].as_input()).unwrap(),
+                Done(_) => panic!(),
+                Fail(error) => panic!("{:?}", error),
+            };
+            assert_eq!(envelope.lane(), Some("info"));
+        }
+    }
+
+    #[test]
+    fn test_envelope_encode_incremental() {
+        static mut TEST_AREA: [u8; 8192] = [0; 8192];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let encoded = "@event(node:\"/unit/\u{e9}\",lane:info)\"\u{2603}\"";
+        let envelope = Envelope::try_parse(pack, encoded).unwrap();
+        let mut buffer = ['\0'; 64];
+        let mut encoder = EnvelopeEncoder::hold(pack, &envelope);
+        let mut length = 0;
+        loop {
+            let mut output = ChunkOutput { chars: ['\0'; 3], len: 0 };
+            let next = encoder.encode(&mut output);
+            buffer[length..length + output.len].copy_from_slice(&output.chars[..output.len]);
+            length += output.len;
+            match next {
+                Cont(next) => encoder = next,
+                Done(_) => break,
+                Fail(_) => unreachable!(),
+            };
+        }
+        assert!(buffer[..length].iter().cloned().eq(encoded.chars()));
+    }
+
+    /// Owned output of at most three chars.
+    struct ChunkOutput {
+        chars: [char; 3],
+        len: usize,
+    }
+
+    impl Output for ChunkOutput {
+        type Token = char;
+        type Out = ();
+        type Err = ();
+
+        fn is_full(&self) -> bool {
+            self.len == self.chars.len()
+        }
+
+        fn push(&mut self, c: char) {
+            self.chars[self.len] = c;
+            self.len += 1;
+        }
+
+        fn take_out(self) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_envelope_decode_malformed() {
+        static mut TEST_AREA: [u8; 4096] = [0; 4096];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        assert_eq!(Envelope::try_parse(pack, "42").unwrap_err(), EnvelopeError::Tag);
+        assert_eq!(Envelope::try_parse(pack, "@blah").unwrap_err(), EnvelopeError::Tag);
+        assert_eq!(Envelope::try_parse(pack, "@link(node:\"/a\")").unwrap_err(), EnvelopeError::Header);
+        assert_eq!(Envelope::try_parse(pack, "@link(node:1,lane:x)").unwrap_err(), EnvelopeError::Header);
+        assert_eq!(Envelope::try_parse(pack, "@link(node:a,lane:b").unwrap_err(),
+                   EnvelopeError::Recon(ReconError::Unexpected));
+    }
+}
@@ -0,0 +1,11 @@
+//! # WARP Protocol
+
+#![no_std]
+
+extern crate swim_core;
+extern crate swim_mem;
+extern crate swim_codec;
+extern crate swim_structure;
+
+pub mod recon;
+pub mod envelope;
@@ -0,0 +1,823 @@
+use core::fmt::{self, Write};
+use core::marker::PhantomData;
+use core::str;
+use swim_core::num::float::{parse_float, write_float};
+use swim_mem::alloc::{Hold, Holder, HoldError};
+use swim_mem::lease::RawBuf;
+use swim_codec::step::{In, Out, Over};
+use swim_codec::then::{Then, Cont, Done, Fail};
+use swim_codec::input::{Input, AsInput};
+use swim_codec::output::{Output, SliceOutput};
+use swim_codec::decoder::Decoder;
+use swim_codec::encoder::Encoder;
+use swim_codec::base64::{Base64, Base64Decoder};
+use swim_structure::item::{Item, Attr, Value, Record, Text, Num, Data};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReconError {
+    /// Unexpected character, or unexpected end of input.
+    Unexpected,
+    /// Malformed number literal.
+    Number,
+    /// Malformed string escape sequence.
+    Escape,
+    /// Malformed base64 data literal.
+    Data,
+    /// Records or attribute parameters nested deeper than `MAX_DEPTH`.
+    Depth,
+    /// Document longer than the decoder's maximum length.
+    Oversized,
+    Hold(HoldError),
+}
+
+impl From<HoldError> for ReconError {
+    fn from(error: HoldError) -> ReconError {
+        ReconError::Hold(error)
+    }
+}
+
+/// Maximum nesting depth of records and attribute parameter lists.
+pub const MAX_DEPTH: usize = 64;
+
+/// Default maximum length, in bytes, of a document buffered by a
+/// `ReconDecoder`.
+pub const MAX_LEN: usize = 16 * 1024 * 1024;
+
+/// A structure that can be written as Recon text.
+pub trait WriteRecon {
+    fn write_recon<W: Write>(&self, output: &mut W) -> fmt::Result;
+}
+
+/// Buffers a complete Recon document, and parses it into a `Value` once
+/// the input is over. Markup literals are not supported.
+pub struct ReconDecoder<'a, I: Input<Token=char>> {
+    string: RawBuf<'a, u8>,
+    max_len: usize,
+    input: PhantomData<I>,
+}
+
+/// Writes a `WriteRecon` structure into a buffer allocated in a `Hold`,
+/// and streams the buffered text to a `char` output.
+pub struct ReconEncoder<'a, 'b, T: WriteRecon + 'b, O: Output<Token=char>> {
+    source: &'b T,
+    string: RawBuf<'a, u8>,
+    offset: usize,
+    output: PhantomData<O>,
+}
+
+/// Recursive descent parser over a complete Recon string.
+struct ReconParser<'a, 'h, 's> {
+    hold: &'h dyn Hold<'a>,
+    input: &'s str,
+    index: usize,
+    /// Number of blocks currently being parsed.
+    depth: usize,
+}
+
+/// Adapts a `RawBuf` to `fmt::Write`, remembering the allocation error
+/// that aborted a write.
+struct ReconBuffer<'a, 'r> {
+    string: &'r mut RawBuf<'a, u8>,
+    error: Option<HoldError>,
+}
+
+/// Parses a Recon document into a `Value` allocated in `hold`.
+pub fn parse_value<'a>(hold: &dyn Hold<'a>, string: &str) -> Result<Value<'a>, ReconError> {
+    let mut parser = ReconParser { hold: hold, input: string, index: 0, depth: 0 };
+    let mut record = Record::try_hold_slice(hold, &[])?;
+    parser.parse_block(&mut record, None)?;
+    Ok(block_value(record, Value::absent()))
+}
+
+/// Writes `value` as a Recon document.
+pub fn write_value<W: Write>(value: &Value, output: &mut W) -> fmt::Result {
+    if let Some(record) = value.cast_as_record() {
+        if record.first().map_or(false, Item::is_attr) {
+            write_body(record, false, output)
+        } else {
+            output.write_char('{')?;
+            write_items(record, output)?;
+            output.write_char('}')
+        }
+    } else if let Some(text) = value.cast_as_text() {
+        write_text(text.as_str(), output)
+    } else if let Some(num) = value.cast_as_num() {
+        write_num(num, output)
+    } else if let Some(data) = value.cast_as_data() {
+        write_data(data.as_slice(), output)
+    } else if let Some(value) = value.cast_as_bool() {
+        output.write_str(if value.to_bool() { "true" } else { "false" })
+    } else {
+        Ok(())
+    }
+}
+
+/// Writes `items` as though they follow an attribute; `bare` indicates
+/// that the preceding attribute had no parameter list, and so must be
+/// separated from a following primary value.
+pub(crate) fn write_body<W: Write>(items: &[Item], mut bare: bool, output: &mut W) -> fmt::Result {
+    let mut index = 0;
+    while index < items.len() && items[index].is_attr() {
+        bare = write_attr(items[index].as_attr(), output)?;
+        index += 1;
+    }
+    let items = &items[index..];
+    if items.len() == 1 && items[0].is_value() && !items[0].is_record() {
+        if bare {
+            output.write_char(' ')?;
+        }
+        write_value(items[0].as_value(), output)
+    } else if !items.is_empty() {
+        output.write_char('{')?;
+        write_items(items, output)?;
+        output.write_char('}')
+    } else {
+        Ok(())
+    }
+}
+
+/// Writes an attribute, returning `true` if it was written without a
+/// parameter list.
+fn write_attr<W: Write>(attr: &Attr, output: &mut W) -> Result<bool, fmt::Error> {
+    output.write_char('@')?;
+    write_text(attr.get_key().as_str(), output)?;
+    let value = attr.get_val();
+    if value.is_extant() || value.is_absent() {
+        return Ok(true);
+    }
+    output.write_char('(')?;
+    match value.cast_as_record() {
+        Some(record) if record.len() != 1 || !record[0].is_value() => write_items(record, output)?,
+        _ => write_value(value, output)?,
+    };
+    output.write_char(')')?;
+    Ok(false)
+}
+
+fn write_items<W: Write>(items: &[Item], output: &mut W) -> fmt::Result {
+    for (index, item) in items.iter().enumerate() {
+        if index != 0 {
+            output.write_char(',')?;
+        }
+        if let Some(attr) = item.cast_as_attr() {
+            write_attr(attr, output)?;
+        } else if let Some(slot) = item.cast_as_slot() {
+            let (key, value) = slot.get_key_val();
+            write_value(key, output)?;
+            output.write_char(':')?;
+            write_value(value, output)?;
+        } else {
+            write_value(item.as_value(), output)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes `string` as an identifier if it is one, and as a quoted string
+/// otherwise.
+pub(crate) fn write_text<W: Write>(string: &str, output: &mut W) -> fmt::Result {
+    if is_ident(string) {
+        return output.write_str(string);
+    }
+    output.write_char('"')?;
+    for c in string.chars() {
+        match c {
+            '"' => output.write_str("\\\"")?,
+            '\\' => output.write_str("\\\\")?,
+            '\n' => output.write_str("\\n")?,
+            '\r' => output.write_str("\\r")?,
+            '\t' => output.write_str("\\t")?,
+            c if c < ' ' => write!(output, "\\u{:04X}", c as u32)?,
+            c => output.write_char(c)?,
+        };
+    }
+    output.write_char('"')
+}
+
+fn write_num<W: Write>(num: &Num, output: &mut W) -> fmt::Result {
+    if num.is_u8() || num.is_u16() || num.is_u32() || num.is_u64() {
+        write!(output, "{}", num.as_u64())
+    } else if num.is_i8() || num.is_i16() || num.is_i32() || num.is_i64() {
+        write!(output, "{}", num.as_i64())
+    } else if num.is_f16() {
+        write_float(num.as_f16(), output)
+    } else if num.is_bf16() {
+        write_float(num.as_bf16(), output)
+    } else if num.is_f32() {
+        write_float(num.as_f32(), output)
+    } else {
+        write_float(num.as_f64(), output)
+    }
+}
+
+fn write_data<W: Write>(data: &[u8], output: &mut W) -> fmt::Result {
+    let alphabet = Base64.as_str();
+    output.write_char('%')?;
+    for chunk in data.chunks(3) {
+        let x = (chunk[0] as u32) << 16 |
+                (*chunk.get(1).unwrap_or(&0) as u32) << 8 |
+                *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                output.write_char(alphabet[(x >> (18 - 6 * i) & 0x3F) as usize] as char)?;
+            } else {
+                output.write_char('=')?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the value of a parsed block: `empty` if it has no items, its
+/// sole item if that item is a value, and the record itself otherwise.
+pub(crate) fn block_value<'a>(mut record: Record<'a>, empty: Value<'a>) -> Value<'a> {
+    if record.is_empty() {
+        empty
+    } else if record.len() == 1 && record[0].is_value() {
+        record.pop().unwrap().into_value()
+    } else {
+        record.into_value()
+    }
+}
+
+impl<'a> WriteRecon for Value<'a> {
+    fn write_recon<W: Write>(&self, output: &mut W) -> fmt::Result {
+        write_value(self, output)
+    }
+}
+
+impl<'a, I> ReconDecoder<'a, I> where I: Input<Token=char> {
+    pub fn try_hold(hold: &dyn Hold<'a>) -> Result<Self, HoldError> {
+        Ok(Self {
+            string: RawBuf::try_hold_empty(hold)?,
+            max_len: MAX_LEN,
+            input: PhantomData,
+        })
+    }
+
+    pub fn hold(hold: &dyn Hold<'a>) -> Self {
+        Self::try_hold(hold).unwrap()
+    }
+
+    /// Fails with `Oversized` once the buffered document would exceed
+    /// `max_len` bytes.
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    pub fn consume(mut self, input: &mut I) -> Result<Value<'a>, ReconError> {
+        loop {
+            match self.decode(input) {
+                Done(value) => return Ok(value),
+                Fail(error) => return Err(error),
+                Cont(next) => {
+                    if input.is_out() {
+                        input.over();
+                        self = next;
+                    } else {
+                        return Err(ReconError::Unexpected);
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl<'a, I> Decoder for ReconDecoder<'a, I> where I: Input<Token=char> {
+    type Input = I;
+    type Output = Value<'a>;
+    type Error = ReconError;
+
+    fn decode(mut self, input: &mut I) -> Then<Self, Value<'a>, ReconError> {
+        loop {
+            match input.head() {
+                In(c) => {
+                    if self.string.len() + c.len_utf8() > self.max_len {
+                        return Fail(ReconError::Oversized);
+                    }
+                    let mut bytes = [0u8; 4];
+                    if let Err(error) = self.string.try_extend_from_slice(c.encode_utf8(&mut bytes).as_bytes()) {
+                        return Fail(ReconError::Hold(error));
+                    }
+                    input.step();
+                },
+                Over => {
+                    let string = unsafe { str::from_utf8_unchecked(&self.string) };
+                    return match parse_value(self.string.holder(), string) {
+                        Ok(value) => Done(value),
+                        Err(error) => Fail(error),
+                    };
+                },
+                Out => return Cont(self),
+            };
+        }
+    }
+}
+
+impl<'a, 'b, T, O> ReconEncoder<'a, 'b, T, O> where T: WriteRecon + 'b, O: Output<Token=char> {
+    pub fn try_hold(hold: &dyn Hold<'a>, source: &'b T) -> Result<Self, HoldError> {
+        let mut string = RawBuf::try_hold_empty(hold)?;
+        let mut buffer = ReconBuffer { string: &mut string, error: None };
+        if source.write_recon(&mut buffer).is_err() {
+            return Err(buffer.error.take().unwrap());
+        }
+        Ok(Self {
+            source: source,
+            string: string,
+            offset: 0,
+            output: PhantomData,
+        })
+    }
+
+    pub fn hold(hold: &dyn Hold<'a>, source: &'b T) -> Self {
+        Self::try_hold(hold, source).unwrap()
+    }
+
+    pub fn produce(mut self, mut output: O) -> Result<O::Out, O::Err> {
+        loop {
+            match self.encode(&mut output) {
+                Done(_) => return output.take_out(),
+                Fail(_) => unreachable!(),
+                Cont(next) => self = next,
+            }
+        }
+    }
+}
+
+impl<'a, 'b, T, O> Encoder for ReconEncoder<'a, 'b, T, O> where T: WriteRecon + 'b, O: Output<Token=char> {
+    type Input = &'b T;
+    type Output = O;
+    type Error = ();
+
+    fn encode(mut self, output: &mut O) -> Then<Self, &'b T, ()> {
+        let string = unsafe { str::from_utf8_unchecked(&self.string) };
+        while !output.is_full() {
+            match string[self.offset..].chars().next() {
+                Some(c) => {
+                    output.push(c);
+                    self.offset += c.len_utf8();
+                },
+                None => return Done(self.source),
+            };
+        }
+        return Cont(self);
+    }
+}
+
+impl<'a, 'r> Write for ReconBuffer<'a, 'r> {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        match self.string.try_extend_from_slice(string.as_bytes()) {
+            Ok(()) => Ok(()),
+            Err(error) => {
+                self.error = Some(error);
+                Err(fmt::Error)
+            },
+        }
+    }
+}
+
+impl<'a, 'h, 's> ReconParser<'a, 'h, 's> {
+    #[inline]
+    fn peek(&self) -> Option<char> {
+        self.input[self.index..].chars().next()
+    }
+
+    #[inline]
+    fn step(&mut self, c: char) {
+        self.index += c.len_utf8();
+    }
+
+    fn skip_space(&mut self) {
+        while let Some(c) = self.peek() {
+            if c != ' ' && c != '\t' && c != '\r' {
+                break;
+            }
+            self.step(c);
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if c != ' ' && c != '\t' && c != '\r' && c != '\n' {
+                break;
+            }
+            self.step(c);
+        }
+    }
+
+    /// Parses `,`, `;` or newline separated items into `record`, up to and
+    /// including the `end` delimiter, or up to the end of input.
+    fn parse_block(&mut self, record: &mut Record<'a>, end: Option<char>) -> Result<(), ReconError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(ReconError::Depth);
+        }
+        self.depth += 1;
+        let result = self.parse_items(record, end);
+        self.depth -= 1;
+        result
+    }
+
+    fn parse_items(&mut self, record: &mut Record<'a>, end: Option<char>) -> Result<(), ReconError> {
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(c) if Some(c) == end => {
+                    self.step(c);
+                    return Ok(());
+                },
+                None if end.is_none() => return Ok(()),
+                None => return Err(ReconError::Unexpected),
+                Some(_) => self.parse_item(record)?,
+            };
+            self.skip_space();
+            match self.peek() {
+                Some(c) if c == ',' || c == ';' || c == '\n' => self.step(c),
+                Some(c) if Some(c) == end => (),
+                None if end.is_none() => (),
+                _ => return Err(ReconError::Unexpected),
+            };
+        }
+    }
+
+    fn parse_item(&mut self, record: &mut Record<'a>) -> Result<(), ReconError> {
+        let key = self.parse_value()?;
+        self.skip_space();
+        if self.peek() == Some(':') {
+            self.step(':');
+            self.skip_space();
+            let mut value = self.parse_value()?;
+            if key.is_absent() {
+                return Err(ReconError::Unexpected);
+            } else if value.is_absent() {
+                value = Value::extant();
+            }
+            record.try_push(Item::slot(key, value))?;
+        } else if key.is_absent() {
+            return Err(ReconError::Unexpected);
+        } else {
+            record.try_push(key.into_item())?;
+        }
+        Ok(())
+    }
+
+    /// Parses a value with any prefix attributes, returning `Absent` if
+    /// no value starts at the current position.
+    fn parse_value(&mut self) -> Result<Value<'a>, ReconError> {
+        match self.peek() {
+            Some('@') => {
+                let mut record = Record::try_hold_slice(self.hold, &[])?;
+                while self.peek() == Some('@') {
+                    self.step('@');
+                    let key = self.parse_name()?;
+                    let value = if self.peek() == Some('(') {
+                        self.step('(');
+                        let mut params = Record::try_hold_slice(self.hold, &[])?;
+                        self.parse_block(&mut params, Some(')'))?;
+                        block_value(params, Value::extant())
+                    } else {
+                        Value::extant()
+                    };
+                    record.try_push(Item::attr(key, value))?;
+                    self.skip_space();
+                }
+                match self.peek() {
+                    Some('{') => {
+                        self.step('{');
+                        self.parse_block(&mut record, Some('}'))?;
+                    },
+                    Some(c) if is_primary_start(c) => {
+                        let value = self.parse_primary(c)?;
+                        record.try_push(value.into_item())?;
+                    },
+                    _ => (),
+                };
+                Ok(record.into_value())
+            },
+            Some('{') => {
+                self.step('{');
+                let mut record = Record::try_hold_slice(self.hold, &[])?;
+                self.parse_block(&mut record, Some('}'))?;
+                Ok(record.into_value())
+            },
+            Some(c) if is_primary_start(c) => self.parse_primary(c),
+            _ => Ok(Value::absent()),
+        }
+    }
+
+    fn parse_primary(&mut self, c: char) -> Result<Value<'a>, ReconError> {
+        if c == '"' || c == '\'' {
+            Ok(self.parse_string(c)?.into_value())
+        } else if c == '%' {
+            self.parse_data()
+        } else if c == '-' || c.is_ascii_digit() {
+            self.parse_number()
+        } else {
+            match self.parse_ident() {
+                "true" => Ok(Value::from_bool(true)),
+                "false" => Ok(Value::from_bool(false)),
+                ident => Ok(Text::try_hold_str(self.hold, ident)?.into_value()),
+            }
+        }
+    }
+
+    fn parse_name(&mut self) -> Result<Text<'a>, ReconError> {
+        match self.peek() {
+            Some(c) if c == '"' || c == '\'' => self.parse_string(c),
+            Some(c) if is_ident_start(c) => Ok(Text::try_hold_str(self.hold, self.parse_ident())?),
+            _ => Err(ReconError::Unexpected),
+        }
+    }
+
+    fn parse_ident(&mut self) -> &'s str {
+        let start = self.index;
+        while let Some(c) = self.peek() {
+            if !is_ident_char(c) {
+                break;
+            }
+            self.step(c);
+        }
+        &self.input[start..self.index]
+    }
+
+    fn parse_string(&mut self, quote: char) -> Result<Text<'a>, ReconError> {
+        self.step(quote);
+        let mut string = RawBuf::<u8>::try_hold_empty(self.hold)?;
+        let mut start = self.index;
+        loop {
+            let c = self.peek().ok_or(ReconError::Unexpected)?;
+            if c == quote || c == '\\' {
+                string.try_extend_from_slice(self.input[start..self.index].as_bytes())?;
+                self.step(c);
+                if c == quote {
+                    let string = unsafe { str::from_utf8_unchecked(&string) };
+                    return Ok(Text::try_hold_str(self.hold, string)?);
+                }
+                let escape = self.peek().ok_or(ReconError::Escape)?;
+                self.step(escape);
+                let c = match escape {
+                    'b' => '\x08',
+                    'f' => '\x0C',
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    'u' => {
+                        let digits = self.input.get(self.index..self.index + 4).ok_or(ReconError::Escape)?;
+                        let code = u32::from_str_radix(digits, 16).map_err(|_| ReconError::Escape)?;
+                        self.index += 4;
+                        core::char::from_u32(code).ok_or(ReconError::Escape)?
+                    },
+                    c if c.is_ascii_punctuation() => c,
+                    _ => return Err(ReconError::Escape),
+                };
+                let mut bytes = [0u8; 4];
+                string.try_extend_from_slice(c.encode_utf8(&mut bytes).as_bytes())?;
+                start = self.index;
+            } else {
+                self.step(c);
+            }
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Value<'a>, ReconError> {
+        let start = self.index;
+        if self.input[start..].starts_with("0x") {
+            self.index += 2;
+            let digits = self.scan(|c| c.is_ascii_hexdigit());
+            let value = u64::from_str_radix(digits, 16).map_err(|_| ReconError::Number)?;
+            return Ok(int_value(value as i64));
+        }
+        if self.peek() == Some('-') {
+            self.step('-');
+        }
+        let mut float = false;
+        if self.scan(|c| c.is_ascii_digit()).is_empty() {
+            return Err(ReconError::Number);
+        }
+        if self.peek() == Some('.') {
+            self.step('.');
+            float = true;
+            if self.scan(|c| c.is_ascii_digit()).is_empty() {
+                return Err(ReconError::Number);
+            }
+        }
+        if let Some(c) = self.peek() {
+            if c == 'e' || c == 'E' {
+                self.step(c);
+                float = true;
+                if let Some(c) = self.peek() {
+                    if c == '+' || c == '-' {
+                        self.step(c);
+                    }
+                }
+                if self.scan(|c| c.is_ascii_digit()).is_empty() {
+                    return Err(ReconError::Number);
+                }
+            }
+        }
+        let literal = &self.input[start..self.index];
+        if !float {
+            if let Ok(value) = literal.parse::<i64>() {
+                return Ok(int_value(value));
+            } else if let Ok(value) = literal.parse::<u64>() {
+                return Ok(Value::from_u64(value));
+            }
+        }
+        parse_float::<f64>(literal).map(Value::from_f64).map_err(|_| ReconError::Number)
+    }
+
+    fn parse_data(&mut self) -> Result<Value<'a>, ReconError> {
+        self.step('%');
+        let digits = self.scan(|c| c.is_ascii_alphanumeric() || c == '+' || c == '/' || c == '=');
+        let len = digits.len() / 4 * 3 + 3;
+        let mut bytes = RawBuf::<u8>::try_hold_cap(self.hold, len)?;
+        for _ in 0..len {
+            bytes.try_push(0)?;
+        }
+        let decoder = Base64Decoder::new(SliceOutput::new(&mut bytes[..])).alphabet(Base64);
+        let data = decoder.consume(&mut digits.as_input()).map_err(|_| ReconError::Data)?;
+        Ok(Data::try_hold_slice(self.hold, data)?.into_value())
+    }
+
+    fn scan<F: Fn(char) -> bool>(&mut self, f: F) -> &'s str {
+        let start = self.index;
+        while let Some(c) = self.peek() {
+            if !f(c) {
+                break;
+            }
+            self.step(c);
+        }
+        &self.input[start..self.index]
+    }
+}
+
+fn int_value<'a>(value: i64) -> Value<'a> {
+    if value as i32 as i64 == value {
+        Value::from_i32(value as i32)
+    } else {
+        Value::from_i64(value)
+    }
+}
+
+#[inline]
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+#[inline]
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+#[inline]
+fn is_primary_start(c: char) -> bool {
+    c == '"' || c == '\'' || c == '%' || c == '-' || c.is_ascii_digit() || is_ident_start(c)
+}
+
+fn is_ident(string: &str) -> bool {
+    let mut chars = string.chars();
+    match chars.next() {
+        Some(c) if is_ident_start(c) => (),
+        _ => return false,
+    };
+    chars.all(is_ident_char) && string != "true" && string != "false"
+}
+
+#[cfg(test)]
+mod tests {
+    use swim_mem::block::Block;
+    use swim_mem::alloc::Pack;
+    use swim_codec::output::StrOutput;
+    use super::*;
+
+    fn assert_transcodes<'a>(hold: &dyn Hold<'a>, recon: &str, value: &Value<'a>) {
+        let decoder = ReconDecoder::hold(hold);
+        assert_eq!(&decoder.consume(&mut recon.as_input()).unwrap(), value);
+        let mut buffer = [0u8; 256];
+        let encoder = ReconEncoder::hold(hold, value);
+        assert_eq!(encoder.produce(StrOutput::new(&mut buffer)).unwrap(), recon);
+    }
+
+    #[test]
+    fn test_recon_transcode_primitives() {
+        static mut TEST_AREA: [u8; 16384] = [0; 16384];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        assert_transcodes(pack, "", &Value::absent());
+        assert_transcodes(pack, "info", &Value::hold_str(pack, "info"));
+        assert_transcodes(pack, "\"/unit/foo\"", &Value::hold_str(pack, "/unit/foo"));
+        assert_transcodes(pack, "\"a\\\"b\\\\c\\n\"", &Value::hold_str(pack, "a\"b\\c\n"));
+        assert_transcodes(pack, "\"true\"", &Value::hold_str(pack, "true"));
+        assert_transcodes(pack, "true", &Value::from_bool(true));
+        assert_transcodes(pack, "-42", &Value::from_i32(-42));
+        assert_transcodes(pack, "8589934592", &Value::from_i64(8589934592));
+        assert_transcodes(pack, "0.5", &Value::from_f64(0.5));
+        assert_transcodes(pack, "-2.5e-7", &Value::from_f64(-2.5e-7));
+        assert_transcodes(pack, "%AQID", &Data::hold_slice(pack, &[1, 2, 3]).into_value());
+        assert_transcodes(pack, "%AQI=", &Data::hold_slice(pack, &[1, 2]).into_value());
+    }
+
+    #[test]
+    fn test_recon_transcode_records() {
+        static mut TEST_AREA: [u8; 16384] = [0; 16384];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let slot = |key: &str, value: Value<'static>| {
+            Item::slot(Value::hold_str(pack, key), value)
+        };
+        assert_transcodes(pack, "{}", &Record::hold_slice(pack, &[]).into_value());
+        assert_transcodes(pack, "{1,2}", &Record::hold_slice(pack, &[Item::from_i32(1), Item::from_i32(2)]).into_value());
+        assert_transcodes(pack, "{x:1,y:\"a b\"}", &Record::hold_slice(pack, &[
+            slot("x", Value::from_i32(1)),
+            slot("y", Value::hold_str(pack, "a b")),
+        ]).into_value());
+        assert_transcodes(pack, "@a", &Record::hold_slice(pack, &[
+            Item::attr(Text::hold_str(pack, "a"), Value::extant()),
+        ]).into_value());
+        assert_transcodes(pack, "@a(1)@b(x:1,y:2)3", &Record::hold_slice(pack, &[
+            Item::attr(Text::hold_str(pack, "a"), Value::from_i32(1)),
+            Item::attr(Text::hold_str(pack, "b"), Record::hold_slice(pack, &[
+                slot("x", Value::from_i32(1)),
+                slot("y", Value::from_i32(2)),
+            ]).into_value()),
+            Item::from_i32(3),
+        ]).into_value());
+        assert_transcodes(pack, "@a foo", &Record::hold_slice(pack, &[
+            Item::attr(Text::hold_str(pack, "a"), Value::extant()),
+            Value::hold_str(pack, "foo").into_item(),
+        ]).into_value());
+        assert_transcodes(pack, "@a{x:,{}}", &Record::hold_slice(pack, &[
+            Item::attr(Text::hold_str(pack, "a"), Value::extant()),
+            slot("x", Value::extant()),
+            Record::hold_slice(pack, &[]).into_item(),
+        ]).into_value());
+    }
+
+    #[test]
+    fn test_recon_parse_lenient_syntax() {
+        static mut TEST_AREA: [u8; 8192] = [0; 8192];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let value = parse_value(pack, " { a : 'x' ;\n b: 0x10 }\n").unwrap();
+        let expected = Record::hold_slice(pack, &[
+            Item::slot(Value::hold_str(pack, "a"), Value::hold_str(pack, "x")),
+            Item::slot(Value::hold_str(pack, "b"), Value::from_i32(16)),
+        ]);
+        assert_eq!(value, expected.into_value());
+        assert_eq!(parse_value(pack, "1\n2").unwrap(), Record::hold_slice(pack, &[
+            Item::from_i32(1), Item::from_i32(2),
+        ]).into_value());
+        assert_eq!(parse_value(pack, "\"\\u00e9\"").unwrap(), Value::hold_str(pack, "\u{e9}"));
+    }
+
+    #[test]
+    fn test_recon_parse_malformed() {
+        static mut TEST_AREA: [u8; 4096] = [0; 4096];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        assert_eq!(parse_value(pack, "{a:1").unwrap_err(), ReconError::Unexpected);
+        assert_eq!(parse_value(pack, "a b").unwrap_err(), ReconError::Unexpected);
+        assert_eq!(parse_value(pack, ":1").unwrap_err(), ReconError::Unexpected);
+        assert_eq!(parse_value(pack, "\"abc").unwrap_err(), ReconError::Unexpected);
+        assert_eq!(parse_value(pack, "\"\\q\"").unwrap_err(), ReconError::Escape);
+        assert_eq!(parse_value(pack, "1.").unwrap_err(), ReconError::Number);
+        assert_eq!(parse_value(pack, "-x").unwrap_err(), ReconError::Number);
+        assert_eq!(parse_value(pack, "%A").unwrap_err(), ReconError::Data);
+    }
+
+    #[test]
+    fn test_recon_parse_too_deep() {
+        static mut TEST_AREA: [u8; 65536] = [0; 65536];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let mut buffer = [0u8; 1024];
+        let mut output = StrOutput::new(&mut buffer);
+        for _ in 0..MAX_DEPTH - 1 {
+            output.push('{');
+        }
+        for _ in 0..MAX_DEPTH - 1 {
+            output.push('}');
+        }
+        assert!(parse_value(pack, output.take_out().unwrap()).is_ok());
+        let mut output = StrOutput::new(&mut buffer);
+        for _ in 0..MAX_DEPTH {
+            output.push('{');
+        }
+        assert_eq!(parse_value(pack, output.take_out().unwrap()).unwrap_err(), ReconError::Depth);
+        let mut output = StrOutput::new(&mut buffer);
+        for _ in 0..MAX_DEPTH {
+            for c in "@a(".chars() {
+                output.push(c);
+            }
+        }
+        assert_eq!(parse_value(pack, output.take_out().unwrap()).unwrap_err(), ReconError::Depth);
+    }
+
+    #[test]
+    fn test_recon_decode_oversized() {
+        static mut TEST_AREA: [u8; 4096] = [0; 4096];
+        let pack = Pack::new(unsafe { Block::from_slice(&mut TEST_AREA) });
+        let decoder = ReconDecoder::hold(pack).max_len(8);
+        assert_eq!(decoder.consume(&mut "{a:1,b:2}".as_input()).unwrap_err(), ReconError::Oversized);
+        let decoder = ReconDecoder::hold(pack).max_len(8);
+        assert_eq!(decoder.consume(&mut "{a:1,b}".as_input()).unwrap(), Record::hold_slice(pack, &[
+            Item::slot(Value::hold_str(pack, "a"), Value::from_i32(1)),
+            Value::hold_str(pack, "b").into_item(),
+        ]).into_value());
+    }
+}
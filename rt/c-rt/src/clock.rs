@@ -0,0 +1,7 @@
+use swim_core::time::Clock;
+use swim_c::time::SystemClock;
+
+#[no_mangle]
+unsafe extern "Rust" fn _swim_global_clock<'a>() -> &'a dyn Clock {
+    SystemClock::global()
+}
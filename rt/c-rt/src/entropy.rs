@@ -0,0 +1,7 @@
+use swim_core::entropy::EntropyError;
+use swim_c::entropy;
+
+#[no_mangle]
+unsafe extern "Rust" fn _swim_entropy(bytes: &mut [u8]) -> Result<(), EntropyError> {
+    entropy::fill(bytes)
+}
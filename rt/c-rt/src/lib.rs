@@ -6,5 +6,8 @@ extern crate swim_core;
 extern crate swim_mem;
 extern crate swim_c;
 
+#[cfg(unix)]
+mod clock;
+mod entropy;
 mod heap;
 mod hold;
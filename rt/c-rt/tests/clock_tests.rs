@@ -0,0 +1,20 @@
+extern crate swim_core;
+extern crate swim_c_rt;
+
+use swim_core::time::{Duration, Instant, Timestamp};
+
+#[test]
+fn test_monotonic_clock() {
+    let t0 = Instant::now();
+    let t1 = Instant::now();
+    assert!(t0 <= t1);
+    assert!(t0.elapsed() >= t1 - t0);
+}
+
+#[test]
+fn test_realtime_clock() {
+    // 2019-01-01T00:00:00Z
+    let t = Timestamp::now();
+    assert!(t > Timestamp::from_secs(1_546_300_800));
+    assert!(t.duration_since(Timestamp::UNIX_EPOCH).unwrap() > Duration::from_secs(1_546_300_800));
+}
@@ -0,0 +1,55 @@
+extern crate swim_core;
+extern crate swim_c_rt;
+
+use core::hash::{BuildHasher, Hasher};
+use swim_core::entropy;
+use swim_core::rand::{Pcg32, Rng, Xoshiro256PlusPlus};
+use swim_core::siphash::SipHash13;
+
+#[test]
+fn test_fill_entropy() {
+    let mut x = [0u8; 64];
+    let mut y = [0u8; 64];
+    entropy::fill(&mut x).unwrap();
+    entropy::fill(&mut y).unwrap();
+    assert_ne!(x, [0u8; 64]);
+    assert_ne!(x, y);
+}
+
+#[test]
+fn test_random_siphash_keys() {
+    let a = SipHash13::new();
+    let b = SipHash13::new();
+    let mut ha = a.build_hasher();
+    let mut hb = b.build_hasher();
+    ha.write(b"key");
+    hb.write(b"key");
+    assert_ne!(ha.finish(), hb.finish());
+}
+
+#[test]
+fn test_concurrent_siphash_keys() {
+    let threads = (0..8).map(|_| {
+        std::thread::spawn(|| {
+            (0..16).map(|_| {
+                let mut hasher = SipHash13::new().build_hasher();
+                hasher.write(b"key");
+                hasher.finish()
+            }).collect::<Vec<u64>>()
+        })
+    }).collect::<Vec<_>>();
+    let mut hashes = threads.into_iter().flat_map(|thread| thread.join().unwrap()).collect::<Vec<u64>>();
+    hashes.sort();
+    hashes.dedup();
+    assert_eq!(hashes.len(), 8 * 16);
+}
+
+#[test]
+fn test_random_rng_seeds() {
+    let mut a = Pcg32::new();
+    let mut b = Pcg32::new();
+    assert_ne!(a.next_u64(), b.next_u64());
+    let mut a = Xoshiro256PlusPlus::new();
+    let mut b = Xoshiro256PlusPlus::new();
+    assert_ne!(a.next_u64(), b.next_u64());
+}
@@ -0,0 +1,37 @@
+extern crate swim_core;
+extern crate swim_c_rt;
+
+use swim_core::time::Timestamp;
+use swim_core::ulid::Ulid;
+use swim_core::uuid::Uuid;
+
+#[test]
+fn test_random_uuids() {
+    let a = Uuid::new_v4();
+    let b = Uuid::new_v4();
+    assert_ne!(a, b);
+    assert_eq!(a.version(), Some(4));
+    assert_eq!(a.timestamp(), None);
+}
+
+#[test]
+fn test_time_ordered_uuids() {
+    let t0 = Timestamp::now();
+    let a = Uuid::new_v7();
+    let b = Uuid::new_v7();
+    assert_ne!(a, b);
+    assert_eq!(a.version(), Some(7));
+    let t = a.timestamp().unwrap();
+    assert!(t0.to_millis() <= t.to_millis());
+    assert!(t <= Timestamp::now());
+}
+
+#[test]
+fn test_random_ulids() {
+    let t0 = Timestamp::now();
+    let a = Ulid::new();
+    let b = Ulid::new();
+    assert_ne!(a, b);
+    assert!(t0.to_millis() <= a.timestamp().to_millis());
+    assert!(a.timestamp() <= b.timestamp());
+}
@@ -1,3 +1,7 @@
 // POSIX.1
 pub mod mman;
 pub use crate::lib::sys::types;
+
+// Linux
+#[cfg(all(target_os = "linux", not(target_env = "uclibc")))]
+pub use crate::lib::sys::random;
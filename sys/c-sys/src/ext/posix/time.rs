@@ -8,6 +8,9 @@ extern "C" {
     pub fn gmtime_r(timer: *const time_t, result: *mut tm) -> *mut tm;
     pub fn localtime_r(timer: *const time_t, result: *mut tm) -> *mut tm;
 
+    pub fn clock_getres(clk_id: clockid_t, res: *mut timespec) -> int;
+    pub fn clock_gettime(clk_id: clockid_t, tp: *mut timespec) -> int;
+
     #[cfg_attr(target_arch = "x86", link_name = "nanosleep$UNIX2003")]
     pub fn nanosleep(rqtp: *const timespec, rmtp: *mut timespec) -> int;
 }
@@ -1,9 +1,10 @@
-use crate::{cchar, int, long, ulong};
+use crate::{cchar, int, long, uint, ulong};
 
 // ISO/IEC 9899
 // 7.23 Date and time
 
 pub type clock_t = ulong;
+pub type clockid_t = uint;
 pub type time_t = long;
 
 #[repr(C)]
@@ -26,3 +27,12 @@ pub struct timespec {
     pub tv_sec: time_t,
     pub tv_nsec: long,
 }
+
+pub const CLOCK_REALTIME: clockid_t = 0;
+pub const CLOCK_MONOTONIC_RAW: clockid_t = 4;
+pub const CLOCK_MONOTONIC_RAW_APPROX: clockid_t = 5;
+pub const CLOCK_MONOTONIC: clockid_t = 6;
+pub const CLOCK_UPTIME_RAW: clockid_t = 8;
+pub const CLOCK_UPTIME_RAW_APPROX: clockid_t = 9;
+pub const CLOCK_PROCESS_CPUTIME_ID: clockid_t = 12;
+pub const CLOCK_THREAD_CPUTIME_ID: clockid_t = 16;
@@ -1,3 +1,6 @@
 // POSIX.1
 pub mod mman;
 pub mod types;
+
+// Linux
+pub mod random;
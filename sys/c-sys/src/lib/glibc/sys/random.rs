@@ -0,0 +1,11 @@
+use crate::{uint, size_t, ssize_t, void};
+
+// Linux
+
+pub const GRND_NONBLOCK: uint = 0x01;
+pub const GRND_RANDOM: uint = 0x02;
+pub const GRND_INSECURE: uint = 0x04;
+
+extern "C" {
+    pub fn getrandom(buf: *mut void, buflen: size_t, flags: uint) -> ssize_t;
+}
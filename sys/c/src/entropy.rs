@@ -0,0 +1,65 @@
+use swim_core::entropy::EntropyError;
+
+/// Fills `bytes` with random data from the operating system's entropy
+/// source. Uses the `getrandom` system call where available, and falls back
+/// to reading `/dev/urandom` on other Unix systems.
+pub fn fill(bytes: &mut [u8]) -> Result<(), EntropyError> {
+    if bytes.is_empty() {
+        return Ok(());
+    }
+    #[cfg(all(target_os = "linux", not(target_env = "uclibc")))]
+    {
+        if getrandom_fill(bytes) {
+            return Ok(());
+        }
+    }
+    #[cfg(unix)]
+    {
+        urandom_fill(bytes)
+    }
+    #[cfg(not(unix))]
+    {
+        Err(EntropyError::Unsupported)
+    }
+}
+
+/// Fills `bytes` using the `getrandom` system call; returns `false` if the
+/// kernel doesn't support the call, or if it fails.
+#[cfg(all(target_os = "linux", not(target_env = "uclibc")))]
+fn getrandom_fill(bytes: &mut [u8]) -> bool {
+    use swim_c_sys::sys::random::getrandom;
+    use swim_c_sys::void;
+    let mut offset = 0;
+    while offset < bytes.len() {
+        let ptr = unsafe { bytes.as_mut_ptr().add(offset) };
+        let len = bytes.len() - offset;
+        // Reads of up to 256 bytes from an initialized entropy pool never
+        // return short, but larger reads can be interrupted by signals.
+        let count = unsafe { getrandom(ptr as *mut void, len, 0) };
+        if count <= 0 {
+            return false;
+        }
+        offset += count as usize;
+    }
+    true
+}
+
+/// Fills `bytes` by reading from the `/dev/urandom` device.
+#[cfg(unix)]
+fn urandom_fill(bytes: &mut [u8]) -> Result<(), EntropyError> {
+    use swim_c_sys::stdio::{fclose, fopen, fread};
+    use swim_c_sys::void;
+    unsafe {
+        let file = fopen(cstr!("/dev/urandom").as_cptr(), cstr!("rb").as_cptr());
+        if file.is_null() {
+            return Err(EntropyError::Unavailable);
+        }
+        let count = fread(bytes.as_mut_ptr() as *mut void, 1, bytes.len(), file);
+        fclose(file);
+        if count == bytes.len() {
+            Ok(())
+        } else {
+            Err(EntropyError::Unavailable)
+        }
+    }
+}
@@ -2,8 +2,10 @@
 
 #![no_std]
 
+#![feature(const_fn)]
 #![feature(core_intrinsics)]
 
+#[macro_use]
 extern crate swim_core;
 extern crate swim_mem;
 extern crate swim_c_sys;
@@ -14,6 +16,9 @@ pub use swim_c_sys::*;
 mod macros;
 
 pub mod stdlib;
+pub mod time;
+
+pub mod entropy;
 
 pub mod cstr;
 pub mod cstring;
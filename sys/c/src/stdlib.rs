@@ -4,7 +4,7 @@ use core::mem;
 use core::ptr;
 use core::sync::atomic::AtomicUsize;
 use core::sync::atomic::Ordering::Relaxed;
-use swim_core::reify::{Reified, Reify};
+use swim_core::reify::Reified;
 use swim_mem::block::{Block, Layout, ZSP};
 use swim_mem::alloc::{AllocTag, Heap, HeapError, Hold, HoldError};
 use swim_mem::lease::RawBox;
@@ -103,16 +103,18 @@ impl<'a> Heap<'a> for MallocHeap<'a> {
     }
 }
 
-/// Managed allocator backed by libc `malloc`.
-pub struct MallocHold<'a> {
-    /// Polymorphic hold type.
-    base: Reified<Hold<'a>>,
-    /// Number of currently allocated memory blocks.
-    live: AtomicUsize,
-    /// Number of currently allocated bytes.
-    used: AtomicUsize,
-    /// Tag shared by all zero-sized allocations.
-    zero: AllocTag<'a>,
+reify! {
+    /// Managed allocator backed by libc `malloc`.
+    pub struct MallocHold<'a>: dyn Hold<'a> + 'a {
+        /// Polymorphic hold type.
+        base: Reified<dyn Hold<'a> + 'a>,
+        /// Number of currently allocated memory blocks.
+        live: AtomicUsize,
+        /// Number of currently allocated bytes.
+        used: AtomicUsize,
+        /// Tag shared by all zero-sized allocations.
+        zero: AllocTag<'a>,
+    }
 }
 
 unsafe impl<'a> Send for MallocHold<'a> {
@@ -127,7 +129,7 @@ impl<'a> MallocHold<'a> {
     pub fn new() -> Result<RawBox<'a, MallocHold<'a>>, HoldError> {
         // Allocate a new malloc hold on the heap using the global malloc hold.
         let mut hold_box = RawBox::try_hold_new(MallocHold::global(), MallocHold {
-            base: unsafe { Reified::uninitialized() },
+            base: MallocHold::reified(),
             live: AtomicUsize::new(0),
             used: AtomicUsize::new(0),
             zero: unsafe { AllocTag::null() },
@@ -135,9 +137,7 @@ impl<'a> MallocHold<'a> {
         unsafe {
             // Borrow a mutable reference to the malloc hold.
             let hold = hold_box.as_mut();
-            // Initialize the malloc hold's vtable (idempotent).
-            MallocHold::deify(hold);
-            // Initialize the malloc hold's shared zero tag (idempotent).
+            // Point the malloc hold's shared zero tag at its final address.
             hold.zero.init(&hold.base);
         }
         Ok(hold_box)
@@ -145,22 +145,16 @@ impl<'a> MallocHold<'a> {
 
     /// Returns a reference to a `Hold` that allocates memory using libc `malloc`.
     pub fn global() -> &'a MallocHold<'a> {
-        // Declare the global malloc heap singleton with an uninitialized vtable.
-        // Rust apparently has no way to get a static pointer to a vtable.
-        static mut GLOBAL: MallocHold<'static> = MallocHold {
-            base: unsafe { Reified::uninitialized() },
+        // Declare the global malloc hold singleton with a statically initialized
+        // vtable, and a shared zero tag that points back to the global hold.
+        static GLOBAL: MallocHold<'static> = MallocHold {
+            base: MallocHold::reified(),
             live: AtomicUsize::new(0),
             used: AtomicUsize::new(0),
-            zero: unsafe { AllocTag::null() },
+            zero: AllocTag::new(&GLOBAL.base),
         };
-        unsafe {
-            // Initialize the global malloc hold's vtable (idempotent).
-            MallocHold::deify(&mut GLOBAL);
-            // Initialize the global malloc hold's shared zero tag (idempotent).
-            GLOBAL.zero.init(&GLOBAL.base);
-            // Return a reference to the global malloc hold.
-            mem::transmute(&GLOBAL)
-        }
+        // Return a reference to the global malloc hold.
+        unsafe { mem::transmute(&GLOBAL) }
     }
 
     /// Returns the number of currently allocated memory blocks in this `MallocHold`.
@@ -301,15 +295,3 @@ unsafe impl<'a> Hold<'a> for MallocHold<'a> {
         Ok(Block::from_raw_parts(new_ptr, new_size))
     }
 }
-
-impl<'a> Reify<'a, Hold<'a> + 'a> for MallocHold<'a> {
-    #[inline]
-    unsafe fn deify(object: &mut (Hold<'a> + 'a)) {
-        Reified::<Hold<'a>>::deify(mem::transmute(object));
-    }
-
-    #[inline]
-    unsafe fn reify(base: &'a Reified<Hold<'a> + 'a>) -> &'a (Hold<'a> + 'a) {
-        mem::transmute(base.reify())
-    }
-}
@@ -0,0 +1,44 @@
+use swim_core::time::{Clock, Duration, Timestamp};
+
+pub use swim_c_sys::time::*;
+
+/// Monotonic and wall-clock time source backed by libc `clock_gettime`.
+pub struct SystemClock;
+
+impl SystemClock {
+    /// Returns a reference to the global system clock.
+    pub fn global() -> &'static SystemClock {
+        static GLOBAL: SystemClock = SystemClock;
+        &GLOBAL
+    }
+}
+
+#[cfg(unix)]
+impl SystemClock {
+    /// Returns the current time of the system clock `clk_id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the system doesn't support the clock.
+    fn get_time(clk_id: clockid_t) -> timespec {
+        let mut tp = timespec { tv_sec: 0, tv_nsec: 0 };
+        let result = unsafe { clock_gettime(clk_id, &mut tp) };
+        if result != 0 {
+            panic!("clock_gettime failed");
+        }
+        tp
+    }
+}
+
+#[cfg(unix)]
+impl Clock for SystemClock {
+    fn monotonic(&self) -> Duration {
+        let tp = SystemClock::get_time(CLOCK_MONOTONIC);
+        Duration::new(tp.tv_sec as u64, tp.tv_nsec as u32)
+    }
+
+    fn realtime(&self) -> Timestamp {
+        let tp = SystemClock::get_time(CLOCK_REALTIME);
+        Timestamp::new(tp.tv_sec as i64, tp.tv_nsec as u32)
+    }
+}
@@ -1,5 +1,6 @@
 #![no_std]
 
+extern crate swim_core;
 extern crate swim_codec;
 extern crate swim_mem;
 #[macro_use]
@@ -1,3 +1,4 @@
+use swim_core::num::cast::TryFromNum;
 use swim_jvm_sys::{jboolean, jbyte, jchar, jshort, jint, jlong, jfloat, jdouble, jvalue};
 use crate::object::JObject;
 use crate::array::JArray;
@@ -19,6 +20,23 @@ pub enum JValue {
     Object(JObject),
 }
 
+impl JValue {
+    /// Losslessly converts this `JValue` to a `T` value, if it holds a
+    /// primitive number that `T` can exactly represent.
+    pub fn try_to<T: TryFromNum>(&self) -> Option<T> {
+        match *self {
+            JValue::Byte(b) => T::try_from_num(b),
+            JValue::Char(c) => T::try_from_num(c),
+            JValue::Short(s) => T::try_from_num(s),
+            JValue::Int(i) => T::try_from_num(i),
+            JValue::Long(j) => T::try_from_num(j),
+            JValue::Float(f) => T::try_from_num(f),
+            JValue::Double(d) => T::try_from_num(d),
+            JValue::Void | JValue::Boolean(_) | JValue::Object(_) => None,
+        }
+    }
+}
+
 impl From<()> for JValue {
     #[inline]
     fn from(_: ()) -> JValue {
@@ -0,0 +1,29 @@
+extern crate swim_jvm;
+extern crate swim_c_rt;
+
+use swim_jvm::JValue;
+
+#[test]
+fn test_jvalue_try_to_integer() {
+    assert_eq!(JValue::Int(-1).try_to::<i8>(), Some(-1));
+    assert_eq!(JValue::Int(-1).try_to::<u32>(), None);
+    assert_eq!(JValue::Long(1 << 40).try_to::<usize>(), Some(1 << 40));
+    assert_eq!(JValue::Long(1 << 40).try_to::<i32>(), None);
+    assert_eq!(JValue::Char(0xFFFF).try_to::<i16>(), None);
+    assert_eq!(JValue::Double(3.0).try_to::<i32>(), Some(3));
+    assert_eq!(JValue::Double(3.5).try_to::<i32>(), None);
+}
+
+#[test]
+fn test_jvalue_try_to_float() {
+    assert_eq!(JValue::Int(16_777_216).try_to::<f32>(), Some(16_777_216.0));
+    assert_eq!(JValue::Int(16_777_217).try_to::<f32>(), None);
+    assert_eq!(JValue::Float(0.5).try_to::<f64>(), Some(0.5));
+    assert_eq!(JValue::Double(0.1).try_to::<f32>(), None);
+}
+
+#[test]
+fn test_jvalue_try_to_non_number() {
+    assert_eq!(JValue::Void.try_to::<i32>(), None);
+    assert_eq!(JValue::Boolean(1).try_to::<u8>(), None);
+}